@@ -12,19 +12,37 @@ read_port_from_file() {
   sed -n 's/.*"port"[[:space:]]*:[[:space:]]*\([0-9][0-9]*\).*/\1/p' "$info_file" | head -n 1
 }
 
+read_token_from_file() {
+  local info_file="$1"
+  sed -n 's/.*"token"[[:space:]]*:[[:space:]]*"\([0-9a-f]*\)".*/\1/p' "$info_file" | head -n 1
+}
+
 try_trigger() {
   local port="$1"
+  local token="$2"
   curl --silent --show-error --fail --max-time 1 \
+    -H "Authorization: Bearer ${token}" \
     -X POST "http://127.0.0.1:${port}/hotkey/${ACTION}" >/dev/null
 }
 
+# The D-Bus service is tried first; it may register after the shortcut was
+# bound, and the bridge server below remains the fallback.
+if command -v gdbus >/dev/null 2>&1 &&
+  gdbus call --session --timeout 1 \
+    --dest com.voquill.Dictation \
+    --object-path /com/voquill/Dictation \
+    --method com.voquill.Dictation.TriggerHotkey "$ACTION" >/dev/null 2>&1; then
+  exit 0
+fi
+
 DEV_INFO_FILE="$CONFIG_ROOT/com.voquill.desktop.dev/bridge-server.json"
 LOCAL_INFO_FILE="$CONFIG_ROOT/com.voquill.desktop.local/bridge-server.json"
 PROD_INFO_FILE="$CONFIG_ROOT/com.voquill.desktop/bridge-server.json"
 
 for info_file in "$DEV_INFO_FILE" "$LOCAL_INFO_FILE" "$PROD_INFO_FILE"; do
   port="$(read_port_from_file "$info_file" || true)"
-  if [[ -n "${port:-}" ]] && try_trigger "$port"; then
+  token="$(read_token_from_file "$info_file" 2>/dev/null || true)"
+  if [[ -n "${port:-}" ]] && try_trigger "$port" "${token:-}"; then
     exit 0
   fi
 done
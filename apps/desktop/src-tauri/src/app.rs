@@ -137,6 +137,13 @@ pub fn build() -> tauri::Builder<tauri::Wry> {
             // Write startup diagnostics for debugging
             crate::system::diagnostics::write_startup_diagnostics(app.handle());
 
+            // Must run before any connection to the database is opened.
+            match crate::system::backup::apply_pending_restore(app.handle()) {
+                Ok(true) => log::info!("Restored data from backup"),
+                Ok(false) => {}
+                Err(err) => log::error!("Failed to apply pending restore: {err}"),
+            }
+
             let db_url = {
                 let handle = app.handle();
                 crate::system::paths::database_url(handle)
@@ -151,10 +158,39 @@ pub fn build() -> tauri::Builder<tauri::Wry> {
             })
             .map_err(|err| -> Box<dyn std::error::Error> { Box::new(err) })?;
 
+            if let Err(err) = crate::system::vault::init(app.handle()) {
+                log::error!("Failed to load the encrypted vault: {err}");
+            }
+
+            let sealed_api_key_data =
+                crate::system::auth_session::has_sealed_refresh_token(app.handle())
+                    || tauri::async_runtime::block_on(
+                        crate::db::api_key_queries::has_sealed_api_keys(pool.clone()),
+                    )
+                    .unwrap_or_else(|err| {
+                        log::warn!("Failed to check for sealed API keys: {err}");
+                        true
+                    });
+            if let Err(err) =
+                crate::system::crypto::init_master_key(app.handle(), sealed_api_key_data)
+            {
+                log::error!("Failed to load API key encryption key: {err}");
+            } else {
+                match tauri::async_runtime::block_on(
+                    crate::system::crypto::reencrypt_legacy_api_keys(pool.clone()),
+                ) {
+                    Ok(0) => {}
+                    Ok(count) => log::info!("Re-encrypted {count} API keys"),
+                    Err(err) => log::error!("Failed to re-encrypt API keys: {err}"),
+                }
+            }
+
             app.manage(crate::state::OptionKeyDatabase::new(pool.clone()));
             app.manage(crate::state::GoogleOAuthState::from_env());
             app.manage(crate::state::OverlayState::new());
             app.manage(crate::state::RemoteReceiverState::new());
+            crate::system::remote_outbox::start(app.handle().clone());
+            crate::system::backup::start_scheduler(app.handle().clone());
 
             match crate::system::auth_session::AuthSession::new(app.handle()) {
                 Ok(session) => {
@@ -208,6 +244,9 @@ pub fn build() -> tauri::Builder<tauri::Wry> {
                 crate::platform::compositor::deploy_trigger_script(app.handle());
             }
 
+            #[cfg(target_os = "linux")]
+            crate::platform::linux::dbus::start(app.handle().clone());
+
             // Open dev tools if VOQUILL_ENABLE_DEVTOOLS is set
             if std::env::var("VOQUILL_ENABLE_DEVTOOLS").is_ok() {
                 log::info!("VOQUILL_ENABLE_DEVTOOLS detected, opening dev tools...");
@@ -243,7 +282,12 @@ pub fn build() -> tauri::Builder<tauri::Wry> {
             crate::commands::remote_receiver_stop,
             crate::commands::remote_receiver_status,
             crate::commands::remote_sender_deliver_final_text,
+            crate::commands::remote_sender_stream_partial_text,
+            crate::commands::remote_sender_discover_receivers,
             crate::commands::remote_sender_pair_with_receiver,
+            crate::commands::remote_outbox_list,
+            crate::commands::remote_outbox_retry,
+            crate::commands::remote_outbox_discard,
             crate::commands::start_recording,
             crate::commands::stop_recording,
             crate::commands::store_transcription_audio,
@@ -255,16 +299,30 @@ pub fn build() -> tauri::Builder<tauri::Wry> {
             crate::commands::copy_to_clipboard,
             crate::commands::transcription_create,
             crate::commands::transcription_list,
+            crate::commands::transcription_search,
             crate::commands::transcription_delete,
             crate::commands::transcription_update,
             crate::commands::transcription_audio_load,
+            crate::commands::transcription_retranscribe,
+            crate::commands::transcription_version_create,
+            crate::commands::transcription_version_list,
+            crate::commands::transcription_version_activate,
             crate::commands::purge_stale_transcription_audio,
             crate::commands::export_transcription,
+            crate::commands::export_transcriptions,
+            crate::commands::import_transcriptions,
             crate::commands::export_diagnostics,
             crate::commands::term_create,
             crate::commands::term_update,
             crate::commands::term_list,
             crate::commands::term_delete,
+            crate::commands::term_export,
+            crate::commands::term_import,
+            crate::commands::term_pack_list,
+            crate::commands::term_pack_subscribe,
+            crate::commands::term_pack_sync,
+            crate::commands::term_pack_unsubscribe,
+            crate::commands::term_preview,
             crate::commands::hotkey_list,
             crate::commands::hotkey_save,
             crate::commands::hotkey_delete,
@@ -279,6 +337,18 @@ pub fn build() -> tauri::Builder<tauri::Wry> {
             crate::commands::tone_list,
             crate::commands::tone_get,
             crate::commands::tone_delete,
+            crate::commands::tone_validate_template,
+            crate::commands::tone_version_list,
+            crate::commands::tone_version_diff,
+            crate::commands::tone_version_restore,
+            crate::commands::tone_export,
+            crate::commands::tone_import,
+            crate::commands::backup_create,
+            crate::commands::backup_restore,
+            crate::commands::backup_schedule_get,
+            crate::commands::backup_schedule_set,
+            crate::commands::vault_get_enabled,
+            crate::commands::vault_set_enabled,
             crate::commands::clear_local_data,
             crate::commands::set_phase,
             crate::commands::set_pill_visibility,
@@ -307,6 +307,22 @@ pub struct RemoteSenderDeliverArgs {
     pub target_device_id: String,
     pub text: String,
     pub mode: String,
+    #[serde(default)]
+    pub utterance_id: Option<String>,
+}
+
+#[derive(serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteSenderStreamPartialArgs {
+    pub target_device_id: String,
+    pub utterance_id: String,
+    pub text: String,
+}
+
+#[derive(serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteOutboxItemArgs {
+    pub event_id: String,
 }
 
 #[derive(serde::Deserialize, specta::Type)]
@@ -347,8 +363,6 @@ pub struct UserPreferencesGetArgs {
     pub user_id: String,
 }
 
-const MAX_RETAINED_TRANSCRIPTION_AUDIO: usize = 20;
-
 #[derive(serde::Serialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct TranscriptionAudioData {
@@ -651,19 +665,99 @@ pub fn remote_receiver_status(
 #[specta::specta]
 pub async fn remote_sender_deliver_final_text(
     args: RemoteSenderDeliverArgs,
+    app: AppHandle,
     database: State<'_, crate::state::OptionKeyDatabase>,
     receiver_state: State<'_, crate::state::RemoteReceiverState>,
-) -> Result<(), String> {
-    crate::system::remote_sender::deliver_final_text(
+) -> Result<crate::domain::RemoteDeliveryOutcome, String> {
+    if args.text.trim().is_empty() {
+        return Ok(crate::domain::RemoteDeliveryOutcome::Delivered);
+    }
+
+    // Transport tests report failures immediately instead of queueing.
+    if args.mode == "test" {
+        crate::system::remote_sender::deliver_final_text(
+            database.pool(),
+            receiver_state.inner().clone(),
+            &args.target_device_id,
+            &args.text,
+            &args.mode,
+        )
+        .await?;
+        return Ok(crate::domain::RemoteDeliveryOutcome::Delivered);
+    }
+
+    crate::system::remote_outbox::deliver(
+        &app,
+        &args.target_device_id,
+        &args.text,
+        &args.mode,
+        args.utterance_id.as_deref(),
+    )
+    .await
+}
+
+/// Streams the utterance's text so far. Returns `false` when the receiver
+/// only accepts final text.
+#[tauri::command]
+#[specta::specta]
+pub async fn remote_sender_stream_partial_text(
+    args: RemoteSenderStreamPartialArgs,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+    receiver_state: State<'_, crate::state::RemoteReceiverState>,
+) -> Result<bool, String> {
+    crate::system::remote_stream::send_partial(
         database.pool(),
         receiver_state.inner().clone(),
         &args.target_device_id,
+        &args.utterance_id,
         &args.text,
-        &args.mode,
     )
     .await
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn remote_outbox_list(
+    app: AppHandle,
+) -> Result<Vec<crate::domain::RemoteOutboxItem>, String> {
+    crate::system::remote_outbox::list(&app).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remote_outbox_retry(
+    args: RemoteOutboxItemArgs,
+    app: AppHandle,
+) -> Result<crate::domain::RemoteDeliveryOutcome, String> {
+    crate::system::remote_outbox::retry(&app, &args.event_id).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remote_outbox_discard(
+    args: RemoteOutboxItemArgs,
+    app: AppHandle,
+) -> Result<(), String> {
+    crate::system::remote_outbox::discard(&app, &args.event_id).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remote_sender_discover_receivers(
+    receiver_state: State<'_, crate::state::RemoteReceiverState>,
+) -> Result<Vec<crate::domain::DiscoveredRemoteReceiver>, String> {
+    let own_device_id = receiver_state.status().device_id;
+    let receivers = crate::system::remote_discovery::discover(
+        crate::system::remote_discovery::DISCOVERY_TIMEOUT,
+    )
+    .await?;
+
+    Ok(receivers
+        .into_iter()
+        .filter(|receiver| receiver.device_id != own_device_id)
+        .collect())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn remote_sender_pair_with_receiver(
@@ -689,9 +783,18 @@ pub async fn transcription_create(
     transcription: crate::domain::Transcription,
     database: State<'_, crate::state::OptionKeyDatabase>,
 ) -> Result<crate::domain::Transcription, String> {
-    crate::db::transcription_queries::insert_transcription(database.pool(), &transcription)
-        .await
-        .map_err(|err| err.to_string())
+    let stored =
+        crate::db::transcription_queries::insert_transcription(database.pool(), &transcription)
+            .await
+            .map_err(|err| err.to_string())?;
+
+    crate::system::bridge_server::publish(crate::system::bridge_server::BridgeEvent::Transcript {
+        id: stored.id.clone(),
+        timestamp: stored.timestamp,
+        transcript: stored.transcript.clone(),
+    });
+
+    Ok(stored)
 }
 
 #[tauri::command]
@@ -709,6 +812,17 @@ pub async fn transcription_list(
         .map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn transcription_search(
+    request: crate::domain::TranscriptionSearchRequest,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<Vec<crate::domain::TranscriptionSearchResult>, String> {
+    crate::db::transcription_queries::search_transcriptions(database.pool(), &request)
+        .await
+        .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn transcription_delete(
@@ -790,6 +904,163 @@ pub async fn transcription_audio_load(
     })
 }
 
+#[derive(Debug, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionRetranscribeArgs {
+    pub id: String,
+    /// Base URL of the running local transcription sidecar.
+    pub sidecar_url: String,
+    pub model: String,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub initial_prompt: Option<String>,
+    #[serde(default)]
+    pub device_id: Option<String>,
+    /// Makes the new version active right away.
+    #[serde(default)]
+    pub activate: bool,
+}
+
+/// Runs the stored audio of a transcription through a local model and
+/// records the result as a new version. Post-processing of the new text is
+/// left to the caller, which saves it through `transcription_update` once
+/// the version is active.
+#[tauri::command]
+#[specta::specta]
+pub async fn transcription_retranscribe(
+    app: AppHandle,
+    args: TranscriptionRetranscribeArgs,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<crate::domain::TranscriptionVersion, String> {
+    use crate::system::local_sidecar;
+
+    let pool = database.pool();
+    let transcription =
+        crate::db::transcription_queries::fetch_transcription(pool.clone(), &args.id)
+            .await
+            .map_err(|err| err.to_string())?
+            .ok_or_else(|| format!("Transcription {} not found", args.id))?;
+
+    let audio_path = transcription
+        .audio
+        .as_ref()
+        .map(|audio| PathBuf::from(&audio.file_path))
+        .ok_or_else(|| "No audio snapshot available for this transcription".to_string())?;
+    let audio_dir = crate::system::audio_store::audio_dir(&app).map_err(|err| err.to_string())?;
+    if !audio_path.starts_with(&audio_dir) {
+        return Err("Audio snapshot path is outside the managed directory".to_string());
+    }
+
+    let (samples, sample_rate) = tauri::async_runtime::spawn_blocking(move || {
+        crate::system::audio_store::load_audio_samples(&audio_path).map_err(|err| err.to_string())
+    })
+    .await
+    .map_err(|err| err.to_string())??;
+
+    let response = local_sidecar::transcribe(
+        &args.sidecar_url,
+        &local_sidecar::TranscribeRequest {
+            model: &args.model,
+            samples: &samples,
+            sample_rate,
+            language: args.language.as_deref(),
+            initial_prompt: args.initial_prompt.as_deref(),
+            device_id: args.device_id.as_deref(),
+            include_confidence: true,
+        },
+    )
+    .await
+    .map_err(|err| err.to_string())?;
+
+    let text = response.text.trim().to_string();
+    let mut version = crate::domain::TranscriptionVersion {
+        id: format!("{}-{:08x}", transcription.id, rand::random::<u32>()),
+        transcription_id: transcription.id.clone(),
+        created_at: chrono::Utc::now().timestamp_millis(),
+        is_active: false,
+        transcript: text.clone(),
+        raw_transcript: Some(text),
+        sanitized_transcript: None,
+        confidence_segments: response.segments,
+        transcription_mode: Some("local".to_string()),
+        model_size: Some(response.model),
+        inference_device: Some(response.inference_device),
+        transcription_prompt: args.initial_prompt,
+        transcription_api_key_id: None,
+        transcription_duration_ms: Some(response.duration_ms as i64),
+        post_process_mode: None,
+        post_process_device: None,
+        post_process_prompt: None,
+        post_process_api_key_id: None,
+        postprocess_duration_ms: None,
+    };
+
+    crate::db::transcription_version_queries::create_version(pool.clone(), &version)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    if args.activate {
+        crate::db::transcription_version_queries::activate_version(
+            pool,
+            &version.transcription_id,
+            &version.id,
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+        version.is_active = true;
+    }
+
+    Ok(version)
+}
+
+/// Records a result produced outside the sidecar, such as a re-transcription
+/// through a cloud provider, as a new inactive version.
+#[tauri::command]
+#[specta::specta]
+pub async fn transcription_version_create(
+    version: crate::domain::TranscriptionVersion,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<crate::domain::TranscriptionVersion, String> {
+    let pool = database.pool();
+    crate::db::transcription_queries::fetch_transcription(pool.clone(), &version.transcription_id)
+        .await
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| format!("Transcription {} not found", version.transcription_id))?;
+
+    crate::db::transcription_version_queries::create_version(pool, &version)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn transcription_version_list(
+    transcription_id: String,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<Vec<crate::domain::TranscriptionVersion>, String> {
+    crate::db::transcription_version_queries::fetch_versions(database.pool(), &transcription_id)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn transcription_version_activate(
+    transcription_id: String,
+    version_id: String,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<crate::domain::Transcription, String> {
+    crate::db::transcription_version_queries::activate_version(
+        database.pool(),
+        &transcription_id,
+        &version_id,
+    )
+    .await
+    .map_err(|err| err.to_string())?
+    .ok_or_else(|| format!("Version {version_id} not found for transcription {transcription_id}"))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn export_transcription(
@@ -855,9 +1126,13 @@ pub async fn export_transcription(
         if let Some(ref audio_path_str) = audio_path {
             let audio_path_buf = PathBuf::from(audio_path_str);
             if audio_path_buf.starts_with(&audio_dir) && audio_path_buf.exists() {
-                let audio_data = std::fs::read(&audio_path_buf)
+                let audio_data = crate::system::audio_store::read_audio_bytes(&audio_path_buf)
                     .map_err(|err| format!("Failed to read audio: {err}"))?;
-                zip.start_file("audio.wav", options)
+                let extension = audio_path_buf
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or("wav");
+                zip.start_file(format!("audio.{extension}"), options)
                     .map_err(|err| err.to_string())?;
                 zip.write_all(&audio_data).map_err(|err| err.to_string())?;
             }
@@ -870,6 +1145,209 @@ pub async fn export_transcription(
     .map_err(|err| err.to_string())?
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn export_transcriptions(
+    app: AppHandle,
+    request: crate::domain::TranscriptionExportRequest,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<bool, String> {
+    use crate::system::history_transfer;
+
+    let pool = database.pool();
+    let mut filter = request.filter.clone();
+    filter.limit = Some(history_transfer::EXPORT_PAGE_SIZE);
+    filter.offset = Some(0);
+
+    let mut transcriptions = Vec::new();
+    loop {
+        let page = crate::db::transcription_queries::search_transcriptions(pool.clone(), &filter)
+            .await
+            .map_err(|err| err.to_string())?;
+        let page_len = page.len() as u32;
+        transcriptions.extend(page.into_iter().map(|result| result.transcription));
+
+        if page_len < history_transfer::EXPORT_PAGE_SIZE {
+            break;
+        }
+        filter.offset = Some(filter.offset.unwrap_or(0) + page_len);
+    }
+
+    let extension = if request.include_audio {
+        "zip"
+    } else {
+        history_transfer::file_extension(request.format)
+    };
+    let date = chrono::Local::now().format("%Y-%m-%d");
+    let dialog = rfd::AsyncFileDialog::new()
+        .set_file_name(format!("voquill-history-{date}.{extension}"))
+        .add_filter("Voquill history", &[extension])
+        .save_file()
+        .await;
+
+    let save_path = match dialog {
+        Some(handle) => handle.path().to_path_buf(),
+        None => return Ok(false),
+    };
+
+    let audio_dir = if request.include_audio {
+        Some(crate::system::audio_store::audio_dir(&app).map_err(|err| err.to_string())?)
+    } else {
+        None
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let entries = history_transfer::prepare_export(transcriptions, audio_dir.as_deref());
+        history_transfer::write_export(
+            &save_path,
+            request.format,
+            &entries,
+            request.include_audio,
+        )?;
+        Ok::<bool, String>(true)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+/// Merges a JSONL history export (bare or zipped with audio) into the local
+/// database. Returns `None` when the file picker is cancelled.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_transcriptions(
+    app: AppHandle,
+    conflict: Option<crate::domain::TranscriptionImportConflict>,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<Option<crate::domain::TranscriptionImportSummary>, String> {
+    use crate::domain::{TranscriptionImportConflict, TranscriptionImportSummary};
+    use crate::system::history_transfer;
+
+    let dialog = rfd::AsyncFileDialog::new()
+        .add_filter("Voquill history", &["jsonl", "zip"])
+        .pick_file()
+        .await;
+
+    let import_path = match dialog {
+        Some(handle) => handle.path().to_path_buf(),
+        None => return Ok(None),
+    };
+
+    let parsed =
+        tauri::async_runtime::spawn_blocking(move || history_transfer::read_import(&import_path))
+            .await
+            .map_err(|err| err.to_string())??;
+
+    let pool = database.pool();
+    let conflict = conflict.unwrap_or_default();
+    let mut summary = TranscriptionImportSummary {
+        errors: parsed.errors,
+        ..Default::default()
+    };
+
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum ImportAction {
+        Insert,
+        Overwrite,
+        Duplicate,
+    }
+
+    // Resolve ids and conflicts first so bundled audio can be extracted
+    // under the id each entry will actually be stored with.
+    let mut planned = Vec::new();
+    for mut transcription in parsed.transcriptions {
+        let existing =
+            crate::db::transcription_queries::fetch_transcription(pool.clone(), &transcription.id)
+                .await
+                .map_err(|err| err.to_string())?;
+
+        let action = match (&existing, conflict) {
+            (None, _) => ImportAction::Insert,
+            (Some(_), TranscriptionImportConflict::Skip) => {
+                summary.skipped += 1;
+                continue;
+            }
+            (Some(_), TranscriptionImportConflict::Overwrite) => ImportAction::Overwrite,
+            (Some(_), TranscriptionImportConflict::KeepBoth) => {
+                transcription.id = format!("{}-{:08x}", transcription.id, rand::random::<u32>());
+                ImportAction::Duplicate
+            }
+        };
+
+        let bundled_audio = match (&parsed.archive_path, transcription.audio.take()) {
+            (Some(_), Some(audio)) if history_transfer::is_bundled_audio_path(&audio.file_path) => {
+                Some(audio)
+            }
+            _ => None,
+        };
+        if bundled_audio.is_none() && action == ImportAction::Overwrite {
+            transcription.audio = existing.and_then(|existing| existing.audio);
+        }
+
+        planned.push((transcription, bundled_audio, action));
+    }
+
+    if let Some(archive_path) = parsed.archive_path.clone() {
+        let mut extractions = Vec::new();
+        for (transcription, bundled_audio, _) in &planned {
+            if let Some(audio) = bundled_audio {
+                let extension = std::path::Path::new(&audio.file_path)
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or("wav");
+                let destination =
+                    crate::system::audio_store::audio_path_for(&app, &transcription.id, extension)
+                        .map_err(|err| err.to_string())?;
+                extractions.push((audio.file_path.clone(), destination));
+            }
+        }
+
+        let jobs = extractions.clone();
+        let results = tauri::async_runtime::spawn_blocking(move || {
+            history_transfer::extract_audio(&archive_path, &jobs)
+        })
+        .await
+        .map_err(|err| err.to_string())??;
+
+        let mut outcomes = extractions.into_iter().zip(results);
+        for (transcription, bundled_audio, _) in planned.iter_mut() {
+            let Some(audio) = bundled_audio.take() else {
+                continue;
+            };
+            let Some(((_, destination), result)) = outcomes.next() else {
+                break;
+            };
+            match result {
+                Ok(()) => {
+                    transcription.audio = Some(crate::domain::TranscriptionAudioSnapshot {
+                        file_path: destination.to_string_lossy().to_string(),
+                        duration_ms: audio.duration_ms,
+                    });
+                }
+                Err(err) => summary.errors.push(format!("{}: {err}", transcription.id)),
+            }
+        }
+    }
+
+    for (transcription, _, action) in planned {
+        let result = if action == ImportAction::Overwrite {
+            crate::db::transcription_queries::update_transcription(pool.clone(), &transcription)
+                .await
+        } else {
+            crate::db::transcription_queries::insert_transcription(pool.clone(), &transcription)
+                .await
+        };
+
+        match (result, action) {
+            (Ok(_), ImportAction::Insert) => summary.imported += 1,
+            (Ok(_), ImportAction::Overwrite) => summary.overwritten += 1,
+            (Ok(_), ImportAction::Duplicate) => summary.duplicated += 1,
+            (Err(err), _) => summary.errors.push(format!("{}: {err}", transcription.id)),
+        }
+    }
+
+    Ok(Some(summary))
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn export_diagnostics(app: AppHandle, diagnostics_info: String) -> Result<bool, String> {
@@ -966,37 +1444,305 @@ pub async fn term_list(
         .map_err(|err| err.to_string())
 }
 
+/// Shows what `text` becomes after the replacement terms run. Uses the
+/// stored local terms unless `terms` is given, which lets callers preview
+/// unsaved edits or apply terms that live in a cloud account.
 #[tauri::command]
 #[specta::specta]
-pub async fn term_delete(
-    id: String,
+pub async fn term_preview(
+    text: String,
+    terms: Option<Vec<crate::domain::Term>>,
     database: State<'_, crate::state::OptionKeyDatabase>,
-) -> Result<(), String> {
-    crate::db::term_queries::delete_term(database.pool(), &id)
-        .await
-        .map_err(|err| err.to_string())
+) -> Result<crate::domain::TermPreview, String> {
+    let terms = match terms {
+        Some(terms) => terms,
+        None => crate::db::term_queries::fetch_terms(database.pool())
+            .await
+            .map_err(|err| err.to_string())?,
+    };
+
+    Ok(crate::system::term_replacement::preview(&text, &terms))
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn hotkey_list(
+pub async fn term_delete(
+    id: String,
     database: State<'_, crate::state::OptionKeyDatabase>,
-) -> Result<Vec<crate::domain::Hotkey>, String> {
-    crate::db::hotkey_queries::fetch_hotkeys(database.pool())
+) -> Result<(), String> {
+    crate::db::term_queries::delete_term(database.pool(), &id)
         .await
         .map_err(|err| err.to_string())
 }
 
 #[tauri::command]
 #[specta::specta]
-pub async fn hotkey_save(
-    hotkey: crate::domain::Hotkey,
+pub async fn term_export(
+    format: crate::domain::TermTransferFormat,
     database: State<'_, crate::state::OptionKeyDatabase>,
-) -> Result<crate::domain::Hotkey, String> {
-    crate::db::hotkey_queries::upsert_hotkey(database.pool(), &hotkey)
+) -> Result<bool, String> {
+    use crate::system::term_transfer::{self, TermRecord};
+
+    let terms = crate::db::term_queries::fetch_terms(database.pool())
         .await
-        .map_err(|err| err.to_string())
-}
+        .map_err(|err| err.to_string())?;
+    let mut records = terms
+        .iter()
+        .filter(|term| term.pack_id.is_none())
+        .map(TermRecord::from_term)
+        .collect::<Vec<_>>();
+    records.reverse();
+
+    let extension = term_transfer::file_extension(format);
+    let dialog = rfd::AsyncFileDialog::new()
+        .set_file_name(format!("voquill-dictionary.{extension}"))
+        .add_filter("Voquill dictionary", &[extension])
+        .save_file()
+        .await;
+
+    let save_path = match dialog {
+        Some(handle) => handle.path().to_path_buf(),
+        None => return Ok(false),
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let document = term_transfer::render(format, &records)?;
+        std::fs::write(&save_path, document)
+            .map_err(|err| format!("Failed to write export: {err}"))?;
+        Ok::<bool, String>(true)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+/// Merges a CSV or JSON term file into the personal dictionary, updating
+/// terms that share a source value. Returns `None` when the file picker is
+/// cancelled.
+#[tauri::command]
+#[specta::specta]
+pub async fn term_import(
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<Option<crate::domain::TermImportSummary>, String> {
+    use crate::system::term_transfer;
+
+    let dialog = rfd::AsyncFileDialog::new()
+        .add_filter("Voquill dictionary", &["csv", "json"])
+        .pick_file()
+        .await;
+
+    let import_path = match dialog {
+        Some(handle) => handle.path().to_path_buf(),
+        None => return Ok(None),
+    };
+
+    let parsed =
+        tauri::async_runtime::spawn_blocking(move || term_transfer::read_terms(&import_path))
+            .await
+            .map_err(|err| err.to_string())??;
+
+    let pool = database.pool();
+    let mut existing = crate::db::term_queries::fetch_terms(pool.clone())
+        .await
+        .map_err(|err| err.to_string())?
+        .into_iter()
+        .filter(|term| term.pack_id.is_none())
+        .map(|term| (term_transfer::dedupe_key(&term.source_value), term))
+        .collect::<std::collections::HashMap<_, _>>();
+
+    let mut summary = crate::domain::TermImportSummary {
+        errors: parsed.errors,
+        ..Default::default()
+    };
+    let created_at = current_timestamp_millis()?;
+
+    for record in term_transfer::dedupe(parsed.records) {
+        let key = term_transfer::dedupe_key(&record.source_value);
+        match existing.remove(&key) {
+            Some(term) if record.matches(&term) => summary.unchanged += 1,
+            Some(term) => {
+                match crate::db::term_queries::update_term(pool.clone(), &record.apply_to(&term))
+                    .await
+                {
+                    Ok(_) => summary.updated += 1,
+                    Err(err) => summary.errors.push(format!("{}: {err}", term.source_value)),
+                }
+            }
+            None => {
+                let id = format!("term-{:016x}", rand::random::<u64>());
+                let term = record.into_term(id, created_at, None);
+                match crate::db::term_queries::insert_term(pool.clone(), &term).await {
+                    Ok(_) => summary.created += 1,
+                    Err(err) => summary.errors.push(format!("{}: {err}", term.source_value)),
+                }
+            }
+        }
+    }
+
+    Ok(Some(summary))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn term_pack_list(
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<Vec<crate::domain::TermPack>, String> {
+    crate::db::term_pack_queries::fetch_packs(database.pool())
+        .await
+        .map_err(|err| err.to_string())
+}
+
+/// Subscribes to a term pack picked from disk, either a single CSV or JSON
+/// file or a directory of them. Subscribing to a path again refreshes the
+/// existing pack. Returns `None` when the picker is cancelled.
+#[tauri::command]
+#[specta::specta]
+pub async fn term_pack_subscribe(
+    directory: bool,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<Option<crate::domain::TermPackSyncSummary>, String> {
+    let dialog = rfd::AsyncFileDialog::new();
+    let picked = if directory {
+        dialog.pick_folder().await
+    } else {
+        dialog
+            .add_filter("Term pack", &["csv", "json"])
+            .pick_file()
+            .await
+    };
+
+    let source_path = match picked {
+        Some(handle) => handle.path().to_path_buf(),
+        None => return Ok(None),
+    };
+
+    sync_term_pack(database.pool(), source_path, None)
+        .await
+        .map(Some)
+}
+
+/// Re-reads subscribed packs from disk and applies any changes. Syncs every
+/// pack when `id` is omitted; a pack whose source cannot be read keeps its
+/// current terms and reports the error.
+#[tauri::command]
+#[specta::specta]
+pub async fn term_pack_sync(
+    id: Option<String>,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<Vec<crate::domain::TermPackSyncSummary>, String> {
+    let pool = database.pool();
+    let packs = crate::db::term_pack_queries::fetch_packs(pool.clone())
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let mut summaries = Vec::new();
+    for pack in packs {
+        if id.as_deref().is_some_and(|id| id != pack.id) {
+            continue;
+        }
+
+        let source_path = std::path::PathBuf::from(&pack.source_path);
+        let summary = match sync_term_pack(pool.clone(), source_path, Some(&pack)).await {
+            Ok(summary) => summary,
+            Err(err) => {
+                crate::db::term_pack_queries::record_sync_error(pool.clone(), &pack.id, &err)
+                    .await
+                    .map_err(|err| err.to_string())?;
+                crate::domain::TermPackSyncSummary {
+                    pack_id: pack.id.clone(),
+                    errors: vec![err],
+                    ..Default::default()
+                }
+            }
+        };
+        summaries.push(summary);
+    }
+
+    Ok(summaries)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn term_pack_unsubscribe(
+    id: String,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<(), String> {
+    crate::db::term_pack_queries::delete_pack(database.pool(), &id)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+async fn sync_term_pack(
+    pool: sqlx::SqlitePool,
+    source_path: std::path::PathBuf,
+    existing: Option<&crate::domain::TermPack>,
+) -> Result<crate::domain::TermPackSyncSummary, String> {
+    use crate::system::term_transfer;
+
+    let pack_id = existing
+        .map(|pack| pack.id.clone())
+        .unwrap_or_else(|| term_transfer::pack_id_for(&source_path));
+    let read_path = source_path.clone();
+    let parsed = tauri::async_runtime::spawn_blocking(move || term_transfer::read_pack(&read_path))
+        .await
+        .map_err(|err| err.to_string())??;
+
+    let existing = match existing {
+        Some(pack) => Some(pack.clone()),
+        None => crate::db::term_pack_queries::fetch_pack(pool.clone(), &pack_id)
+            .await
+            .map_err(|err| err.to_string())?,
+    };
+    let now = current_timestamp_millis()?;
+    let pack = crate::domain::TermPack {
+        id: pack_id.clone(),
+        name: parsed.name.unwrap_or_else(|| pack_id.clone()),
+        source_path: source_path.to_string_lossy().to_string(),
+        version: parsed.version,
+        term_count: 0,
+        subscribed_at: existing.as_ref().map_or(now, |pack| pack.subscribed_at),
+        last_synced_at: Some(now),
+        last_error: None,
+    };
+    crate::db::term_pack_queries::upsert_pack(pool.clone(), &pack)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let terms = parsed
+        .records
+        .into_iter()
+        .map(|record| {
+            let id = term_transfer::pack_term_id(&pack_id, &record.source_value);
+            record.into_term(id, now, Some(pack_id.clone()))
+        })
+        .collect::<Vec<_>>();
+    let mut summary = crate::db::term_pack_queries::replace_pack_terms(pool, &pack_id, &terms)
+        .await
+        .map_err(|err| err.to_string())?;
+    summary.errors = parsed.errors;
+
+    Ok(summary)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn hotkey_list(
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<Vec<crate::domain::Hotkey>, String> {
+    crate::db::hotkey_queries::fetch_hotkeys(database.pool())
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn hotkey_save(
+    hotkey: crate::domain::Hotkey,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<crate::domain::Hotkey, String> {
+    crate::db::hotkey_queries::upsert_hotkey(database.pool(), &hotkey)
+        .await
+        .map_err(|err| err.to_string())
+}
 
 #[tauri::command]
 #[specta::specta]
@@ -1036,7 +1782,7 @@ pub async fn api_key_create(
         include_v1_path,
     } = api_key;
 
-    let protected = protect_api_key(&key);
+    let protected = protect_api_key(&key).map_err(|err| err.to_string())?;
     let created_at = current_timestamp_millis()?;
 
     let stored = ApiKey {
@@ -1106,7 +1852,7 @@ pub async fn api_key_update(
     let (salt, key_hash, key_ciphertext, key_suffix, full_key) =
         match request.key.as_deref().filter(|k| !k.is_empty()) {
             Some(raw_key) => {
-                let protected = protect_api_key(raw_key);
+                let protected = protect_api_key(raw_key).map_err(|err| err.to_string())?;
                 (
                     Some(protected.salt_b64),
                     Some(protected.hash_b64),
@@ -1159,6 +1905,7 @@ pub async fn tone_upsert(
     tone: crate::domain::Tone,
     database: State<'_, crate::state::OptionKeyDatabase>,
 ) -> Result<crate::domain::Tone, String> {
+    crate::system::tone_library::ensure_valid_template(&tone.prompt_template)?;
     let pool = database.pool();
 
     if let Some(existing) = crate::db::tone_queries::fetch_tone_by_id(pool.clone(), &tone.id)
@@ -1222,6 +1969,364 @@ pub async fn tone_delete(
         .map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn tone_validate_template(template: String) -> crate::domain::ToneTemplateValidation {
+    crate::system::tone_library::validate_template(&template)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn tone_version_list(
+    tone_id: String,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<Vec<crate::domain::ToneVersion>, String> {
+    crate::db::tone_version_queries::fetch_versions(database.pool(), &tone_id)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+/// Diffs the template of `from_version_id` against `to_version_id`, or
+/// against the tone's current template when no target is given.
+#[tauri::command]
+#[specta::specta]
+pub async fn tone_version_diff(
+    tone_id: String,
+    from_version_id: String,
+    to_version_id: Option<String>,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<Vec<crate::domain::ToneDiffLine>, String> {
+    let pool = database.pool();
+    let from =
+        crate::db::tone_version_queries::fetch_version(pool.clone(), &tone_id, &from_version_id)
+            .await
+            .map_err(|err| err.to_string())?
+            .ok_or_else(|| format!("Tone version {from_version_id} not found"))?;
+
+    let to_template = match to_version_id {
+        Some(version_id) => {
+            crate::db::tone_version_queries::fetch_version(pool, &tone_id, &version_id)
+                .await
+                .map_err(|err| err.to_string())?
+                .ok_or_else(|| format!("Tone version {version_id} not found"))?
+                .prompt_template
+        }
+        None => {
+            crate::db::tone_queries::fetch_tone_by_id(pool, &tone_id)
+                .await
+                .map_err(|err| err.to_string())?
+                .ok_or_else(|| format!("Tone {tone_id} not found"))?
+                .prompt_template
+        }
+    };
+
+    Ok(crate::system::tone_library::diff_templates(
+        &from.prompt_template,
+        &to_template,
+    ))
+}
+
+/// Rolls a tone back to an earlier version. The rollback is itself saved
+/// as a new version, so it can be undone the same way.
+#[tauri::command]
+#[specta::specta]
+pub async fn tone_version_restore(
+    tone_id: String,
+    version_id: String,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<crate::domain::Tone, String> {
+    let pool = database.pool();
+    let version =
+        crate::db::tone_version_queries::fetch_version(pool.clone(), &tone_id, &version_id)
+            .await
+            .map_err(|err| err.to_string())?
+            .ok_or_else(|| format!("Tone version {version_id} not found"))?;
+    crate::system::tone_library::ensure_valid_template(&version.prompt_template)?;
+
+    let tone = crate::db::tone_queries::fetch_tone_by_id(pool.clone(), &tone_id)
+        .await
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| format!("Tone {tone_id} not found"))?;
+    let restored = crate::domain::Tone {
+        name: version.name,
+        prompt_template: version.prompt_template,
+        ..tone
+    };
+
+    crate::db::tone_queries::update_tone(pool, &restored)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn tone_export(
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<bool, String> {
+    let tones = crate::db::tone_queries::fetch_all_tones(database.pool())
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let dialog = rfd::AsyncFileDialog::new()
+        .set_file_name("voquill-tones.json")
+        .add_filter("Voquill tones", &["json"])
+        .save_file()
+        .await;
+
+    let save_path = match dialog {
+        Some(handle) => handle.path().to_path_buf(),
+        None => return Ok(false),
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let document = crate::system::tone_library::render_tone_file(&tones)?;
+        std::fs::write(&save_path, document)
+            .map_err(|err| format!("Failed to write export: {err}"))?;
+        Ok::<bool, String>(true)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+/// Imports a tone file. Tones are matched by id, then by name; matches are
+/// updated (adding a version) and the rest are created. Returns `None` when
+/// the file picker is cancelled.
+#[tauri::command]
+#[specta::specta]
+pub async fn tone_import(
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<Option<crate::domain::ToneImportSummary>, String> {
+    let dialog = rfd::AsyncFileDialog::new()
+        .add_filter("Voquill tones", &["json"])
+        .pick_file()
+        .await;
+
+    let import_path = match dialog {
+        Some(handle) => handle.path().to_path_buf(),
+        None => return Ok(None),
+    };
+
+    let parsed = tauri::async_runtime::spawn_blocking(move || {
+        let contents = std::fs::read_to_string(&import_path)
+            .map_err(|err| format!("Failed to read import: {err}"))?;
+        crate::system::tone_library::parse_tone_file(&contents)
+    })
+    .await
+    .map_err(|err| err.to_string())??;
+
+    let pool = database.pool();
+    let mut existing = crate::db::tone_queries::fetch_all_tones(pool.clone())
+        .await
+        .map_err(|err| err.to_string())?;
+    let mut summary = crate::domain::ToneImportSummary {
+        errors: parsed.errors,
+        ..Default::default()
+    };
+    let created_at = current_timestamp_millis()?;
+
+    for entry in parsed.tones {
+        let matched = existing
+            .iter()
+            .position(|tone| entry.id.as_deref() == Some(tone.id.as_str()))
+            .or_else(|| {
+                existing
+                    .iter()
+                    .position(|tone| tone.name.trim().eq_ignore_ascii_case(entry.name.trim()))
+            });
+
+        match matched {
+            Some(index) => {
+                let tone = &existing[index];
+                if tone.name == entry.name && tone.prompt_template == entry.prompt_template {
+                    summary.unchanged += 1;
+                    continue;
+                }
+                let updated = crate::domain::Tone {
+                    name: entry.name,
+                    prompt_template: entry.prompt_template,
+                    sort_order: entry.sort_order.unwrap_or(tone.sort_order),
+                    ..tone.clone()
+                };
+                match crate::db::tone_queries::update_tone(pool.clone(), &updated).await {
+                    Ok(updated) => {
+                        existing[index] = updated;
+                        summary.updated += 1;
+                    }
+                    Err(err) => summary.errors.push(format!("{}: {err}", updated.name)),
+                }
+            }
+            None => {
+                let id = entry
+                    .id
+                    .unwrap_or_else(|| format!("tone-{:016x}", rand::random::<u64>()));
+                let tone = crate::domain::Tone {
+                    id,
+                    name: entry.name,
+                    prompt_template: entry.prompt_template,
+                    created_at,
+                    sort_order: entry.sort_order.unwrap_or(existing.len() as i32),
+                };
+                match crate::db::tone_queries::insert_tone(pool.clone(), &tone).await {
+                    Ok(created) => {
+                        existing.push(created);
+                        summary.created += 1;
+                    }
+                    Err(err) => summary.errors.push(format!("{}: {err}", tone.name)),
+                }
+            }
+        }
+    }
+
+    Ok(Some(summary))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn backup_create(
+    app: AppHandle,
+    passphrase: Option<String>,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<Option<crate::domain::BackupManifest>, String> {
+    use crate::system::backup::BACKUP_EXTENSION;
+
+    let date = chrono::Local::now().format("%Y-%m-%d");
+    let dialog = rfd::AsyncFileDialog::new()
+        .set_file_name(format!("voquill-backup-{date}.{BACKUP_EXTENSION}"))
+        .add_filter("Voquill backup", &[BACKUP_EXTENSION])
+        .save_file()
+        .await;
+
+    let save_path = match dialog {
+        Some(handle) => handle.path().to_path_buf(),
+        None => return Ok(None),
+    };
+
+    let passphrase = passphrase.filter(|value| !value.is_empty());
+    crate::system::backup::create_backup(&app, &database.pool(), &save_path, passphrase)
+        .await
+        .map(Some)
+        .map_err(|err| err.to_string())
+}
+
+/// Validates and stages a backup. The data is swapped in on the next launch,
+/// so callers should relaunch the app once this returns a manifest.
+#[tauri::command]
+#[specta::specta]
+pub async fn backup_restore(
+    app: AppHandle,
+    passphrase: Option<String>,
+) -> Result<Option<crate::domain::BackupManifest>, String> {
+    use crate::system::backup::BACKUP_EXTENSION;
+
+    let dialog = rfd::AsyncFileDialog::new()
+        .add_filter("Voquill backup", &[BACKUP_EXTENSION])
+        .pick_file()
+        .await;
+
+    let source = match dialog {
+        Some(handle) => handle.path().to_path_buf(),
+        None => return Ok(None),
+    };
+
+    let passphrase = passphrase.filter(|value| !value.is_empty());
+    crate::system::backup::stage_restore(&app, &source, passphrase)
+        .await
+        .map(Some)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn backup_schedule_get(app: AppHandle) -> Result<crate::domain::BackupSchedule, String> {
+    crate::system::backup::load_schedule(&app).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn backup_schedule_set(
+    app: AppHandle,
+    schedule: crate::domain::BackupSchedule,
+) -> Result<crate::domain::BackupSchedule, String> {
+    crate::system::backup::save_schedule(&app, &schedule).map_err(|err| err.to_string())?;
+    Ok(schedule)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn vault_get_enabled() -> bool {
+    crate::system::vault::is_enabled()
+}
+
+/// Turns the encrypted vault on or off, then rewrites stored transcripts,
+/// queued remote texts and audio to match. Reads accept both forms, so an interrupted run is safe
+/// and simply completes on the next call.
+#[tauri::command]
+#[specta::specta]
+pub async fn vault_set_enabled(
+    app: AppHandle,
+    enabled: bool,
+    database: State<'_, crate::state::OptionKeyDatabase>,
+) -> Result<bool, String> {
+    const PAGE_SIZE: u32 = 200;
+
+    crate::system::vault::set_enabled(&app, enabled).map_err(|err| err.to_string())?;
+    let pool = database.pool();
+
+    let mut offset = 0;
+    loop {
+        let page =
+            crate::db::transcription_queries::fetch_transcriptions(pool.clone(), PAGE_SIZE, offset)
+                .await
+                .map_err(|err| err.to_string())?;
+        let page_len = page.len() as u32;
+
+        for transcription in page {
+            crate::db::transcription_queries::update_transcription(pool.clone(), &transcription)
+                .await
+                .map_err(|err| err.to_string())?;
+        }
+
+        if page_len < PAGE_SIZE {
+            break;
+        }
+        offset += page_len;
+    }
+
+    crate::db::transcription_version_queries::reseal_versions(pool.clone(), PAGE_SIZE)
+        .await
+        .map_err(|err| err.to_string())?;
+    crate::db::remote_outbox_queries::reseal_remote_outbox_items(pool.clone())
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let audio_dir = crate::system::audio_store::audio_dir(&app).map_err(|err| err.to_string())?;
+    tauri::async_runtime::spawn_blocking(move || {
+        for entry in std::fs::read_dir(&audio_dir).map_err(|err| err.to_string())? {
+            let path = entry.map_err(|err| err.to_string())?.path();
+            if path.is_file() {
+                crate::system::vault::migrate_file(&path)
+                    .map_err(|err| format!("{}: {err}", path.display()))?;
+            }
+        }
+        Ok::<(), String>(())
+    })
+    .await
+    .map_err(|err| err.to_string())??;
+
+    if enabled {
+        // Drop plaintext left behind in index segments and free pages.
+        sqlx::query("INSERT INTO transcriptions_fts (transcriptions_fts) VALUES ('optimize')")
+            .execute(&pool)
+            .await
+            .map_err(|err| err.to_string())?;
+        if let Err(err) = sqlx::query("VACUUM").execute(&pool).await {
+            log::warn!("VACUUM failed after enabling the vault: {err}");
+        }
+    }
+
+    Ok(enabled)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn clear_local_data(
@@ -1230,12 +2335,14 @@ pub async fn clear_local_data(
     let pool = database.pool();
     let mut transaction = pool.begin().await.map_err(|err| err.to_string())?;
 
-    const TABLES_TO_CLEAR: [&str; 8] = [
+    const TABLES_TO_CLEAR: [&str; 10] = [
         "chat_messages",
         "conversations",
         "user_profiles",
         "transcriptions",
+        "transcription_versions",
         "terms",
+        "term_packs",
         "hotkeys",
         "api_keys",
         "user_preferences",
@@ -1390,6 +2497,7 @@ pub async fn store_transcription_audio(
     id: String,
     samples: Vec<f64>,
     sample_rate: u32,
+    database: State<'_, crate::state::OptionKeyDatabase>,
 ) -> Result<TranscriptionAudioSnapshot, String> {
     if sample_rate == 0 {
         return Err("Audio sample rate must be greater than zero".to_string());
@@ -1406,6 +2514,11 @@ pub async fn store_transcription_audio(
         return Err("No usable audio samples provided".to_string());
     }
 
+    let format = crate::db::preferences_queries::fetch_audio_storage_format(database.pool())
+        .await
+        .map_err(|err| err.to_string())?;
+    let format = crate::system::audio_store::AudioStorageFormat::from_preference(&format);
+
     let handle = app.clone();
     let audio_id = id.clone();
 
@@ -1415,6 +2528,7 @@ pub async fn store_transcription_audio(
             &audio_id,
             &filtered,
             sample_rate,
+            format,
         )
         .map_err(|err| err.to_string())
     })
@@ -1451,11 +2565,16 @@ pub fn storage_get_download_url(app: AppHandle, path: String) -> Result<String,
 pub async fn purge_stale_transcription_audio(
     app: AppHandle,
     database: State<'_, crate::state::OptionKeyDatabase>,
-) -> Result<Vec<String>, String> {
+) -> Result<crate::domain::AudioPurgeSummary, String> {
+    use crate::system::audio_store::{self, StoredAudioEntry};
+
     let pool = database.pool();
+    let policy = crate::db::preferences_queries::fetch_audio_retention_policy(pool.clone())
+        .await
+        .map_err(|err| err.to_string())?;
 
     let rows = sqlx::query(
-        "SELECT id, audio_path
+        "SELECT id, audio_path, timestamp
          FROM transcriptions
          WHERE audio_path IS NOT NULL
          ORDER BY timestamp DESC",
@@ -1464,28 +2583,57 @@ pub async fn purge_stale_transcription_audio(
     .await
     .map_err(|err| err.to_string())?;
 
-    let stale_entries: Vec<(String, String)> = rows
+    let candidates: Vec<(String, PathBuf, i64)> = rows
         .into_iter()
-        .skip(MAX_RETAINED_TRANSCRIPTION_AUDIO)
         .map(|row| {
             (
                 row.get::<String, _>("id"),
-                row.get::<String, _>("audio_path"),
+                PathBuf::from(row.get::<String, _>("audio_path")),
+                row.get::<i64, _>("timestamp"),
             )
         })
         .collect();
 
-    if stale_entries.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    let purged_ids = delete_audio_entries(app.clone(), stale_entries).await?;
-
-    if purged_ids.is_empty() {
-        return Ok(purged_ids);
-    }
+    let purge_app = app.clone();
+    let summary = tauri::async_runtime::spawn_blocking(move || {
+        let entries = candidates
+            .into_iter()
+            .map(|(id, path, timestamp)| {
+                let size_bytes = std::fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+                StoredAudioEntry {
+                    id,
+                    path,
+                    timestamp,
+                    size_bytes,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut summary = crate::domain::AudioPurgeSummary::default();
+        for index in audio_store::select_for_purge(&entries, &policy, now_ms) {
+            let entry = &entries[index];
+            // A file that is already gone counts as deleted. Other failures
+            // keep the audio path so the next pass retries them.
+            match audio_store::delete_audio_file(&purge_app, &entry.path) {
+                Ok(()) => {
+                    summary.reclaimed_bytes += entry.size_bytes as i64;
+                    summary.purged_ids.push(entry.id.clone());
+                }
+                Err(err) => {
+                    log::error!(
+                        "Failed to delete audio file for transcription {}: {err}",
+                        entry.id
+                    );
+                }
+            }
+        }
+        summary
+    })
+    .await
+    .map_err(|err| err.to_string())?;
 
-    for id in &purged_ids {
+    for id in &summary.purged_ids {
         sqlx::query(
             "UPDATE transcriptions
              SET audio_path = NULL,
@@ -1498,7 +2646,15 @@ pub async fn purge_stale_transcription_audio(
         .map_err(|err| err.to_string())?;
     }
 
-    Ok(purged_ids)
+    if summary.reclaimed_bytes > 0 {
+        log::info!(
+            "Purged audio for {} transcriptions, reclaimed {} bytes",
+            summary.purged_ids.len(),
+            summary.reclaimed_bytes
+        );
+    }
+
+    Ok(summary)
 }
 
 #[tauri::command]
@@ -1525,6 +2681,9 @@ pub fn set_pill_window_size(
 #[tauri::command]
 #[specta::specta]
 pub fn sync_native_pill_assistant(app: AppHandle, payload: String) {
+    if let Some(event) = crate::system::bridge_server::error_from_assistant_payload(&payload) {
+        crate::system::bridge_server::publish(event);
+    }
     crate::platform::overlay::notify_assistant_state(&app, &payload);
 }
 
@@ -1544,6 +2703,26 @@ pub async fn paste(
     text: String,
     keybind: Option<String>,
     skip_clipboard_restore: Option<bool>,
+) -> Result<PasteOutcome, String> {
+    let result = paste_inner(text, keybind, skip_clipboard_restore).await;
+
+    let (outcome, error) = match &result {
+        Ok(PasteOutcome::Pasted) => ("pasted", None),
+        Ok(PasteOutcome::CopiedToClipboard) => ("copied_to_clipboard", None),
+        Err(err) => ("failed", Some(err.clone())),
+    };
+    crate::system::bridge_server::publish(crate::system::bridge_server::BridgeEvent::PasteResult {
+        outcome: outcome.to_string(),
+        error,
+    });
+
+    result
+}
+
+async fn paste_inner(
+    text: String,
+    keybind: Option<String>,
+    skip_clipboard_restore: Option<bool>,
 ) -> Result<PasteOutcome, String> {
     // Probe the focused target first. If it clearly can't accept text, write
     // the transcript to the clipboard and skip the paste keystroke entirely —
@@ -1617,8 +2796,12 @@ pub fn set_phase(
     let resolved =
         OverlayPhase::parse(phase.as_str()).ok_or_else(|| format!("invalid phase: {phase}"))?;
 
+    let previous = overlay_state.get_phase();
     overlay_state.set_phase(&resolved);
     crate::platform::overlay::notify_phase(&app, &resolved);
+    for event in crate::system::bridge_server::phase_events(&previous, &resolved) {
+        crate::system::bridge_server::publish(event);
+    }
 
     let payload = OverlayPhasePayload {
         phase: resolved.clone(),
@@ -1,6 +1,7 @@
 use sqlx::{Row, SqlitePool};
 
 use crate::domain::{ApiKey, ApiKeyUpdateRequest};
+use crate::system::crypto::AEAD_PREFIX;
 
 pub async fn insert_api_key(pool: SqlitePool, api_key: &ApiKey) -> Result<ApiKey, sqlx::Error> {
     sqlx::query(
@@ -100,6 +101,61 @@ pub async fn update_api_key(
     Ok(())
 }
 
+/// The table is created by the SQL plugin's migrations, which only run once
+/// the frontend opens the database; on a fresh install it may not exist yet.
+pub async fn api_keys_table_exists(pool: SqlitePool) -> Result<bool, sqlx::Error> {
+    let count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(1)
+         FROM sqlite_master
+         WHERE type = 'table' AND name = 'api_keys'",
+    )
+    .fetch_one(&pool)
+    .await?;
+
+    Ok(count > 0)
+}
+
+/// Whether any key is sealed under the master key, which proves a master key
+/// was created before.
+pub async fn has_sealed_api_keys(pool: SqlitePool) -> Result<bool, sqlx::Error> {
+    if !api_keys_table_exists(pool.clone()).await? {
+        return Ok(false);
+    }
+
+    let exists: i64 = sqlx::query_scalar(
+        "SELECT EXISTS(
+             SELECT 1 FROM api_keys WHERE key_ciphertext LIKE ?1 || '%'
+         )",
+    )
+    .bind(AEAD_PREFIX)
+    .fetch_one(&pool)
+    .await?;
+
+    Ok(exists != 0)
+}
+
+pub async fn update_api_key_secret(
+    pool: SqlitePool,
+    id: &str,
+    salt: &str,
+    key_hash: &str,
+    key_ciphertext: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE api_keys
+         SET salt = ?2, key_hash = ?3, key_ciphertext = ?4
+         WHERE id = ?1",
+    )
+    .bind(id)
+    .bind(salt)
+    .bind(key_hash)
+    .bind(key_ciphertext)
+    .execute(&pool)
+    .await?;
+
+    Ok(())
+}
+
 pub async fn delete_api_key(pool: SqlitePool, id: &str) -> Result<(), sqlx::Error> {
     sqlx::query(
         "DELETE FROM api_keys
@@ -0,0 +1 @@
+ALTER TABLE transcriptions ADD COLUMN confidence_json TEXT;
@@ -0,0 +1,35 @@
+-- Standalone (not external-content) FTS table keyed by the transcription id:
+-- `transcriptions` has a TEXT primary key, so its implicit rowid can change
+-- on VACUUM and cannot be used to join back.
+CREATE VIRTUAL TABLE IF NOT EXISTS transcriptions_fts USING fts5(
+    id UNINDEXED,
+    transcript,
+    raw_transcript,
+    sanitized_transcript,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+INSERT INTO transcriptions_fts (id, transcript, raw_transcript, sanitized_transcript)
+SELECT id, transcript, raw_transcript, sanitized_transcript
+FROM transcriptions;
+
+CREATE TRIGGER IF NOT EXISTS transcriptions_fts_after_insert
+AFTER INSERT ON transcriptions
+BEGIN
+    INSERT INTO transcriptions_fts (id, transcript, raw_transcript, sanitized_transcript)
+    VALUES (new.id, new.transcript, new.raw_transcript, new.sanitized_transcript);
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcriptions_fts_after_delete
+AFTER DELETE ON transcriptions
+BEGIN
+    DELETE FROM transcriptions_fts WHERE id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcriptions_fts_after_update
+AFTER UPDATE OF id, transcript, raw_transcript, sanitized_transcript ON transcriptions
+BEGIN
+    DELETE FROM transcriptions_fts WHERE id = old.id;
+    INSERT INTO transcriptions_fts (id, transcript, raw_transcript, sanitized_transcript)
+    VALUES (new.id, new.transcript, new.raw_transcript, new.sanitized_transcript);
+END;
@@ -0,0 +1,26 @@
+-- Transcripts sealed by the encrypted vault (prefixed `vault:v1:`) are kept
+-- out of the full-text index; it would otherwise hold only ciphertext.
+DROP TRIGGER IF EXISTS transcriptions_fts_after_insert;
+DROP TRIGGER IF EXISTS transcriptions_fts_after_update;
+
+CREATE TRIGGER IF NOT EXISTS transcriptions_fts_after_insert
+AFTER INSERT ON transcriptions
+BEGIN
+    INSERT INTO transcriptions_fts (id, transcript, raw_transcript, sanitized_transcript)
+    SELECT new.id, new.transcript, new.raw_transcript, new.sanitized_transcript
+    WHERE new.transcript NOT LIKE 'vault:v1:%';
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcriptions_fts_after_update
+AFTER UPDATE OF id, transcript, raw_transcript, sanitized_transcript ON transcriptions
+BEGIN
+    DELETE FROM transcriptions_fts WHERE id = old.id;
+    INSERT INTO transcriptions_fts (id, transcript, raw_transcript, sanitized_transcript)
+    SELECT new.id, new.transcript, new.raw_transcript, new.sanitized_transcript
+    WHERE new.transcript NOT LIKE 'vault:v1:%';
+END;
+
+DELETE FROM transcriptions_fts
+WHERE id IN (
+    SELECT id FROM transcriptions WHERE transcript LIKE 'vault:v1:%'
+);
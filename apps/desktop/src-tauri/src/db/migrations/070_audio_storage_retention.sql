@@ -0,0 +1,5 @@
+ALTER TABLE user_preferences ADD COLUMN audio_storage_format TEXT NOT NULL DEFAULT 'flac';
+-- Existing installs keep the previous fixed limit of 20 clips. NULL means no limit.
+ALTER TABLE user_preferences ADD COLUMN audio_retention_max_count INTEGER DEFAULT 20;
+ALTER TABLE user_preferences ADD COLUMN audio_retention_max_age_days INTEGER;
+ALTER TABLE user_preferences ADD COLUMN audio_retention_max_total_mb INTEGER;
@@ -0,0 +1,69 @@
+CREATE TABLE IF NOT EXISTS transcription_versions (
+    id TEXT PRIMARY KEY,
+    transcription_id TEXT NOT NULL,
+    created_at INTEGER NOT NULL,
+    transcript TEXT NOT NULL,
+    raw_transcript TEXT,
+    sanitized_transcript TEXT,
+    confidence_json TEXT,
+    transcription_mode TEXT,
+    model_size TEXT,
+    inference_device TEXT,
+    transcription_prompt TEXT,
+    transcription_api_key_id TEXT,
+    transcription_duration_ms INTEGER,
+    post_process_mode TEXT,
+    post_process_device TEXT,
+    post_process_prompt TEXT,
+    post_process_api_key_id TEXT,
+    postprocess_duration_ms INTEGER
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcription_versions_transcription
+    ON transcription_versions (transcription_id, created_at);
+
+ALTER TABLE transcriptions ADD COLUMN active_version_id TEXT;
+
+-- Existing results become the first version of each transcription.
+INSERT INTO transcription_versions (
+    id,
+    transcription_id,
+    created_at,
+    transcript,
+    raw_transcript,
+    sanitized_transcript,
+    confidence_json,
+    transcription_mode,
+    model_size,
+    inference_device,
+    transcription_prompt,
+    transcription_api_key_id,
+    transcription_duration_ms,
+    post_process_mode,
+    post_process_device,
+    post_process_prompt,
+    post_process_api_key_id,
+    postprocess_duration_ms
+)
+SELECT
+    id,
+    id,
+    timestamp,
+    transcript,
+    raw_transcript,
+    sanitized_transcript,
+    confidence_json,
+    transcription_mode,
+    model_size,
+    inference_device,
+    transcription_prompt,
+    transcription_api_key_id,
+    transcription_duration_ms,
+    post_process_mode,
+    post_process_device,
+    post_process_prompt,
+    post_process_api_key_id,
+    postprocess_duration_ms
+FROM transcriptions;
+
+UPDATE transcriptions SET active_version_id = id;
@@ -0,0 +1,3 @@
+ALTER TABLE terms ADD COLUMN match_mode TEXT NOT NULL DEFAULT 'whole_word';
+ALTER TABLE terms ADD COLUMN case_sensitive INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE terms ADD COLUMN priority INTEGER NOT NULL DEFAULT 0;
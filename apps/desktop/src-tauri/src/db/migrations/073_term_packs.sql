@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS term_packs (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    source_path TEXT NOT NULL UNIQUE,
+    version TEXT,
+    subscribed_at INTEGER NOT NULL,
+    last_synced_at INTEGER,
+    last_error TEXT
+);
+
+ALTER TABLE terms ADD COLUMN pack_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_terms_pack_id ON terms(pack_id);
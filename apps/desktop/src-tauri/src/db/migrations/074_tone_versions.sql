@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS tone_versions (
+    id TEXT PRIMARY KEY,
+    tone_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    prompt_template TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_tone_versions_tone ON tone_versions(tone_id, created_at);
+
+-- Every existing tone starts its history with its current template.
+INSERT INTO tone_versions (id, tone_id, name, prompt_template, created_at)
+SELECT id, id, name, prompt_template, created_at
+FROM tones;
@@ -0,0 +1 @@
+ALTER TABLE remote_outbox ADD COLUMN utterance_id TEXT;
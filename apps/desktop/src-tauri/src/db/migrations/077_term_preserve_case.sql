@@ -0,0 +1 @@
+ALTER TABLE terms ADD COLUMN preserve_case INTEGER NOT NULL DEFAULT 0;
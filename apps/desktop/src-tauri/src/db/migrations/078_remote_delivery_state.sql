@@ -0,0 +1,11 @@
+ALTER TABLE remote_outbox ADD COLUMN confirmed INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS remote_delivered_events (
+    sender_device_id TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    delivered_at INTEGER NOT NULL,
+    PRIMARY KEY (sender_device_id, event_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_remote_delivered_events_sender
+    ON remote_delivered_events(sender_device_id, delivered_at);
@@ -5,9 +5,14 @@ pub mod conversation_queries;
 pub mod hotkey_queries;
 pub mod paired_remote_device_queries;
 pub mod preferences_queries;
+pub mod remote_delivered_event_queries;
+pub mod remote_outbox_queries;
+pub mod term_pack_queries;
 pub mod term_queries;
 pub mod tone_queries;
+pub mod tone_version_queries;
 pub mod transcription_queries;
+pub mod transcription_version_queries;
 pub mod user_queries;
 
 pub const DB_FILENAME: &str = "voquill.db";
@@ -125,6 +130,27 @@ pub const DICTATION_LIMIT_MINUTES_MIGRATION_SQL: &str =
     include_str!("migrations/065_dictation_limit_minutes.sql");
 pub const MENU_BAR_ICON_HIDDEN_MIGRATION_SQL: &str =
     include_str!("migrations/066_menu_bar_icon_hidden.sql");
+pub const TRANSCRIPTION_CONFIDENCE_MIGRATION_SQL: &str =
+    include_str!("migrations/067_transcription_confidence.sql");
+pub const TRANSCRIPTION_SEARCH_MIGRATION_SQL: &str =
+    include_str!("migrations/068_transcription_search.sql");
+pub const TRANSCRIPTION_SEARCH_VAULT_MIGRATION_SQL: &str =
+    include_str!("migrations/069_transcription_search_vault.sql");
+pub const AUDIO_STORAGE_RETENTION_MIGRATION_SQL: &str =
+    include_str!("migrations/070_audio_storage_retention.sql");
+pub const TRANSCRIPTION_VERSIONS_MIGRATION_SQL: &str =
+    include_str!("migrations/071_transcription_versions.sql");
+pub const TERM_MATCH_OPTIONS_MIGRATION_SQL: &str =
+    include_str!("migrations/072_term_match_options.sql");
+pub const TERM_PACKS_MIGRATION_SQL: &str = include_str!("migrations/073_term_packs.sql");
+pub const TONE_VERSIONS_MIGRATION_SQL: &str = include_str!("migrations/074_tone_versions.sql");
+pub const REMOTE_OUTBOX_MIGRATION_SQL: &str = include_str!("migrations/075_remote_outbox.sql");
+pub const REMOTE_OUTBOX_UTTERANCE_MIGRATION_SQL: &str =
+    include_str!("migrations/076_remote_outbox_utterance.sql");
+pub const TERM_PRESERVE_CASE_MIGRATION_SQL: &str =
+    include_str!("migrations/077_term_preserve_case.sql");
+pub const REMOTE_DELIVERY_STATE_MIGRATION_SQL: &str =
+    include_str!("migrations/078_remote_delivery_state.sql");
 
 pub fn migrations() -> Vec<tauri_plugin_sql::Migration> {
     vec![
@@ -524,5 +550,95 @@ pub fn migrations() -> Vec<tauri_plugin_sql::Migration> {
             sql: MENU_BAR_ICON_HIDDEN_MIGRATION_SQL,
             kind: tauri_plugin_sql::MigrationKind::Up,
         },
+        tauri_plugin_sql::Migration {
+            version: 67,
+            description: "add_transcription_confidence",
+            sql: TRANSCRIPTION_CONFIDENCE_MIGRATION_SQL,
+            kind: tauri_plugin_sql::MigrationKind::Up,
+        },
+        tauri_plugin_sql::Migration {
+            version: 68,
+            description: "add_transcription_search",
+            sql: TRANSCRIPTION_SEARCH_MIGRATION_SQL,
+            kind: tauri_plugin_sql::MigrationKind::Up,
+        },
+        tauri_plugin_sql::Migration {
+            version: 69,
+            description: "skip_vault_sealed_transcripts_in_search",
+            sql: TRANSCRIPTION_SEARCH_VAULT_MIGRATION_SQL,
+            kind: tauri_plugin_sql::MigrationKind::Up,
+        },
+        tauri_plugin_sql::Migration {
+            version: 70,
+            description: "add_audio_storage_retention",
+            sql: AUDIO_STORAGE_RETENTION_MIGRATION_SQL,
+            kind: tauri_plugin_sql::MigrationKind::Up,
+        },
+        tauri_plugin_sql::Migration {
+            version: 71,
+            description: "add_transcription_versions",
+            sql: TRANSCRIPTION_VERSIONS_MIGRATION_SQL,
+            kind: tauri_plugin_sql::MigrationKind::Up,
+        },
+        tauri_plugin_sql::Migration {
+            version: 72,
+            description: "add_term_match_options",
+            sql: TERM_MATCH_OPTIONS_MIGRATION_SQL,
+            kind: tauri_plugin_sql::MigrationKind::Up,
+        },
+        tauri_plugin_sql::Migration {
+            version: 73,
+            description: "create_term_packs",
+            sql: TERM_PACKS_MIGRATION_SQL,
+            kind: tauri_plugin_sql::MigrationKind::Up,
+        },
+        tauri_plugin_sql::Migration {
+            version: 74,
+            description: "create_tone_versions",
+            sql: TONE_VERSIONS_MIGRATION_SQL,
+            kind: tauri_plugin_sql::MigrationKind::Up,
+        },
+        tauri_plugin_sql::Migration {
+            version: 75,
+            description: "create_remote_outbox",
+            sql: REMOTE_OUTBOX_MIGRATION_SQL,
+            kind: tauri_plugin_sql::MigrationKind::Up,
+        },
+        tauri_plugin_sql::Migration {
+            version: 76,
+            description: "add_remote_outbox_utterance",
+            sql: REMOTE_OUTBOX_UTTERANCE_MIGRATION_SQL,
+            kind: tauri_plugin_sql::MigrationKind::Up,
+        },
+        tauri_plugin_sql::Migration {
+            version: 77,
+            description: "add_term_preserve_case",
+            sql: TERM_PRESERVE_CASE_MIGRATION_SQL,
+            kind: tauri_plugin_sql::MigrationKind::Up,
+        },
+        tauri_plugin_sql::Migration {
+            version: 78,
+            description: "add_remote_delivery_state",
+            sql: REMOTE_DELIVERY_STATE_MIGRATION_SQL,
+            kind: tauri_plugin_sql::MigrationKind::Up,
+        },
     ]
 }
+
+/// Opens an in-memory database with every migration applied. A single
+/// connection is used because each in-memory connection is its own database.
+#[cfg(test)]
+pub(crate) async fn open_test_pool() -> sqlx::SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("in-memory database opens");
+    for migration in migrations() {
+        sqlx::raw_sql(migration.sql)
+            .execute(&pool)
+            .await
+            .unwrap_or_else(|err| panic!("migration {} applies: {err}", migration.version));
+    }
+    pool
+}
@@ -124,6 +124,25 @@ pub async fn fetch_paired_remote_device_by_id(
     }))
 }
 
+/// Records where a paired device was last found on the network.
+pub async fn update_last_known_address(
+    pool: SqlitePool,
+    id: &str,
+    address: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE paired_remote_devices
+         SET last_known_address = ?2
+         WHERE id = ?1",
+    )
+    .bind(id)
+    .bind(address)
+    .execute(&pool)
+    .await?;
+
+    Ok(())
+}
+
 pub async fn delete_paired_remote_device(pool: SqlitePool, id: &str) -> Result<(), sqlx::Error> {
     sqlx::query("DELETE FROM paired_remote_devices WHERE id = ?1")
         .bind(id)
@@ -1,6 +1,12 @@
 use sqlx::{Row, SqlitePool};
 
-use crate::domain::{preferences::DEFAULT_DICTATION_LIMIT_MINUTES, UserPreferences};
+use crate::domain::{
+    preferences::{
+        AudioRetentionPolicy, DEFAULT_AUDIO_RETENTION_MAX_COUNT, DEFAULT_AUDIO_STORAGE_FORMAT,
+        DEFAULT_DICTATION_LIMIT_MINUTES,
+    },
+    UserPreferences,
+};
 const SEP: &str = "::";
 
 fn serialize_additional_languages(languages: &Option<Vec<String>>) -> Option<String> {
@@ -59,9 +65,13 @@ pub async fn upsert_user_preferences(
              remote_receiver_port,
              remote_receiver_auto_start,
              dictation_audio_dim,
-             menu_bar_icon_hidden
+             menu_bar_icon_hidden,
+             audio_storage_format,
+             audio_retention_max_count,
+             audio_retention_max_age_days,
+             audio_retention_max_total_mb
          )
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34, ?35, ?36, ?37)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34, ?35, ?36, ?37, ?38, ?39, ?40, ?41)
          ON CONFLICT(user_id) DO UPDATE SET
             transcription_mode = excluded.transcription_mode,
             transcription_api_key_id = excluded.transcription_api_key_id,
@@ -98,7 +108,11 @@ pub async fn upsert_user_preferences(
             remote_receiver_port = excluded.remote_receiver_port,
             remote_receiver_auto_start = excluded.remote_receiver_auto_start,
             dictation_audio_dim = excluded.dictation_audio_dim,
-            menu_bar_icon_hidden = excluded.menu_bar_icon_hidden",
+            menu_bar_icon_hidden = excluded.menu_bar_icon_hidden,
+            audio_storage_format = excluded.audio_storage_format,
+            audio_retention_max_count = excluded.audio_retention_max_count,
+            audio_retention_max_age_days = excluded.audio_retention_max_age_days,
+            audio_retention_max_total_mb = excluded.audio_retention_max_total_mb",
     )
     .bind(&preferences.user_id)
     .bind(&preferences.transcription_mode)
@@ -137,6 +151,10 @@ pub async fn upsert_user_preferences(
     .bind(preferences.remote_receiver_auto_start)
     .bind(preferences.dictation_audio_dim)
     .bind(preferences.menu_bar_icon_hidden)
+    .bind(&preferences.audio_storage_format)
+    .bind(preferences.audio_retention_max_count)
+    .bind(preferences.audio_retention_max_age_days)
+    .bind(preferences.audio_retention_max_total_mb)
     .execute(&pool)
     .await?;
 
@@ -185,7 +203,11 @@ pub async fn fetch_user_preferences(
             remote_receiver_port,
             remote_receiver_auto_start,
             dictation_audio_dim,
-            menu_bar_icon_hidden
+            menu_bar_icon_hidden,
+            audio_storage_format,
+            audio_retention_max_count,
+            audio_retention_max_age_days,
+            audio_retention_max_total_mb
          FROM user_preferences
          WHERE user_id = ?1
          LIMIT 1",
@@ -309,13 +331,23 @@ pub async fn fetch_user_preferences(
             .try_get::<i64, _>("remote_receiver_auto_start")
             .map(|v| v != 0)
             .unwrap_or(false),
-        dictation_audio_dim: row
-            .try_get::<f64, _>("dictation_audio_dim")
-            .unwrap_or(1.0),
+        dictation_audio_dim: row.try_get::<f64, _>("dictation_audio_dim").unwrap_or(1.0),
         menu_bar_icon_hidden: row
             .try_get::<i64, _>("menu_bar_icon_hidden")
             .map(|v| v != 0)
             .unwrap_or(false),
+        audio_storage_format: row
+            .try_get::<String, _>("audio_storage_format")
+            .unwrap_or_else(|_| DEFAULT_AUDIO_STORAGE_FORMAT.to_string()),
+        audio_retention_max_count: row
+            .try_get::<Option<i64>, _>("audio_retention_max_count")
+            .unwrap_or(None),
+        audio_retention_max_age_days: row
+            .try_get::<Option<i64>, _>("audio_retention_max_age_days")
+            .unwrap_or(None),
+        audio_retention_max_total_mb: row
+            .try_get::<Option<i64>, _>("audio_retention_max_total_mb")
+            .unwrap_or(None),
     });
 
     Ok(preferences)
@@ -346,3 +378,57 @@ pub async fn fetch_transcription_mode(pool: SqlitePool) -> Result<Option<String>
 
     Ok(row.flatten())
 }
+
+pub async fn fetch_active_tone_id(pool: SqlitePool) -> Result<Option<String>, sqlx::Error> {
+    let row: Option<Option<String>> = sqlx::query_scalar(
+        "SELECT active_tone_id FROM user_preferences WHERE user_id = ?1 LIMIT 1",
+    )
+    .bind(LOCAL_USER_ID)
+    .fetch_optional(&pool)
+    .await?;
+
+    Ok(row.flatten())
+}
+
+pub async fn fetch_audio_storage_format(pool: SqlitePool) -> Result<String, sqlx::Error> {
+    let row: Option<String> = sqlx::query_scalar(
+        "SELECT audio_storage_format FROM user_preferences WHERE user_id = ?1 LIMIT 1",
+    )
+    .bind(LOCAL_USER_ID)
+    .fetch_optional(&pool)
+    .await?;
+
+    Ok(row.unwrap_or_else(|| DEFAULT_AUDIO_STORAGE_FORMAT.to_string()))
+}
+
+/// Returns the local user's retention policy, or the historical default of
+/// keeping the 20 most recent clips when no preferences are stored yet.
+pub async fn fetch_audio_retention_policy(
+    pool: SqlitePool,
+) -> Result<AudioRetentionPolicy, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT audio_retention_max_count,
+                audio_retention_max_age_days,
+                audio_retention_max_total_mb
+         FROM user_preferences
+         WHERE user_id = ?1
+         LIMIT 1",
+    )
+    .bind(LOCAL_USER_ID)
+    .fetch_optional(&pool)
+    .await?;
+
+    let Some(row) = row else {
+        return Ok(AudioRetentionPolicy::from_preferences(
+            Some(DEFAULT_AUDIO_RETENTION_MAX_COUNT),
+            None,
+            None,
+        ));
+    };
+
+    Ok(AudioRetentionPolicy::from_preferences(
+        row.try_get::<Option<i64>, _>("audio_retention_max_count")?,
+        row.try_get::<Option<i64>, _>("audio_retention_max_age_days")?,
+        row.try_get::<Option<i64>, _>("audio_retention_max_total_mb")?,
+    ))
+}
@@ -0,0 +1,98 @@
+use sqlx::SqlitePool;
+
+/// Delivered event ids kept per sender to ignore resends after a lost ack,
+/// including resends that arrive after a restart.
+pub const DELIVERED_EVENT_LIMIT: i64 = 512;
+
+pub async fn has_delivered_event(
+    pool: SqlitePool,
+    sender_device_id: &str,
+    event_id: &str,
+) -> Result<bool, sqlx::Error> {
+    let found: Option<i64> = sqlx::query_scalar(
+        "SELECT 1
+         FROM remote_delivered_events
+         WHERE sender_device_id = ?1 AND event_id = ?2
+         LIMIT 1",
+    )
+    .bind(sender_device_id)
+    .bind(event_id)
+    .fetch_optional(&pool)
+    .await?;
+
+    Ok(found.is_some())
+}
+
+/// Records a delivered event and forgets the sender's oldest events past
+/// [`DELIVERED_EVENT_LIMIT`].
+pub async fn record_delivered_event(
+    pool: SqlitePool,
+    sender_device_id: &str,
+    event_id: &str,
+    delivered_at: i64,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        "INSERT OR IGNORE INTO remote_delivered_events (sender_device_id, event_id, delivered_at)
+         VALUES (?1, ?2, ?3)",
+    )
+    .bind(sender_device_id)
+    .bind(event_id)
+    .bind(delivered_at)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM remote_delivered_events
+         WHERE sender_device_id = ?1
+           AND rowid NOT IN (
+               SELECT rowid
+               FROM remote_delivered_events
+               WHERE sender_device_id = ?1
+               ORDER BY delivered_at DESC, rowid DESC
+               LIMIT ?2
+           )",
+    )
+    .bind(sender_device_id)
+    .bind(DELIVERED_EVENT_LIMIT)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delivered_events_are_remembered_per_sender_up_to_the_limit() {
+        tauri::async_runtime::block_on(async {
+            let pool = crate::db::open_test_pool().await;
+
+            record_delivered_event(pool.clone(), "laptop", "event-0", 0)
+                .await
+                .unwrap();
+            assert!(has_delivered_event(pool.clone(), "laptop", "event-0")
+                .await
+                .unwrap());
+            assert!(!has_delivered_event(pool.clone(), "phone", "event-0")
+                .await
+                .unwrap());
+
+            for index in 1..=DELIVERED_EVENT_LIMIT {
+                record_delivered_event(pool.clone(), "laptop", &format!("event-{index}"), index)
+                    .await
+                    .unwrap();
+            }
+            assert!(!has_delivered_event(pool.clone(), "laptop", "event-0")
+                .await
+                .unwrap());
+            assert!(has_delivered_event(pool.clone(), "laptop", "event-1")
+                .await
+                .unwrap());
+        });
+    }
+}
@@ -0,0 +1,322 @@
+use sqlx::{sqlite::SqliteRow, Row, SqlitePool};
+
+use crate::domain::RemoteOutboxItem;
+use crate::system::vault;
+
+/// Failed attempts after which an item is reported as stuck. The outbox
+/// keeps retrying it at the slowest backoff.
+pub const STUCK_AFTER_ATTEMPTS: i64 = 5;
+/// Age after which a queued text is only sent once the user confirms it,
+/// so a receiver that comes back hours later does not type stale text.
+pub const CONFIRM_AFTER_MS: i64 = 10 * 60 * 1000;
+
+/// Queued text is sealed by the vault like transcripts, bound to this column.
+const TEXT_COLUMN: &str = "remote_outbox.text";
+
+const ITEM_COLUMNS: &str = "sequence,
+            event_id,
+            target_device_id,
+            text,
+            mode,
+            created_at,
+            utterance_id,
+            attempts,
+            next_attempt_at,
+            last_error,
+            confirmed";
+
+fn row_to_item(row: SqliteRow) -> Result<RemoteOutboxItem, sqlx::Error> {
+    let attempts: i64 = row.get("attempts");
+    let created_at: String = row.get("created_at");
+    let confirmed = row.get::<i64, _>("confirmed") != 0;
+    Ok(RemoteOutboxItem {
+        needs_confirmation: !confirmed
+            && is_stale(&created_at, chrono::Utc::now().timestamp_millis()),
+        sequence: row.get("sequence"),
+        event_id: row.get("event_id"),
+        target_device_id: row.get("target_device_id"),
+        text: reveal_text(row.get("text"))?,
+        mode: row.get("mode"),
+        created_at,
+        utterance_id: row.try_get("utterance_id")?,
+        attempts,
+        next_attempt_at: row.get("next_attempt_at"),
+        last_error: row.try_get("last_error")?,
+        stuck: attempts >= STUCK_AFTER_ATTEMPTS,
+    })
+}
+
+fn protect_text(text: &str) -> Result<String, sqlx::Error> {
+    vault::protect_text(TEXT_COLUMN, text).map_err(|err| sqlx::Error::Encode(Box::new(err)))
+}
+
+fn reveal_text(text: String) -> Result<String, sqlx::Error> {
+    vault::reveal_text(TEXT_COLUMN, text).map_err(|err| sqlx::Error::Decode(Box::new(err)))
+}
+
+/// Unreadable timestamps count as stale, so such items are never sent
+/// unprompted.
+fn is_stale(created_at: &str, now: i64) -> bool {
+    match chrono::DateTime::parse_from_rfc3339(created_at) {
+        Ok(created_at) => now - created_at.timestamp_millis() >= CONFIRM_AFTER_MS,
+        Err(_) => true,
+    }
+}
+
+/// Appends the item to the outbox and returns it with its sequence.
+pub async fn enqueue_remote_outbox_item(
+    pool: SqlitePool,
+    item: &RemoteOutboxItem,
+) -> Result<RemoteOutboxItem, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO remote_outbox (
+            event_id,
+            target_device_id,
+            text,
+            mode,
+            created_at,
+            utterance_id,
+            attempts,
+            next_attempt_at,
+            last_error
+         )
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+    )
+    .bind(&item.event_id)
+    .bind(&item.target_device_id)
+    .bind(protect_text(&item.text)?)
+    .bind(&item.mode)
+    .bind(&item.created_at)
+    .bind(&item.utterance_id)
+    .bind(item.attempts)
+    .bind(item.next_attempt_at)
+    .bind(&item.last_error)
+    .execute(&pool)
+    .await?;
+
+    Ok(RemoteOutboxItem {
+        sequence: result.last_insert_rowid(),
+        ..item.clone()
+    })
+}
+
+/// Every queued item, oldest first.
+pub async fn fetch_remote_outbox_items(
+    pool: SqlitePool,
+) -> Result<Vec<RemoteOutboxItem>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "SELECT {ITEM_COLUMNS}
+         FROM remote_outbox
+         ORDER BY sequence ASC"
+    ))
+    .fetch_all(&pool)
+    .await?;
+
+    rows.into_iter().map(row_to_item).collect()
+}
+
+pub async fn fetch_remote_outbox_items_for_target(
+    pool: SqlitePool,
+    target_device_id: &str,
+) -> Result<Vec<RemoteOutboxItem>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "SELECT {ITEM_COLUMNS}
+         FROM remote_outbox
+         WHERE target_device_id = ?1
+         ORDER BY sequence ASC"
+    ))
+    .bind(target_device_id)
+    .fetch_all(&pool)
+    .await?;
+
+    rows.into_iter().map(row_to_item).collect()
+}
+
+pub async fn fetch_remote_outbox_item(
+    pool: SqlitePool,
+    event_id: &str,
+) -> Result<Option<RemoteOutboxItem>, sqlx::Error> {
+    let row = sqlx::query(&format!(
+        "SELECT {ITEM_COLUMNS}
+         FROM remote_outbox
+         WHERE event_id = ?1
+         LIMIT 1"
+    ))
+    .bind(event_id)
+    .fetch_optional(&pool)
+    .await?;
+
+    row.map(row_to_item).transpose()
+}
+
+pub async fn record_remote_outbox_failure(
+    pool: SqlitePool,
+    event_id: &str,
+    error: &str,
+    next_attempt_at: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE remote_outbox
+         SET attempts = attempts + 1,
+             last_error = ?2,
+             next_attempt_at = ?3
+         WHERE event_id = ?1",
+    )
+    .bind(event_id)
+    .bind(error)
+    .bind(next_attempt_at)
+    .execute(&pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Clears the backoff of every item queued for the target and confirms
+/// them for sending.
+pub async fn reset_remote_outbox_target(
+    pool: SqlitePool,
+    target_device_id: &str,
+    next_attempt_at: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE remote_outbox
+         SET attempts = 0,
+             next_attempt_at = ?2,
+             confirmed = 1
+         WHERE target_device_id = ?1",
+    )
+    .bind(target_device_id)
+    .bind(next_attempt_at)
+    .execute(&pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn delete_remote_outbox_item(
+    pool: SqlitePool,
+    event_id: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM remote_outbox WHERE event_id = ?1")
+        .bind(event_id)
+        .execute(&pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Re-saves every queued text so it matches the current vault mode. The
+/// outbox only holds undelivered items, so it is done in one pass.
+pub async fn reseal_remote_outbox_items(pool: SqlitePool) -> Result<(), sqlx::Error> {
+    let rows = sqlx::query("SELECT sequence, text FROM remote_outbox")
+        .fetch_all(&pool)
+        .await?;
+
+    for row in rows {
+        let stored: String = row.get("text");
+        let resealed = protect_text(&reveal_text(stored.clone())?)?;
+        if resealed == stored {
+            continue;
+        }
+
+        sqlx::query("UPDATE remote_outbox SET text = ?2 WHERE sequence = ?1")
+            .bind(row.get::<i64, _>("sequence"))
+            .bind(&resealed)
+            .execute(&pool)
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(event_id: &str, created_at: &str) -> RemoteOutboxItem {
+        RemoteOutboxItem {
+            sequence: 0,
+            event_id: event_id.to_string(),
+            target_device_id: "laptop".to_string(),
+            text: "hello".to_string(),
+            mode: "dictation".to_string(),
+            created_at: created_at.to_string(),
+            utterance_id: None,
+            attempts: 0,
+            next_attempt_at: 0,
+            last_error: None,
+            stuck: false,
+            needs_confirmation: false,
+        }
+    }
+
+    #[test]
+    fn old_items_wait_for_confirmation_until_the_target_is_retried() {
+        tauri::async_runtime::block_on(async {
+            let pool = crate::db::open_test_pool().await;
+            let recent = chrono::Utc::now().to_rfc3339();
+            let old = (chrono::Utc::now() - chrono::Duration::hours(3)).to_rfc3339();
+            enqueue_remote_outbox_item(pool.clone(), &item("old", &old))
+                .await
+                .unwrap();
+            enqueue_remote_outbox_item(pool.clone(), &item("recent", &recent))
+                .await
+                .unwrap();
+
+            let held = |items: Vec<RemoteOutboxItem>| {
+                items
+                    .into_iter()
+                    .filter(|item| item.needs_confirmation)
+                    .map(|item| item.event_id)
+                    .collect::<Vec<_>>()
+            };
+            let items = fetch_remote_outbox_items(pool.clone()).await.unwrap();
+            assert_eq!(held(items), vec!["old".to_string()]);
+
+            reset_remote_outbox_target(pool.clone(), "laptop", 0)
+                .await
+                .unwrap();
+            let items = fetch_remote_outbox_items(pool.clone()).await.unwrap();
+            assert!(held(items).is_empty());
+        });
+    }
+
+    #[test]
+    fn unreadable_timestamps_count_as_stale() {
+        let created_at = "2026-01-01T00:00:00Z";
+        let created_ms = chrono::DateTime::parse_from_rfc3339(created_at)
+            .unwrap()
+            .timestamp_millis();
+        assert!(!is_stale(created_at, created_ms + CONFIRM_AFTER_MS - 1));
+        assert!(is_stale(created_at, created_ms + CONFIRM_AFTER_MS));
+        assert!(is_stale("yesterday", created_ms));
+    }
+
+    #[test]
+    fn sealed_texts_are_revealed_and_resealed_with_the_vault_mode() {
+        tauri::async_runtime::block_on(async {
+            let pool = crate::db::open_test_pool().await;
+            let now = chrono::Utc::now().to_rfc3339();
+            let mut sealed = item("sealed", &now);
+            sealed.text = vault::seal_text_for_test(TEXT_COLUMN, "hello");
+            enqueue_remote_outbox_item(pool.clone(), &sealed)
+                .await
+                .unwrap();
+
+            let fetched = fetch_remote_outbox_item(pool.clone(), "sealed")
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(fetched.text, "hello");
+
+            // The vault is disabled in tests, so resealing stores plain text.
+            reseal_remote_outbox_items(pool.clone()).await.unwrap();
+            let stored: String =
+                sqlx::query_scalar("SELECT text FROM remote_outbox WHERE event_id = 'sealed'")
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap();
+            assert_eq!(stored, "hello");
+        });
+    }
+}
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+
+use sqlx::{sqlite::SqliteRow, Row, SqlitePool};
+
+use crate::db::term_queries::row_to_term;
+use crate::domain::{Term, TermPack, TermPackSyncSummary};
+
+const PACK_COLUMNS: &str = "p.id,
+                p.name,
+                p.source_path,
+                p.version,
+                p.subscribed_at,
+                p.last_synced_at,
+                p.last_error,
+                (SELECT COUNT(*) FROM terms t WHERE t.pack_id = p.id) AS term_count";
+
+fn row_to_pack(row: SqliteRow) -> TermPack {
+    TermPack {
+        id: row.get::<String, _>("id"),
+        name: row.get::<String, _>("name"),
+        source_path: row.get::<String, _>("source_path"),
+        version: row.get::<Option<String>, _>("version"),
+        term_count: row.get::<i64, _>("term_count") as u32,
+        subscribed_at: row.get::<i64, _>("subscribed_at"),
+        last_synced_at: row.get::<Option<i64>, _>("last_synced_at"),
+        last_error: row.get::<Option<String>, _>("last_error"),
+    }
+}
+
+pub async fn fetch_packs(pool: SqlitePool) -> Result<Vec<TermPack>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "SELECT {PACK_COLUMNS}
+         FROM term_packs p
+         ORDER BY p.name COLLATE NOCASE, p.id"
+    ))
+    .fetch_all(&pool)
+    .await?;
+
+    Ok(rows.into_iter().map(row_to_pack).collect())
+}
+
+pub async fn fetch_pack(pool: SqlitePool, id: &str) -> Result<Option<TermPack>, sqlx::Error> {
+    let row = sqlx::query(&format!(
+        "SELECT {PACK_COLUMNS}
+         FROM term_packs p
+         WHERE p.id = ?1"
+    ))
+    .bind(id)
+    .fetch_optional(&pool)
+    .await?;
+
+    Ok(row.map(row_to_pack))
+}
+
+/// Records a subscription, or refreshes the name and version of an existing
+/// one. The original subscription time is kept.
+pub async fn upsert_pack(pool: SqlitePool, pack: &TermPack) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO term_packs (id, name, source_path, version, subscribed_at, last_synced_at, last_error)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+         ON CONFLICT(id) DO UPDATE SET
+             name = excluded.name,
+             version = excluded.version,
+             last_synced_at = excluded.last_synced_at,
+             last_error = excluded.last_error",
+    )
+    .bind(&pack.id)
+    .bind(&pack.name)
+    .bind(&pack.source_path)
+    .bind(pack.version.as_deref())
+    .bind(pack.subscribed_at)
+    .bind(pack.last_synced_at)
+    .bind(pack.last_error.as_deref())
+    .execute(&pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Notes a failed refresh without touching the pack's terms.
+pub async fn record_sync_error(pool: SqlitePool, id: &str, error: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE term_packs
+         SET last_error = ?2
+         WHERE id = ?1",
+    )
+    .bind(id)
+    .bind(error)
+    .execute(&pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Makes the terms stored for `pack_id` exactly `terms`, matched by id.
+/// Unchanged terms are left alone, so applying the same pack twice is a
+/// no-op.
+pub async fn replace_pack_terms(
+    pool: SqlitePool,
+    pack_id: &str,
+    terms: &[Term],
+) -> Result<TermPackSyncSummary, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let mut summary = TermPackSyncSummary {
+        pack_id: pack_id.to_string(),
+        ..Default::default()
+    };
+
+    let rows = sqlx::query(
+        "SELECT id, created_at, created_by_user_id, source_value, destination_value, is_replacement, is_deleted, match_mode, case_sensitive, preserve_case, priority, pack_id
+         FROM terms
+         WHERE pack_id = ?1",
+    )
+    .bind(pack_id)
+    .fetch_all(&mut *tx)
+    .await?;
+    let mut existing: HashMap<String, Term> = rows
+        .into_iter()
+        .map(row_to_term)
+        .map(|term| (term.id.clone(), term))
+        .collect();
+
+    for term in terms {
+        match existing.remove(&term.id) {
+            Some(current) => {
+                let unchanged = current.source_value == term.source_value
+                    && current.destination_value == term.destination_value
+                    && current.is_replacement == term.is_replacement
+                    && current.match_mode == term.match_mode
+                    && current.case_sensitive == term.case_sensitive
+                    && current.preserve_case == term.preserve_case
+                    && current.priority == term.priority
+                    && !current.is_deleted;
+                if unchanged {
+                    continue;
+                }
+                sqlx::query(
+                    "UPDATE terms
+                     SET source_value = ?2,
+                         destination_value = ?3,
+                         is_replacement = ?4,
+                         is_deleted = 0,
+                         match_mode = ?5,
+                         case_sensitive = ?6,
+                         preserve_case = ?7,
+                         priority = ?8
+                     WHERE id = ?1",
+                )
+                .bind(&term.id)
+                .bind(&term.source_value)
+                .bind(&term.destination_value)
+                .bind(term.is_replacement as i64)
+                .bind(term.match_mode.as_str())
+                .bind(term.case_sensitive as i64)
+                .bind(term.preserve_case as i64)
+                .bind(term.priority)
+                .execute(&mut *tx)
+                .await?;
+                summary.updated += 1;
+            }
+            None => {
+                sqlx::query(
+                    "INSERT INTO terms (id, created_at, created_by_user_id, source_value, destination_value, is_replacement, is_deleted, match_mode, case_sensitive, preserve_case, priority, pack_id)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, 0, ?7, ?8, ?9, ?10, ?11)",
+                )
+                .bind(&term.id)
+                .bind(term.created_at)
+                .bind(&term.created_by_user_id)
+                .bind(&term.source_value)
+                .bind(&term.destination_value)
+                .bind(term.is_replacement as i64)
+                .bind(term.match_mode.as_str())
+                .bind(term.case_sensitive as i64)
+                .bind(term.preserve_case as i64)
+                .bind(term.priority)
+                .bind(pack_id)
+                .execute(&mut *tx)
+                .await?;
+                summary.added += 1;
+            }
+        }
+    }
+
+    // Whatever is left was dropped from the pack.
+    for id in existing.keys() {
+        sqlx::query("DELETE FROM terms WHERE id = ?1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        summary.removed += 1;
+    }
+
+    tx.commit().await?;
+    Ok(summary)
+}
+
+/// Removes the pack together with every term it brought in.
+pub async fn delete_pack(pool: SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DELETE FROM terms WHERE pack_id = ?1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM term_packs WHERE id = ?1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
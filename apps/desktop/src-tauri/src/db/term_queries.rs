@@ -1,11 +1,28 @@
-use sqlx::{Row, SqlitePool};
+use sqlx::{sqlite::SqliteRow, Row, SqlitePool};
 
-use crate::domain::Term;
+use crate::domain::{Term, TermMatchMode};
+
+pub(crate) fn row_to_term(row: SqliteRow) -> Term {
+    Term {
+        id: row.get::<String, _>("id"),
+        created_at: row.get::<i64, _>("created_at"),
+        created_by_user_id: row.get::<String, _>("created_by_user_id"),
+        source_value: row.get::<String, _>("source_value"),
+        destination_value: row.get::<String, _>("destination_value"),
+        is_replacement: row.get::<i64, _>("is_replacement") != 0,
+        is_deleted: row.get::<i64, _>("is_deleted") != 0,
+        match_mode: TermMatchMode::from_db(&row.get::<String, _>("match_mode")),
+        case_sensitive: row.get::<i64, _>("case_sensitive") != 0,
+        preserve_case: row.get::<i64, _>("preserve_case") != 0,
+        priority: row.get::<i64, _>("priority"),
+        pack_id: row.get::<Option<String>, _>("pack_id"),
+    }
+}
 
 pub async fn insert_term(pool: SqlitePool, term: &Term) -> Result<Term, sqlx::Error> {
     sqlx::query(
-        "INSERT INTO terms (id, created_at, created_by_user_id, source_value, destination_value, is_replacement, is_deleted)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        "INSERT INTO terms (id, created_at, created_by_user_id, source_value, destination_value, is_replacement, is_deleted, match_mode, case_sensitive, preserve_case, priority, pack_id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
     )
     .bind(&term.id)
     .bind(term.created_at)
@@ -14,6 +31,11 @@ pub async fn insert_term(pool: SqlitePool, term: &Term) -> Result<Term, sqlx::Er
     .bind(&term.destination_value)
     .bind(term.is_replacement as i64)
     .bind(term.is_deleted as i64)
+    .bind(term.match_mode.as_str())
+    .bind(term.case_sensitive as i64)
+    .bind(term.preserve_case as i64)
+    .bind(term.priority)
+    .bind(term.pack_id.as_deref())
     .execute(&pool)
     .await?;
 
@@ -22,7 +44,7 @@ pub async fn insert_term(pool: SqlitePool, term: &Term) -> Result<Term, sqlx::Er
 
 pub async fn fetch_terms(pool: SqlitePool) -> Result<Vec<Term>, sqlx::Error> {
     let rows = sqlx::query(
-        "SELECT id, created_at, created_by_user_id, source_value, destination_value, is_replacement, is_deleted
+        "SELECT id, created_at, created_by_user_id, source_value, destination_value, is_replacement, is_deleted, match_mode, case_sensitive, preserve_case, priority, pack_id
          FROM terms
          WHERE is_deleted = 0
          ORDER BY created_at DESC",
@@ -30,20 +52,7 @@ pub async fn fetch_terms(pool: SqlitePool) -> Result<Vec<Term>, sqlx::Error> {
     .fetch_all(&pool)
     .await?;
 
-    let terms = rows
-        .into_iter()
-        .map(|row| Term {
-            id: row.get::<String, _>("id"),
-            created_at: row.get::<i64, _>("created_at"),
-            created_by_user_id: row.get::<String, _>("created_by_user_id"),
-            source_value: row.get::<String, _>("source_value"),
-            destination_value: row.get::<String, _>("destination_value"),
-            is_replacement: row.get::<i64, _>("is_replacement") != 0,
-            is_deleted: row.get::<i64, _>("is_deleted") != 0,
-        })
-        .collect();
-
-    Ok(terms)
+    Ok(rows.into_iter().map(row_to_term).collect())
 }
 
 pub async fn update_term(pool: SqlitePool, term: &Term) -> Result<Term, sqlx::Error> {
@@ -52,7 +61,11 @@ pub async fn update_term(pool: SqlitePool, term: &Term) -> Result<Term, sqlx::Er
          SET source_value = ?2,
              destination_value = ?3,
              is_replacement = ?4,
-             is_deleted = ?5
+             is_deleted = ?5,
+             match_mode = ?6,
+             case_sensitive = ?7,
+             preserve_case = ?8,
+             priority = ?9
          WHERE id = ?1",
     )
     .bind(&term.id)
@@ -60,6 +73,10 @@ pub async fn update_term(pool: SqlitePool, term: &Term) -> Result<Term, sqlx::Er
     .bind(&term.destination_value)
     .bind(term.is_replacement as i64)
     .bind(term.is_deleted as i64)
+    .bind(term.match_mode.as_str())
+    .bind(term.case_sensitive as i64)
+    .bind(term.preserve_case as i64)
+    .bind(term.priority)
     .execute(&pool)
     .await?;
 
@@ -1,8 +1,12 @@
+use chrono::Utc;
 use sqlx::{Row, SqlitePool};
 
+use crate::db::tone_version_queries;
 use crate::domain::Tone;
 
 pub async fn insert_tone(pool: SqlitePool, tone: &Tone) -> Result<Tone, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
     sqlx::query(
         "INSERT INTO tones (
              id,
@@ -18,13 +22,19 @@ pub async fn insert_tone(pool: SqlitePool, tone: &Tone) -> Result<Tone, sqlx::Er
     .bind(&tone.prompt_template)
     .bind(tone.created_at)
     .bind(tone.sort_order)
-    .execute(&pool)
+    .execute(&mut *tx)
     .await?;
 
+    tone_version_queries::record_version(&mut tx, tone, tone.created_at).await?;
+    tx.commit().await?;
+
     Ok(tone.clone())
 }
 
+/// Saves `tone` and adds a version when its name or template changed.
 pub async fn update_tone(pool: SqlitePool, tone: &Tone) -> Result<Tone, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
     sqlx::query(
         "UPDATE tones SET
             name = ?2,
@@ -36,18 +46,28 @@ pub async fn update_tone(pool: SqlitePool, tone: &Tone) -> Result<Tone, sqlx::Er
     .bind(&tone.name)
     .bind(&tone.prompt_template)
     .bind(tone.sort_order)
-    .execute(&pool)
+    .execute(&mut *tx)
     .await?;
 
+    tone_version_queries::record_version(&mut tx, tone, Utc::now().timestamp_millis()).await?;
+    tx.commit().await?;
+
     Ok(tone.clone())
 }
 
 pub async fn delete_tone(pool: SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
     sqlx::query("DELETE FROM tones WHERE id = ?1")
         .bind(id)
-        .execute(&pool)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM tone_versions WHERE tone_id = ?1")
+        .bind(id)
+        .execute(&mut *tx)
         .await?;
 
+    tx.commit().await?;
     Ok(())
 }
 
@@ -103,6 +123,9 @@ pub async fn count_tones(pool: SqlitePool) -> Result<i64, sqlx::Error> {
 
 pub async fn delete_all_tones(pool: SqlitePool) -> Result<(), sqlx::Error> {
     sqlx::query("DELETE FROM tones").execute(&pool).await?;
+    sqlx::query("DELETE FROM tone_versions")
+        .execute(&pool)
+        .await?;
 
     Ok(())
 }
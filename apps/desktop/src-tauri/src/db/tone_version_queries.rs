@@ -0,0 +1,96 @@
+use sqlx::{sqlite::SqliteRow, Row, SqliteConnection, SqlitePool};
+
+use crate::domain::{Tone, ToneVersion};
+
+fn row_to_version(row: SqliteRow) -> ToneVersion {
+    ToneVersion {
+        id: row.get::<String, _>("id"),
+        tone_id: row.get::<String, _>("tone_id"),
+        name: row.get::<String, _>("name"),
+        prompt_template: row.get::<String, _>("prompt_template"),
+        created_at: row.get::<i64, _>("created_at"),
+        is_current: false,
+    }
+}
+
+/// Appends `tone` to its history unless the newest version already holds
+/// the same name and template. Returns whether a version was added.
+pub(crate) async fn record_version(
+    conn: &mut SqliteConnection,
+    tone: &Tone,
+    created_at: i64,
+) -> Result<bool, sqlx::Error> {
+    let latest = sqlx::query(
+        "SELECT name, prompt_template
+         FROM tone_versions
+         WHERE tone_id = ?1
+         ORDER BY created_at DESC, rowid DESC
+         LIMIT 1",
+    )
+    .bind(&tone.id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let unchanged = latest.is_some_and(|row| {
+        row.get::<String, _>("name") == tone.name
+            && row.get::<String, _>("prompt_template") == tone.prompt_template
+    });
+    if unchanged {
+        return Ok(false);
+    }
+
+    sqlx::query(
+        "INSERT INTO tone_versions (id, tone_id, name, prompt_template, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+    )
+    .bind(format!("{}-{:08x}", tone.id, rand::random::<u32>()))
+    .bind(&tone.id)
+    .bind(&tone.name)
+    .bind(&tone.prompt_template)
+    .bind(created_at)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(true)
+}
+
+/// Lists the history of a tone, oldest first. The last entry is current.
+pub async fn fetch_versions(
+    pool: SqlitePool,
+    tone_id: &str,
+) -> Result<Vec<ToneVersion>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT id, tone_id, name, prompt_template, created_at
+         FROM tone_versions
+         WHERE tone_id = ?1
+         ORDER BY created_at ASC, rowid ASC",
+    )
+    .bind(tone_id)
+    .fetch_all(&pool)
+    .await?;
+
+    let mut versions: Vec<ToneVersion> = rows.into_iter().map(row_to_version).collect();
+    if let Some(current) = versions.last_mut() {
+        current.is_current = true;
+    }
+
+    Ok(versions)
+}
+
+pub async fn fetch_version(
+    pool: SqlitePool,
+    tone_id: &str,
+    version_id: &str,
+) -> Result<Option<ToneVersion>, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT id, tone_id, name, prompt_template, created_at
+         FROM tone_versions
+         WHERE id = ?1 AND tone_id = ?2",
+    )
+    .bind(version_id)
+    .bind(tone_id)
+    .fetch_optional(&pool)
+    .await?;
+
+    Ok(row.map(row_to_version))
+}
@@ -1,6 +1,39 @@
-use sqlx::{sqlite::SqliteRow, Row, SqlitePool};
+use sqlx::{sqlite::SqliteRow, QueryBuilder, Row, Sqlite, SqlitePool};
 
-use crate::domain::{Transcription, TranscriptionAudioSnapshot};
+use crate::db::transcription_version_queries;
+use crate::domain::{
+    Transcription, TranscriptionAudioSnapshot, TranscriptionSearchRequest,
+    TranscriptionSearchResult, TranscriptionSegmentConfidence, TranscriptionSnippetPart,
+    TranscriptionVersion,
+};
+use crate::system::vault;
+
+const SNIPPET_MATCH_START: char = '\u{E000}';
+const SNIPPET_MATCH_END: char = '\u{E001}';
+const SEARCH_DEFAULT_LIMIT: u32 = 20;
+const SEARCH_MAX_LIMIT: u32 = 200;
+const SEARCH_COLUMNS: &str = "t.id,
+                t.transcript,
+                t.timestamp,
+                t.audio_path,
+                t.audio_duration_ms,
+                t.model_size,
+                t.inference_device,
+                t.raw_transcript,
+                t.sanitized_transcript,
+                t.transcription_prompt,
+                t.post_process_prompt,
+                t.transcription_api_key_id,
+                t.post_process_api_key_id,
+                t.transcription_mode,
+                t.post_process_mode,
+                t.post_process_device,
+                t.transcription_duration_ms,
+                t.postprocess_duration_ms,
+                t.warnings_json,
+                t.remote_status,
+                t.remote_device_id,
+                t.confidence_json";
 
 fn serialize_warnings(warnings: &Option<Vec<String>>) -> Option<String> {
     warnings
@@ -8,6 +41,59 @@ fn serialize_warnings(warnings: &Option<Vec<String>>) -> Option<String> {
         .and_then(|list| serde_json::to_string(list).ok())
 }
 
+fn serialize_confidence(segments: &Option<Vec<TranscriptionSegmentConfidence>>) -> Option<String> {
+    segments
+        .as_ref()
+        .and_then(|list| serde_json::to_string(list).ok())
+}
+
+/// Text columns as stored: sealed by the vault when it is enabled.
+pub(crate) struct StoredText {
+    pub transcript: String,
+    pub raw_transcript: Option<String>,
+    pub sanitized_transcript: Option<String>,
+    pub confidence_json: Option<String>,
+}
+
+pub(crate) fn protect_text(
+    transcript: &str,
+    raw_transcript: Option<&str>,
+    sanitized_transcript: Option<&str>,
+    confidence_segments: &Option<Vec<TranscriptionSegmentConfidence>>,
+) -> Result<StoredText, sqlx::Error> {
+    let protect = |column: &str, value: &str| {
+        vault::protect_text(column, value).map_err(|err| sqlx::Error::Encode(Box::new(err)))
+    };
+    let protect_optional =
+        |column: &str, value: Option<&str>| value.map(|value| protect(column, value)).transpose();
+
+    Ok(StoredText {
+        transcript: protect("transcript", transcript)?,
+        raw_transcript: protect_optional("raw_transcript", raw_transcript)?,
+        sanitized_transcript: protect_optional("sanitized_transcript", sanitized_transcript)?,
+        confidence_json: protect_optional(
+            "confidence_json",
+            serialize_confidence(confidence_segments).as_deref(),
+        )?,
+    })
+}
+
+fn protect_text_columns(transcription: &Transcription) -> Result<StoredText, sqlx::Error> {
+    protect_text(
+        &transcription.transcript,
+        transcription.raw_transcript.as_deref(),
+        transcription.sanitized_transcript.as_deref(),
+        &transcription.confidence_segments,
+    )
+}
+
+pub(crate) fn reveal_column(row: &SqliteRow, column: &str) -> Result<Option<String>, sqlx::Error> {
+    row.try_get::<Option<String>, _>(column)?
+        .map(|value| vault::reveal_text(column, value))
+        .transpose()
+        .map_err(|err| sqlx::Error::Decode(Box::new(err)))
+}
+
 fn row_to_transcription(row: SqliteRow) -> Result<Transcription, sqlx::Error> {
     let audio_path: Option<String> = row.try_get("audio_path")?;
     let audio_duration: Option<i64> = row.try_get("audio_duration_ms")?;
@@ -20,16 +106,19 @@ fn row_to_transcription(row: SqliteRow) -> Result<Transcription, sqlx::Error> {
     let warnings = warnings_json.and_then(|json| serde_json::from_str::<Vec<String>>(&json).ok());
     let remote_status: Option<String> = row.try_get("remote_status")?;
     let remote_device_id: Option<String> = row.try_get("remote_device_id")?;
+    let confidence_json = reveal_column(&row, "confidence_json")?;
+    let confidence_segments = confidence_json
+        .and_then(|json| serde_json::from_str::<Vec<TranscriptionSegmentConfidence>>(&json).ok());
 
     Ok(Transcription {
         id: row.get::<String, _>("id"),
-        transcript: row.get::<String, _>("transcript"),
+        transcript: reveal_column(&row, "transcript")?.unwrap_or_default(),
         timestamp: row.get::<i64, _>("timestamp"),
         audio,
         model_size: row.try_get::<Option<String>, _>("model_size")?,
         inference_device: row.try_get::<Option<String>, _>("inference_device")?,
-        raw_transcript: row.try_get::<Option<String>, _>("raw_transcript")?,
-        sanitized_transcript: row.try_get::<Option<String>, _>("sanitized_transcript")?,
+        raw_transcript: reveal_column(&row, "raw_transcript")?,
+        sanitized_transcript: reveal_column(&row, "sanitized_transcript")?,
         transcription_prompt: row.try_get::<Option<String>, _>("transcription_prompt")?,
         post_process_prompt: row.try_get::<Option<String>, _>("post_process_prompt")?,
         transcription_api_key_id: row.try_get::<Option<String>, _>("transcription_api_key_id")?,
@@ -42,6 +131,7 @@ fn row_to_transcription(row: SqliteRow) -> Result<Transcription, sqlx::Error> {
         warnings,
         remote_status,
         remote_device_id,
+        confidence_segments,
     })
 }
 
@@ -49,6 +139,9 @@ pub async fn insert_transcription(
     pool: SqlitePool,
     transcription: &Transcription,
 ) -> Result<Transcription, sqlx::Error> {
+    let stored = protect_text_columns(transcription)?;
+    let mut tx = pool.begin().await?;
+
     sqlx::query(
         "INSERT INTO transcriptions (
              id,
@@ -71,12 +164,14 @@ pub async fn insert_transcription(
              postprocess_duration_ms,
              warnings_json,
              remote_status,
-             remote_device_id
+             remote_device_id,
+             confidence_json,
+             active_version_id
          )
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21)",
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?1)",
     )
     .bind(&transcription.id)
-    .bind(&transcription.transcript)
+    .bind(&stored.transcript)
     .bind(transcription.timestamp)
     .bind(
         transcription
@@ -87,8 +182,8 @@ pub async fn insert_transcription(
     .bind(transcription.audio.as_ref().map(|audio| audio.duration_ms))
     .bind(transcription.model_size.as_deref())
     .bind(transcription.inference_device.as_deref())
-    .bind(transcription.raw_transcript.as_deref())
-    .bind(transcription.sanitized_transcript.as_deref())
+    .bind(stored.raw_transcript.as_deref())
+    .bind(stored.sanitized_transcript.as_deref())
     .bind(transcription.transcription_prompt.as_deref())
     .bind(transcription.post_process_prompt.as_deref())
     .bind(transcription.transcription_api_key_id.as_deref())
@@ -101,9 +196,19 @@ pub async fn insert_transcription(
     .bind(serialize_warnings(&transcription.warnings))
     .bind(transcription.remote_status.as_deref())
     .bind(transcription.remote_device_id.as_deref())
-    .execute(&pool)
+    .bind(stored.confidence_json.as_deref())
+    .execute(&mut *tx)
     .await?;
 
+    // The first result shares the transcription's id as its version id.
+    let version = TranscriptionVersion::from_transcription(
+        transcription,
+        transcription.id.clone(),
+        transcription.timestamp,
+    );
+    transcription_version_queries::insert_version(&mut tx, &version, &stored).await?;
+    tx.commit().await?;
+
     Ok(transcription.clone())
 }
 
@@ -133,7 +238,8 @@ pub async fn fetch_transcriptions(
                 postprocess_duration_ms,
                 warnings_json,
                 remote_status,
-                remote_device_id
+                remote_device_id,
+                confidence_json
          FROM transcriptions
          ORDER BY timestamp DESC
          LIMIT ?1 OFFSET ?2",
@@ -156,6 +262,9 @@ pub async fn update_transcription(
     pool: SqlitePool,
     transcription: &Transcription,
 ) -> Result<Transcription, sqlx::Error> {
+    let stored = protect_text_columns(transcription)?;
+    let mut tx = pool.begin().await?;
+
     sqlx::query(
         "UPDATE transcriptions
          SET transcript = ?2,
@@ -177,11 +286,12 @@ pub async fn update_transcription(
              postprocess_duration_ms = ?18,
              warnings_json = ?19,
              remote_status = ?20,
-             remote_device_id = ?21
+             remote_device_id = ?21,
+             confidence_json = COALESCE(?22, confidence_json)
          WHERE id = ?1",
     )
     .bind(&transcription.id)
-    .bind(&transcription.transcript)
+    .bind(&stored.transcript)
     .bind(transcription.timestamp)
     .bind(
         transcription
@@ -192,8 +302,8 @@ pub async fn update_transcription(
     .bind(transcription.audio.as_ref().map(|audio| audio.duration_ms))
     .bind(transcription.model_size.as_deref())
     .bind(transcription.inference_device.as_deref())
-    .bind(transcription.raw_transcript.as_deref())
-    .bind(transcription.sanitized_transcript.as_deref())
+    .bind(stored.raw_transcript.as_deref())
+    .bind(stored.sanitized_transcript.as_deref())
     .bind(transcription.transcription_prompt.as_deref())
     .bind(transcription.post_process_prompt.as_deref())
     .bind(transcription.transcription_api_key_id.as_deref())
@@ -206,9 +316,13 @@ pub async fn update_transcription(
     .bind(serialize_warnings(&transcription.warnings))
     .bind(transcription.remote_status.as_deref())
     .bind(transcription.remote_device_id.as_deref())
-    .execute(&pool)
+    .bind(stored.confidence_json.as_deref())
+    .execute(&mut *tx)
     .await?;
 
+    transcription_version_queries::sync_active_version(&mut tx, transcription, &stored).await?;
+    tx.commit().await?;
+
     let row = sqlx::query(
         "SELECT id,
                 transcript,
@@ -230,7 +344,8 @@ pub async fn update_transcription(
                 postprocess_duration_ms,
                 warnings_json,
                 remote_status,
-                remote_device_id
+                remote_device_id,
+                confidence_json
          FROM transcriptions
          WHERE id = ?1",
     )
@@ -243,13 +358,502 @@ pub async fn update_transcription(
 }
 
 pub async fn delete_transcription(pool: SqlitePool, id: &str) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
     sqlx::query(
         "DELETE FROM transcriptions
          WHERE id = ?1",
     )
     .bind(id)
-    .execute(&pool)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM transcription_versions
+         WHERE transcription_id = ?1",
+    )
+    .bind(id)
+    .execute(&mut *tx)
     .await?;
 
+    tx.commit().await?;
     Ok(())
 }
+
+pub async fn fetch_transcription(
+    pool: SqlitePool,
+    id: &str,
+) -> Result<Option<Transcription>, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT id,
+                transcript,
+                timestamp,
+                audio_path,
+                audio_duration_ms,
+                model_size,
+                inference_device,
+                raw_transcript,
+                sanitized_transcript,
+                transcription_prompt,
+                post_process_prompt,
+                transcription_api_key_id,
+                post_process_api_key_id,
+                transcription_mode,
+                post_process_mode,
+                post_process_device,
+                transcription_duration_ms,
+                postprocess_duration_ms,
+                warnings_json,
+                remote_status,
+                remote_device_id,
+                confidence_json
+         FROM transcriptions
+         WHERE id = ?1",
+    )
+    .bind(id)
+    .fetch_optional(&pool)
+    .await?;
+
+    row.map(row_to_transcription).transpose()
+}
+
+pub async fn search_transcriptions(
+    pool: SqlitePool,
+    request: &TranscriptionSearchRequest,
+) -> Result<Vec<TranscriptionSearchResult>, sqlx::Error> {
+    let match_query = request.query.as_deref().and_then(build_match_query);
+    let limit = request
+        .limit
+        .unwrap_or(SEARCH_DEFAULT_LIMIT)
+        .clamp(1, SEARCH_MAX_LIMIT) as usize;
+    let offset = request.offset.unwrap_or(0) as usize;
+
+    let Some(match_query) = match_query else {
+        let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+            "SELECT {SEARCH_COLUMNS}
+         FROM transcriptions t
+         WHERE 1 = 1"
+        ));
+        push_search_filters(&mut builder, request);
+        builder
+            .push(" ORDER BY t.timestamp DESC LIMIT ")
+            .push_bind(limit as i64)
+            .push(" OFFSET ")
+            .push_bind(offset as i64);
+
+        let rows = builder.build().fetch_all(&pool).await?;
+        let mut results = Vec::with_capacity(rows.len());
+        for row in rows {
+            results.push(TranscriptionSearchResult {
+                transcription: row_to_transcription(row)?,
+                snippet: Vec::new(),
+                rank: None,
+            });
+        }
+        return Ok(results);
+    };
+
+    // Sealed transcripts are kept out of the index, so they are matched after
+    // decryption and listed after the ranked matches. Only the sealed rows are
+    // scanned, and only when there are any.
+    let scan_sealed = vault::has_key() && sealed_transcriptions_exist(&pool).await?;
+
+    // Column weights follow the declaration order in the FTS table:
+    // id (unindexed), transcript, raw_transcript, sanitized_transcript.
+    let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+        "SELECT {SEARCH_COLUMNS},
+                bm25(transcriptions_fts, 0.0, 1.0, 0.6, 0.8) AS search_rank,
+                snippet(transcriptions_fts, -1, '{SNIPPET_MATCH_START}', '{SNIPPET_MATCH_END}', '…', 12) AS search_snippet
+         FROM transcriptions t
+         JOIN transcriptions_fts ON transcriptions_fts.id = t.id
+         WHERE transcriptions_fts MATCH "
+    ));
+    builder.push_bind(match_query.as_str());
+    push_search_filters(&mut builder, request);
+    builder.push(" ORDER BY search_rank ASC, t.timestamp DESC LIMIT ");
+    if scan_sealed {
+        // The page is cut after merging, so every ranked match up to its end
+        // is needed.
+        builder.push_bind((offset + limit) as i64).push(" OFFSET 0");
+    } else {
+        builder
+            .push_bind(limit as i64)
+            .push(" OFFSET ")
+            .push_bind(offset as i64);
+    }
+
+    let rows = builder.build().fetch_all(&pool).await?;
+    let mut results = Vec::with_capacity(rows.len());
+    for row in rows {
+        let rank: f64 = row.try_get("search_rank")?;
+        let snippet: Option<String> = row.try_get("search_snippet")?;
+        results.push(TranscriptionSearchResult {
+            transcription: row_to_transcription(row)?,
+            snippet: snippet.as_deref().map(parse_snippet).unwrap_or_default(),
+            rank: Some(rank),
+        });
+    }
+
+    if !scan_sealed {
+        return Ok(results);
+    }
+
+    let terms = request.query.as_deref().map(scan_terms).unwrap_or_default();
+    let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(format!(
+        "SELECT {SEARCH_COLUMNS}
+         FROM transcriptions t
+         WHERE t.transcript LIKE "
+    ));
+    builder.push_bind(format!("{}%", vault::TEXT_PREFIX));
+    push_search_filters(&mut builder, request);
+    builder.push(" ORDER BY t.timestamp DESC");
+
+    for row in builder.build().fetch_all(&pool).await? {
+        let transcription = row_to_transcription(row)?;
+        if transcription_matches(&transcription, &terms) {
+            results.push(TranscriptionSearchResult {
+                transcription,
+                snippet: Vec::new(),
+                rank: None,
+            });
+        }
+    }
+
+    Ok(results.into_iter().skip(offset).take(limit).collect())
+}
+
+async fn sealed_transcriptions_exist(pool: &SqlitePool) -> Result<bool, sqlx::Error> {
+    let exists: i64 = sqlx::query_scalar(
+        "SELECT EXISTS(
+             SELECT 1 FROM transcriptions WHERE transcript LIKE ?1 || '%'
+         )",
+    )
+    .bind(vault::TEXT_PREFIX)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(exists != 0)
+}
+
+fn push_search_filters<'a>(
+    builder: &mut QueryBuilder<'a, Sqlite>,
+    request: &'a TranscriptionSearchRequest,
+) {
+    if let Some(from) = request.from_timestamp {
+        builder.push(" AND t.timestamp >= ").push_bind(from);
+    }
+    if let Some(to) = request.to_timestamp {
+        builder.push(" AND t.timestamp <= ").push_bind(to);
+    }
+    if let Some(mode) = non_empty(&request.transcription_mode) {
+        builder.push(" AND t.transcription_mode = ").push_bind(mode);
+    }
+    if let Some(model_size) = non_empty(&request.model_size) {
+        builder.push(" AND t.model_size = ").push_bind(model_size);
+    }
+    if let Some(api_key_id) = non_empty(&request.api_key_id) {
+        builder
+            .push(" AND (t.transcription_api_key_id = ")
+            .push_bind(api_key_id)
+            .push(" OR t.post_process_api_key_id = ")
+            .push_bind(api_key_id)
+            .push(")");
+    }
+    if let Some(remote_status) = non_empty(&request.remote_status) {
+        builder
+            .push(" AND t.remote_status = ")
+            .push_bind(remote_status);
+    }
+}
+
+fn scan_terms(input: &str) -> Vec<String> {
+    input.split_whitespace().map(str::to_lowercase).collect()
+}
+
+fn transcription_matches(transcription: &Transcription, terms: &[String]) -> bool {
+    let haystack = [
+        Some(transcription.transcript.as_str()),
+        transcription.raw_transcript.as_deref(),
+        transcription.sanitized_transcript.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join("\n")
+    .to_lowercase();
+
+    terms.iter().all(|term| haystack.contains(term.as_str()))
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// Turns free-form user input into an FTS5 query. Every term is quoted so
+/// that FTS operators and punctuation in the input are matched literally,
+/// and the last term is a prefix match so results update while typing.
+fn build_match_query(input: &str) -> Option<String> {
+    let terms: Vec<&str> = input.split_whitespace().collect();
+    let last = terms.len().checked_sub(1)?;
+
+    let query = terms
+        .iter()
+        .enumerate()
+        .map(|(index, term)| {
+            let quoted = format!("\"{}\"", term.replace('"', "\"\""));
+            if index == last {
+                format!("{quoted}*")
+            } else {
+                quoted
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Some(query)
+}
+
+/// Splits an FTS5 snippet on the private-use markers passed to `snippet()`
+/// so the frontend can render highlights without parsing markup.
+fn parse_snippet(snippet: &str) -> Vec<TranscriptionSnippetPart> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut highlighted = false;
+
+    for ch in snippet.chars() {
+        let toggles_to = match ch {
+            SNIPPET_MATCH_START => Some(true),
+            SNIPPET_MATCH_END => Some(false),
+            _ => None,
+        };
+
+        match toggles_to {
+            Some(next) => {
+                if !current.is_empty() {
+                    parts.push(TranscriptionSnippetPart {
+                        text: std::mem::take(&mut current),
+                        highlighted,
+                    });
+                }
+                highlighted = next;
+            }
+            None => current.push(ch),
+        }
+    }
+
+    if !current.is_empty() {
+        parts.push(TranscriptionSnippetPart {
+            text: current,
+            highlighted,
+        });
+    }
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_query_quotes_terms_and_prefixes_the_last() {
+        assert_eq!(
+            build_match_query("hello  wor").as_deref(),
+            Some("\"hello\" \"wor\"*")
+        );
+        assert_eq!(
+            build_match_query("say \"hi\" OR").as_deref(),
+            Some("\"say\" \"\"\"hi\"\"\" \"OR\"*")
+        );
+        assert_eq!(build_match_query("   "), None);
+    }
+
+    #[test]
+    fn snippet_markers_become_highlighted_parts() {
+        let parts = parse_snippet("…the \u{E000}quick\u{E001} brown \u{E000}fox\u{E001}");
+        assert_eq!(
+            parts,
+            vec![
+                TranscriptionSnippetPart {
+                    text: "…the ".to_string(),
+                    highlighted: false,
+                },
+                TranscriptionSnippetPart {
+                    text: "quick".to_string(),
+                    highlighted: true,
+                },
+                TranscriptionSnippetPart {
+                    text: " brown ".to_string(),
+                    highlighted: false,
+                },
+                TranscriptionSnippetPart {
+                    text: "fox".to_string(),
+                    highlighted: true,
+                },
+            ]
+        );
+    }
+
+    fn transcription(id: &str, transcript: &str, timestamp: i64) -> Transcription {
+        Transcription {
+            id: id.to_string(),
+            transcript: transcript.to_string(),
+            timestamp,
+            audio: None,
+            model_size: None,
+            inference_device: None,
+            raw_transcript: None,
+            sanitized_transcript: None,
+            transcription_prompt: None,
+            post_process_prompt: None,
+            transcription_api_key_id: None,
+            post_process_api_key_id: None,
+            transcription_mode: None,
+            post_process_mode: None,
+            post_process_device: None,
+            transcription_duration_ms: None,
+            postprocess_duration_ms: None,
+            warnings: None,
+            remote_status: None,
+            remote_device_id: None,
+            confidence_segments: None,
+        }
+    }
+
+    async fn search_ids(pool: &SqlitePool, query: &str) -> Vec<String> {
+        let request = TranscriptionSearchRequest {
+            query: Some(query.to_string()),
+            ..Default::default()
+        };
+        search_transcriptions(pool.clone(), &request)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|result| result.transcription.id)
+            .collect()
+    }
+
+    #[test]
+    fn search_index_follows_inserts_updates_and_deletes() {
+        tauri::async_runtime::block_on(async {
+            let pool = crate::db::open_test_pool().await;
+
+            insert_transcription(pool.clone(), &transcription("a", "the quick brown fox", 1))
+                .await
+                .unwrap();
+            insert_transcription(pool.clone(), &transcription("b", "a lazy dog sleeps", 2))
+                .await
+                .unwrap();
+            assert_eq!(search_ids(&pool, "fox").await, vec!["a"]);
+            assert_eq!(search_ids(&pool, "do").await, vec!["b"]);
+
+            let mut edited = transcription("a", "the quick brown cat", 1);
+            edited.raw_transcript = Some("the quick brown fox".to_string());
+            update_transcription(pool.clone(), &edited).await.unwrap();
+            assert_eq!(search_ids(&pool, "cat").await, vec!["a"]);
+            // The raw transcript is indexed too.
+            assert_eq!(search_ids(&pool, "fox").await, vec!["a"]);
+
+            update_transcription(pool.clone(), &transcription("a", "the quick brown cat", 1))
+                .await
+                .unwrap();
+            assert!(search_ids(&pool, "fox").await.is_empty());
+
+            delete_transcription(pool.clone(), "a").await.unwrap();
+            assert!(search_ids(&pool, "cat").await.is_empty());
+            assert_eq!(search_ids(&pool, "lazy").await, vec!["b"]);
+
+            let indexed: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM transcriptions_fts")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(indexed, 1);
+        });
+    }
+
+    #[test]
+    fn search_results_carry_a_highlighted_snippet() {
+        tauri::async_runtime::block_on(async {
+            let pool = crate::db::open_test_pool().await;
+            insert_transcription(
+                pool.clone(),
+                &transcription("a", "ship the release today", 1),
+            )
+            .await
+            .unwrap();
+
+            let request = TranscriptionSearchRequest {
+                query: Some("release".to_string()),
+                ..Default::default()
+            };
+            let results = search_transcriptions(pool, &request).await.unwrap();
+
+            assert_eq!(results.len(), 1);
+            assert!(results[0].rank.is_some());
+            assert_eq!(
+                results[0].snippet,
+                vec![
+                    TranscriptionSnippetPart {
+                        text: "ship the ".to_string(),
+                        highlighted: false,
+                    },
+                    TranscriptionSnippetPart {
+                        text: "release".to_string(),
+                        highlighted: true,
+                    },
+                    TranscriptionSnippetPart {
+                        text: " today".to_string(),
+                        highlighted: false,
+                    },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn sealed_transcripts_are_matched_after_the_ranked_plain_ones() {
+        tauri::async_runtime::block_on(async {
+            let pool = crate::db::open_test_pool().await;
+            let sealed = |transcript: &str| vault::seal_text_for_test("transcript", transcript);
+
+            for row in [
+                transcription("a", "ship the release today", 1),
+                transcription("b", "a lazy dog sleeps", 2),
+                transcription("c", &sealed("release party tonight"), 3),
+                transcription("d", &sealed("the lazy cat"), 4),
+            ] {
+                insert_transcription(pool.clone(), &row).await.unwrap();
+            }
+
+            let request = TranscriptionSearchRequest {
+                query: Some("release".to_string()),
+                ..Default::default()
+            };
+            let results = search_transcriptions(pool.clone(), &request).await.unwrap();
+            let ids: Vec<_> = results
+                .iter()
+                .map(|r| r.transcription.id.as_str())
+                .collect();
+            assert_eq!(ids, vec!["a", "c"]);
+            assert!(results[0].rank.is_some());
+            assert!(!results[0].snippet.is_empty());
+            assert_eq!(results[1].rank, None);
+            assert_eq!(results[1].transcription.transcript, "release party tonight");
+
+            assert_eq!(search_ids(&pool, "lazy").await, vec!["b", "d"]);
+
+            let second_page = TranscriptionSearchRequest {
+                query: Some("release".to_string()),
+                limit: Some(1),
+                offset: Some(1),
+                ..Default::default()
+            };
+            let results = search_transcriptions(pool, &second_page).await.unwrap();
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].transcription.id, "c");
+        });
+    }
+}
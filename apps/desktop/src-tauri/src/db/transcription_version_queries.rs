@@ -0,0 +1,284 @@
+use sqlx::{sqlite::SqliteRow, Row, SqliteConnection, SqlitePool};
+
+use crate::db::transcription_queries::{self, reveal_column, StoredText};
+use crate::domain::{Transcription, TranscriptionSegmentConfidence, TranscriptionVersion};
+
+const VERSION_COLUMNS: &str = "v.id,
+                v.transcription_id,
+                v.created_at,
+                v.transcript,
+                v.raw_transcript,
+                v.sanitized_transcript,
+                v.confidence_json,
+                v.transcription_mode,
+                v.model_size,
+                v.inference_device,
+                v.transcription_prompt,
+                v.transcription_api_key_id,
+                v.transcription_duration_ms,
+                v.post_process_mode,
+                v.post_process_device,
+                v.post_process_prompt,
+                v.post_process_api_key_id,
+                v.postprocess_duration_ms,
+                t.active_version_id IS v.id AS is_active";
+
+fn row_to_version(row: SqliteRow) -> Result<TranscriptionVersion, sqlx::Error> {
+    let confidence_segments = reveal_column(&row, "confidence_json")?
+        .and_then(|json| serde_json::from_str::<Vec<TranscriptionSegmentConfidence>>(&json).ok());
+
+    Ok(TranscriptionVersion {
+        id: row.get::<String, _>("id"),
+        transcription_id: row.get::<String, _>("transcription_id"),
+        created_at: row.get::<i64, _>("created_at"),
+        is_active: row.try_get::<i64, _>("is_active").unwrap_or(0) != 0,
+        transcript: reveal_column(&row, "transcript")?.unwrap_or_default(),
+        raw_transcript: reveal_column(&row, "raw_transcript")?,
+        sanitized_transcript: reveal_column(&row, "sanitized_transcript")?,
+        confidence_segments,
+        transcription_mode: row.try_get::<Option<String>, _>("transcription_mode")?,
+        model_size: row.try_get::<Option<String>, _>("model_size")?,
+        inference_device: row.try_get::<Option<String>, _>("inference_device")?,
+        transcription_prompt: row.try_get::<Option<String>, _>("transcription_prompt")?,
+        transcription_api_key_id: row.try_get::<Option<String>, _>("transcription_api_key_id")?,
+        transcription_duration_ms: row.try_get::<Option<i64>, _>("transcription_duration_ms")?,
+        post_process_mode: row.try_get::<Option<String>, _>("post_process_mode")?,
+        post_process_device: row.try_get::<Option<String>, _>("post_process_device")?,
+        post_process_prompt: row.try_get::<Option<String>, _>("post_process_prompt")?,
+        post_process_api_key_id: row.try_get::<Option<String>, _>("post_process_api_key_id")?,
+        postprocess_duration_ms: row.try_get::<Option<i64>, _>("postprocess_duration_ms")?,
+    })
+}
+
+fn protect_version_text(version: &TranscriptionVersion) -> Result<StoredText, sqlx::Error> {
+    transcription_queries::protect_text(
+        &version.transcript,
+        version.raw_transcript.as_deref(),
+        version.sanitized_transcript.as_deref(),
+        &version.confidence_segments,
+    )
+}
+
+/// Inserts `version` with its text columns already protected as `stored`.
+pub(crate) async fn insert_version(
+    conn: &mut SqliteConnection,
+    version: &TranscriptionVersion,
+    stored: &StoredText,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO transcription_versions (
+             id,
+             transcription_id,
+             created_at,
+             transcript,
+             raw_transcript,
+             sanitized_transcript,
+             confidence_json,
+             transcription_mode,
+             model_size,
+             inference_device,
+             transcription_prompt,
+             transcription_api_key_id,
+             transcription_duration_ms,
+             post_process_mode,
+             post_process_device,
+             post_process_prompt,
+             post_process_api_key_id,
+             postprocess_duration_ms
+         )
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+    )
+    .bind(&version.id)
+    .bind(&version.transcription_id)
+    .bind(version.created_at)
+    .bind(&stored.transcript)
+    .bind(stored.raw_transcript.as_deref())
+    .bind(stored.sanitized_transcript.as_deref())
+    .bind(stored.confidence_json.as_deref())
+    .bind(version.transcription_mode.as_deref())
+    .bind(version.model_size.as_deref())
+    .bind(version.inference_device.as_deref())
+    .bind(version.transcription_prompt.as_deref())
+    .bind(version.transcription_api_key_id.as_deref())
+    .bind(version.transcription_duration_ms)
+    .bind(version.post_process_mode.as_deref())
+    .bind(version.post_process_device.as_deref())
+    .bind(version.post_process_prompt.as_deref())
+    .bind(version.post_process_api_key_id.as_deref())
+    .bind(version.postprocess_duration_ms)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Copies the result fields of `transcription` onto its active version, so
+/// edits and post-processing of the active text are kept with that version.
+/// Confidence is kept when the update carries none, as edits do not send it.
+pub(crate) async fn sync_active_version(
+    conn: &mut SqliteConnection,
+    transcription: &Transcription,
+    stored: &StoredText,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE transcription_versions
+         SET transcript = ?2,
+             raw_transcript = ?3,
+             sanitized_transcript = ?4,
+             confidence_json = COALESCE(?5, confidence_json),
+             transcription_mode = ?6,
+             model_size = ?7,
+             inference_device = ?8,
+             transcription_prompt = ?9,
+             transcription_api_key_id = ?10,
+             transcription_duration_ms = ?11,
+             post_process_mode = ?12,
+             post_process_device = ?13,
+             post_process_prompt = ?14,
+             post_process_api_key_id = ?15,
+             postprocess_duration_ms = ?16
+         WHERE id = (SELECT active_version_id FROM transcriptions WHERE id = ?1)",
+    )
+    .bind(&transcription.id)
+    .bind(&stored.transcript)
+    .bind(stored.raw_transcript.as_deref())
+    .bind(stored.sanitized_transcript.as_deref())
+    .bind(stored.confidence_json.as_deref())
+    .bind(transcription.transcription_mode.as_deref())
+    .bind(transcription.model_size.as_deref())
+    .bind(transcription.inference_device.as_deref())
+    .bind(transcription.transcription_prompt.as_deref())
+    .bind(transcription.transcription_api_key_id.as_deref())
+    .bind(transcription.transcription_duration_ms)
+    .bind(transcription.post_process_mode.as_deref())
+    .bind(transcription.post_process_device.as_deref())
+    .bind(transcription.post_process_prompt.as_deref())
+    .bind(transcription.post_process_api_key_id.as_deref())
+    .bind(transcription.postprocess_duration_ms)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn create_version(
+    pool: SqlitePool,
+    version: &TranscriptionVersion,
+) -> Result<TranscriptionVersion, sqlx::Error> {
+    let stored = protect_version_text(version)?;
+    let mut conn = pool.acquire().await?;
+    insert_version(&mut conn, version, &stored).await?;
+
+    Ok(TranscriptionVersion {
+        is_active: false,
+        ..version.clone()
+    })
+}
+
+pub async fn fetch_versions(
+    pool: SqlitePool,
+    transcription_id: &str,
+) -> Result<Vec<TranscriptionVersion>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "SELECT {VERSION_COLUMNS}
+         FROM transcription_versions v
+         LEFT JOIN transcriptions t ON t.id = v.transcription_id
+         WHERE v.transcription_id = ?1
+         ORDER BY v.created_at ASC, v.id ASC"
+    ))
+    .bind(transcription_id)
+    .fetch_all(&pool)
+    .await?;
+
+    rows.into_iter().map(row_to_version).collect()
+}
+
+/// Makes `version_id` the active version, copying its result onto the
+/// transcription row. Sealed text is copied as-is, since both tables bind
+/// the same column names into the ciphertext. Returns `None` when the
+/// version does not belong to the transcription.
+pub async fn activate_version(
+    pool: SqlitePool,
+    transcription_id: &str,
+    version_id: &str,
+) -> Result<Option<Transcription>, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE transcriptions
+         SET transcript = v.transcript,
+             raw_transcript = v.raw_transcript,
+             sanitized_transcript = v.sanitized_transcript,
+             confidence_json = v.confidence_json,
+             transcription_mode = v.transcription_mode,
+             model_size = v.model_size,
+             inference_device = v.inference_device,
+             transcription_prompt = v.transcription_prompt,
+             transcription_api_key_id = v.transcription_api_key_id,
+             transcription_duration_ms = v.transcription_duration_ms,
+             post_process_mode = v.post_process_mode,
+             post_process_device = v.post_process_device,
+             post_process_prompt = v.post_process_prompt,
+             post_process_api_key_id = v.post_process_api_key_id,
+             postprocess_duration_ms = v.postprocess_duration_ms,
+             active_version_id = v.id
+         FROM transcription_versions v
+         WHERE transcriptions.id = ?1
+           AND v.id = ?2
+           AND v.transcription_id = transcriptions.id",
+    )
+    .bind(transcription_id)
+    .bind(version_id)
+    .execute(&pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    transcription_queries::fetch_transcription(pool, transcription_id).await
+}
+
+/// Re-saves every version so its text matches the current vault mode.
+pub async fn reseal_versions(pool: SqlitePool, page_size: u32) -> Result<(), sqlx::Error> {
+    let mut offset = 0i64;
+    loop {
+        let rows = sqlx::query(&format!(
+            "SELECT {VERSION_COLUMNS}
+             FROM transcription_versions v
+             LEFT JOIN transcriptions t ON t.id = v.transcription_id
+             ORDER BY v.id
+             LIMIT ?1 OFFSET ?2"
+        ))
+        .bind(page_size as i64)
+        .bind(offset)
+        .fetch_all(&pool)
+        .await?;
+        let page_len = rows.len() as i64;
+
+        for row in rows {
+            let version = row_to_version(row)?;
+            let stored = protect_version_text(&version)?;
+            sqlx::query(
+                "UPDATE transcription_versions
+                 SET transcript = ?2,
+                     raw_transcript = ?3,
+                     sanitized_transcript = ?4,
+                     confidence_json = ?5
+                 WHERE id = ?1",
+            )
+            .bind(&version.id)
+            .bind(&stored.transcript)
+            .bind(stored.raw_transcript.as_deref())
+            .bind(stored.sanitized_transcript.as_deref())
+            .bind(stored.confidence_json.as_deref())
+            .execute(&pool)
+            .await?;
+        }
+
+        if page_len < page_size as i64 {
+            break;
+        }
+        offset += page_len;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// Stored as `manifest.json` at the root of every backup archive.
+#[derive(Clone, Debug, Deserialize, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    /// Highest database migration applied when the backup was taken.
+    pub schema_version: i64,
+    pub created_at: String,
+    pub audio_files: u32,
+    pub storage_files: u32,
+    /// Sealed data can only be read on the install holding the vault key.
+    #[serde(default)]
+    pub vault_enabled: bool,
+    /// Passphrase-encrypted backups carry the vault key, so they can be
+    /// restored on another install.
+    #[serde(default)]
+    pub vault_key_included: bool,
+}
+
+/// Automatic backups are written unencrypted to the app data `backups`
+/// folder; only the newest `keep` of them are retained.
+#[derive(Clone, Debug, Deserialize, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupSchedule {
+    pub enabled: bool,
+    pub interval_hours: u32,
+    pub keep: u32,
+}
+
+impl Default for BackupSchedule {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: 24,
+            keep: 7,
+        }
+    }
+}
@@ -1,5 +1,6 @@
 pub mod api_key;
 pub mod app_target;
+pub mod backup;
 pub mod chat_message;
 pub mod conversation;
 pub mod hotkey;
@@ -10,6 +11,7 @@ pub mod paired_remote_device;
 pub mod permissions;
 pub mod preferences;
 pub mod recording;
+pub mod remote_outbox;
 pub mod term;
 pub mod tone;
 pub mod transcription;
@@ -17,20 +19,32 @@ pub mod user;
 
 pub use api_key::{ApiKey, ApiKeyCreateRequest, ApiKeyUpdateRequest, ApiKeyView};
 pub use app_target::{AppTarget, EVT_REGISTER_CURRENT_APP};
+pub use backup::{BackupManifest, BackupSchedule};
 pub use chat_message::ChatMessage;
 pub use conversation::Conversation;
 pub use hotkey::{CompositorBinding, Hotkey};
 pub use keyboard::{KeysHeldPayload, EVT_KEYS_HELD};
 pub use monitor::{MonitorAtCursor, OverlayAnchor, ScreenVisibleArea};
 pub use overlay::{OverlayPhase, OverlayPhasePayload, PillWindowSize, EVT_OVERLAY_PHASE};
-pub use paired_remote_device::PairedRemoteDevice;
+pub use paired_remote_device::{DiscoveredRemoteReceiver, PairedRemoteDevice};
 pub use permissions::{PermissionKind, PermissionState, PermissionStatus};
-pub use preferences::UserPreferences;
+pub use preferences::{AudioRetentionPolicy, UserPreferences};
 pub use recording::{
     AudioChunkPayload, RecordedAudio, RecordingLevelPayload, RecordingMetrics, RecordingResult,
     EVT_AUDIO_CHUNK, EVT_REC_LEVEL,
 };
-pub use term::Term;
-pub use tone::Tone;
-pub use transcription::{Transcription, TranscriptionAudioSnapshot};
+pub use remote_outbox::{RemoteDeliveryOutcome, RemoteOutboxItem};
+pub use term::{
+    Term, TermImportSummary, TermMatchMode, TermPack, TermPackSyncSummary, TermPreview,
+    TermPreviewError, TermPreviewMatch, TermTransferFormat,
+};
+pub use tone::{
+    Tone, ToneDiffLine, ToneDiffOp, ToneImportSummary, ToneTemplateValidation, ToneVersion,
+};
+pub use transcription::{
+    AudioPurgeSummary, Transcription, TranscriptionAudioSnapshot, TranscriptionExportFormat,
+    TranscriptionExportRequest, TranscriptionImportConflict, TranscriptionImportSummary,
+    TranscriptionSearchRequest, TranscriptionSearchResult, TranscriptionSegmentConfidence,
+    TranscriptionSnippetPart, TranscriptionVersion, TranscriptionWordConfidence,
+};
 pub use user::User;
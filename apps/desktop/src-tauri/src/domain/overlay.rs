@@ -33,4 +33,12 @@ impl OverlayPhase {
             _ => None,
         }
     }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Recording => "recording",
+            Self::Loading => "loading",
+        }
+    }
 }
@@ -16,3 +16,13 @@ pub struct PairedRemoteDevice {
     #[serde(default)]
     pub trusted: bool,
 }
+
+/// A remote receiver advertising itself on the local network.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveredRemoteReceiver {
+    pub device_id: String,
+    pub name: String,
+    pub platform: String,
+    pub address: String,
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 pub const DEFAULT_DICTATION_LIMIT_MINUTES: i64 = 5;
+pub const DEFAULT_AUDIO_STORAGE_FORMAT: &str = "flac";
+pub const DEFAULT_AUDIO_RETENTION_MAX_COUNT: i64 = 20;
 
 #[derive(Clone, Debug, Deserialize, Serialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
@@ -78,6 +80,39 @@ pub struct UserPreferences {
     pub dictation_audio_dim: f64,
     #[serde(default)]
     pub menu_bar_icon_hidden: bool,
+    /// Either "flac" or "wav"; applies to newly stored clips only.
+    #[serde(default = "default_audio_storage_format")]
+    pub audio_storage_format: String,
+    /// Retention limits for stored clips. `None` means no limit.
+    #[serde(default)]
+    pub audio_retention_max_count: Option<i64>,
+    #[serde(default)]
+    pub audio_retention_max_age_days: Option<i64>,
+    #[serde(default)]
+    pub audio_retention_max_total_mb: Option<i64>,
+}
+
+/// Limits applied when purging stored transcription audio. Each `None`
+/// disables that limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AudioRetentionPolicy {
+    pub max_count: Option<usize>,
+    pub max_age_days: Option<i64>,
+    pub max_total_bytes: Option<u64>,
+}
+
+impl AudioRetentionPolicy {
+    pub fn from_preferences(
+        max_count: Option<i64>,
+        max_age_days: Option<i64>,
+        max_total_mb: Option<i64>,
+    ) -> Self {
+        Self {
+            max_count: max_count.map(|count| count.max(0) as usize),
+            max_age_days: max_age_days.map(|days| days.max(0)),
+            max_total_bytes: max_total_mb.map(|mb| mb.max(0) as u64 * 1024 * 1024),
+        }
+    }
 }
 
 fn default_dictation_pill_visibility() -> String {
@@ -91,3 +126,7 @@ fn default_dictation_limit_minutes() -> i64 {
 fn default_dictation_audio_dim() -> f64 {
     1.0
 }
+
+fn default_audio_storage_format() -> String {
+    DEFAULT_AUDIO_STORAGE_FORMAT.to_string()
+}
@@ -0,0 +1,31 @@
+use serde::Serialize;
+
+/// A final text waiting to reach a paired receiver. Items for the same
+/// receiver are delivered in `sequence` order.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteOutboxItem {
+    pub sequence: i64,
+    pub event_id: String,
+    pub target_device_id: String,
+    pub text: String,
+    pub mode: String,
+    pub created_at: String,
+    /// The streamed utterance this text completes, if partials were sent.
+    pub utterance_id: Option<String>,
+    pub attempts: i64,
+    pub next_attempt_at: i64,
+    pub last_error: Option<String>,
+    pub stuck: bool,
+    /// Queued too long ago to be typed unprompted; held until the user
+    /// retries or discards it.
+    pub needs_confirmation: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteDeliveryOutcome {
+    Delivered,
+    /// The receiver could not be reached; the outbox will retry.
+    Queued,
+}
@@ -1,5 +1,37 @@
 use serde::{Deserialize, Serialize};
 
+/// How a replacement term's source value is matched against text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TermMatchMode {
+    /// Matches the source as a whole word or phrase.
+    #[default]
+    WholeWord,
+    /// Matches the source anywhere, including inside other words.
+    Substring,
+    /// Treats the source as a regular expression; the destination may
+    /// reference capture groups as `$1` or `${name}`.
+    Regex,
+}
+
+impl TermMatchMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::WholeWord => "whole_word",
+            Self::Substring => "substring",
+            Self::Regex => "regex",
+        }
+    }
+
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "substring" => Self::Substring,
+            "regex" => Self::Regex,
+            _ => Self::WholeWord,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct Term {
@@ -10,4 +42,86 @@ pub struct Term {
     pub destination_value: String,
     pub is_replacement: bool,
     pub is_deleted: bool,
+    #[serde(default)]
+    pub match_mode: TermMatchMode,
+    /// When false, matching ignores case.
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// When set, a case-insensitive match carries its capitalization over to
+    /// the destination; otherwise the destination is inserted as written.
+    #[serde(default)]
+    pub preserve_case: bool,
+    /// Replacements with a higher priority are applied first.
+    #[serde(default)]
+    pub priority: i64,
+    /// Set on terms that belong to a subscribed pack; `None` for personal
+    /// terms.
+    #[serde(default)]
+    pub pack_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TermPreviewMatch {
+    pub term_id: String,
+    pub count: u32,
+}
+
+#[derive(Clone, Debug, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TermPreviewError {
+    pub term_id: String,
+    pub message: String,
+}
+
+/// Result of running replacement terms over a piece of text.
+#[derive(Clone, Debug, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TermPreview {
+    pub text: String,
+    pub applied: Vec<TermPreviewMatch>,
+    pub errors: Vec<TermPreviewError>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TermTransferFormat {
+    Csv,
+    Json,
+}
+
+/// Outcome of importing a term file into the personal dictionary. Entries
+/// are matched to existing terms by source value.
+#[derive(Clone, Debug, Default, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TermImportSummary {
+    pub created: u32,
+    pub updated: u32,
+    pub unchanged: u32,
+    pub errors: Vec<String>,
+}
+
+/// A named bundle of terms read from a local file or directory. Its terms
+/// are kept apart from personal terms and removed with the pack.
+#[derive(Clone, Debug, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TermPack {
+    pub id: String,
+    pub name: String,
+    pub source_path: String,
+    pub version: Option<String>,
+    pub term_count: u32,
+    pub subscribed_at: i64,
+    pub last_synced_at: Option<i64>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TermPackSyncSummary {
+    pub pack_id: String,
+    pub added: u32,
+    pub updated: u32,
+    pub removed: u32,
+    pub errors: Vec<String>,
 }
@@ -9,3 +9,49 @@ pub struct Tone {
     pub created_at: i64,
     pub sort_order: i32,
 }
+
+/// A saved revision of a tone's name and prompt template. The newest
+/// version always mirrors the tone itself.
+#[derive(Clone, Debug, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ToneVersion {
+    pub id: String,
+    pub tone_id: String,
+    pub name: String,
+    pub prompt_template: String,
+    pub created_at: i64,
+    pub is_current: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ToneDiffOp {
+    Unchanged,
+    Added,
+    Removed,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ToneDiffLine {
+    pub op: ToneDiffOp,
+    pub text: String,
+}
+
+/// The `<name/>` placeholders a prompt template uses, split into those the
+/// runtime fills in and those it does not know.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ToneTemplateValidation {
+    pub variables: Vec<String>,
+    pub unknown: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ToneImportSummary {
+    pub created: u32,
+    pub updated: u32,
+    pub unchanged: u32,
+    pub errors: Vec<String>,
+}
@@ -7,6 +7,100 @@ pub struct TranscriptionAudioSnapshot {
     pub duration_ms: i64,
 }
 
+/// One transcription and post-processing result for a recording. The
+/// active version's text is mirrored on the `transcriptions` row.
+#[derive(Clone, Debug, Deserialize, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionVersion {
+    pub id: String,
+    pub transcription_id: String,
+    pub created_at: i64,
+    pub is_active: bool,
+    pub transcript: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_transcript: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sanitized_transcript: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence_segments: Option<Vec<TranscriptionSegmentConfidence>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcription_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_size: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inference_device: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcription_prompt: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcription_api_key_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcription_duration_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_process_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_process_device: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_process_prompt: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_process_api_key_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub postprocess_duration_ms: Option<i64>,
+}
+
+impl TranscriptionVersion {
+    /// Captures the current result of `transcription` as a version.
+    pub fn from_transcription(transcription: &Transcription, id: String, created_at: i64) -> Self {
+        Self {
+            id,
+            transcription_id: transcription.id.clone(),
+            created_at,
+            is_active: false,
+            transcript: transcription.transcript.clone(),
+            raw_transcript: transcription.raw_transcript.clone(),
+            sanitized_transcript: transcription.sanitized_transcript.clone(),
+            confidence_segments: transcription.confidence_segments.clone(),
+            transcription_mode: transcription.transcription_mode.clone(),
+            model_size: transcription.model_size.clone(),
+            inference_device: transcription.inference_device.clone(),
+            transcription_prompt: transcription.transcription_prompt.clone(),
+            transcription_api_key_id: transcription.transcription_api_key_id.clone(),
+            transcription_duration_ms: transcription.transcription_duration_ms,
+            post_process_mode: transcription.post_process_mode.clone(),
+            post_process_device: transcription.post_process_device.clone(),
+            post_process_prompt: transcription.post_process_prompt.clone(),
+            post_process_api_key_id: transcription.post_process_api_key_id.clone(),
+            postprocess_duration_ms: transcription.postprocess_duration_ms,
+        }
+    }
+}
+
+/// Outcome of applying the audio retention policy.
+#[derive(Clone, Debug, Default, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioPurgeSummary {
+    pub purged_ids: Vec<String>,
+    pub reclaimed_bytes: i64,
+}
+
+/// Word-level probability reported by the local sidecar. `confidence` is the
+/// lowest token probability within the word.
+#[derive(Clone, Debug, Deserialize, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionWordConfidence {
+    pub text: String,
+    pub confidence: f32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionSegmentConfidence {
+    pub text: String,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub confidence: f32,
+    pub words: Vec<TranscriptionWordConfidence>,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct Transcription {
@@ -47,4 +141,92 @@ pub struct Transcription {
     pub remote_status: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub remote_device_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence_segments: Option<Vec<TranscriptionSegmentConfidence>>,
+}
+
+/// Filters for `transcription_search`. Every field is optional; with no
+/// `query` the results are the filtered history, newest first.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionSearchRequest {
+    #[serde(default)]
+    pub query: Option<String>,
+    #[serde(default)]
+    pub from_timestamp: Option<i64>,
+    #[serde(default)]
+    pub to_timestamp: Option<i64>,
+    #[serde(default)]
+    pub transcription_mode: Option<String>,
+    #[serde(default)]
+    pub model_size: Option<String>,
+    /// Matches either the transcription or the post-processing API key.
+    #[serde(default)]
+    pub api_key_id: Option<String>,
+    #[serde(default)]
+    pub remote_status: Option<String>,
+    #[serde(default)]
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub offset: Option<u32>,
+}
+
+/// A run of snippet text; `highlighted` runs are the matched terms.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionSnippetPart {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionSearchResult {
+    pub transcription: Transcription,
+    /// Excerpt around the best match. Empty when searching by filters only.
+    pub snippet: Vec<TranscriptionSnippetPart>,
+    /// BM25 score, lower is better. `None` when searching by filters only.
+    pub rank: Option<f64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionExportFormat {
+    Jsonl,
+    Csv,
+    Markdown,
+}
+
+/// Bulk export of the transcriptions matching `filter`. Pagination fields on
+/// the filter are ignored; every match is exported.
+#[derive(Clone, Debug, Deserialize, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionExportRequest {
+    pub format: TranscriptionExportFormat,
+    #[serde(default)]
+    pub filter: TranscriptionSearchRequest,
+    /// Bundles the export and the recorded audio into a zip archive.
+    #[serde(default)]
+    pub include_audio: bool,
+}
+
+/// What to do when an imported transcription id already exists locally.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionImportConflict {
+    #[default]
+    Skip,
+    Overwrite,
+    KeepBoth,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionImportSummary {
+    pub imported: u32,
+    pub overwritten: u32,
+    pub skipped: u32,
+    /// Conflicting entries imported under a fresh id.
+    pub duplicated: u32,
+    pub errors: Vec<String>,
 }
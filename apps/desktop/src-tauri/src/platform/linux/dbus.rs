@@ -0,0 +1,234 @@
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast::error::RecvError;
+use zbus::fdo;
+use zbus::object_server::SignalEmitter;
+
+use crate::domain::OverlayPhase;
+use crate::system::bridge_server::{
+    BridgeControlAction, BridgeControlPayload, BridgeEvent, BridgeHotkeyTriggerPayload,
+    EVT_BRIDGE_CONTROL, EVT_BRIDGE_HOTKEY_TRIGGER,
+};
+
+pub const BUS_NAME: &str = "com.voquill.Dictation";
+pub const OBJECT_PATH: &str = "/com/voquill/Dictation";
+
+struct DictationService {
+    app: AppHandle,
+}
+
+impl DictationService {
+    fn control(
+        &self,
+        action: BridgeControlAction,
+        tone_id: Option<String>,
+        language: Option<String>,
+    ) -> fdo::Result<()> {
+        let payload = BridgeControlPayload {
+            action,
+            tone_id,
+            language,
+        };
+        self.app
+            .emit(EVT_BRIDGE_CONTROL, &payload)
+            .map_err(|err| fdo::Error::Failed(format!("Failed to reach the app: {err}")))?;
+        log::info!("D-Bus control requested: {action:?}");
+        Ok(())
+    }
+}
+
+/// What `Toggle` does in each overlay phase.
+fn toggle_action(phase: OverlayPhase) -> fdo::Result<BridgeControlAction> {
+    match phase {
+        OverlayPhase::Idle => Ok(BridgeControlAction::StartDictation),
+        OverlayPhase::Recording => Ok(BridgeControlAction::StopDictation),
+        OverlayPhase::Loading => Err(fdo::Error::Failed(
+            "A transcription is in progress".to_string(),
+        )),
+    }
+}
+
+fn required(value: String, name: &str) -> fdo::Result<String> {
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        return Err(fdo::Error::InvalidArgs(format!("{name} is required")));
+    }
+    Ok(value)
+}
+
+#[zbus::interface(name = "com.voquill.Dictation")]
+impl DictationService {
+    /// Starts dictation when idle and stops it while recording.
+    async fn toggle(&self) -> fdo::Result<()> {
+        let phase = self.app.state::<crate::state::OverlayState>().get_phase();
+        self.control(toggle_action(phase)?, None, None)
+    }
+
+    async fn start(&self) -> fdo::Result<()> {
+        self.control(BridgeControlAction::StartDictation, None, None)
+    }
+
+    async fn stop(&self) -> fdo::Result<()> {
+        self.control(BridgeControlAction::StopDictation, None, None)
+    }
+
+    async fn cancel(&self) -> fdo::Result<()> {
+        self.control(BridgeControlAction::CancelDictation, None, None)
+    }
+
+    async fn set_tone(&self, tone_id: String) -> fdo::Result<()> {
+        let tone_id = required(tone_id, "tone id")?;
+        self.control(BridgeControlAction::SetTone, Some(tone_id), None)
+    }
+
+    async fn set_language(&self, language: String) -> fdo::Result<()> {
+        let language = required(language, "language")?;
+        self.control(BridgeControlAction::SetLanguage, None, Some(language))
+    }
+
+    /// Fires a registered hotkey action, as compositor shortcuts do.
+    async fn trigger_hotkey(&self, name: String) -> fdo::Result<()> {
+        let hotkey = required(name, "hotkey name")?;
+        self.app
+            .emit(
+                EVT_BRIDGE_HOTKEY_TRIGGER,
+                &BridgeHotkeyTriggerPayload {
+                    hotkey: hotkey.clone(),
+                },
+            )
+            .map_err(|err| fdo::Error::Failed(format!("Failed to reach the app: {err}")))?;
+        log::info!("D-Bus hotkey triggered: {hotkey}");
+        Ok(())
+    }
+
+    /// Returns the saved text of a transcript announced by `TranscriptReady`.
+    async fn get_transcript(&self, id: String) -> fdo::Result<String> {
+        let id = required(id, "transcript id")?;
+        let pool = self.app.state::<crate::state::OptionKeyDatabase>().pool();
+        match crate::db::transcription_queries::fetch_transcription(pool, &id).await {
+            Ok(Some(transcription)) => Ok(transcription.transcript),
+            Ok(None) => Err(fdo::Error::InvalidArgs(format!(
+                "No transcript with id {id}"
+            ))),
+            Err(err) => {
+                log::error!("D-Bus failed to load transcript {id}: {err}");
+                Err(fdo::Error::Failed(
+                    "Failed to load the transcript".to_string(),
+                ))
+            }
+        }
+    }
+
+    #[zbus(property)]
+    async fn phase(&self) -> String {
+        self.app
+            .state::<crate::state::OverlayState>()
+            .get_phase()
+            .as_str()
+            .to_string()
+    }
+
+    /// The selected tone id, or an empty string when none is selected.
+    /// Changes made in the app are not announced.
+    #[zbus(property(emits_changed_signal = "false"))]
+    async fn active_tone(&self) -> String {
+        let pool = self.app.state::<crate::state::OptionKeyDatabase>().pool();
+        match crate::db::preferences_queries::fetch_active_tone_id(pool).await {
+            Ok(tone_id) => tone_id.unwrap_or_default(),
+            Err(err) => {
+                log::error!("D-Bus failed to load the active tone: {err}");
+                String::new()
+            }
+        }
+    }
+
+    #[zbus(signal, name = "PhaseChanged")]
+    async fn emit_phase_changed(emitter: &SignalEmitter<'_>, phase: &str) -> zbus::Result<()>;
+
+    /// Announces a saved transcript by id only; signals reach every listener
+    /// on the session bus, so the text is fetched with `GetTranscript`.
+    #[zbus(signal)]
+    async fn transcript_ready(emitter: &SignalEmitter<'_>, id: &str) -> zbus::Result<()>;
+}
+
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        if let Err(err) = serve(app).await {
+            log::error!("D-Bus service stopped: {err}");
+        }
+    });
+}
+
+async fn serve(app: AppHandle) -> zbus::Result<()> {
+    // Subscribe first so nothing published while connecting is lost.
+    let mut events = crate::system::bridge_server::subscribe();
+
+    let connection = zbus::connection::Builder::session()?
+        .name(BUS_NAME)?
+        .serve_at(OBJECT_PATH, DictationService { app })?
+        .build()
+        .await?;
+    log::info!("D-Bus service registered as {BUS_NAME}");
+
+    let iface = connection
+        .object_server()
+        .interface::<_, DictationService>(OBJECT_PATH)
+        .await?;
+    let emitter = iface.signal_emitter();
+
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(skipped)) => {
+                log::warn!("D-Bus service skipped {skipped} events");
+                continue;
+            }
+            Err(RecvError::Closed) => return Ok(()),
+        };
+
+        let result = match event {
+            BridgeEvent::PhaseChanged { phase } => {
+                match DictationService::emit_phase_changed(emitter, phase.as_str()).await {
+                    Ok(()) => iface.get().await.phase_changed(emitter).await,
+                    Err(err) => Err(err),
+                }
+            }
+            BridgeEvent::Transcript { id, .. } => {
+                DictationService::transcript_ready(emitter, &id).await
+            }
+            _ => Ok(()),
+        };
+        if let Err(err) = result {
+            log::warn!("Failed to emit D-Bus signal: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_starts_when_idle_and_stops_while_recording() {
+        assert!(matches!(
+            toggle_action(OverlayPhase::Idle),
+            Ok(BridgeControlAction::StartDictation)
+        ));
+        assert!(matches!(
+            toggle_action(OverlayPhase::Recording),
+            Ok(BridgeControlAction::StopDictation)
+        ));
+        assert!(matches!(
+            toggle_action(OverlayPhase::Loading),
+            Err(fdo::Error::Failed(_))
+        ));
+    }
+
+    #[test]
+    fn required_arguments_are_trimmed_and_must_not_be_blank() {
+        assert_eq!(required("  warm ".to_string(), "tone id").unwrap(), "warm");
+        assert!(matches!(
+            required("   ".to_string(), "tone id"),
+            Err(fdo::Error::InvalidArgs(message)) if message == "tone id is required"
+        ));
+    }
+}
@@ -21,3 +21,29 @@ pub(crate) fn paste_text_into_focused_field(
         super::x11::input::paste_text(target, keybind, skip_clipboard_restore)
     }
 }
+
+/// Types text as keystrokes, leaving the clipboard alone.
+pub(crate) fn type_text_at_cursor(text: &str) -> Result<(), String> {
+    if text.is_empty() {
+        return Ok(());
+    }
+
+    if super::detect::is_wayland() {
+        super::wl::input::type_text(text)
+    } else {
+        super::x11::input::type_text(text)
+    }
+}
+
+/// Presses Backspace `count` times.
+pub(crate) fn erase_before_cursor(count: usize) -> Result<(), String> {
+    if count == 0 {
+        return Ok(());
+    }
+
+    if super::detect::is_wayland() {
+        super::wl::input::press_backspace(count)
+    } else {
+        super::x11::input::press_backspace(count)
+    }
+}
@@ -5,6 +5,7 @@ mod x11;
 pub mod accessibility;
 pub mod audio;
 pub mod compositor;
+pub mod dbus;
 pub mod feedback;
 pub mod init;
 pub mod input;
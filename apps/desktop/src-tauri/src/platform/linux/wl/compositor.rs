@@ -157,6 +157,13 @@ pub fn sync_compositor_hotkeys(
     }
 }
 
+/// Shortcuts always run the trigger script, which calls the D-Bus service
+/// when it is on the bus and the bridge server otherwise. The service
+/// registers asynchronously, so a binding chosen at sync time could go stale.
+fn trigger_command(script_path: &Path, action_name: &str) -> String {
+    format!("{} {}", script_path.display(), action_name)
+}
+
 // --- Key translation ---
 
 fn classify_key(key: &str) -> Option<(&'static str, bool)> {
@@ -373,7 +380,7 @@ fn sync_gnome(script_path: &Path, bindings: &[CompositorBinding]) -> Result<(),
             sanitize_action_for_path(&binding.action_name)
         );
         let gnome_binding = keys_to_gnome_binding(&binding.keys);
-        let command = format!("{} {}", script_path.display(), binding.action_name);
+        let command = trigger_command(script_path, &binding.action_name);
 
         gsettings_set_custom_keybinding(
             &dconf_path,
@@ -419,10 +426,9 @@ fn sync_sway(script_path: &Path, bindings: &[CompositorBinding]) -> Result<(), S
         }
         let sway_keys = keys_to_sway_binding(&binding.keys);
         content.push_str(&format!(
-            "bindsym {} exec {} {}\n",
+            "bindsym {} exec {}\n",
             sway_keys,
-            script_path.display(),
-            binding.action_name,
+            trigger_command(script_path, &binding.action_name),
         ));
     }
 
@@ -459,11 +465,10 @@ fn sync_hyprland(script_path: &Path, bindings: &[CompositorBinding]) -> Result<(
         }
         let (mods, key) = keys_to_hyprland_binding(&binding.keys);
         content.push_str(&format!(
-            "bind = {}, {}, exec, {} {}\n",
+            "bind = {}, {}, exec, {}\n",
             mods,
             key,
-            script_path.display(),
-            binding.action_name,
+            trigger_command(script_path, &binding.action_name),
         ));
     }
 
@@ -611,11 +616,10 @@ fn sync_kde(script_path: &Path, bindings: &[CompositorBinding]) -> Result<(), St
             "[Desktop Entry]\n\
              Type=Application\n\
              Name={friendly}\n\
-             Exec={script} {action}\n\
+             Exec={command}\n\
              NoDisplay=true\n\
              X-KDE-Shortcuts={shortcut}\n",
-            action = binding.action_name,
-            script = script_path.display(),
+            command = trigger_command(script_path, &binding.action_name),
             shortcut = kde_shortcut,
         );
         fs::write(&desktop_path, &desktop_content)
@@ -789,7 +793,7 @@ fn sync_cosmic(script_path: &Path, bindings: &[CompositorBinding]) -> Result<(),
             continue;
         }
         let (mods, key) = keys_to_cosmic_binding(&binding.keys);
-        let command = format!("{} {}", script_path.display(), binding.action_name);
+        let command = trigger_command(script_path, &binding.action_name);
         let description = format!("Voquill {}", binding.action_name);
         all_entries.push(format!(
             "(modifiers: [{mods}], key: \"{key}\", description: Some(\"{desc}\")): Spawn(\"{cmd}\")",
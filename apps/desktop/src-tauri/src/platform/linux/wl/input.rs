@@ -25,7 +25,8 @@ pub(crate) fn clipboard_set(text: &str) -> Result<(), String> {
 //
 // v0.1.x: key combos as "modifier+key" (e.g. "ctrl+v")
 // v1.x:   scancode pairs as "code:1" (press) / "code:0" (release)
-//         KEY_LEFTCTRL = 29, KEY_LEFTSHIFT = 42, KEY_C = 46, KEY_V = 47, KEY_INSERT = 110
+//         KEY_BACKSPACE = 14, KEY_LEFTCTRL = 29, KEY_LEFTSHIFT = 42, KEY_C = 46, KEY_V = 47,
+//         KEY_INSERT = 110
 
 use std::sync::OnceLock;
 
@@ -144,8 +145,64 @@ pub(crate) fn simulate_copy_keystroke() -> Result<(), String> {
     wtype_key(&["ctrl"], "c")
 }
 
+fn ydotool_backspace(count: usize) -> Result<(), String> {
+    let keys: &[&str] = if is_ydotool_v1() {
+        &["14:1", "14:0"]
+    } else {
+        &["backspace"]
+    };
+    let args: Vec<&str> = keys
+        .iter()
+        .copied()
+        .cycle()
+        .take(keys.len() * count)
+        .collect();
+    ydotool_key(&args)
+}
+
+fn wtype_run(args: &[&str]) -> Result<(), String> {
+    let status = Command::new(wtype_bin()?)
+        .args(args)
+        .status()
+        .map_err(|err| format!("wtype failed: {err}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err("wtype exited with non-zero status".into())
+    }
+}
+
 // --- Public API ---
 
+/// Types text through the virtual keyboard. wtype handles any character;
+/// ydotool is the fallback where the compositor lacks the protocol.
+pub fn type_text(text: &str) -> Result<(), String> {
+    wtype_run(&["--", text]).or_else(|err| {
+        log::warn!("wtype typing failed ({err}), trying ydotool");
+        let output = Command::new("ydotool")
+            .args(["type", "--", text])
+            .output()
+            .map_err(|err| format!("ydotool failed: {err}"))?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err("ydotool exited with non-zero status".into())
+        }
+    })
+}
+
+pub fn press_backspace(count: usize) -> Result<(), String> {
+    if ydotool_available() {
+        return ydotool_backspace(count);
+    }
+    let args: Vec<&str> = ["-k", "BackSpace"]
+        .into_iter()
+        .cycle()
+        .take(2 * count)
+        .collect();
+    wtype_run(&args)
+}
+
 pub fn paste_text(
     text: &str,
     keybind: Option<&str>,
@@ -17,6 +17,23 @@ pub fn paste_text(
     })
 }
 
+pub fn type_text(text: &str) -> Result<(), String> {
+    enigo_type_text(text)
+}
+
+pub fn press_backspace(count: usize) -> Result<(), String> {
+    let mut enigo = Enigo::new();
+    enigo.key_up(Key::Shift);
+    enigo.key_up(Key::Control);
+    enigo.key_up(Key::Alt);
+    thread::sleep(Duration::from_millis(30));
+    for _ in 0..count {
+        enigo.key_click(Key::Backspace);
+        thread::sleep(Duration::from_millis(5));
+    }
+    Ok(())
+}
+
 fn enigo_type_text(text: &str) -> Result<(), String> {
     let mut enigo = Enigo::new();
     enigo.key_up(Key::Shift);
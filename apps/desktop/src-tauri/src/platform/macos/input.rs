@@ -6,6 +6,7 @@ use super::accessibility;
 
 const KEY_A: CGKeyCode = 0;
 const KEY_C: CGKeyCode = 8;
+const KEY_DELETE: CGKeyCode = 51;
 const KEY_SPACE: CGKeyCode = 49;
 const KEY_V: CGKeyCode = 9;
 
@@ -27,6 +28,27 @@ pub(crate) fn paste_text_into_focused_field(
     }
 }
 
+/// Inserts text without pasting when accessibility allows it. Streamed
+/// partials arrive quickly, so restoring the clipboard between them would race.
+pub(crate) fn type_text_at_cursor(text: &str) -> Result<(), String> {
+    if text.is_empty() {
+        return Ok(());
+    }
+
+    accessibility::insert_text_at_cursor(text).or_else(|err| {
+        log::warn!("Accessibility insert failed ({err}), falling back to clipboard paste");
+        paste_via_clipboard(text, true)
+    })
+}
+
+pub(crate) fn erase_before_cursor(count: usize) -> Result<(), String> {
+    for _ in 0..count {
+        simulate_keypress(KEY_DELETE, CGEventFlags::empty())?;
+        thread::sleep(Duration::from_millis(5));
+    }
+    Ok(())
+}
+
 fn paste_via_clipboard(text: &str, skip_clipboard_restore: bool) -> Result<(), String> {
     let trimmed_text = text.trim_end_matches(' ');
     let trailing_spaces = text.len() - trimmed_text.len();
@@ -6,8 +6,8 @@ use windows::Win32::Foundation::HWND;
 use windows::Win32::UI::Input::KeyboardAndMouse::{
     GetAsyncKeyState, SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, INPUT_MOUSE, KEYBDINPUT,
     KEYEVENTF_KEYUP, MOUSEEVENTF_RIGHTDOWN, MOUSEEVENTF_RIGHTUP, MOUSEINPUT, VIRTUAL_KEY, VK_A,
-    VK_C, VK_CONTROL, VK_DELETE, VK_INSERT, VK_LCONTROL, VK_LMENU, VK_LSHIFT, VK_LWIN, VK_MENU,
-    VK_RCONTROL, VK_RIGHT, VK_RMENU, VK_RSHIFT, VK_RWIN, VK_SHIFT, VK_V,
+    VK_BACK, VK_C, VK_CONTROL, VK_DELETE, VK_INSERT, VK_LCONTROL, VK_LMENU, VK_LSHIFT, VK_LWIN,
+    VK_MENU, VK_RCONTROL, VK_RIGHT, VK_RMENU, VK_RSHIFT, VK_RWIN, VK_SHIFT, VK_V,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
     GetClassNameW, GetForegroundWindow, GetWindowTextLengthW, GetWindowTextW,
@@ -122,6 +122,23 @@ pub(crate) fn type_text_via_keystrokes(text: &str) -> Result<(), String> {
     Ok(())
 }
 
+pub(crate) fn type_text_at_cursor(text: &str) -> Result<(), String> {
+    type_text_via_keystrokes(text)
+}
+
+pub(crate) fn erase_before_cursor(count: usize) -> Result<(), String> {
+    if count == 0 {
+        return Ok(());
+    }
+    release_modifier_keys();
+    for _ in 0..count {
+        send_key_down(VK_BACK);
+        send_key_up(VK_BACK);
+        thread::sleep(Duration::from_millis(5));
+    }
+    Ok(())
+}
+
 pub(crate) fn shift_select_right(count: usize) {
     if count == 0 {
         return;
@@ -1,3 +1,4 @@
+use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 
 use rand::{rngs::OsRng, RngCore};
@@ -5,6 +6,9 @@ use serde::Serialize;
 use sha2::{Digest, Sha256};
 use tokio::sync::watch;
 
+/// Streamed utterances tracked at once; the oldest is forgotten first.
+const OPEN_UTTERANCE_LIMIT: usize = 16;
+
 #[derive(Clone, Debug, Serialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct RemoteReceiverStatus {
@@ -25,9 +29,18 @@ pub struct RemoteReceiverStatus {
     pub device_platform: String,
 }
 
+/// Text typed so far for an utterance streamed by a sender.
+struct OpenUtterance {
+    sender_device_id: String,
+    utterance_id: String,
+    typed: String,
+}
+
 struct RemoteReceiverStateInner {
     status: RemoteReceiverStatus,
     shutdown: Option<watch::Sender<bool>>,
+    advertisement: Option<String>,
+    utterances: VecDeque<OpenUtterance>,
 }
 
 #[derive(Clone)]
@@ -74,6 +87,8 @@ impl RemoteReceiverState {
                     device_platform: std::env::consts::OS.to_string(),
                 },
                 shutdown: None,
+                advertisement: None,
+                utterances: VecDeque::new(),
             })),
         }
     }
@@ -105,6 +120,16 @@ impl RemoteReceiverState {
         inner.status.port = None;
     }
 
+    /// Remembers the mDNS service name so [`Self::take_advertisement`] can
+    /// withdraw it when the receiver stops.
+    pub fn set_advertisement(&self, fullname: String) {
+        self.inner.lock().unwrap().advertisement = Some(fullname);
+    }
+
+    pub fn take_advertisement(&self) -> Option<String> {
+        self.inner.lock().unwrap().advertisement.take()
+    }
+
     pub fn rotate_pairing_code(&self) {
         let mut inner = self.inner.lock().unwrap();
         inner.status.pairing_code = generate_pairing_code();
@@ -130,6 +155,49 @@ impl RemoteReceiverState {
         inner.status.last_target_editable = target_editable;
     }
 
+    pub fn utterance_text(&self, sender_device_id: &str, utterance_id: &str) -> Option<String> {
+        self.inner
+            .lock()
+            .unwrap()
+            .utterances
+            .iter()
+            .find(|open| {
+                open.sender_device_id == sender_device_id && open.utterance_id == utterance_id
+            })
+            .map(|open| open.typed.clone())
+    }
+
+    pub fn set_utterance_text(&self, sender_device_id: &str, utterance_id: &str, typed: String) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(open) = inner.utterances.iter_mut().find(|open| {
+            open.sender_device_id == sender_device_id && open.utterance_id == utterance_id
+        }) {
+            open.typed = typed;
+            return;
+        }
+
+        if inner.utterances.len() == OPEN_UTTERANCE_LIMIT {
+            inner.utterances.pop_front();
+        }
+        inner.utterances.push_back(OpenUtterance {
+            sender_device_id: sender_device_id.to_string(),
+            utterance_id: utterance_id.to_string(),
+            typed,
+        });
+    }
+
+    pub fn take_utterance_text(
+        &self,
+        sender_device_id: &str,
+        utterance_id: &str,
+    ) -> Option<String> {
+        let mut inner = self.inner.lock().unwrap();
+        let index = inner.utterances.iter().position(|open| {
+            open.sender_device_id == sender_device_id && open.utterance_id == utterance_id
+        })?;
+        inner.utterances.remove(index).map(|open| open.typed)
+    }
+
     pub fn record_error(
         &self,
         sender_device_id: Option<String>,
@@ -1,19 +1,52 @@
 use std::fs;
-use std::io;
+use std::io::{self, Cursor};
 use std::path::{Path, PathBuf};
 
 use tauri::Manager;
 
+use flacenc::component::BitRepr;
+use flacenc::error::Verify;
 use hound::{SampleFormat, WavReader, WavSpec, WavWriter};
 
-use crate::domain::TranscriptionAudioSnapshot;
+use crate::domain::{AudioRetentionPolicy, TranscriptionAudioSnapshot};
 
 const AUDIO_DIR_NAME: &str = "transcription-audio";
+const FLAC_MAGIC: &[u8; 4] = b"fLaC";
+const MS_PER_DAY: i64 = 24 * 60 * 60 * 1_000;
+
+/// Container used for newly stored clips. Existing clips keep whatever
+/// format they were written in; readers detect it from the file contents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AudioStorageFormat {
+    Wav,
+    Flac,
+}
+
+impl AudioStorageFormat {
+    /// Parses the `audio_storage_format` preference, falling back to FLAC.
+    pub fn from_preference(value: &str) -> Self {
+        match value {
+            "wav" => Self::Wav,
+            _ => Self::Flac,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Wav => "wav",
+            Self::Flac => "flac",
+        }
+    }
+}
 
 fn map_hound_error(err: hound::Error) -> io::Error {
     io::Error::other(err.to_string())
 }
 
+fn map_claxon_error(err: claxon::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
+
 fn sanitize_id(id: &str) -> String {
     let mut sanitized = id
         .chars()
@@ -37,9 +70,13 @@ pub fn audio_dir(app: &tauri::AppHandle) -> io::Result<PathBuf> {
     Ok(path)
 }
 
-pub fn audio_path_for(app: &tauri::AppHandle, transcription_id: &str) -> io::Result<PathBuf> {
+pub fn audio_path_for(
+    app: &tauri::AppHandle,
+    transcription_id: &str,
+    extension: &str,
+) -> io::Result<PathBuf> {
     let mut path = audio_dir(app)?;
-    path.push(format!("{}.wav", sanitize_id(transcription_id)));
+    path.push(format!("{}.{extension}", sanitize_id(transcription_id)));
     Ok(path)
 }
 
@@ -48,6 +85,7 @@ pub fn save_transcription_audio(
     transcription_id: &str,
     samples: &[f32],
     sample_rate: u32,
+    format: AudioStorageFormat,
 ) -> io::Result<TranscriptionAudioSnapshot> {
     if samples.is_empty() {
         return Err(io::Error::new(
@@ -63,8 +101,27 @@ pub fn save_transcription_audio(
         ));
     }
 
-    let path = audio_path_for(app, transcription_id)?;
+    let path = audio_path_for(app, transcription_id, format.extension())?;
+    let quantized = samples
+        .iter()
+        .map(|sample| (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16)
+        .collect::<Vec<_>>();
+
+    let encoded = match format {
+        AudioStorageFormat::Wav => encode_wav(&quantized, sample_rate)?,
+        AudioStorageFormat::Flac => encode_flac(&quantized, sample_rate)?,
+    };
+    crate::system::vault::write_file(&path, &encoded)?;
+
+    let duration_ms = ((samples.len() as f64 / sample_rate as f64) * 1_000.0).round() as i64;
+
+    Ok(TranscriptionAudioSnapshot {
+        file_path: path.to_string_lossy().to_string(),
+        duration_ms,
+    })
+}
 
+fn encode_wav(samples: &[i16], sample_rate: u32) -> io::Result<Vec<u8>> {
     let spec = WavSpec {
         channels: 1,
         sample_rate,
@@ -72,20 +129,34 @@ pub fn save_transcription_audio(
         sample_format: SampleFormat::Int,
     };
 
-    let mut writer = WavWriter::create(&path, spec).map_err(map_hound_error)?;
+    let mut encoded = Cursor::new(Vec::new());
+    let mut writer = WavWriter::new(&mut encoded, spec).map_err(map_hound_error)?;
     for sample in samples {
-        let normalized = sample.clamp(-1.0, 1.0);
-        let quantized = (normalized * i16::MAX as f32).round() as i16;
-        writer.write_sample(quantized).map_err(map_hound_error)?;
+        writer.write_sample(*sample).map_err(map_hound_error)?;
     }
     writer.finalize().map_err(map_hound_error)?;
+    Ok(encoded.into_inner())
+}
 
-    let duration_ms = ((samples.len() as f64 / sample_rate as f64) * 1_000.0).round() as i64;
-
-    Ok(TranscriptionAudioSnapshot {
-        file_path: path.to_string_lossy().to_string(),
-        duration_ms,
-    })
+/// Encodes 16-bit mono PCM losslessly; speech typically shrinks to about
+/// half the size of the equivalent WAV.
+fn encode_flac(samples: &[i16], sample_rate: u32) -> io::Result<Vec<u8>> {
+    let config = flacenc::config::Encoder::default()
+        .into_verified()
+        .map_err(|(_, err)| io::Error::other(format!("Invalid FLAC config: {err:?}")))?;
+    let pcm = samples
+        .iter()
+        .map(|sample| *sample as i32)
+        .collect::<Vec<_>>();
+    let source = flacenc::source::MemSource::from_samples(&pcm, 1, 16, sample_rate as usize);
+    let stream = flacenc::encode_with_fixed_block_size(&config, source, config.block_size)
+        .map_err(|err| io::Error::other(format!("FLAC encoding failed: {err:?}")))?;
+
+    let mut sink = flacenc::bitsink::ByteSink::new();
+    stream
+        .write(&mut sink)
+        .map_err(|err| io::Error::other(format!("FLAC encoding failed: {err:?}")))?;
+    Ok(sink.as_slice().to_vec())
 }
 
 pub fn delete_audio_file(app: &tauri::AppHandle, file_path: &Path) -> io::Result<()> {
@@ -105,8 +176,18 @@ pub fn delete_audio_file(app: &tauri::AppHandle, file_path: &Path) -> io::Result
     }
 }
 
+/// Reads a stored audio file, decrypting it if it was written by the vault.
+pub fn read_audio_bytes(path: &Path) -> io::Result<Vec<u8>> {
+    Ok(crate::system::vault::read_file(path)?)
+}
+
 pub fn load_audio_samples(path: &Path) -> io::Result<(Vec<f32>, u32)> {
-    let mut reader = WavReader::open(path).map_err(map_hound_error)?;
+    let bytes = read_audio_bytes(path)?;
+    if bytes.starts_with(FLAC_MAGIC) {
+        return decode_flac(bytes);
+    }
+
+    let mut reader = WavReader::new(Cursor::new(bytes)).map_err(map_hound_error)?;
     let spec = reader.spec();
 
     if spec.sample_rate == 0 {
@@ -260,3 +341,160 @@ pub fn load_audio_samples(path: &Path) -> io::Result<(Vec<f32>, u32)> {
 
     Ok((samples, spec.sample_rate))
 }
+
+fn decode_flac(bytes: Vec<u8>) -> io::Result<(Vec<f32>, u32)> {
+    let mut reader = claxon::FlacReader::new(Cursor::new(bytes)).map_err(map_claxon_error)?;
+    let info = reader.streaminfo();
+
+    if info.sample_rate == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Audio file missing sample rate",
+        ));
+    }
+
+    let channels = info.channels.max(1) as usize;
+    let scale = (1i64 << (info.bits_per_sample.saturating_sub(1))) as f32;
+    let capacity = info.samples.unwrap_or(0) as usize;
+    let mut samples = Vec::with_capacity(capacity);
+    let mut frame_sum = 0.0f32;
+    let mut frame_len = 0usize;
+
+    for sample in reader.samples() {
+        frame_sum += sample.map_err(map_claxon_error)? as f32 / scale;
+        frame_len += 1;
+        if frame_len == channels {
+            samples.push((frame_sum / channels as f32).clamp(-1.0, 1.0));
+            frame_sum = 0.0;
+            frame_len = 0;
+        }
+    }
+
+    if frame_len != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "Audio frame truncated",
+        ));
+    }
+
+    if samples.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Audio file did not contain usable samples",
+        ));
+    }
+
+    Ok((samples, info.sample_rate))
+}
+
+/// A stored clip considered by the retention policy.
+#[derive(Clone, Debug)]
+pub struct StoredAudioEntry {
+    pub id: String,
+    pub path: PathBuf,
+    pub timestamp: i64,
+    pub size_bytes: u64,
+}
+
+/// Returns the indices of `entries` that fall outside `policy`. `entries`
+/// must be ordered newest first so that the most recent clips are kept.
+pub fn select_for_purge(
+    entries: &[StoredAudioEntry],
+    policy: &AudioRetentionPolicy,
+    now_ms: i64,
+) -> Vec<usize> {
+    let cutoff = policy
+        .max_age_days
+        .map(|days| now_ms.saturating_sub(days.saturating_mul(MS_PER_DAY)));
+    let mut kept_count = 0usize;
+    let mut kept_bytes = 0u64;
+    let mut purged = Vec::new();
+
+    for (index, entry) in entries.iter().enumerate() {
+        let over_count = policy.max_count.is_some_and(|max| kept_count >= max);
+        let too_old = cutoff.is_some_and(|cutoff| entry.timestamp < cutoff);
+        let over_size = policy
+            .max_total_bytes
+            .is_some_and(|max| kept_bytes.saturating_add(entry.size_bytes) > max);
+
+        if over_count || too_old || over_size {
+            purged.push(index);
+        } else {
+            kept_count += 1;
+            kept_bytes += entry.size_bytes;
+        }
+    }
+
+    purged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, timestamp: i64, size_bytes: u64) -> StoredAudioEntry {
+        StoredAudioEntry {
+            id: id.to_string(),
+            path: PathBuf::from(format!("{id}.flac")),
+            timestamp,
+            size_bytes,
+        }
+    }
+
+    #[test]
+    fn flac_round_trips_samples() {
+        let samples = (0..4_800)
+            .map(|i| {
+                ((i as f32 / 16_000.0 * 440.0 * std::f32::consts::TAU).sin()
+                    * 0.5
+                    * i16::MAX as f32) as i16
+            })
+            .collect::<Vec<_>>();
+
+        let encoded = encode_flac(&samples, 16_000).unwrap();
+        assert!(encoded.starts_with(FLAC_MAGIC));
+
+        let (decoded, sample_rate) = decode_flac(encoded).unwrap();
+        assert_eq!(sample_rate, 16_000);
+        assert_eq!(decoded.len(), samples.len());
+        for (original, restored) in samples.iter().zip(&decoded) {
+            assert_eq!((restored * 32_768.0).round() as i16, *original);
+        }
+    }
+
+    #[test]
+    fn retention_without_limits_keeps_everything() {
+        let entries = vec![entry("a", 3, 10), entry("b", 2, 10), entry("c", 1, 10)];
+        let policy = AudioRetentionPolicy::default();
+        assert!(select_for_purge(&entries, &policy, 4).is_empty());
+    }
+
+    #[test]
+    fn retention_applies_count_age_and_size_limits() {
+        let now = 10 * MS_PER_DAY;
+        let entries = vec![
+            entry("a", now, 400),
+            entry("b", now - MS_PER_DAY, 700),
+            entry("c", now - 2 * MS_PER_DAY, 300),
+            entry("d", now - 5 * MS_PER_DAY, 100),
+        ];
+
+        let by_count = AudioRetentionPolicy {
+            max_count: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(select_for_purge(&entries, &by_count, now), vec![2, 3]);
+
+        let by_age = AudioRetentionPolicy {
+            max_age_days: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(select_for_purge(&entries, &by_age, now), vec![3]);
+
+        let by_size = AudioRetentionPolicy {
+            max_total_bytes: Some(800),
+            ..Default::default()
+        };
+        assert_eq!(select_for_purge(&entries, &by_size, now), vec![1]);
+    }
+}
@@ -11,7 +11,7 @@ use tauri::{AppHandle, Manager};
 use tokio::sync::Mutex;
 use url::Url;
 
-use crate::system::crypto::{protect_api_key, reveal_api_key};
+use crate::system::crypto::{is_legacy_ciphertext, protect_api_key, reveal_api_key};
 
 const SESSION_FILE_NAME: &str = "auth_session.json";
 const ID_TOKEN_SKEW_SECONDS: i64 = 30;
@@ -190,7 +190,8 @@ impl AuthSession {
         custom_token: &str,
     ) -> Result<(), AuthSessionError> {
         let mut guard = self.inner.lock().await;
-        let response = sign_in_with_custom_token(&guard.client, &guard.config, custom_token).await?;
+        let response =
+            sign_in_with_custom_token(&guard.client, &guard.config, custom_token).await?;
         persist_refresh_token(&guard.session_path, &response.refresh_token)?;
         guard.refresh_token = Some(response.refresh_token);
         guard.id_token = Some(IdTokenCache {
@@ -249,7 +250,9 @@ impl AuthSession {
                 Ok(false)
             }
             Err(AuthSessionError::Firebase(msg)) => {
-                log::warn!("auth_is_signed_in: firebase rejected refresh ({msg}); clearing session");
+                log::warn!(
+                    "auth_is_signed_in: firebase rejected refresh ({msg}); clearing session"
+                );
                 clear_session(&mut guard)?;
                 Ok(false)
             }
@@ -352,8 +355,8 @@ pub(crate) fn navigate_main_to_built_in(app: &AppHandle) -> Result<(), String> {
         .get_webview_window("main")
         .ok_or_else(|| "main webview not available".to_string())?;
     let url = built_in_url(app)?.to_string();
-    let url_json = serde_json::to_string(&url)
-        .map_err(|err| format!("serialize built-in url: {err}"))?;
+    let url_json =
+        serde_json::to_string(&url).map_err(|err| format!("serialize built-in url: {err}"))?;
     let script = format!("window.location.replace({url_json});");
     window
         .eval(&script)
@@ -385,20 +388,37 @@ fn session_file_path(app: &AppHandle) -> Result<PathBuf, AuthSessionError> {
     Ok(path)
 }
 
+/// Whether a refresh token sealed under the master key is on disk. A file
+/// that cannot be read counts as sealed, so no new key replaces its key.
+pub fn has_sealed_refresh_token(app: &AppHandle) -> bool {
+    let Ok(path) = session_file_path(app) else {
+        return true;
+    };
+    match fs::read(&path) {
+        Ok(bytes) => serde_json::from_slice::<PersistedSession>(&bytes)
+            .map(|persisted| !is_legacy_ciphertext(&persisted.refresh_token_ciphertext_b64))
+            .unwrap_or(true),
+        Err(err) => err.kind() != io::ErrorKind::NotFound,
+    }
+}
+
 fn load_refresh_token(path: &PathBuf) -> Result<Option<String>, AuthSessionError> {
     if !path.exists() {
         return Ok(None);
     }
     let bytes = fs::read(path)?;
     let persisted: PersistedSession = serde_json::from_slice(&bytes)?;
-    let plaintext =
-        reveal_api_key(&persisted.salt_b64, &persisted.refresh_token_ciphertext_b64)
-            .map_err(|err| AuthSessionError::Crypto(err.to_string()))?;
+    let plaintext = reveal_api_key(&persisted.salt_b64, &persisted.refresh_token_ciphertext_b64)
+        .map_err(|err| AuthSessionError::Crypto(err.to_string()))?;
+    if is_legacy_ciphertext(&persisted.refresh_token_ciphertext_b64) {
+        persist_refresh_token(path, &plaintext)?;
+    }
     Ok(Some(plaintext))
 }
 
 fn persist_refresh_token(path: &PathBuf, refresh_token: &str) -> Result<(), AuthSessionError> {
-    let protected = protect_api_key(refresh_token);
+    let protected =
+        protect_api_key(refresh_token).map_err(|err| AuthSessionError::Crypto(err.to_string()))?;
     let persisted = PersistedSession {
         salt_b64: protected.salt_b64,
         refresh_token_ciphertext_b64: protected.ciphertext_b64,
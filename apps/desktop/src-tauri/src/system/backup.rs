@@ -0,0 +1,715 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Manager};
+use zip::write::SimpleFileOptions;
+
+use crate::domain::{BackupManifest, BackupSchedule};
+use crate::system::crypto::{self, CryptoError};
+
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+pub const BACKUP_EXTENSION: &str = "vqbackup";
+
+const MANIFEST_ENTRY: &str = "manifest.json";
+const DATABASE_ENTRY: &str = "voquill.db";
+const AUDIO_ENTRY_DIR: &str = "transcription-audio";
+const STORAGE_ENTRY_DIR: &str = "storage";
+const VAULT_KEY_ENTRY: &str = "vault.key";
+
+const BACKUPS_DIR_NAME: &str = "backups";
+const SCHEDULE_FILE_NAME: &str = "schedule.json";
+const AUTO_BACKUP_PREFIX: &str = "voquill-auto-";
+const RESTORE_STAGING_DIR_NAME: &str = "restore-staging";
+const RESTORE_READY_MARKER: &str = "restore-ready.json";
+const RESTORE_ROLLBACK_SUFFIX: &str = "restore-rollback";
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+const SCHEDULER_FIRST_CHECK: Duration = Duration::from_secs(60);
+const SCHEDULER_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, thiserror::Error)]
+pub enum BackupError {
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("archive error: {0}")]
+    Archive(#[from] zip::result::ZipError),
+    #[error(transparent)]
+    Crypto(#[from] CryptoError),
+    #[error("this backup is encrypted; a passphrase is required")]
+    PassphraseRequired,
+    #[error("backup is missing {0}")]
+    MissingEntry(&'static str),
+    #[error("backup manifest is invalid: {0}")]
+    InvalidManifest(String),
+    #[error(
+        "backup was created by a newer version of Voquill (schema {backup}, this version supports up to {supported})"
+    )]
+    NewerSchema { backup: i64, supported: i64 },
+    #[error("backup database is not a valid SQLite file")]
+    InvalidDatabase,
+    #[error("background task failed: {0}")]
+    Task(String),
+}
+
+/// Writes a full backup to `destination`: a consistent snapshot of the
+/// database, recorded audio, the storage directory and a manifest, zipped
+/// and optionally sealed with `passphrase`.
+pub async fn create_backup(
+    app: &AppHandle,
+    pool: &SqlitePool,
+    destination: &Path,
+    passphrase: Option<String>,
+) -> Result<BackupManifest, BackupError> {
+    let work_dir = new_work_dir(app)?;
+    let snapshot_path = work_dir.join(DATABASE_ENTRY);
+
+    let result = async {
+        // VACUUM INTO reads through a single transaction, so the snapshot is
+        // consistent even while the app keeps writing.
+        sqlx::query("VACUUM INTO ?1")
+            .bind(snapshot_path.to_string_lossy().to_string())
+            .execute(pool)
+            .await?;
+
+        let schema_version = current_schema_version(pool).await;
+        let audio_dir = crate::system::audio_store::audio_dir(app)?;
+        let storage_dir = crate::system::paths::storage_dir(app)?;
+        let app_version = app.package_info().version.to_string();
+        let destination = destination.to_path_buf();
+        let work_dir = work_dir.clone();
+
+        // Only a passphrase-sealed archive may carry the vault key; a plain
+        // backup of sealed data can only be restored on this install.
+        let vault_key = crate::system::vault::export_key();
+        if vault_key.is_some() && passphrase.is_none() {
+            log::warn!("Backup is not encrypted, so it does not include the vault key");
+        }
+        let vault_key = vault_key.filter(|_| passphrase.is_some());
+
+        tauri::async_runtime::spawn_blocking(move || {
+            let audio_files = list_files(&audio_dir)?;
+            let storage_files = list_files(&storage_dir)?;
+            let manifest = BackupManifest {
+                format_version: BACKUP_FORMAT_VERSION,
+                app_version,
+                schema_version,
+                created_at: chrono::Utc::now().to_rfc3339(),
+                audio_files: audio_files.len() as u32,
+                storage_files: storage_files.len() as u32,
+                vault_enabled: crate::system::vault::is_enabled(),
+                vault_key_included: vault_key.is_some(),
+            };
+
+            let partial = partial_path(&destination);
+            let archive_path = if passphrase.is_some() {
+                work_dir.join("backup.zip")
+            } else {
+                partial.clone()
+            };
+
+            write_archive(
+                &archive_path,
+                &manifest,
+                &snapshot_path,
+                (&audio_dir, &audio_files),
+                (&storage_dir, &storage_files),
+                vault_key.as_ref(),
+            )?;
+
+            if let Some(passphrase) = passphrase.as_deref() {
+                let reader = BufReader::new(File::open(&archive_path)?);
+                let writer = BufWriter::new(File::create(&partial)?);
+                crypto::encrypt_with_passphrase(reader, writer, passphrase)?;
+            }
+
+            fs::rename(&partial, &destination)?;
+            Ok::<_, BackupError>(manifest)
+        })
+        .await
+        .map_err(|err| BackupError::Task(err.to_string()))?
+    }
+    .await;
+
+    if result.is_err() {
+        let _ = fs::remove_file(partial_path(destination));
+    }
+    let _ = fs::remove_dir_all(&work_dir);
+    result
+}
+
+/// Validates a backup and unpacks it into a staging directory. The live data
+/// is only swapped by [`apply_pending_restore`] on the next launch, before
+/// any database connection is opened.
+pub async fn stage_restore(
+    app: &AppHandle,
+    source: &Path,
+    passphrase: Option<String>,
+) -> Result<BackupManifest, BackupError> {
+    let staging_dir = restore_staging_dir(app)?;
+    let source = source.to_path_buf();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = stage_restore_blocking(&source, &staging_dir, passphrase.as_deref());
+        if result.is_err() {
+            let _ = fs::remove_dir_all(&staging_dir);
+        }
+        result
+    })
+    .await
+    .map_err(|err| BackupError::Task(err.to_string()))?
+}
+
+/// Swaps a staged restore into place. Every current path is first moved
+/// aside; if any step fails, everything moved so far is put back. Returns
+/// whether a restore was applied.
+pub fn apply_pending_restore(app: &AppHandle) -> Result<bool, BackupError> {
+    let staging_dir = restore_staging_dir(app)?;
+    let marker = staging_dir.join(RESTORE_READY_MARKER);
+    if !marker.exists() {
+        if staging_dir.exists() {
+            // Left over from an interrupted staging; never half-applied.
+            let _ = fs::remove_dir_all(&staging_dir);
+        }
+        return Ok(false);
+    }
+
+    let manifest: BackupManifest = serde_json::from_slice(&fs::read(&marker)?)
+        .map_err(|err| BackupError::InvalidManifest(err.to_string()))?;
+    let vault_key = match fs::read(staging_dir.join(VAULT_KEY_ENTRY)) {
+        Ok(bytes) => Some(<[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| {
+            BackupError::InvalidManifest("vault key has the wrong length".to_string())
+        })?),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+        Err(err) => return Err(err.into()),
+    };
+
+    let database_path = crate::system::paths::database_path(app)?;
+    let mut targets = vec![
+        (
+            Some(staging_dir.join(DATABASE_ENTRY)),
+            database_path.clone(),
+        ),
+        (
+            Some(staging_dir.join(AUDIO_ENTRY_DIR)),
+            crate::system::audio_store::audio_dir(app)?,
+        ),
+        (
+            Some(staging_dir.join(STORAGE_ENTRY_DIR)),
+            crate::system::paths::storage_dir(app)?,
+        ),
+    ];
+    // Stale WAL files would be replayed over the restored database, so they
+    // are moved aside with nothing put in their place.
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = database_path.clone().into_os_string();
+        sidecar.push(suffix);
+        targets.push((None, PathBuf::from(sidecar)));
+    }
+
+    let mut moved_aside: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let mut placed: Vec<PathBuf> = Vec::new();
+    let result = (|| -> Result<(), BackupError> {
+        for (staged, live) in &targets {
+            if live.exists() {
+                let rollback = rollback_path(live);
+                remove_path(&rollback)?;
+                move_path(live, &rollback)?;
+                moved_aside.push((rollback, live.clone()));
+            }
+            if let Some(staged) = staged {
+                move_path(staged, live)?;
+                placed.push(live.clone());
+            }
+        }
+        // Last, so a failed swap never leaves this install's data paired
+        // with the backup's key.
+        if let Some(vault_key) = &vault_key {
+            crate::system::vault::import_key(app, vault_key, manifest.vault_enabled)
+                .map_err(|err| BackupError::Io(err.into()))?;
+        }
+        Ok(())
+    })();
+
+    if let Err(err) = result {
+        log::error!("Restore failed, rolling back: {err}");
+        for live in placed {
+            let _ = remove_path(&live);
+        }
+        for (rollback, live) in moved_aside.into_iter().rev() {
+            if let Err(rollback_err) = move_path(&rollback, &live) {
+                log::error!("Failed to roll back {}: {rollback_err}", live.display());
+            }
+        }
+        let _ = fs::remove_dir_all(&staging_dir);
+        return Err(err);
+    }
+
+    for (rollback, _) in moved_aside {
+        if let Err(err) = remove_path(&rollback) {
+            log::warn!("Failed to remove {}: {err}", rollback.display());
+        }
+    }
+    let _ = fs::remove_dir_all(&staging_dir);
+    Ok(true)
+}
+
+pub fn load_schedule(app: &AppHandle) -> Result<BackupSchedule, BackupError> {
+    let path = backups_dir(app)?.join(SCHEDULE_FILE_NAME);
+    match fs::read(&path) {
+        Ok(bytes) => serde_json::from_slice(&bytes).map_err(|err| {
+            BackupError::Io(io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+        }),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(BackupSchedule::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+pub fn save_schedule(app: &AppHandle, schedule: &BackupSchedule) -> Result<(), BackupError> {
+    let path = backups_dir(app)?.join(SCHEDULE_FILE_NAME);
+    let bytes = serde_json::to_vec_pretty(schedule)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+    let partial = partial_path(&path);
+    fs::write(&partial, bytes)?;
+    fs::rename(&partial, &path)?;
+    Ok(())
+}
+
+/// Periodically takes an automatic backup when the schedule is enabled and
+/// the newest one is older than the configured interval.
+pub fn start_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SCHEDULER_FIRST_CHECK).await;
+        loop {
+            if let Err(err) = run_scheduled_backup(&app).await {
+                log::warn!("Automatic backup failed: {err}");
+            }
+            tokio::time::sleep(SCHEDULER_INTERVAL).await;
+        }
+    });
+}
+
+async fn run_scheduled_backup(app: &AppHandle) -> Result<(), BackupError> {
+    let schedule = load_schedule(app)?;
+    if !schedule.enabled {
+        return Ok(());
+    }
+
+    let dir = backups_dir(app)?;
+    let existing = list_automatic_backups(&dir)?;
+    let interval = Duration::from_secs(u64::from(schedule.interval_hours.max(1)) * 3600);
+    let is_due = match existing.first() {
+        Some(latest) => fs::metadata(latest)?
+            .modified()?
+            .elapsed()
+            .map(|age| age >= interval)
+            .unwrap_or(true),
+        None => true,
+    };
+    if !is_due {
+        return Ok(());
+    }
+
+    let Some(database) = app.try_state::<crate::state::OptionKeyDatabase>() else {
+        return Ok(());
+    };
+    let file_name = format!(
+        "{AUTO_BACKUP_PREFIX}{}.{BACKUP_EXTENSION}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let destination = dir.join(file_name);
+    create_backup(app, &database.pool(), &destination, None).await?;
+    log::info!("Automatic backup written to {}", destination.display());
+
+    let keep = schedule.keep.max(1) as usize;
+    for stale in list_automatic_backups(&dir)?.into_iter().skip(keep) {
+        if let Err(err) = fs::remove_file(&stale) {
+            log::warn!("Failed to rotate backup {}: {err}", stale.display());
+        }
+    }
+
+    Ok(())
+}
+
+fn stage_restore_blocking(
+    source: &Path,
+    staging_dir: &Path,
+    passphrase: Option<&str>,
+) -> Result<BackupManifest, BackupError> {
+    remove_path(staging_dir)?;
+    fs::create_dir_all(staging_dir)?;
+
+    let mut prefix = [0u8; 8];
+    let read = File::open(source)?.read(&mut prefix)?;
+    let archive_path = if crypto::is_passphrase_encrypted(&prefix[..read]) {
+        let passphrase = passphrase.ok_or(BackupError::PassphraseRequired)?;
+        let decrypted = staging_dir.join("backup.zip");
+        let reader = BufReader::new(File::open(source)?);
+        let writer = BufWriter::new(File::create(&decrypted)?);
+        crypto::decrypt_with_passphrase(reader, writer, passphrase)?;
+        decrypted
+    } else {
+        source.to_path_buf()
+    };
+
+    let mut archive = zip::ZipArchive::new(File::open(&archive_path)?)?;
+    let manifest = read_manifest(&mut archive)?;
+    validate_manifest(&manifest, latest_schema_version())?;
+
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        let Some(name) = entry.enclosed_name() else {
+            continue;
+        };
+        let is_known = name == Path::new(DATABASE_ENTRY)
+            || name == Path::new(VAULT_KEY_ENTRY)
+            || name.starts_with(AUDIO_ENTRY_DIR)
+            || name.starts_with(STORAGE_ENTRY_DIR);
+        if !is_known || entry.is_dir() {
+            continue;
+        }
+
+        let destination = staging_dir.join(&name);
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut output = File::create(&destination)?;
+        io::copy(&mut entry, &mut output)?;
+    }
+
+    let database = staging_dir.join(DATABASE_ENTRY);
+    let mut header = [0u8; 16];
+    File::open(&database)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .map_err(|_| BackupError::MissingEntry(DATABASE_ENTRY))?;
+    if &header != SQLITE_HEADER {
+        return Err(BackupError::InvalidDatabase);
+    }
+
+    // The swap needs every directory present, even if the backup had none.
+    fs::create_dir_all(staging_dir.join(AUDIO_ENTRY_DIR))?;
+    fs::create_dir_all(staging_dir.join(STORAGE_ENTRY_DIR))?;
+    if archive_path != source {
+        fs::remove_file(&archive_path)?;
+    }
+
+    // Written last: its presence means the staging directory is complete.
+    let marker = serde_json::to_vec_pretty(&manifest)
+        .map_err(|err| BackupError::InvalidManifest(err.to_string()))?;
+    let marker_path = staging_dir.join(RESTORE_READY_MARKER);
+    fs::write(partial_path(&marker_path), marker)?;
+    fs::rename(partial_path(&marker_path), marker_path)?;
+
+    Ok(manifest)
+}
+
+fn write_archive(
+    path: &Path,
+    manifest: &BackupManifest,
+    database_snapshot: &Path,
+    audio: (&Path, &[PathBuf]),
+    storage: (&Path, &[PathBuf]),
+    vault_key: Option<&[u8; 32]>,
+) -> Result<(), BackupError> {
+    let mut zip = zip::ZipWriter::new(BufWriter::new(File::create(path)?));
+    let deflated =
+        SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let stored = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored)
+        .large_file(true);
+
+    zip.start_file(MANIFEST_ENTRY, deflated)?;
+    let manifest_json = serde_json::to_vec_pretty(manifest)
+        .map_err(|err| BackupError::InvalidManifest(err.to_string()))?;
+    zip.write_all(&manifest_json)?;
+
+    if let Some(vault_key) = vault_key {
+        zip.start_file(VAULT_KEY_ENTRY, stored)?;
+        zip.write_all(vault_key)?;
+    }
+
+    zip.start_file(DATABASE_ENTRY, deflated.large_file(true))?;
+    io::copy(&mut File::open(database_snapshot)?, &mut zip)?;
+
+    for (entry_dir, (root, files), options) in [
+        (AUDIO_ENTRY_DIR, audio, stored),
+        (STORAGE_ENTRY_DIR, storage, deflated),
+    ] {
+        for file in files {
+            let relative = file.strip_prefix(root).unwrap_or(file);
+            let name = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            zip.start_file(format!("{entry_dir}/{name}"), options)?;
+            io::copy(&mut File::open(file)?, &mut zip)?;
+        }
+    }
+
+    zip.finish()?.flush()?;
+    Ok(())
+}
+
+fn read_manifest<R: Read + io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+) -> Result<BackupManifest, BackupError> {
+    let mut entry = archive
+        .by_name(MANIFEST_ENTRY)
+        .map_err(|_| BackupError::MissingEntry(MANIFEST_ENTRY))?;
+    let mut contents = String::new();
+    entry.read_to_string(&mut contents)?;
+    serde_json::from_str(&contents).map_err(|err| BackupError::InvalidManifest(err.to_string()))
+}
+
+fn validate_manifest(manifest: &BackupManifest, supported_schema: i64) -> Result<(), BackupError> {
+    if manifest.format_version == 0 || manifest.format_version > BACKUP_FORMAT_VERSION {
+        return Err(BackupError::InvalidManifest(format!(
+            "unsupported backup format version {}",
+            manifest.format_version
+        )));
+    }
+
+    if manifest.schema_version > supported_schema {
+        return Err(BackupError::NewerSchema {
+            backup: manifest.schema_version,
+            supported: supported_schema,
+        });
+    }
+
+    Ok(())
+}
+
+fn latest_schema_version() -> i64 {
+    crate::db::migrations()
+        .iter()
+        .map(|migration| migration.version)
+        .max()
+        .unwrap_or_default()
+}
+
+/// Migrations are applied by the SQL plugin through sqlx's migrator, which
+/// records them in `_sqlx_migrations`.
+async fn current_schema_version(pool: &SqlitePool) -> i64 {
+    sqlx::query_scalar::<_, i64>(
+        "SELECT COALESCE(MAX(version), 0)
+         FROM _sqlx_migrations
+         WHERE success = 1",
+    )
+    .fetch_one(pool)
+    .await
+    .unwrap_or_else(|err| {
+        log::warn!("Failed to read migration level, assuming latest: {err}");
+        latest_schema_version()
+    })
+}
+
+fn backups_dir(app: &AppHandle) -> io::Result<PathBuf> {
+    let mut path = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| io::Error::other(err.to_string()))?;
+    path.push(BACKUPS_DIR_NAME);
+    fs::create_dir_all(&path)?;
+    Ok(path)
+}
+
+fn restore_staging_dir(app: &AppHandle) -> io::Result<PathBuf> {
+    let mut path = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| io::Error::other(err.to_string()))?;
+    path.push(RESTORE_STAGING_DIR_NAME);
+    Ok(path)
+}
+
+fn new_work_dir(app: &AppHandle) -> io::Result<PathBuf> {
+    let path = backups_dir(app)?.join(format!(".work-{:08x}", rand::random::<u32>()));
+    fs::create_dir_all(&path)?;
+    Ok(path)
+}
+
+/// Newest first; names embed a sortable timestamp.
+fn list_automatic_backups(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut backups = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| {
+                    name.starts_with(AUTO_BACKUP_PREFIX)
+                        && name.ends_with(&format!(".{BACKUP_EXTENSION}"))
+                })
+        })
+        .collect::<Vec<_>>();
+    backups.sort();
+    backups.reverse();
+    Ok(backups)
+}
+
+fn list_files(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        for entry in entries {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                pending.push(entry.path());
+            } else if file_type.is_file() {
+                files.push(entry.path());
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn partial_path(path: &Path) -> PathBuf {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    PathBuf::from(partial)
+}
+
+fn rollback_path(path: &Path) -> PathBuf {
+    let mut rollback = path.as_os_str().to_owned();
+    rollback.push(format!(".{RESTORE_ROLLBACK_SUFFIX}"));
+    PathBuf::from(rollback)
+}
+
+fn remove_path(path: &Path) -> io::Result<()> {
+    let result = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+    match result {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        other => other,
+    }
+}
+
+/// The database lives in the config dir while staging is in the data dir;
+/// on some systems those are different filesystems, where a rename fails.
+fn move_path(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::rename(from, to) {
+        Ok(()) => Ok(()),
+        Err(_) if from.is_file() => {
+            fs::copy(from, to)?;
+            fs::remove_file(from)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(format_version: u32, schema_version: i64) -> BackupManifest {
+        BackupManifest {
+            format_version,
+            app_version: "1.0.0".to_string(),
+            schema_version,
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            audio_files: 0,
+            storage_files: 0,
+            vault_enabled: false,
+            vault_key_included: false,
+        }
+    }
+
+    #[test]
+    fn refuses_backups_from_newer_schemas() {
+        assert!(validate_manifest(&manifest(1, 60), 68).is_ok());
+        assert!(validate_manifest(&manifest(1, 68), 68).is_ok());
+        assert!(matches!(
+            validate_manifest(&manifest(1, 69), 68),
+            Err(BackupError::NewerSchema {
+                backup: 69,
+                supported: 68
+            })
+        ));
+        assert!(matches!(
+            validate_manifest(&manifest(BACKUP_FORMAT_VERSION + 1, 1), 68),
+            Err(BackupError::InvalidManifest(_))
+        ));
+    }
+
+    #[test]
+    fn vault_key_is_staged_from_an_encrypted_backup() {
+        let dir = std::env::temp_dir().join(format!("voquill-backup-{}", rand::random::<u32>()));
+        fs::create_dir_all(&dir).unwrap();
+        let database = dir.join("snapshot.db");
+        let mut contents = SQLITE_HEADER.to_vec();
+        contents.extend_from_slice(b"rest of the database");
+        fs::write(&database, contents).unwrap();
+
+        let archive = dir.join("backup.zip");
+        let vault_key = [9u8; 32];
+        write_archive(
+            &archive,
+            &manifest(1, 1),
+            &database,
+            (&dir, &[]),
+            (&dir, &[]),
+            Some(&vault_key),
+        )
+        .unwrap();
+        let sealed = dir.join("backup.vqbackup");
+        crypto::encrypt_with_passphrase(
+            File::open(&archive).unwrap(),
+            File::create(&sealed).unwrap(),
+            "passphrase",
+        )
+        .unwrap();
+
+        let staging = dir.join("staging");
+        let result = stage_restore_blocking(&sealed, &staging, Some("passphrase"));
+        let staged_key = fs::read(staging.join(VAULT_KEY_ENTRY));
+        fs::remove_dir_all(&dir).unwrap();
+
+        result.unwrap();
+        assert_eq!(staged_key.unwrap(), vault_key);
+    }
+
+    #[test]
+    fn automatic_backups_are_listed_newest_first() {
+        let dir = std::env::temp_dir().join(format!("voquill-backups-{}", rand::random::<u32>()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "voquill-auto-20260101-000000.vqbackup",
+            "voquill-auto-20260301-000000.vqbackup",
+            "manual.vqbackup",
+            "voquill-auto-20260201-000000.vqbackup.partial",
+        ] {
+            fs::write(dir.join(name), b"").unwrap();
+        }
+
+        let names = list_automatic_backups(&dir)
+            .unwrap()
+            .into_iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            names,
+            vec![
+                "voquill-auto-20260301-000000.vqbackup",
+                "voquill-auto-20260101-000000.vqbackup",
+            ]
+        );
+    }
+}
@@ -1,16 +1,186 @@
-use serde::Serialize;
+use once_cell::sync::Lazy;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
 use std::fs;
+use std::path::Path;
 use tauri::{AppHandle, Emitter, Manager};
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+
+use crate::domain::OverlayPhase;
 
 pub const EVT_BRIDGE_HOTKEY_TRIGGER: &str = "bridge_hotkey_trigger";
+pub const EVT_BRIDGE_CONTROL: &str = "bridge_control";
+
+const BRIDGE_FILE_NAME: &str = "bridge-server.json";
+/// Headers plus body; the API only ever receives small JSON documents.
+const MAX_REQUEST_BYTES: u64 = 32 * 1024;
+const MAX_HEADERS: usize = 64;
+/// How long a client may take to send a complete request before the
+/// connection is answered and closed.
+const REQUEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+/// Events held for slow stream clients before they start skipping.
+const EVENT_BUFFER: usize = 64;
+/// Comment lines sent on quiet streams so dead clients are noticed.
+const EVENT_KEEPALIVE: std::time::Duration = std::time::Duration::from_secs(15);
+
+static EVENTS: Lazy<broadcast::Sender<BridgeEvent>> =
+    Lazy::new(|| broadcast::channel(EVENT_BUFFER).0);
 
 #[derive(Debug, Clone, Serialize)]
 pub struct BridgeHotkeyTriggerPayload {
     pub hotkey: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BridgeControlAction {
+    StartDictation,
+    StopDictation,
+    CancelDictation,
+    SetTone,
+    SetLanguage,
+}
+
+/// Asks the frontend, which owns the dictation session, to act.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BridgeControlPayload {
+    pub action: BridgeControlAction,
+    pub tone_id: Option<String>,
+    pub language: Option<String>,
+}
+
+/// Dictation lifecycle as streamed from `GET /v1/events`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BridgeEvent {
+    PhaseChanged {
+        phase: OverlayPhase,
+    },
+    RecordingStarted,
+    RecordingStopped,
+    TranscriptionStarted,
+    /// Only sent to clients that asked for transcripts.
+    Transcript {
+        id: String,
+        timestamp: i64,
+        transcript: String,
+    },
+    PasteResult {
+        outcome: String,
+        error: Option<String>,
+    },
+    Error {
+        message: String,
+    },
+}
+
+impl BridgeEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::PhaseChanged { .. } => "phase_changed",
+            Self::RecordingStarted => "recording_started",
+            Self::RecordingStopped => "recording_stopped",
+            Self::TranscriptionStarted => "transcription_started",
+            Self::Transcript { .. } => "transcript",
+            Self::PasteResult { .. } => "paste_result",
+            Self::Error { .. } => "error",
+        }
+    }
+}
+
+/// Hands an event to every connected stream. Nothing is kept when no one is
+/// listening.
+pub fn publish(event: BridgeEvent) {
+    let _ = EVENTS.send(event);
+}
+
+/// Listens to everything published from now on.
+pub fn subscribe() -> broadcast::Receiver<BridgeEvent> {
+    EVENTS.subscribe()
+}
+
+/// Events implied by the overlay moving from `previous` to `next`.
+pub fn phase_events(previous: &OverlayPhase, next: &OverlayPhase) -> Vec<BridgeEvent> {
+    let mut events = Vec::new();
+    if previous != next {
+        events.push(BridgeEvent::PhaseChanged {
+            phase: next.clone(),
+        });
+    }
+    events.extend(match (previous, next) {
+        (OverlayPhase::Recording, OverlayPhase::Recording) => Vec::new(),
+        (_, OverlayPhase::Recording) => vec![BridgeEvent::RecordingStarted],
+        (OverlayPhase::Recording, OverlayPhase::Loading) => vec![
+            BridgeEvent::RecordingStopped,
+            BridgeEvent::TranscriptionStarted,
+        ],
+        (OverlayPhase::Recording, OverlayPhase::Idle) => vec![BridgeEvent::RecordingStopped],
+        (OverlayPhase::Idle, OverlayPhase::Loading) => vec![BridgeEvent::TranscriptionStarted],
+        _ => Vec::new(),
+    });
+    events
+}
+
+/// Picks error toasts out of the payloads the frontend sends to the pill.
+pub fn error_from_assistant_payload(payload: &str) -> Option<BridgeEvent> {
+    let value = serde_json::from_str::<serde_json::Value>(payload).ok()?;
+    if value.get("type")?.as_str()? != "toast" || value.get("toast_type")?.as_str()? != "error" {
+        return None;
+    }
+    let message = value.get("message")?.as_str()?.to_string();
+    Some(BridgeEvent::Error { message })
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ControlBody {
+    #[serde(default)]
+    tone_id: Option<String>,
+    #[serde(default)]
+    language: Option<String>,
+}
+
+#[derive(Debug)]
+struct BridgeRequest {
+    method: String,
+    path: String,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+struct BridgeResponse {
+    status: u16,
+    body: serde_json::Value,
+}
+
+#[derive(Debug, PartialEq)]
+enum BridgeRoute {
+    Status,
+    LatestTranscript,
+    Control(BridgeControlPayload),
+    Hotkey(String),
+    Events { transcripts: bool },
+}
+
+impl BridgeResponse {
+    fn ok(body: serde_json::Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, code: &str, message: &str) -> Self {
+        Self {
+            status,
+            body: json!({ "error": { "code": code, "message": message } }),
+        }
+    }
+}
+
 pub fn start(app: AppHandle) {
     tauri::async_runtime::spawn(async move {
         match start_inner(&app).await {
@@ -30,7 +200,8 @@ async fn start_inner(app: &AppHandle) -> Result<u16, String> {
         .map_err(|err| format!("Failed to get bridge server address: {err}"))?
         .port();
 
-    write_port_file(app, port)?;
+    let token = generate_token();
+    write_bridge_file(app, port, &token)?;
 
     let app_handle = app.clone();
     tauri::async_runtime::spawn(async move {
@@ -38,8 +209,9 @@ async fn start_inner(app: &AppHandle) -> Result<u16, String> {
             match listener.accept().await {
                 Ok((stream, _)) => {
                     let app = app_handle.clone();
+                    let token = token.clone();
                     tauri::async_runtime::spawn(async move {
-                        if let Err(err) = handle_connection(stream, app).await {
+                        if let Err(err) = handle_connection(stream, app, &token, port).await {
                             log::error!("Bridge server connection error: {err}");
                         }
                     });
@@ -55,7 +227,14 @@ async fn start_inner(app: &AppHandle) -> Result<u16, String> {
     Ok(port)
 }
 
-fn write_port_file(app: &AppHandle, port: u16) -> Result<(), String> {
+/// A fresh token per launch, so a leaked file is only useful until restart.
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn write_bridge_file(app: &AppHandle, port: u16, token: &str) -> Result<(), String> {
     let config_dir = app
         .path()
         .app_config_dir()
@@ -63,79 +242,341 @@ fn write_port_file(app: &AppHandle, port: u16) -> Result<(), String> {
 
     fs::create_dir_all(&config_dir).map_err(|err| format!("Failed to create config dir: {err}"))?;
 
-    let file_path = config_dir.join("bridge-server.json");
-    let content = serde_json::json!({ "port": port }).to_string();
-    fs::write(&file_path, &content)
-        .map_err(|err| format!("Failed to write bridge-server.json: {err}"))?;
+    let file_path = config_dir.join(BRIDGE_FILE_NAME);
+    let content = json!({ "port": port, "token": token }).to_string();
+    write_private_file(&file_path, &content)
+        .map_err(|err| format!("Failed to write {BRIDGE_FILE_NAME}: {err}"))?;
 
-    log::info!("Wrote bridge server port to {}", file_path.display());
+    log::info!("Wrote bridge server details to {}", file_path.display());
     Ok(())
 }
 
-async fn handle_connection(stream: tokio::net::TcpStream, app: AppHandle) -> Result<(), String> {
+/// The token grants control over dictation, so only the owner may read it.
+#[cfg(unix)]
+fn write_private_file(path: &Path, content: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // `mode` only applies on creation; tighten a file left by older builds.
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    file.write_all(content.as_bytes())
+}
+
+#[cfg(not(unix))]
+fn write_private_file(path: &Path, content: &str) -> std::io::Result<()> {
+    fs::write(path, content)
+}
+
+async fn handle_connection(
+    stream: tokio::net::TcpStream,
+    app: AppHandle,
+    token: &str,
+    port: u16,
+) -> Result<(), String> {
     let (reader, mut writer) = stream.into_split();
-    let mut buf_reader = BufReader::new(reader);
+    let mut buf_reader = BufReader::new(reader.take(MAX_REQUEST_BYTES));
+
+    let response = match read_request_within(&mut buf_reader, REQUEST_TIMEOUT).await {
+        Ok(request) => match authorize(&request, token, port) {
+            Ok(()) => match resolve(&request) {
+                Ok(BridgeRoute::Events { transcripts }) => {
+                    return stream_events(&mut writer, transcripts).await;
+                }
+                Ok(route) => execute(&app, route).await,
+                Err(response) => response,
+            },
+            Err(response) => response,
+        },
+        Err(response) => response,
+    };
+
+    write_response(&mut writer, &response).await
+}
+
+async fn read_request_within<R>(
+    reader: &mut R,
+    limit: std::time::Duration,
+) -> Result<BridgeRequest, BridgeResponse>
+where
+    R: tokio::io::AsyncBufRead + Unpin,
+{
+    tokio::time::timeout(limit, read_request(reader))
+        .await
+        .unwrap_or_else(|_| {
+            Err(BridgeResponse::error(
+                408,
+                "request_timeout",
+                "Request was not received in time",
+            ))
+        })
+}
+
+async fn read_request<R>(reader: &mut R) -> Result<BridgeRequest, BridgeResponse>
+where
+    R: tokio::io::AsyncBufRead + Unpin,
+{
+    let bad_request = || BridgeResponse::error(400, "bad_request", "Malformed request");
 
     let mut request_line = String::new();
-    buf_reader
+    reader
         .read_line(&mut request_line)
         .await
-        .map_err(|err| format!("Failed to read request line: {err}"))?;
+        .map_err(|_| bad_request())?;
 
-    // Consume remaining headers until blank line
-    let mut header = String::new();
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(bad_request());
+    };
+
+    let mut headers = HashMap::new();
+    let mut line = String::new();
     loop {
-        header.clear();
-        let n = buf_reader
-            .read_line(&mut header)
+        line.clear();
+        let read = reader
+            .read_line(&mut line)
             .await
-            .map_err(|err| format!("Failed to read header: {err}"))?;
-        if n == 0 || header.trim().is_empty() {
+            .map_err(|_| bad_request())?;
+        if read == 0 || line.trim().is_empty() {
             break;
         }
+        if headers.len() == MAX_HEADERS {
+            return Err(bad_request());
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let content_length = match headers.get("content-length") {
+        Some(value) => value.parse::<usize>().map_err(|_| bad_request())?,
+        None => 0,
+    };
+    if content_length as u64 > MAX_REQUEST_BYTES {
+        return Err(BridgeResponse::error(
+            413,
+            "too_large",
+            "Request body too large",
+        ));
+    }
+    let mut body = vec![0u8; content_length];
+    reader
+        .read_exact(&mut body)
+        .await
+        .map_err(|_| bad_request())?;
+
+    Ok(BridgeRequest {
+        method: method.to_string(),
+        path: path.to_string(),
+        headers,
+        body,
+    })
+}
+
+/// Rejects requests that are not addressed to the loopback listener by
+/// name, which stops DNS rebinding, and requests without the bearer token
+/// from the bridge file.
+fn authorize(request: &BridgeRequest, token: &str, port: u16) -> Result<(), BridgeResponse> {
+    let host_allowed = request
+        .headers
+        .get("host")
+        .is_some_and(|host| is_allowed_host(host, port));
+    if !host_allowed {
+        return Err(BridgeResponse::error(
+            403,
+            "forbidden_host",
+            "Host not allowed",
+        ));
     }
 
-    let parts: Vec<&str> = request_line.split_whitespace().collect();
-    if parts.len() < 2 {
-        return write_response(&mut writer, 400, "Bad Request").await;
+    let presented = request
+        .headers
+        .get("authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+    match presented {
+        Some(presented) if constant_time_eq(presented.as_bytes(), token.as_bytes()) => Ok(()),
+        _ => Err(BridgeResponse::error(
+            401,
+            "unauthorized",
+            "Missing or invalid token",
+        )),
     }
+}
 
-    let method = parts[0];
-    let path = parts[1];
+fn is_allowed_host(host: &str, port: u16) -> bool {
+    let host = host.to_ascii_lowercase();
+    ["127.0.0.1", "localhost", "[::1]"]
+        .iter()
+        .any(|name| host == format!("{name}:{port}"))
+}
 
-    if method != "POST" {
-        return write_response(&mut writer, 405, "Method Not Allowed").await;
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn resolve(request: &BridgeRequest) -> Result<BridgeRoute, BridgeResponse> {
+    let (path, query) = request
+        .path
+        .split_once('?')
+        .unwrap_or((request.path.as_str(), ""));
+
+    if let Some(hotkey) = path.strip_prefix("/hotkey/") {
+        if hotkey.is_empty() || hotkey.contains('/') {
+            return Err(BridgeResponse::error(404, "not_found", "Not Found"));
+        }
+        return match request.method.as_str() {
+            "POST" => Ok(BridgeRoute::Hotkey(hotkey.to_string())),
+            _ => Err(method_not_allowed()),
+        };
     }
 
-    let hotkey = match path.strip_prefix("/hotkey/") {
-        Some(name) if !name.is_empty() && !name.contains('/') => name,
-        _ => return write_response(&mut writer, 404, "Not Found").await,
+    let control = |action: BridgeControlAction| -> Result<BridgeRoute, BridgeResponse> {
+        let body = if request.body.is_empty() {
+            ControlBody::default()
+        } else {
+            serde_json::from_slice::<ControlBody>(&request.body)
+                .map_err(|err| BridgeResponse::error(400, "bad_request", &err.to_string()))?
+        };
+        let tone_id = body.tone_id.filter(|value| !value.trim().is_empty());
+        let language = body.language.filter(|value| !value.trim().is_empty());
+
+        match action {
+            BridgeControlAction::SetTone if tone_id.is_none() => {
+                return Err(BridgeResponse::error(
+                    400,
+                    "bad_request",
+                    "toneId is required",
+                ));
+            }
+            BridgeControlAction::SetLanguage if language.is_none() => {
+                return Err(BridgeResponse::error(
+                    400,
+                    "bad_request",
+                    "language is required",
+                ));
+            }
+            _ => {}
+        }
+
+        Ok(BridgeRoute::Control(BridgeControlPayload {
+            action,
+            tone_id,
+            language,
+        }))
     };
 
-    let payload = BridgeHotkeyTriggerPayload {
-        hotkey: hotkey.to_string(),
+    match (request.method.as_str(), path) {
+        ("GET", "/v1/status") => Ok(BridgeRoute::Status),
+        ("GET", "/v1/transcripts/latest") => Ok(BridgeRoute::LatestTranscript),
+        ("GET", "/v1/events") => Ok(BridgeRoute::Events {
+            transcripts: query_flag(query, "transcripts"),
+        }),
+        ("POST", "/v1/dictation/start") => control(BridgeControlAction::StartDictation),
+        ("POST", "/v1/dictation/stop") => control(BridgeControlAction::StopDictation),
+        ("POST", "/v1/dictation/cancel") => control(BridgeControlAction::CancelDictation),
+        ("POST", "/v1/tone") => control(BridgeControlAction::SetTone),
+        ("POST", "/v1/language") => control(BridgeControlAction::SetLanguage),
+        (
+            _,
+            "/v1/status"
+            | "/v1/transcripts/latest"
+            | "/v1/events"
+            | "/v1/dictation/start"
+            | "/v1/dictation/stop"
+            | "/v1/dictation/cancel"
+            | "/v1/tone"
+            | "/v1/language",
+        ) => Err(method_not_allowed()),
+        _ => Err(BridgeResponse::error(404, "not_found", "Not Found")),
+    }
+}
+
+fn query_flag(query: &str, name: &str) -> bool {
+    query.split('&').any(|pair| match pair.split_once('=') {
+        Some((key, value)) => key == name && matches!(value, "1" | "true"),
+        None => pair == name,
+    })
+}
+
+fn method_not_allowed() -> BridgeResponse {
+    BridgeResponse::error(405, "method_not_allowed", "Method Not Allowed")
+}
+
+async fn execute(app: &AppHandle, route: BridgeRoute) -> BridgeResponse {
+    let emit_failed = |err: tauri::Error| {
+        log::error!("Failed to emit bridge event: {err}");
+        BridgeResponse::error(500, "internal", "Failed to reach the app")
     };
 
-    match app.emit(EVT_BRIDGE_HOTKEY_TRIGGER, &payload) {
-        Ok(()) => {
-            log::info!("Bridge hotkey triggered: {hotkey}");
-            write_response(&mut writer, 200, "OK").await
+    match route {
+        BridgeRoute::Status => {
+            let phase = app.state::<crate::state::OverlayState>().get_phase();
+            BridgeResponse::ok(json!({ "phase": phase }))
         }
-        Err(err) => {
-            log::error!("Failed to emit bridge hotkey event: {err}");
-            write_response(&mut writer, 500, "Internal Server Error").await
+        BridgeRoute::LatestTranscript => {
+            let pool = app.state::<crate::state::OptionKeyDatabase>().pool();
+            match crate::db::transcription_queries::fetch_transcriptions(pool, 1, 0).await {
+                Ok(transcriptions) => {
+                    let latest = transcriptions.into_iter().next().map(|transcription| {
+                        json!({
+                            "id": transcription.id,
+                            "timestamp": transcription.timestamp,
+                            "transcript": transcription.transcript,
+                        })
+                    });
+                    BridgeResponse::ok(json!({ "transcript": latest }))
+                }
+                Err(err) => {
+                    log::error!("Bridge failed to load the latest transcript: {err}");
+                    BridgeResponse::error(500, "internal", "Failed to load transcript")
+                }
+            }
+        }
+        BridgeRoute::Control(payload) => match app.emit(EVT_BRIDGE_CONTROL, &payload) {
+            Ok(()) => {
+                log::info!("Bridge control requested: {:?}", payload.action);
+                BridgeResponse::ok(json!({ "accepted": true }))
+            }
+            Err(err) => emit_failed(err),
+        },
+        BridgeRoute::Events { .. } => {
+            BridgeResponse::error(500, "internal", "Event streams are served separately")
+        }
+        BridgeRoute::Hotkey(hotkey) => {
+            let payload = BridgeHotkeyTriggerPayload {
+                hotkey: hotkey.clone(),
+            };
+            match app.emit(EVT_BRIDGE_HOTKEY_TRIGGER, &payload) {
+                Ok(()) => {
+                    log::info!("Bridge hotkey triggered: {hotkey}");
+                    BridgeResponse::ok(json!({ "accepted": true }))
+                }
+                Err(err) => emit_failed(err),
+            }
         }
     }
 }
 
 async fn write_response(
     writer: &mut tokio::net::tcp::OwnedWriteHalf,
-    status: u16,
-    reason: &str,
+    response: &BridgeResponse,
 ) -> Result<(), String> {
-    let body = format!("{status} {reason}");
+    let body = response.body.to_string();
     let response = format!(
-        "HTTP/1.1 {status} {reason}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        response.status,
+        reason_phrase(response.status),
         body.len()
     );
     writer
@@ -144,3 +585,295 @@ async fn write_response(
         .map_err(|err| format!("Failed to write response: {err}"))?;
     Ok(())
 }
+
+/// Serves a server-sent event stream until the client goes away.
+async fn stream_events(
+    writer: &mut tokio::net::tcp::OwnedWriteHalf,
+    transcripts: bool,
+) -> Result<(), String> {
+    // Subscribe before answering so nothing is missed in between.
+    let mut receiver = subscribe();
+
+    writer
+        .write_all(
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n",
+        )
+        .await
+        .map_err(|err| format!("Failed to write event stream header: {err}"))?;
+
+    loop {
+        let chunk = match tokio::time::timeout(EVENT_KEEPALIVE, receiver.recv()).await {
+            Ok(Ok(event)) => match format_event(&event, transcripts) {
+                Some(chunk) => chunk,
+                None => continue,
+            },
+            Ok(Err(broadcast::error::RecvError::Lagged(skipped))) => {
+                format!("event: lagged\ndata: {}\n\n", json!({ "skipped": skipped }))
+            }
+            Ok(Err(broadcast::error::RecvError::Closed)) => return Ok(()),
+            Err(_) => ": keepalive\n\n".to_string(),
+        };
+
+        if writer.write_all(chunk.as_bytes()).await.is_err() {
+            // The client disconnected; that is how streams normally end.
+            return Ok(());
+        }
+    }
+}
+
+/// Renders one server-sent event, or nothing when the client did not opt in
+/// to transcript text.
+fn format_event(event: &BridgeEvent, transcripts: bool) -> Option<String> {
+    if matches!(event, BridgeEvent::Transcript { .. }) && !transcripts {
+        return None;
+    }
+    let data = serde_json::to_string(event).ok()?;
+    Some(format!("event: {}\ndata: {data}\n\n", event.name()))
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "secret-token";
+    const PORT: u16 = 4821;
+
+    fn request(method: &str, path: &str, headers: &[(&str, &str)], body: &str) -> BridgeRequest {
+        BridgeRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    fn status_of<T>(result: Result<T, BridgeResponse>) -> u16 {
+        result.err().map_or(200, |response| response.status)
+    }
+
+    #[test]
+    fn requests_need_a_loopback_host_and_the_token() {
+        let auth = "Bearer secret-token";
+        let ok = request(
+            "GET",
+            "/v1/status",
+            &[("host", "127.0.0.1:4821"), ("authorization", auth)],
+            "",
+        );
+        assert_eq!(status_of(authorize(&ok, TOKEN, PORT)), 200);
+
+        let localhost = request(
+            "GET",
+            "/v1/status",
+            &[("host", "LOCALHOST:4821"), ("authorization", auth)],
+            "",
+        );
+        assert_eq!(status_of(authorize(&localhost, TOKEN, PORT)), 200);
+
+        let rebound = request(
+            "GET",
+            "/v1/status",
+            &[("host", "evil.example:4821"), ("authorization", auth)],
+            "",
+        );
+        assert_eq!(status_of(authorize(&rebound, TOKEN, PORT)), 403);
+
+        let no_host = request("GET", "/v1/status", &[("authorization", auth)], "");
+        assert_eq!(status_of(authorize(&no_host, TOKEN, PORT)), 403);
+
+        let wrong_token = request(
+            "GET",
+            "/v1/status",
+            &[("host", "127.0.0.1:4821"), ("authorization", "Bearer nope")],
+            "",
+        );
+        assert_eq!(status_of(authorize(&wrong_token, TOKEN, PORT)), 401);
+
+        let no_token = request("POST", "/hotkey/dictate", &[("host", "127.0.0.1:4821")], "");
+        assert_eq!(status_of(authorize(&no_token, TOKEN, PORT)), 401);
+    }
+
+    #[test]
+    fn routes_resolve_with_their_bodies() {
+        assert_eq!(
+            resolve(&request(
+                "POST",
+                "/v1/dictation/start",
+                &[],
+                r#"{"language":"fr"}"#
+            )),
+            Ok(BridgeRoute::Control(BridgeControlPayload {
+                action: BridgeControlAction::StartDictation,
+                tone_id: None,
+                language: Some("fr".to_string()),
+            }))
+        );
+        assert_eq!(
+            resolve(&request("POST", "/v1/tone", &[], r#"{"toneId":"formal"}"#)),
+            Ok(BridgeRoute::Control(BridgeControlPayload {
+                action: BridgeControlAction::SetTone,
+                tone_id: Some("formal".to_string()),
+                language: None,
+            }))
+        );
+        assert_eq!(
+            resolve(&request("POST", "/hotkey/dictate", &[], "")),
+            Ok(BridgeRoute::Hotkey("dictate".to_string()))
+        );
+        assert_eq!(
+            resolve(&request("GET", "/v1/status?verbose=1", &[], "")),
+            Ok(BridgeRoute::Status)
+        );
+        assert_eq!(
+            resolve(&request("GET", "/v1/events", &[], "")),
+            Ok(BridgeRoute::Events { transcripts: false })
+        );
+        assert_eq!(
+            resolve(&request("GET", "/v1/events?transcripts=1", &[], "")),
+            Ok(BridgeRoute::Events { transcripts: true })
+        );
+        assert_eq!(
+            resolve(&request("GET", "/v1/events?transcripts=0", &[], "")),
+            Ok(BridgeRoute::Events { transcripts: false })
+        );
+
+        assert_eq!(
+            status_of(resolve(&request("POST", "/v1/tone", &[], "{}"))),
+            400
+        );
+        assert_eq!(
+            status_of(resolve(&request("POST", "/v1/language", &[], "nope"))),
+            400
+        );
+        assert_eq!(
+            status_of(resolve(&request("GET", "/v1/dictation/stop", &[], ""))),
+            405
+        );
+        assert_eq!(
+            status_of(resolve(&request("GET", "/hotkey/dictate", &[], ""))),
+            405
+        );
+        assert_eq!(
+            status_of(resolve(&request("GET", "/v1/unknown", &[], ""))),
+            404
+        );
+    }
+
+    #[test]
+    fn oversized_bodies_are_rejected() {
+        let raw = format!(
+            "POST /v1/tone HTTP/1.1\r\nHost: 127.0.0.1:{PORT}\r\nContent-Length: {}\r\n\r\n",
+            MAX_REQUEST_BYTES + 1
+        );
+        let mut reader = BufReader::new(raw.as_bytes());
+        let result = tauri::async_runtime::block_on(read_request(&mut reader));
+        assert_eq!(status_of(result), 413);
+
+        let raw = "GET /v1/status HTTP/1.1\r\nHost: 127.0.0.1:4821\r\n\r\n";
+        let mut reader = BufReader::new(raw.as_bytes());
+        let parsed =
+            tauri::async_runtime::block_on(read_request(&mut reader)).expect("request parses");
+        assert_eq!(
+            parsed.headers.get("host").map(String::as_str),
+            Some("127.0.0.1:4821")
+        );
+    }
+
+    #[test]
+    fn stalled_requests_time_out() {
+        let (mut client, server) = tokio::io::duplex(64);
+        let mut reader = BufReader::new(server);
+        let result = tauri::async_runtime::block_on(async {
+            client
+                .write_all(b"GET /v1/status HTTP/1.1\r\n")
+                .await
+                .expect("partial request is written");
+            read_request_within(&mut reader, std::time::Duration::from_millis(50)).await
+        });
+        assert_eq!(status_of(result), 408);
+        drop(client);
+    }
+
+    #[test]
+    fn transcripts_are_only_streamed_on_request() {
+        let transcript = BridgeEvent::Transcript {
+            id: "t1".to_string(),
+            timestamp: 5,
+            transcript: "hello".to_string(),
+        };
+        assert_eq!(format_event(&transcript, false), None);
+        assert_eq!(
+            format_event(&transcript, true).as_deref(),
+            Some(
+                "event: transcript\ndata: {\"type\":\"transcript\",\"id\":\"t1\",\"timestamp\":5,\"transcript\":\"hello\"}\n\n"
+            )
+        );
+        assert_eq!(
+            format_event(&BridgeEvent::RecordingStarted, false).as_deref(),
+            Some("event: recording_started\ndata: {\"type\":\"recording_started\"}\n\n")
+        );
+    }
+
+    #[test]
+    fn phase_changes_map_to_lifecycle_events() {
+        use OverlayPhase::{Idle, Loading, Recording};
+
+        let changed = |phase: OverlayPhase| BridgeEvent::PhaseChanged { phase };
+
+        assert_eq!(
+            phase_events(&Idle, &Recording),
+            vec![changed(Recording), BridgeEvent::RecordingStarted]
+        );
+        assert_eq!(
+            phase_events(&Recording, &Loading),
+            vec![
+                changed(Loading),
+                BridgeEvent::RecordingStopped,
+                BridgeEvent::TranscriptionStarted
+            ]
+        );
+        assert_eq!(
+            phase_events(&Recording, &Idle),
+            vec![changed(Idle), BridgeEvent::RecordingStopped]
+        );
+        assert!(phase_events(&Recording, &Recording).is_empty());
+        assert_eq!(phase_events(&Loading, &Idle), vec![changed(Idle)]);
+    }
+
+    #[test]
+    fn error_toasts_become_error_events() {
+        assert_eq!(
+            error_from_assistant_payload(
+                r#"{"type":"toast","message":"Mic unavailable","toast_type":"error"}"#
+            ),
+            Some(BridgeEvent::Error {
+                message: "Mic unavailable".to_string()
+            })
+        );
+        assert_eq!(
+            error_from_assistant_payload(r#"{"type":"toast","message":"Hi","toast_type":"info"}"#),
+            None
+        );
+        assert_eq!(
+            error_from_assistant_payload(r#"{"type":"dismiss_toast"}"#),
+            None
+        );
+    }
+}
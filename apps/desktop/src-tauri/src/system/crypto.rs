@@ -1,12 +1,53 @@
+use argon2::{Algorithm, Argon2, Params, Version};
 use base64::{engine::general_purpose, Engine as _};
+use chacha20poly1305::aead::stream::{DecryptorBE32, EncryptorBE32};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
 use rand::{rngs::OsRng, RngCore};
 use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
 use std::sync::OnceLock;
+use tauri::Manager;
 
-const SECRET_ENV: &str = "VOQUILL_API_KEY_SECRET";
-const DEFAULT_SECRET: &str = "voquill-default-secret";
-static RUNTIME_SECRET: OnceLock<Vec<u8>> = OnceLock::new();
-static LOGGED_FALLBACK: OnceLock<()> = OnceLock::new();
+/// Only used to read values written before AEAD protection existed.
+const LEGACY_SECRET_ENV: &str = "VOQUILL_API_KEY_SECRET";
+const LEGACY_DEFAULT_SECRET: &str = "voquill-default-secret";
+static LEGACY_SECRET: OnceLock<Vec<u8>> = OnceLock::new();
+
+/// Per-install key for API key protection, kept in the OS keyring with a
+/// 0600 file in the config directory as fallback.
+static MASTER_KEY: OnceLock<[u8; 32]> = OnceLock::new();
+const KEYRING_USER: &str = "api-key-master-key";
+const MASTER_KEY_FILE_NAME: &str = "api-key-master.key";
+
+/// Envelopes start with the data key wrapped under the master key
+/// (nonce, key, tag), followed by the payload sealed with the data key.
+const ENVELOPE_KEY_AAD: &[u8] = b"voquill-envelope-key";
+const WRAPPED_KEY_LEN: usize = AEAD_NONCE_LEN + 32 + AEAD_TAG_LEN;
+
+/// Prefix of ciphertexts sealed with XChaCha20-Poly1305 under the master
+/// key: `v2:` followed by base64 of the 24-byte nonce and sealed bytes.
+pub const AEAD_PREFIX: &str = "v2:";
+const AEAD_NONCE_LEN: usize = 24;
+const AEAD_TAG_LEN: usize = 16;
+
+/// Header of passphrase-encrypted streams: magic, Argon2id cost parameters
+/// (memory KiB, iterations, lanes as little-endian u32), salt, and the
+/// STREAM nonce prefix. Chunks of XChaCha20-Poly1305 ciphertext follow.
+const PASSPHRASE_MAGIC: &[u8; 8] = b"VQENC\x00\x00\x01";
+const PASSPHRASE_SALT_LEN: usize = 16;
+const STREAM_NONCE_LEN: usize = 19;
+const STREAM_CHUNK_LEN: usize = 64 * 1024;
+const STREAM_TAG_LEN: usize = 16;
+
+/// Upper bounds for the Argon2id costs read from a stream header, so a
+/// crafted file cannot make key derivation allocate or run without limit.
+const MAX_PASSPHRASE_M_COST: u32 = 1024 * 1024;
+const MAX_PASSPHRASE_T_COST: u32 = 10;
+const MAX_PASSPHRASE_P_COST: u32 = 16;
 
 pub struct ProtectedApiKey {
     pub salt_b64: String,
@@ -15,56 +56,567 @@ pub struct ProtectedApiKey {
     pub key_suffix: Option<String>,
 }
 
-pub fn runtime_secret() -> &'static [u8] {
-    RUNTIME_SECRET
-        .get_or_init(|| match std::env::var(SECRET_ENV) {
-            Ok(value) if !value.is_empty() => value.into_bytes(),
-            _ => {
-                LOGGED_FALLBACK.get_or_init(|| {
-                    log::warn!(
-                        "{SECRET_ENV} not set; falling back to default secret. Set this \
-                         environment variable to secure stored API keys."
-                    );
-                });
-                DEFAULT_SECRET.as_bytes().to_vec()
-            }
-        })
-        .as_slice()
+/// Loads the master key, creating and storing one on first launch. Must run
+/// before any API key is protected or revealed. `sealed_data_exists` tells
+/// whether anything was already sealed with a master key.
+pub fn init_master_key(
+    app: &tauri::AppHandle,
+    sealed_data_exists: bool,
+) -> Result<(), CryptoError> {
+    if MASTER_KEY.get().is_some() {
+        return Ok(());
+    }
+
+    let key =
+        load_or_create_install_key(app, KEYRING_USER, MASTER_KEY_FILE_NAME, sealed_data_exists)?;
+    let _ = MASTER_KEY.set(key);
+    Ok(())
+}
+
+/// Returns the 32-byte key stored under `keyring_user` in the OS keyring, or
+/// in `file_name` inside the config directory when no keyring is available.
+/// A new random key is created on first use; while the keyring cannot be
+/// read, only when `sealed_data_exists` is false.
+pub fn load_or_create_install_key(
+    app: &tauri::AppHandle,
+    keyring_user: &str,
+    file_name: &str,
+    sealed_data_exists: bool,
+) -> Result<[u8; 32], CryptoError> {
+    let (service, key_file) = install_key_location(app, file_name)?;
+    load_or_create_key(&service, keyring_user, &key_file, sealed_data_exists)
+}
+
+/// Reads the key stored under `keyring_user` without creating one, so a
+/// caller can confirm a key will be found again on the next launch.
+pub fn read_install_key(
+    app: &tauri::AppHandle,
+    keyring_user: &str,
+    file_name: &str,
+) -> Result<Option<[u8; 32]>, CryptoError> {
+    let (service, key_file) = install_key_location(app, file_name)?;
+    let entry = keyring::Entry::new(&service, keyring_user).map_err(|err| err.to_string());
+    read_key(read_keyring_key(&entry), &key_file)
 }
 
-pub fn protect_api_key(key: &str) -> ProtectedApiKey {
-    let secret = runtime_secret();
+/// Replaces the key stored under `keyring_user`, for instance with one
+/// restored from a backup. Falls back to `file_name` like
+/// [`load_or_create_install_key`].
+pub fn store_install_key(
+    app: &tauri::AppHandle,
+    keyring_user: &str,
+    file_name: &str,
+    key: &[u8; 32],
+) -> Result<(), CryptoError> {
+    let (service, key_file) = install_key_location(app, file_name)?;
+    let encoded = general_purpose::STANDARD.encode(key);
+
+    let stored =
+        keyring::Entry::new(&service, keyring_user).and_then(|entry| entry.set_password(&encoded));
+    match stored {
+        Ok(()) => {
+            // The keyring is read first, but a stale file would be picked up
+            // if the keyring later becomes unavailable.
+            return match fs::remove_file(&key_file) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+                _ => Ok(()),
+            };
+        }
+        Err(err) => log::warn!("Failed to store key in OS keyring, using key file: {err}"),
+    }
+
+    let mut partial = key_file.clone().into_os_string();
+    partial.push(".partial");
+    let partial = std::path::PathBuf::from(partial);
+    let _ = fs::remove_file(&partial);
+    write_master_key_file(&partial, &encoded)?;
+    fs::rename(&partial, &key_file)?;
+    Ok(())
+}
+
+/// Seals `plaintext` under a fresh random data key, which is itself wrapped
+/// by `master_key`. `aad` must be supplied again to open the envelope.
+pub fn seal_envelope(
+    master_key: &[u8; 32],
+    aad: &[u8],
+    plaintext: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    let mut data_key = [0u8; 32];
+    OsRng.fill_bytes(&mut data_key);
+
+    let mut envelope = seal_bytes(master_key, ENVELOPE_KEY_AAD, &data_key)?;
+    envelope.extend(seal_bytes(&data_key, aad, plaintext)?);
+    Ok(envelope)
+}
+
+pub fn open_envelope(
+    master_key: &[u8; 32],
+    aad: &[u8],
+    envelope: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    if envelope.len() < WRAPPED_KEY_LEN {
+        return Err(CryptoError::Decryption);
+    }
+
+    let (wrapped_key, payload) = envelope.split_at(WRAPPED_KEY_LEN);
+    let data_key: [u8; 32] = open_bytes(master_key, ENVELOPE_KEY_AAD, wrapped_key)?
+        .try_into()
+        .map_err(|_| CryptoError::Decryption)?;
+    open_bytes(&data_key, aad, payload)
+}
+
+pub fn protect_api_key(key: &str) -> Result<ProtectedApiKey, CryptoError> {
+    let master_key = master_key()?;
     let salt = generate_salt();
-    let salt_b64 = general_purpose::STANDARD.encode(salt);
-    let hash = hash_key(secret, &salt, key.as_bytes());
-    let ciphertext = encrypt_key(secret, &salt, key.as_bytes());
+    let hash = hash_key(master_key, &salt, key.as_bytes());
+    let ciphertext = seal(master_key, &salt, key.as_bytes())?;
 
-    ProtectedApiKey {
-        salt_b64,
+    Ok(ProtectedApiKey {
+        salt_b64: general_purpose::STANDARD.encode(salt),
         hash_b64: general_purpose::STANDARD.encode(hash),
-        ciphertext_b64: general_purpose::STANDARD.encode(ciphertext),
+        ciphertext_b64: ciphertext,
         key_suffix: compute_key_suffix(key),
-    }
+    })
 }
 
+/// Accepts both AEAD ciphertexts and legacy XOR ones, so values are readable
+/// before [`reencrypt_legacy_api_keys`] has rewritten them.
 pub fn reveal_api_key(salt_b64: &str, ciphertext_b64: &str) -> Result<String, CryptoError> {
     let salt = general_purpose::STANDARD
         .decode(salt_b64)
         .map_err(|err| CryptoError::Base64(err.to_string()))?;
-    let ciphertext = general_purpose::STANDARD
-        .decode(ciphertext_b64)
-        .map_err(|err| CryptoError::Base64(err.to_string()))?;
-    let secret = runtime_secret();
-    let plaintext = decrypt_key(secret, &salt, &ciphertext);
+
+    let plaintext = match ciphertext_b64.strip_prefix(AEAD_PREFIX) {
+        Some(sealed) => open(master_key()?, &salt, sealed)?,
+        None => {
+            let ciphertext = general_purpose::STANDARD
+                .decode(ciphertext_b64)
+                .map_err(|err| CryptoError::Base64(err.to_string()))?;
+            xor_keystream(legacy_secret(), &salt, &ciphertext)
+        }
+    };
     String::from_utf8(plaintext).map_err(|err| CryptoError::InvalidUtf8(err.to_string()))
 }
 
+pub fn is_legacy_ciphertext(ciphertext_b64: &str) -> bool {
+    !ciphertext_b64.starts_with(AEAD_PREFIX)
+}
+
+/// Rewrites `api_keys` rows still using the legacy XOR scheme. Rows whose
+/// stored hash does not match the decrypted key (for instance because
+/// `VOQUILL_API_KEY_SECRET` changed) are left untouched.
+pub async fn reencrypt_legacy_api_keys(pool: SqlitePool) -> Result<usize, CryptoError> {
+    let table_exists = crate::db::api_key_queries::api_keys_table_exists(pool.clone())
+        .await
+        .map_err(|err| CryptoError::Storage(err.to_string()))?;
+    if !table_exists {
+        return Ok(0);
+    }
+
+    let api_keys = crate::db::api_key_queries::fetch_api_keys(pool.clone())
+        .await
+        .map_err(|err| CryptoError::Storage(err.to_string()))?;
+
+    let mut migrated = 0;
+    for api_key in api_keys {
+        if !is_legacy_ciphertext(&api_key.key_ciphertext) {
+            continue;
+        }
+
+        let verified = reveal_api_key(&api_key.salt, &api_key.key_ciphertext)
+            .ok()
+            .filter(|key| {
+                general_purpose::STANDARD
+                    .decode(&api_key.salt)
+                    .map(|salt| {
+                        let hash = hash_key(legacy_secret(), &salt, key.as_bytes());
+                        general_purpose::STANDARD.encode(hash) == api_key.key_hash
+                    })
+                    .unwrap_or(false)
+            });
+        let Some(key) = verified else {
+            log::warn!(
+                "Skipping re-encryption of API key {}: legacy secret does not match",
+                api_key.id
+            );
+            continue;
+        };
+
+        let protected = protect_api_key(&key)?;
+        crate::db::api_key_queries::update_api_key_secret(
+            pool.clone(),
+            &api_key.id,
+            &protected.salt_b64,
+            &protected.hash_b64,
+            &protected.ciphertext_b64,
+        )
+        .await
+        .map_err(|err| CryptoError::Storage(err.to_string()))?;
+        migrated += 1;
+    }
+
+    Ok(migrated)
+}
+
+fn master_key() -> Result<&'static [u8; 32], CryptoError> {
+    MASTER_KEY
+        .get()
+        .ok_or_else(|| CryptoError::KeyUnavailable("master key not initialized".to_string()))
+}
+
+fn legacy_secret() -> &'static [u8] {
+    LEGACY_SECRET
+        .get_or_init(|| match std::env::var(LEGACY_SECRET_ENV) {
+            Ok(value) if !value.is_empty() => value.into_bytes(),
+            _ => LEGACY_DEFAULT_SECRET.as_bytes().to_vec(),
+        })
+        .as_slice()
+}
+
+fn install_key_location(
+    app: &tauri::AppHandle,
+    file_name: &str,
+) -> Result<(String, std::path::PathBuf), CryptoError> {
+    let key_file = app
+        .path()
+        .app_config_dir()
+        .map_err(|err| CryptoError::KeyUnavailable(err.to_string()))?
+        .join(file_name);
+    Ok((app.config().identifier.clone(), key_file))
+}
+
+fn load_or_create_key(
+    service: &str,
+    keyring_user: &str,
+    key_file: &Path,
+    sealed_data_exists: bool,
+) -> Result<[u8; 32], CryptoError> {
+    let entry = keyring::Entry::new(service, keyring_user).map_err(|err| err.to_string());
+    resolve_key(
+        read_keyring_key(&entry),
+        key_file,
+        sealed_data_exists,
+        |encoded| {
+            entry
+                .as_ref()
+                .map_err(Clone::clone)?
+                .set_password(encoded)
+                .map_err(|err| err.to_string())
+        },
+    )
+}
+
+fn read_keyring_key(entry: &Result<keyring::Entry, String>) -> Result<Option<[u8; 32]>, String> {
+    entry
+        .as_ref()
+        .map_err(Clone::clone)
+        .and_then(|entry| match entry.get_password() {
+            Ok(encoded) => decode_master_key(&encoded).map(Some),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(err) => Err(err.to_string()),
+        })
+}
+
+/// Returns the key from the keyring, or from the fallback file. Fails when
+/// the keyring could not be read and no file exists, since a key may still
+/// be stored in the keyring.
+fn read_key(
+    keyring_key: Result<Option<[u8; 32]>, String>,
+    key_file: &Path,
+) -> Result<Option<[u8; 32]>, CryptoError> {
+    let keyring_error = match keyring_key {
+        Ok(Some(key)) => return Ok(Some(key)),
+        Ok(None) => None,
+        Err(err) => {
+            log::warn!("OS keyring unavailable, using key file: {err}");
+            Some(err)
+        }
+    };
+
+    match (read_master_key_file(key_file)?, keyring_error) {
+        (Some(key), _) => Ok(Some(key)),
+        (None, Some(err)) => Err(CryptoError::KeyUnavailable(format!(
+            "OS keyring could not be read: {err}"
+        ))),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Picks the install key given what the keyring returned. When the keyring
+/// fails, a key may still be stored there, so a new one is only written to
+/// the fallback file while nothing sealed exists yet; replacing a key that
+/// sealed data would orphan everything sealed with it.
+fn resolve_key(
+    keyring_key: Result<Option<[u8; 32]>, String>,
+    key_file: &Path,
+    sealed_data_exists: bool,
+    store_in_keyring: impl FnOnce(&str) -> Result<(), String>,
+) -> Result<[u8; 32], CryptoError> {
+    let keyring_failed = keyring_key.is_err();
+    match read_key(keyring_key, key_file) {
+        Ok(Some(key)) => return Ok(key),
+        Ok(None) => {}
+        Err(err @ CryptoError::KeyUnavailable(_)) if keyring_failed && !sealed_data_exists => {
+            log::warn!("No sealed data yet, creating key file instead: {err}");
+        }
+        Err(err) => return Err(err),
+    }
+
+    let mut key = [0u8; 32];
+    OsRng.fill_bytes(&mut key);
+    let encoded = general_purpose::STANDARD.encode(key);
+
+    if !keyring_failed {
+        match store_in_keyring(&encoded) {
+            Ok(()) => return Ok(key),
+            Err(err) => log::warn!("Failed to store master key in OS keyring: {err}"),
+        }
+    }
+
+    write_master_key_file(key_file, &encoded)?;
+    Ok(key)
+}
+
+fn decode_master_key(encoded: &str) -> Result<[u8; 32], String> {
+    general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|err| err.to_string())?
+        .try_into()
+        .map_err(|_| "stored master key has the wrong length".to_string())
+}
+
+fn read_master_key_file(path: &Path) -> Result<Option<[u8; 32]>, CryptoError> {
+    match fs::read_to_string(path) {
+        Ok(encoded) => decode_master_key(&encoded)
+            .map(Some)
+            .map_err(CryptoError::KeyUnavailable),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn write_master_key_file(path: &Path, encoded: &str) -> Result<(), CryptoError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options.open(path)?;
+    file.write_all(encoded.as_bytes())?;
+    file.sync_all()?;
+    Ok(())
+}
+
+/// The salt is bound as associated data so a ciphertext cannot be moved to
+/// another row without detection.
+fn seal(key: &[u8; 32], salt: &[u8], plaintext: &[u8]) -> Result<String, CryptoError> {
+    let combined = seal_bytes(key, salt, plaintext)?;
+    Ok(format!(
+        "{AEAD_PREFIX}{}",
+        general_purpose::STANDARD.encode(combined)
+    ))
+}
+
+fn open(key: &[u8; 32], salt: &[u8], sealed_b64: &str) -> Result<Vec<u8>, CryptoError> {
+    let combined = general_purpose::STANDARD
+        .decode(sealed_b64)
+        .map_err(|err| CryptoError::Base64(err.to_string()))?;
+    open_bytes(key, salt, &combined)
+}
+
+/// Returns the random nonce followed by the ciphertext and tag.
+fn seal_bytes(key: &[u8; 32], aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let mut nonce = [0u8; AEAD_NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+
+    let sealed = XChaCha20Poly1305::new(key.into())
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|_| CryptoError::Encryption)?;
+
+    let mut combined = nonce.to_vec();
+    combined.extend_from_slice(&sealed);
+    Ok(combined)
+}
+
+fn open_bytes(key: &[u8; 32], aad: &[u8], combined: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if combined.len() < AEAD_NONCE_LEN + AEAD_TAG_LEN {
+        return Err(CryptoError::Decryption);
+    }
+
+    let (nonce, sealed) = combined.split_at(AEAD_NONCE_LEN);
+    XChaCha20Poly1305::new(key.into())
+        .decrypt(XNonce::from_slice(nonce), Payload { msg: sealed, aad })
+        .map_err(|_| CryptoError::Decryption)
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum CryptoError {
     #[error("invalid base64 data: {0}")]
     Base64(String),
     #[error("stored API key is not valid UTF-8: {0}")]
     InvalidUtf8(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to derive key from passphrase: {0}")]
+    KeyDerivation(String),
+    #[error("data is not passphrase-encrypted or uses an unsupported format")]
+    UnsupportedFormat,
+    #[error("incorrect passphrase or corrupted data")]
+    Decryption,
+    #[error("encryption failed")]
+    Encryption,
+    #[error("encryption key unavailable: {0}")]
+    KeyUnavailable(String),
+    #[error("failed to update stored keys: {0}")]
+    Storage(String),
+}
+
+/// True when `prefix` starts with the header written by
+/// [`encrypt_with_passphrase`].
+pub fn is_passphrase_encrypted(prefix: &[u8]) -> bool {
+    prefix.starts_with(PASSPHRASE_MAGIC)
+}
+
+/// Streams `reader` into `writer` encrypted with a key derived from
+/// `passphrase`, so large archives never need to fit in memory.
+pub fn encrypt_with_passphrase<R: Read, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    passphrase: &str,
+) -> Result<(), CryptoError> {
+    let params = Params::default();
+    let salt = generate_salt();
+    let mut nonce = [0u8; STREAM_NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+
+    let cipher = derive_passphrase_cipher(passphrase, &salt, &params)?;
+    let mut encryptor = EncryptorBE32::from_aead(cipher, nonce.as_slice().into());
+
+    writer.write_all(PASSPHRASE_MAGIC)?;
+    for value in [params.m_cost(), params.t_cost(), params.p_cost()] {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    writer.write_all(&salt)?;
+    writer.write_all(&nonce)?;
+
+    // The final chunk is sealed differently, so always read one ahead.
+    let mut current = read_chunk(&mut reader, STREAM_CHUNK_LEN)?;
+    loop {
+        let next = read_chunk(&mut reader, STREAM_CHUNK_LEN)?;
+        if next.is_empty() {
+            let sealed = encryptor
+                .encrypt_last(current.as_slice())
+                .map_err(|_| CryptoError::Encryption)?;
+            writer.write_all(&sealed)?;
+            break;
+        }
+
+        let sealed = encryptor
+            .encrypt_next(current.as_slice())
+            .map_err(|_| CryptoError::Encryption)?;
+        writer.write_all(&sealed)?;
+        current = next;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn decrypt_with_passphrase<R: Read, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    passphrase: &str,
+) -> Result<(), CryptoError> {
+    let mut magic = [0u8; 8];
+    reader
+        .read_exact(&mut magic)
+        .map_err(|_| CryptoError::UnsupportedFormat)?;
+    if !is_passphrase_encrypted(&magic) {
+        return Err(CryptoError::UnsupportedFormat);
+    }
+
+    let mut costs = [0u32; 3];
+    for cost in costs.iter_mut() {
+        let mut bytes = [0u8; 4];
+        reader.read_exact(&mut bytes)?;
+        *cost = u32::from_le_bytes(bytes);
+    }
+    let mut salt = [0u8; PASSPHRASE_SALT_LEN];
+    reader.read_exact(&mut salt)?;
+    let mut nonce = [0u8; STREAM_NONCE_LEN];
+    reader.read_exact(&mut nonce)?;
+
+    let params = passphrase_params(costs)?;
+    let cipher = derive_passphrase_cipher(passphrase, &salt, &params)?;
+    let mut decryptor = DecryptorBE32::from_aead(cipher, nonce.as_slice().into());
+
+    let sealed_len = STREAM_CHUNK_LEN + STREAM_TAG_LEN;
+    let mut current = read_chunk(&mut reader, sealed_len)?;
+    loop {
+        let next = read_chunk(&mut reader, sealed_len)?;
+        if next.is_empty() {
+            let plaintext = decryptor
+                .decrypt_last(current.as_slice())
+                .map_err(|_| CryptoError::Decryption)?;
+            writer.write_all(&plaintext)?;
+            break;
+        }
+
+        let plaintext = decryptor
+            .decrypt_next(current.as_slice())
+            .map_err(|_| CryptoError::Decryption)?;
+        writer.write_all(&plaintext)?;
+        current = next;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn passphrase_params([m_cost, t_cost, p_cost]: [u32; 3]) -> Result<Params, CryptoError> {
+    if m_cost > MAX_PASSPHRASE_M_COST
+        || t_cost > MAX_PASSPHRASE_T_COST
+        || p_cost > MAX_PASSPHRASE_P_COST
+    {
+        return Err(CryptoError::KeyDerivation(format!(
+            "cost parameters (m={m_cost}, t={t_cost}, p={p_cost}) exceed the supported maximum"
+        )));
+    }
+
+    Params::new(m_cost, t_cost, p_cost, None)
+        .map_err(|err| CryptoError::KeyDerivation(err.to_string()))
+}
+
+fn derive_passphrase_cipher(
+    passphrase: &str,
+    salt: &[u8],
+    params: &Params,
+) -> Result<XChaCha20Poly1305, CryptoError> {
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params.clone())
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| CryptoError::KeyDerivation(err.to_string()))?;
+    XChaCha20Poly1305::new_from_slice(&key).map_err(|_| CryptoError::Encryption)
+}
+
+/// Fills up to `len` bytes, stopping early only at end of input.
+fn read_chunk<R: Read>(reader: &mut R, len: usize) -> std::io::Result<Vec<u8>> {
+    let mut buffer = Vec::with_capacity(len);
+    reader.by_ref().take(len as u64).read_to_end(&mut buffer)?;
+    Ok(buffer)
 }
 
 fn generate_salt() -> [u8; 16] {
@@ -81,14 +633,6 @@ fn hash_key(secret: &[u8], salt: &[u8], key: &[u8]) -> [u8; 32] {
     hasher.finalize().into()
 }
 
-fn encrypt_key(secret: &[u8], salt: &[u8], key: &[u8]) -> Vec<u8> {
-    xor_keystream(secret, salt, key)
-}
-
-fn decrypt_key(secret: &[u8], salt: &[u8], ciphertext: &[u8]) -> Vec<u8> {
-    xor_keystream(secret, salt, ciphertext)
-}
-
 fn xor_keystream(secret: &[u8], salt: &[u8], data: &[u8]) -> Vec<u8> {
     let keystream = derive_keystream(secret, salt, data.len());
     data.iter()
@@ -133,3 +677,184 @@ fn compute_key_suffix(key: &str) -> Option<String> {
         Some(buffer.into_iter().collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_keys_are_bound_to_their_salt() {
+        let key = [7u8; 32];
+        let sealed = seal(&key, b"salt-a", b"sk-test").unwrap();
+        assert!(!is_legacy_ciphertext(&sealed));
+
+        let encoded = sealed.strip_prefix(AEAD_PREFIX).unwrap();
+        assert_eq!(open(&key, b"salt-a", encoded).unwrap(), b"sk-test");
+        assert!(matches!(
+            open(&key, b"salt-b", encoded),
+            Err(CryptoError::Decryption)
+        ));
+        assert!(matches!(
+            open(&[8u8; 32], b"salt-a", encoded),
+            Err(CryptoError::Decryption)
+        ));
+    }
+
+    #[test]
+    fn envelopes_round_trip_and_check_associated_data() {
+        let master = [3u8; 32];
+        let envelope = seal_envelope(&master, b"transcript", b"hello").unwrap();
+
+        assert_eq!(
+            open_envelope(&master, b"transcript", &envelope).unwrap(),
+            b"hello"
+        );
+        assert!(open_envelope(&master, b"raw_transcript", &envelope).is_err());
+        assert!(open_envelope(&[4u8; 32], b"transcript", &envelope).is_err());
+        assert!(open_envelope(&master, b"transcript", &envelope[..40]).is_err());
+    }
+
+    #[test]
+    fn keyring_errors_never_replace_a_key_that_sealed_data() {
+        let dir = std::env::temp_dir().join(format!("voquill-keys-{}", rand::random::<u32>()));
+        let key_file = dir.join("master.key");
+
+        let result = resolve_key(Err("locked".to_string()), &key_file, true, |_| {
+            panic!("nothing should be stored")
+        });
+        assert!(matches!(result, Err(CryptoError::KeyUnavailable(_))));
+        assert!(!key_file.exists());
+
+        // A key kept in the fallback file is still used.
+        write_master_key_file(&key_file, &general_purpose::STANDARD.encode([5u8; 32])).unwrap();
+        let result = resolve_key(Err("locked".to_string()), &key_file, true, |_| {
+            panic!("nothing should be stored")
+        });
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(result.unwrap(), [5u8; 32]);
+    }
+
+    #[test]
+    fn keyring_errors_create_a_key_file_before_anything_is_sealed() {
+        let dir = std::env::temp_dir().join(format!("voquill-keys-{}", rand::random::<u32>()));
+        let key_file = dir.join("master.key");
+
+        let key = resolve_key(
+            Err("no secret service".to_string()),
+            &key_file,
+            false,
+            |_| panic!("the keyring should not be written"),
+        )
+        .unwrap();
+        let from_file = read_master_key_file(&key_file).unwrap();
+        assert_eq!(from_file, Some(key));
+
+        // The next launch reads the same key back from the file.
+        let again = resolve_key(
+            Err("no secret service".to_string()),
+            &key_file,
+            true,
+            |_| panic!("nothing should be stored"),
+        );
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(again.unwrap(), key);
+    }
+
+    #[test]
+    fn reading_a_key_never_creates_one() {
+        let dir = std::env::temp_dir().join(format!("voquill-keys-{}", rand::random::<u32>()));
+        let key_file = dir.join("master.key");
+
+        assert_eq!(read_key(Ok(None), &key_file).unwrap(), None);
+        assert_eq!(
+            read_key(Ok(Some([6u8; 32])), &key_file).unwrap(),
+            Some([6u8; 32])
+        );
+        assert!(matches!(
+            read_key(Err("locked".to_string()), &key_file),
+            Err(CryptoError::KeyUnavailable(_))
+        ));
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn missing_keyring_entry_creates_and_stores_a_key() {
+        let dir = std::env::temp_dir().join(format!("voquill-keys-{}", rand::random::<u32>()));
+        let key_file = dir.join("master.key");
+
+        let mut stored = None;
+        let key = resolve_key(Ok(None), &key_file, false, |encoded| {
+            stored = Some(encoded.to_string());
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(decode_master_key(&stored.unwrap()).unwrap(), key);
+        assert!(!key_file.exists());
+
+        // Without a working keyring the key falls back to the file.
+        let key = resolve_key(Ok(None), &key_file, false, |_| Err("denied".to_string())).unwrap();
+        let from_file = read_master_key_file(&key_file).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(from_file, Some(key));
+    }
+
+    #[test]
+    fn legacy_ciphertexts_are_still_revealed() {
+        let salt = [1u8; 16];
+        let ciphertext = xor_keystream(legacy_secret(), &salt, b"sk-legacy");
+        let revealed = reveal_api_key(
+            &general_purpose::STANDARD.encode(salt),
+            &general_purpose::STANDARD.encode(ciphertext),
+        )
+        .unwrap();
+        assert_eq!(revealed, "sk-legacy");
+    }
+
+    #[test]
+    fn passphrase_round_trip_spans_multiple_chunks() {
+        let plaintext = (0..STREAM_CHUNK_LEN * 2 + 123)
+            .map(|index| (index % 251) as u8)
+            .collect::<Vec<_>>();
+
+        let mut sealed = Vec::new();
+        encrypt_with_passphrase(plaintext.as_slice(), &mut sealed, "correct horse").unwrap();
+        assert!(is_passphrase_encrypted(&sealed));
+
+        let mut opened = Vec::new();
+        decrypt_with_passphrase(sealed.as_slice(), &mut opened, "correct horse").unwrap();
+        assert_eq!(opened, plaintext);
+    }
+
+    #[test]
+    fn wrong_passphrase_and_truncation_are_rejected() {
+        let mut sealed = Vec::new();
+        encrypt_with_passphrase(&b"secret"[..], &mut sealed, "one").unwrap();
+
+        let result = decrypt_with_passphrase(sealed.as_slice(), std::io::sink(), "two");
+        assert!(matches!(result, Err(CryptoError::Decryption)));
+
+        let truncated = &sealed[..sealed.len() - 1];
+        let result = decrypt_with_passphrase(truncated, std::io::sink(), "one");
+        assert!(matches!(result, Err(CryptoError::Decryption)));
+    }
+
+    #[test]
+    fn excessive_header_costs_are_rejected_before_key_derivation() {
+        let mut sealed = Vec::new();
+        encrypt_with_passphrase(&b"secret"[..], &mut sealed, "one").unwrap();
+
+        let magic_len = PASSPHRASE_MAGIC.len();
+        for (index, cost) in [
+            (0, MAX_PASSPHRASE_M_COST + 1),
+            (1, MAX_PASSPHRASE_T_COST + 1),
+            (2, u32::MAX),
+        ] {
+            let mut crafted = sealed.clone();
+            let offset = magic_len + index * 4;
+            crafted[offset..offset + 4].copy_from_slice(&cost.to_le_bytes());
+
+            let result = decrypt_with_passphrase(crafted.as_slice(), std::io::sink(), "one");
+            assert!(matches!(result, Err(CryptoError::KeyDerivation(_))));
+        }
+    }
+}
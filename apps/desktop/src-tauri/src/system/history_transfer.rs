@@ -0,0 +1,437 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, Utc};
+use zip::write::SimpleFileOptions;
+
+use crate::domain::{Transcription, TranscriptionExportFormat};
+
+/// Page size used when walking every search match for a bulk export.
+pub const EXPORT_PAGE_SIZE: u32 = 200;
+
+const BUNDLE_AUDIO_DIR: &str = "audio";
+const BUNDLE_BASENAME: &str = "transcriptions";
+const ZIP_MAGIC: &[u8; 4] = b"PK\x03\x04";
+
+const CSV_HEADER: &[&str] = &[
+    "id",
+    "timestamp",
+    "transcript",
+    "raw_transcript",
+    "sanitized_transcript",
+    "transcription_mode",
+    "model_size",
+    "inference_device",
+    "post_process_mode",
+    "post_process_device",
+    "transcription_api_key_id",
+    "post_process_api_key_id",
+    "remote_status",
+    "remote_device_id",
+    "transcription_duration_ms",
+    "postprocess_duration_ms",
+    "audio_file",
+    "audio_duration_ms",
+];
+
+/// A transcription ready to be written out. When audio is bundled,
+/// `transcription.audio.file_path` is rewritten to the path inside the
+/// archive and `audio_source` points at the managed file to copy.
+pub struct ExportEntry {
+    pub transcription: Transcription,
+    pub audio_source: Option<PathBuf>,
+}
+
+pub struct ParsedImport {
+    pub transcriptions: Vec<Transcription>,
+    pub errors: Vec<String>,
+    /// Set when the import came from a zip bundle that may carry audio.
+    pub archive_path: Option<PathBuf>,
+}
+
+pub fn file_extension(format: TranscriptionExportFormat) -> &'static str {
+    match format {
+        TranscriptionExportFormat::Jsonl => "jsonl",
+        TranscriptionExportFormat::Csv => "csv",
+        TranscriptionExportFormat::Markdown => "md",
+    }
+}
+
+/// Local audio paths mean nothing on another machine, so they are either
+/// rewritten to their bundled location or dropped.
+pub fn prepare_export(
+    transcriptions: Vec<Transcription>,
+    bundle_audio_from: Option<&Path>,
+) -> Vec<ExportEntry> {
+    transcriptions
+        .into_iter()
+        .map(|mut transcription| {
+            let audio_source = match (bundle_audio_from, transcription.audio.as_ref()) {
+                (Some(audio_dir), Some(audio)) => {
+                    let source = PathBuf::from(&audio.file_path);
+                    (source.starts_with(audio_dir) && source.is_file()).then_some(source)
+                }
+                _ => None,
+            };
+
+            let bundled_name = audio_source
+                .as_ref()
+                .and_then(|source| source.file_name())
+                .map(|name| format!("{BUNDLE_AUDIO_DIR}/{}", name.to_string_lossy()));
+
+            match (bundled_name, transcription.audio.as_mut()) {
+                (Some(name), Some(audio)) => audio.file_path = name,
+                _ => transcription.audio = None,
+            }
+
+            ExportEntry {
+                transcription,
+                audio_source,
+            }
+        })
+        .collect()
+}
+
+pub fn render(
+    format: TranscriptionExportFormat,
+    transcriptions: &[Transcription],
+) -> Result<String, String> {
+    match format {
+        TranscriptionExportFormat::Jsonl => render_jsonl(transcriptions),
+        TranscriptionExportFormat::Csv => Ok(render_csv(transcriptions)),
+        TranscriptionExportFormat::Markdown => Ok(render_markdown(transcriptions)),
+    }
+}
+
+/// Writes a plain export file, or a zip with the export plus an `audio/`
+/// folder when `bundle` is set.
+pub fn write_export(
+    path: &Path,
+    format: TranscriptionExportFormat,
+    entries: &[ExportEntry],
+    bundle: bool,
+) -> Result<(), String> {
+    let transcriptions = entries
+        .iter()
+        .map(|entry| entry.transcription.clone())
+        .collect::<Vec<_>>();
+    let document = render(format, &transcriptions)?;
+
+    if !bundle {
+        return std::fs::write(path, document)
+            .map_err(|err| format!("Failed to write export: {err}"));
+    }
+
+    let file = File::create(path).map_err(|err| format!("Failed to create file: {err}"))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file(
+        format!("{BUNDLE_BASENAME}.{}", file_extension(format)),
+        options,
+    )
+    .map_err(|err| err.to_string())?;
+    zip.write_all(document.as_bytes())
+        .map_err(|err| err.to_string())?;
+
+    for entry in entries {
+        let (Some(source), Some(audio)) = (&entry.audio_source, &entry.transcription.audio) else {
+            continue;
+        };
+        let audio_data = crate::system::audio_store::read_audio_bytes(source)
+            .map_err(|err| format!("Failed to read audio: {err}"))?;
+        // Audio is already compact; deflating it again only costs time.
+        zip.start_file(
+            audio.file_path.as_str(),
+            options.compression_method(zip::CompressionMethod::Stored),
+        )
+        .map_err(|err| err.to_string())?;
+        zip.write_all(&audio_data).map_err(|err| err.to_string())?;
+    }
+
+    zip.finish().map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+/// Reads a JSONL export, either bare or from inside a zip bundle. Lines that
+/// fail to parse are reported instead of aborting the whole import.
+pub fn read_import(path: &Path) -> Result<ParsedImport, String> {
+    let mut file = File::open(path).map_err(|err| format!("Failed to open import: {err}"))?;
+    let mut magic = [0u8; 4];
+    let is_zip = file.read_exact(&mut magic).is_ok() && &magic == ZIP_MAGIC;
+    drop(file);
+
+    let (contents, archive_path) = if is_zip {
+        let file = File::open(path).map_err(|err| format!("Failed to open import: {err}"))?;
+        let mut archive = zip::ZipArchive::new(file).map_err(|err| err.to_string())?;
+        let entry_name = format!("{BUNDLE_BASENAME}.jsonl");
+        let mut entry = archive.by_name(&entry_name).map_err(|_| {
+            format!("Archive does not contain {entry_name}; only JSONL exports can be imported")
+        })?;
+        let mut contents = String::new();
+        entry
+            .read_to_string(&mut contents)
+            .map_err(|err| format!("Failed to read {entry_name}: {err}"))?;
+        (contents, Some(path.to_path_buf()))
+    } else {
+        let contents =
+            std::fs::read_to_string(path).map_err(|err| format!("Failed to read import: {err}"))?;
+        (contents, None)
+    };
+
+    let (transcriptions, errors) = parse_jsonl(&contents);
+    Ok(ParsedImport {
+        transcriptions,
+        errors,
+        archive_path,
+    })
+}
+
+/// Copies bundled audio entries to their destinations. Returns one result
+/// per requested entry, in order.
+pub fn extract_audio(
+    archive_path: &Path,
+    entries: &[(String, PathBuf)],
+) -> Result<Vec<Result<(), String>>, String> {
+    let file = File::open(archive_path).map_err(|err| format!("Failed to open import: {err}"))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|err| err.to_string())?;
+
+    let results = entries
+        .iter()
+        .map(|(name, destination)| {
+            let mut entry = archive
+                .by_name(name)
+                .map_err(|_| format!("Archive is missing {name}"))?;
+            let mut audio = Vec::new();
+            entry
+                .read_to_end(&mut audio)
+                .map_err(|err| format!("Failed to extract {name}: {err}"))?;
+            crate::system::vault::write_file(destination, &audio)
+                .map_err(|err| format!("Failed to write {}: {err}", destination.display()))
+        })
+        .collect();
+
+    Ok(results)
+}
+
+/// True when the audio snapshot of an imported transcription refers to a
+/// file inside the bundle rather than a path on the exporting machine.
+pub fn is_bundled_audio_path(file_path: &str) -> bool {
+    file_path
+        .strip_prefix(BUNDLE_AUDIO_DIR)
+        .and_then(|rest| rest.strip_prefix('/'))
+        .is_some_and(|name| !name.is_empty() && !name.contains(['/', '\\']) && name != "..")
+}
+
+fn render_jsonl(transcriptions: &[Transcription]) -> Result<String, String> {
+    let mut output = String::new();
+    for transcription in transcriptions {
+        let line = serde_json::to_string(transcription).map_err(|err| err.to_string())?;
+        output.push_str(&line);
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+fn parse_jsonl(contents: &str) -> (Vec<Transcription>, Vec<String>) {
+    let mut transcriptions = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<Transcription>(line) {
+            Ok(transcription) => transcriptions.push(transcription),
+            Err(err) => errors.push(format!("Line {}: {err}", index + 1)),
+        }
+    }
+
+    (transcriptions, errors)
+}
+
+fn render_csv(transcriptions: &[Transcription]) -> String {
+    let mut output = CSV_HEADER.join(",");
+    output.push_str("\r\n");
+
+    for transcription in transcriptions {
+        let optional_number = |value: Option<i64>| value.map(|v| v.to_string()).unwrap_or_default();
+        let fields = [
+            transcription.id.clone(),
+            format_utc(transcription.timestamp),
+            transcription.transcript.clone(),
+            transcription.raw_transcript.clone().unwrap_or_default(),
+            transcription
+                .sanitized_transcript
+                .clone()
+                .unwrap_or_default(),
+            transcription.transcription_mode.clone().unwrap_or_default(),
+            transcription.model_size.clone().unwrap_or_default(),
+            transcription.inference_device.clone().unwrap_or_default(),
+            transcription.post_process_mode.clone().unwrap_or_default(),
+            transcription
+                .post_process_device
+                .clone()
+                .unwrap_or_default(),
+            transcription
+                .transcription_api_key_id
+                .clone()
+                .unwrap_or_default(),
+            transcription
+                .post_process_api_key_id
+                .clone()
+                .unwrap_or_default(),
+            transcription.remote_status.clone().unwrap_or_default(),
+            transcription.remote_device_id.clone().unwrap_or_default(),
+            optional_number(transcription.transcription_duration_ms),
+            optional_number(transcription.postprocess_duration_ms),
+            transcription
+                .audio
+                .as_ref()
+                .map(|audio| audio.file_path.clone())
+                .unwrap_or_default(),
+            optional_number(transcription.audio.as_ref().map(|audio| audio.duration_ms)),
+        ];
+
+        let row = fields
+            .iter()
+            .map(|field| escape_csv(field))
+            .collect::<Vec<_>>()
+            .join(",");
+        output.push_str(&row);
+        output.push_str("\r\n");
+    }
+
+    output
+}
+
+pub(crate) fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// A journal ordered oldest-first with one heading per local calendar day.
+fn render_markdown(transcriptions: &[Transcription]) -> String {
+    let mut days: BTreeMap<String, Vec<&Transcription>> = BTreeMap::new();
+    for transcription in transcriptions {
+        let day = local_time(transcription.timestamp)
+            .map(|time| time.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "Unknown date".to_string());
+        days.entry(day).or_default().push(transcription);
+    }
+
+    let mut output = String::from("# Voquill transcriptions\n");
+    for (day, mut entries) in days {
+        entries.sort_by_key(|transcription| transcription.timestamp);
+        output.push_str(&format!("\n## {day}\n"));
+
+        for transcription in entries {
+            let time = local_time(transcription.timestamp)
+                .map(|time| time.format("%H:%M").to_string())
+                .unwrap_or_default();
+            output.push_str(&format!("\n### {time}\n\n"));
+            output.push_str(transcription.transcript.trim());
+            output.push('\n');
+
+            if let Some(audio) = &transcription.audio {
+                output.push_str(&format!("\n[Audio]({})\n", audio.file_path));
+            }
+        }
+    }
+
+    output
+}
+
+fn local_time(timestamp_ms: i64) -> Option<DateTime<Local>> {
+    DateTime::<Utc>::from_timestamp_millis(timestamp_ms).map(|time| time.with_timezone(&Local))
+}
+
+fn format_utc(timestamp_ms: i64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(timestamp_ms)
+        .map(|time| time.to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::TranscriptionAudioSnapshot;
+
+    fn transcription(id: &str, timestamp: i64, transcript: &str) -> Transcription {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "transcript": transcript,
+            "timestamp": timestamp,
+        }))
+        .expect("valid transcription")
+    }
+
+    #[test]
+    fn jsonl_round_trips_and_reports_bad_lines() {
+        let mut first = transcription("a", 1_700_000_000_000, "hello");
+        first.model_size = Some("base".to_string());
+        let second = transcription("b", 1_700_000_100_000, "world");
+
+        let mut document = render_jsonl(&[first, second]).unwrap();
+        document.push_str("{not json}\n");
+
+        let (parsed, errors) = parse_jsonl(&document);
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].model_size.as_deref(), Some("base"));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("Line 3:"));
+    }
+
+    #[test]
+    fn csv_quotes_fields_with_separators() {
+        let entry = transcription("a", 0, "one, \"two\"\nthree");
+        let document = render_csv(&[entry]);
+        let row = document.split("\r\n").nth(1).unwrap_or_default();
+
+        assert!(row.starts_with("a,1970-01-01T00:00:00.000Z,\"one, \"\"two\"\"\nthree\","));
+    }
+
+    #[test]
+    fn markdown_groups_entries_by_day() {
+        // Midday UTC keeps each pair on the same local day in any timezone.
+        let day = 86_400_000;
+        let noon = 1_700_000_000_000 / day * day + day / 2;
+        let entries = [
+            transcription("a", noon + 60_000, "second"),
+            transcription("b", noon, "first"),
+            transcription("c", noon + 3 * day, "later"),
+        ];
+
+        let document = render_markdown(&entries);
+        assert_eq!(document.matches("\n## ").count(), 2);
+        assert!(document.find("first").unwrap() < document.find("second").unwrap());
+    }
+
+    #[test]
+    fn export_drops_audio_that_is_not_bundled() {
+        let mut entry = transcription("a", 0, "hello");
+        entry.audio = Some(TranscriptionAudioSnapshot {
+            file_path: "/elsewhere/a.wav".to_string(),
+            duration_ms: 1_000,
+        });
+
+        let prepared = prepare_export(vec![entry], None);
+        assert!(prepared[0].transcription.audio.is_none());
+        assert!(prepared[0].audio_source.is_none());
+    }
+
+    #[test]
+    fn bundled_audio_paths_stay_inside_the_audio_folder() {
+        assert!(is_bundled_audio_path("audio/a.wav"));
+        assert!(!is_bundled_audio_path("audio/../secrets"));
+        assert!(!is_bundled_audio_path("/home/me/a.wav"));
+        assert!(!is_bundled_audio_path("audio/"));
+    }
+}
@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::domain::TranscriptionSegmentConfidence;
+
+/// Local inference can take several minutes for long clips on CPU.
+const TRANSCRIBE_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, thiserror::Error)]
+pub enum LocalSidecarError {
+    #[error("invalid sidecar url: {0}")]
+    InvalidUrl(String),
+    #[error("network error: {0}")]
+    Network(#[from] reqwest::Error),
+    #[error("sidecar error ({code}): {message}")]
+    Sidecar { code: String, message: String },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscribeRequest<'a> {
+    pub model: &'a str,
+    pub samples: &'a [f32],
+    pub sample_rate: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initial_prompt: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<&'a str>,
+    pub include_confidence: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscribeResponse {
+    pub text: String,
+    pub model: String,
+    pub inference_device: String,
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub segments: Option<Vec<TranscriptionSegmentConfidence>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorBody {
+    error: ErrorDetail,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorDetail {
+    code: String,
+    message: String,
+}
+
+/// Only loopback sidecars are accepted so stored audio never leaves the
+/// machine through this path.
+fn parse_base_url(base_url: &str) -> Result<Url, LocalSidecarError> {
+    let url = Url::parse(base_url).map_err(|err| LocalSidecarError::InvalidUrl(err.to_string()))?;
+    let is_loopback = match url.host() {
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+        Some(url::Host::Domain(domain)) => domain == "localhost",
+        None => false,
+    };
+
+    if url.scheme() != "http" || !is_loopback {
+        return Err(LocalSidecarError::InvalidUrl(format!(
+            "{base_url} is not a local sidecar"
+        )));
+    }
+
+    Ok(url)
+}
+
+/// Runs a one-shot transcription against the local transcription sidecar.
+pub async fn transcribe(
+    base_url: &str,
+    request: &TranscribeRequest<'_>,
+) -> Result<TranscribeResponse, LocalSidecarError> {
+    let endpoint = parse_base_url(base_url)?
+        .join("/v1/transcriptions")
+        .map_err(|err| LocalSidecarError::InvalidUrl(err.to_string()))?;
+
+    let client = Client::builder().timeout(TRANSCRIBE_TIMEOUT).build()?;
+    let response = client.post(endpoint).json(request).send().await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        return Err(match response.json::<ErrorBody>().await {
+            Ok(body) => LocalSidecarError::Sidecar {
+                code: body.error.code,
+                message: body.error.message,
+            },
+            Err(_) => LocalSidecarError::Sidecar {
+                code: status.as_u16().to_string(),
+                message: status.to_string(),
+            },
+        });
+    }
+
+    Ok(response.json::<TranscribeResponse>().await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_loopback_http_urls_are_accepted() {
+        assert!(parse_base_url("http://127.0.0.1:4312").is_ok());
+        assert!(parse_base_url("http://localhost:4312").is_ok());
+        assert!(parse_base_url("http://[::1]:4312").is_ok());
+        assert!(parse_base_url("https://127.0.0.1:4312").is_err());
+        assert!(parse_base_url("http://192.168.1.10:4312").is_err());
+        assert!(parse_base_url("http://example.com").is_err());
+        assert!(parse_base_url("not a url").is_err());
+    }
+}
@@ -1,19 +1,30 @@
 pub mod audio_feedback;
 pub mod audio_store;
 pub mod auth_session;
+pub mod backup;
 pub mod bridge_server;
 pub mod crypto;
 pub mod diagnostics;
 pub mod enterprise_oidc;
 pub mod google_oauth;
 pub mod gpu;
+pub mod history_transfer;
+pub mod local_sidecar;
 pub mod models;
 pub mod oauth_callback_page;
 pub mod paths;
+pub mod remote_channel;
+pub mod remote_discovery;
+pub mod remote_outbox;
 pub mod remote_receiver;
 pub mod remote_sender;
+pub mod remote_stream;
 pub mod storage_repo;
+pub mod term_replacement;
+pub mod term_transfer;
+pub mod tone_library;
 pub mod tray;
+pub mod vault;
 
 pub use paths::*;
 pub use storage_repo::StorageRepo;
@@ -0,0 +1,639 @@
+//! Encrypted transport shared by the remote sender and receiver.
+//!
+//! Every connection starts with a cleartext version handshake. The sender
+//! offers the protocol versions it speaks together with a random nonce, and
+//! the receiver answers with the version it picked and a nonce of its own.
+//! Both sides then derive one ChaCha20-Poly1305 key per direction with
+//! HKDF-SHA256 from a pre-shared key (the pairing code while pairing, the
+//! pairing's shared secret afterwards) salted with both nonces. Frames are
+//! sealed with a per-direction counter as nonce, so a frame that is
+//! replayed, reordered or sealed under another key fails to open. Proving
+//! knowledge of the key is what authenticates each side; the key itself
+//! never crosses the wire.
+
+use base64::{engine::general_purpose, Engine as _};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use hkdf::Hkdf;
+use rand::{rngs::OsRng, RngCore};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, Lines};
+
+/// Version 1 was plaintext JSON with the shared secret sent as a token.
+pub const PROTOCOL_VERSION: u32 = 2;
+const SUPPORTED_VERSIONS: &[u32] = &[PROTOCOL_VERSION];
+
+const HANDSHAKE_NONCE_LEN: usize = 32;
+
+pub const CODE_UNSUPPORTED_VERSION: &str = "unsupported_protocol_version";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelPurpose {
+    /// Keyed by the receiver's current pairing code.
+    Pairing,
+    /// Keyed by the shared secret agreed while pairing.
+    Session,
+}
+
+impl ChannelPurpose {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pairing => "pairing",
+            Self::Session => "session",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Frame {
+    ClientHello {
+        versions: Vec<u32>,
+        purpose: ChannelPurpose,
+        sender_device_id: String,
+        nonce: String,
+    },
+    ServerHello {
+        version: u32,
+        nonce: String,
+    },
+    Rejected {
+        code: String,
+        message: String,
+    },
+    Sealed {
+        data: String,
+    },
+}
+
+/// What a version 1 sender understands, so it can show why it was turned
+/// away instead of just seeing the connection drop.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum LegacyFrame<'a> {
+    DeliveryError {
+        session_id: &'a str,
+        event_id: &'a str,
+        sequence: u64,
+        code: &'a str,
+        message: &'a str,
+    },
+}
+
+/// A sender whose hello was read but not yet answered.
+pub struct PendingChannel<R, W> {
+    lines: Lines<BufReader<R>>,
+    writer: W,
+    version: u32,
+    purpose: ChannelPurpose,
+    sender_device_id: String,
+    client_nonce: Vec<u8>,
+}
+
+/// An established connection. Messages are JSON sealed into frames.
+pub struct SecureChannel<R, W> {
+    lines: Lines<BufReader<R>>,
+    writer: W,
+    sealer: ChaCha20Poly1305,
+    opener: ChaCha20Poly1305,
+    sent: u64,
+    received: u64,
+}
+
+struct DirectionKeys {
+    sender_to_receiver: [u8; 32],
+    receiver_to_sender: [u8; 32],
+}
+
+/// Opens a channel as the sender.
+pub async fn connect<R, W>(
+    reader: R,
+    mut writer: W,
+    purpose: ChannelPurpose,
+    sender_device_id: &str,
+    psk: &[u8],
+) -> Result<SecureChannel<R, W>, String>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut lines = BufReader::new(reader).lines();
+    let client_nonce = random_nonce();
+    write_line(
+        &mut writer,
+        &Frame::ClientHello {
+            versions: SUPPORTED_VERSIONS.to_vec(),
+            purpose,
+            sender_device_id: sender_device_id.to_string(),
+            nonce: hex_encode(&client_nonce),
+        },
+    )
+    .await?;
+
+    let Some(line) = lines
+        .next_line()
+        .await
+        .map_err(|err| format!("Failed to read remote handshake: {err}"))?
+    else {
+        return Err(
+            "The remote device closed the connection during the handshake. \
+                    It may be running an older Voquill without encrypted delivery; \
+                    update it and pair again."
+                .to_string(),
+        );
+    };
+
+    match serde_json::from_str::<Frame>(&line) {
+        Ok(Frame::ServerHello { version, nonce }) => {
+            if !SUPPORTED_VERSIONS.contains(&version) {
+                return Err(format!(
+                    "The remote device chose unsupported protocol version {version}."
+                ));
+            }
+            let server_nonce = parse_nonce(&nonce)?;
+            let keys = derive_keys(
+                psk,
+                version,
+                purpose,
+                sender_device_id,
+                &client_nonce,
+                &server_nonce,
+            )?;
+            Ok(SecureChannel::new(
+                lines,
+                writer,
+                &keys.sender_to_receiver,
+                &keys.receiver_to_sender,
+            ))
+        }
+        Ok(Frame::Rejected { message, .. }) => Err(message),
+        _ => Err("The remote device sent an unexpected handshake.".to_string()),
+    }
+}
+
+/// Reads a sender's hello. Senders that only speak an older protocol are
+/// told so and turned away with an error.
+pub async fn read_hello<R, W>(reader: R, mut writer: W) -> Result<PendingChannel<R, W>, String>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut lines = BufReader::new(reader).lines();
+    let line = lines
+        .next_line()
+        .await
+        .map_err(|err| format!("Failed to read remote handshake: {err}"))?
+        .ok_or_else(|| "Sender closed the connection before the handshake.".to_string())?;
+
+    match serde_json::from_str::<Frame>(&line) {
+        Ok(Frame::ClientHello {
+            versions,
+            purpose,
+            sender_device_id,
+            nonce,
+        }) => {
+            let Some(version) = SUPPORTED_VERSIONS
+                .iter()
+                .rev()
+                .copied()
+                .find(|version| versions.contains(version))
+            else {
+                let message = format!(
+                    "No common remote protocol version (offered {versions:?}, supported {SUPPORTED_VERSIONS:?}). Update Voquill on both devices."
+                );
+                write_line(
+                    &mut writer,
+                    &Frame::Rejected {
+                        code: CODE_UNSUPPORTED_VERSION.to_string(),
+                        message: message.clone(),
+                    },
+                )
+                .await?;
+                return Err(message);
+            };
+
+            Ok(PendingChannel {
+                lines,
+                writer,
+                version,
+                purpose,
+                sender_device_id,
+                client_nonce: parse_nonce(&nonce)?,
+            })
+        }
+        _ if is_legacy_message(&line) => {
+            let message = "This device requires encrypted delivery. Update Voquill on the sending device and pair again.";
+            write_line(
+                &mut writer,
+                &LegacyFrame::DeliveryError {
+                    session_id: "",
+                    event_id: "",
+                    sequence: 0,
+                    code: CODE_UNSUPPORTED_VERSION,
+                    message,
+                },
+            )
+            .await?;
+            Err("Sender uses the unencrypted version 1 protocol.".to_string())
+        }
+        _ => Err("Sender sent an unexpected handshake.".to_string()),
+    }
+}
+
+fn is_legacy_message(line: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(line)
+        .ok()
+        .and_then(|value| value.get("type")?.as_str().map(str::to_string))
+        .is_some_and(|kind| matches!(kind.as_str(), "pairing_request" | "session_hello"))
+}
+
+impl<R, W> PendingChannel<R, W>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    pub fn purpose(&self) -> ChannelPurpose {
+        self.purpose
+    }
+
+    pub fn sender_device_id(&self) -> &str {
+        &self.sender_device_id
+    }
+
+    /// Answers the hello and keys the channel with `psk`.
+    pub async fn accept(mut self, psk: &[u8]) -> Result<SecureChannel<R, W>, String> {
+        let server_nonce = random_nonce();
+        let keys = derive_keys(
+            psk,
+            self.version,
+            self.purpose,
+            &self.sender_device_id,
+            &self.client_nonce,
+            &server_nonce,
+        )?;
+        write_line(
+            &mut self.writer,
+            &Frame::ServerHello {
+                version: self.version,
+                nonce: hex_encode(&server_nonce),
+            },
+        )
+        .await?;
+
+        Ok(SecureChannel::new(
+            self.lines,
+            self.writer,
+            &keys.receiver_to_sender,
+            &keys.sender_to_receiver,
+        ))
+    }
+
+    pub async fn reject(mut self, code: &str, message: &str) -> Result<(), String> {
+        write_line(
+            &mut self.writer,
+            &Frame::Rejected {
+                code: code.to_string(),
+                message: message.to_string(),
+            },
+        )
+        .await
+    }
+}
+
+impl<R, W> SecureChannel<R, W>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    fn new(lines: Lines<BufReader<R>>, writer: W, seal_key: &[u8], open_key: &[u8]) -> Self {
+        Self {
+            lines,
+            writer,
+            sealer: ChaCha20Poly1305::new(Key::from_slice(seal_key)),
+            opener: ChaCha20Poly1305::new(Key::from_slice(open_key)),
+            sent: 0,
+            received: 0,
+        }
+    }
+
+    /// Whether any frame from the peer has opened, which proves it holds
+    /// the key.
+    pub fn is_authenticated(&self) -> bool {
+        self.received > 0
+    }
+
+    pub async fn send<T: Serialize>(&mut self, message: &T) -> Result<(), String> {
+        let plaintext = serde_json::to_vec(message)
+            .map_err(|err| format!("Failed to serialize remote message: {err}"))?;
+        let ciphertext = self
+            .sealer
+            .encrypt(&counter_nonce(self.sent)?, plaintext.as_slice())
+            .map_err(|_| "Failed to encrypt remote message.".to_string())?;
+        self.sent += 1;
+
+        write_line(
+            &mut self.writer,
+            &Frame::Sealed {
+                data: general_purpose::STANDARD.encode(ciphertext),
+            },
+        )
+        .await
+    }
+
+    /// Reads the next message, or `None` once the peer hangs up.
+    pub async fn recv<T: DeserializeOwned>(&mut self) -> Result<Option<T>, String> {
+        let Some(line) = self
+            .lines
+            .next_line()
+            .await
+            .map_err(|err| format!("Failed to read remote message: {err}"))?
+        else {
+            return Ok(None);
+        };
+
+        let data = match serde_json::from_str::<Frame>(&line) {
+            Ok(Frame::Sealed { data }) => data,
+            Ok(Frame::Rejected { message, .. }) => return Err(message),
+            _ => return Err("Remote device sent an unexpected frame.".to_string()),
+        };
+        let ciphertext = general_purpose::STANDARD
+            .decode(data)
+            .map_err(|_| "Remote device sent a malformed frame.".to_string())?;
+        let plaintext = self
+            .opener
+            .decrypt(&counter_nonce(self.received)?, ciphertext.as_slice())
+            .map_err(|_| {
+                "Failed to authenticate remote message; the devices may need to pair again."
+                    .to_string()
+            })?;
+        self.received += 1;
+
+        serde_json::from_slice(&plaintext)
+            .map(Some)
+            .map_err(|err| format!("Failed to parse remote message: {err}"))
+    }
+
+    /// Tells the peer why the connection is being closed. Sent in the clear,
+    /// since the peer may not hold the right key.
+    pub async fn reject(&mut self, code: &str, message: &str) -> Result<(), String> {
+        write_line(
+            &mut self.writer,
+            &Frame::Rejected {
+                code: code.to_string(),
+                message: message.to_string(),
+            },
+        )
+        .await
+    }
+}
+
+fn derive_keys(
+    psk: &[u8],
+    version: u32,
+    purpose: ChannelPurpose,
+    sender_device_id: &str,
+    client_nonce: &[u8],
+    server_nonce: &[u8],
+) -> Result<DirectionKeys, String> {
+    if psk.is_empty() {
+        return Err("Remote channel key is empty.".to_string());
+    }
+
+    let salt = [client_nonce, server_nonce].concat();
+    let hkdf = Hkdf::<Sha256>::new(Some(&salt), psk);
+    let expand = |direction: &str| -> Result<[u8; 32], String> {
+        let info = format!(
+            "voquill-remote/v{version}/{}/{sender_device_id}/{direction}",
+            purpose.as_str()
+        );
+        let mut key = [0u8; 32];
+        hkdf.expand(info.as_bytes(), &mut key)
+            .map_err(|_| "Failed to derive remote channel key.".to_string())?;
+        Ok(key)
+    };
+
+    Ok(DirectionKeys {
+        sender_to_receiver: expand("sender-to-receiver")?,
+        receiver_to_sender: expand("receiver-to-sender")?,
+    })
+}
+
+fn counter_nonce(counter: u64) -> Result<Nonce, String> {
+    if counter == u64::MAX {
+        return Err("Remote channel frame counter exhausted.".to_string());
+    }
+    let mut nonce = [0u8; 12];
+    nonce[4..].copy_from_slice(&counter.to_be_bytes());
+    Ok(*Nonce::from_slice(&nonce))
+}
+
+fn random_nonce() -> Vec<u8> {
+    let mut nonce = vec![0u8; HANDSHAKE_NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    nonce
+}
+
+fn parse_nonce(value: &str) -> Result<Vec<u8>, String> {
+    let bytes = hex_decode(value).ok_or_else(|| "Malformed handshake nonce.".to_string())?;
+    if bytes.len() != HANDSHAKE_NONCE_LEN {
+        return Err("Malformed handshake nonce.".to_string());
+    }
+    Ok(bytes)
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn hex_decode(value: &str) -> Option<Vec<u8>> {
+    if value.len() % 2 != 0 {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(value.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+async fn write_line<W, T>(writer: &mut W, message: &T) -> Result<(), String>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let mut json = serde_json::to_string(message)
+        .map_err(|err| format!("Failed to serialize remote frame: {err}"))?;
+    json.push('\n');
+    writer
+        .write_all(json.as_bytes())
+        .await
+        .map_err(|err| format!("Failed to write remote frame: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{duplex, split, DuplexStream, ReadHalf, WriteHalf};
+
+    type Half = (ReadHalf<DuplexStream>, WriteHalf<DuplexStream>);
+
+    fn pipe() -> (Half, Half) {
+        let (left, right) = duplex(64 * 1024);
+        (split(left), split(right))
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Message {
+        text: String,
+    }
+
+    fn message(text: &str) -> Message {
+        Message {
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn matching_keys_exchange_messages_both_ways() {
+        tauri::async_runtime::block_on(async {
+            let ((sender_read, sender_write), (receiver_read, receiver_write)) = pipe();
+
+            let receiver = async {
+                let pending = read_hello(receiver_read, receiver_write).await.unwrap();
+                assert_eq!(pending.purpose(), ChannelPurpose::Session);
+                assert_eq!(pending.sender_device_id(), "device-a");
+                let mut channel = pending.accept(b"secret").await.unwrap();
+                let received: Message = channel.recv().await.unwrap().unwrap();
+                assert!(channel.is_authenticated());
+                channel.send(&message("ack")).await.unwrap();
+                received
+            };
+            let sender = async {
+                let mut channel = connect(
+                    sender_read,
+                    sender_write,
+                    ChannelPurpose::Session,
+                    "device-a",
+                    b"secret",
+                )
+                .await
+                .unwrap();
+                channel.send(&message("hello")).await.unwrap();
+                channel.recv::<Message>().await.unwrap().unwrap()
+            };
+
+            let (received, reply) = tokio::join!(receiver, sender);
+            assert_eq!(received, message("hello"));
+            assert_eq!(reply, message("ack"));
+        });
+    }
+
+    #[test]
+    fn a_wrong_key_fails_to_open() {
+        tauri::async_runtime::block_on(async {
+            let ((sender_read, sender_write), (receiver_read, receiver_write)) = pipe();
+
+            let receiver = async {
+                let pending = read_hello(receiver_read, receiver_write).await.unwrap();
+                let mut channel = pending.accept(b"right").await.unwrap();
+                let result = channel.recv::<Message>().await;
+                assert!(!channel.is_authenticated());
+                result
+            };
+            let sender = async {
+                let mut channel = connect(
+                    sender_read,
+                    sender_write,
+                    ChannelPurpose::Pairing,
+                    "device-a",
+                    b"wrong",
+                )
+                .await
+                .unwrap();
+                channel.send(&message("hello")).await.unwrap();
+            };
+
+            let (result, ()) = tokio::join!(receiver, sender);
+            assert!(result.is_err());
+        });
+    }
+
+    #[test]
+    fn replayed_frames_fail_to_open() {
+        let keys = derive_keys(
+            b"secret",
+            PROTOCOL_VERSION,
+            ChannelPurpose::Session,
+            "device-a",
+            &[1; HANDSHAKE_NONCE_LEN],
+            &[2; HANDSHAKE_NONCE_LEN],
+        )
+        .unwrap();
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&keys.sender_to_receiver));
+        let first = cipher
+            .encrypt(&counter_nonce(0).unwrap(), b"one".as_slice())
+            .unwrap();
+
+        assert!(cipher
+            .decrypt(&counter_nonce(0).unwrap(), first.as_slice())
+            .is_ok());
+        // The receiver expects counter 1 next, so the same frame is refused.
+        assert!(cipher
+            .decrypt(&counter_nonce(1).unwrap(), first.as_slice())
+            .is_err());
+
+        // Each direction has its own key.
+        let reverse = ChaCha20Poly1305::new(Key::from_slice(&keys.receiver_to_sender));
+        assert!(reverse
+            .decrypt(&counter_nonce(0).unwrap(), first.as_slice())
+            .is_err());
+    }
+
+    #[test]
+    fn version_one_senders_are_told_to_update() {
+        tauri::async_runtime::block_on(async {
+            let ((sender_read, mut sender_write), (receiver_read, receiver_write)) = pipe();
+
+            sender_write
+                .write_all(b"{\"type\":\"session_hello\",\"session_id\":\"s\",\"sender_device_id\":\"a\",\"auth_token\":\"t\"}\n")
+                .await
+                .unwrap();
+            assert!(read_hello(receiver_read, receiver_write).await.is_err());
+
+            let mut lines = BufReader::new(sender_read).lines();
+            let reply = lines.next_line().await.unwrap().unwrap();
+            let reply: serde_json::Value = serde_json::from_str(&reply).unwrap();
+            assert_eq!(reply["type"], "delivery_error");
+            assert_eq!(reply["code"], CODE_UNSUPPORTED_VERSION);
+        });
+    }
+
+    #[test]
+    fn senders_without_a_common_version_are_rejected() {
+        tauri::async_runtime::block_on(async {
+            let ((sender_read, mut sender_write), (receiver_read, receiver_write)) = pipe();
+
+            let hello = serde_json::to_string(&Frame::ClientHello {
+                versions: vec![9],
+                purpose: ChannelPurpose::Session,
+                sender_device_id: "a".to_string(),
+                nonce: hex_encode(&[0; HANDSHAKE_NONCE_LEN]),
+            })
+            .unwrap();
+            sender_write
+                .write_all(format!("{hello}\n").as_bytes())
+                .await
+                .unwrap();
+            assert!(read_hello(receiver_read, receiver_write).await.is_err());
+
+            let mut lines = BufReader::new(sender_read).lines();
+            let reply = lines.next_line().await.unwrap().unwrap();
+            assert!(matches!(
+                serde_json::from_str::<Frame>(&reply),
+                Ok(Frame::Rejected { code, .. }) if code == CODE_UNSUPPORTED_VERSION
+            ));
+        });
+    }
+}
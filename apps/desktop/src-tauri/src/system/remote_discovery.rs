@@ -0,0 +1,251 @@
+use std::net::IpAddr;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use once_cell::sync::Lazy;
+use tokio::time::Instant;
+
+use crate::domain::DiscoveredRemoteReceiver;
+
+pub const SERVICE_TYPE: &str = "_voquill._tcp.local.";
+
+/// How long the pairing dialog listens for receivers.
+pub const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(2);
+/// How long a delivery waits to locate its target once the last known
+/// address stopped answering.
+pub const RESOLVE_TIMEOUT: Duration = Duration::from_millis(1500);
+
+const TXT_DEVICE_ID: &str = "id";
+const TXT_NAME: &str = "name";
+const TXT_PLATFORM: &str = "platform";
+
+static DAEMON: OnceLock<ServiceDaemon> = OnceLock::new();
+/// The daemon keeps one browse per service type, so lookups take turns.
+static BROWSE_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+fn daemon() -> Result<&'static ServiceDaemon, String> {
+    if let Some(daemon) = DAEMON.get() {
+        return Ok(daemon);
+    }
+    let daemon = ServiceDaemon::new().map_err(|err| format!("Failed to start mDNS: {err}"))?;
+    Ok(DAEMON.get_or_init(|| daemon))
+}
+
+/// Announces this receiver on every interface. Returns the service name to
+/// pass to [`withdraw`].
+pub fn advertise(
+    device_id: &str,
+    device_name: &str,
+    platform: &str,
+    port: u16,
+) -> Result<String, String> {
+    let properties = [
+        (TXT_DEVICE_ID, device_id),
+        (TXT_NAME, device_name),
+        (TXT_PLATFORM, platform),
+    ];
+    let info = ServiceInfo::new(
+        SERVICE_TYPE,
+        device_id,
+        &format!("{device_id}.local."),
+        "",
+        port,
+        &properties[..],
+    )
+    .map_err(|err| format!("Invalid mDNS service: {err}"))?
+    .enable_addr_auto();
+    let fullname = info.get_fullname().to_string();
+
+    daemon()?
+        .register(info)
+        .map_err(|err| format!("Failed to advertise remote receiver: {err}"))?;
+    log::info!("Advertising remote receiver as {fullname}");
+    Ok(fullname)
+}
+
+pub fn withdraw(fullname: &str) {
+    let Ok(daemon) = daemon() else {
+        return;
+    };
+    if let Err(err) = daemon.unregister(fullname) {
+        log::warn!("Failed to withdraw remote receiver advertisement: {err}");
+    }
+}
+
+/// Lists the receivers that answer within `timeout`.
+pub async fn discover(timeout: Duration) -> Result<Vec<DiscoveredRemoteReceiver>, String> {
+    let receivers = browse(timeout, |_| false).await?;
+    Ok(receivers
+        .into_iter()
+        .map(|(receiver, _)| receiver)
+        .collect())
+}
+
+/// Looks up the addresses a paired receiver currently announces, best
+/// first. Empty when it does not answer in time.
+pub async fn resolve(device_id: &str) -> Vec<String> {
+    match browse(RESOLVE_TIMEOUT, |receiver| receiver.device_id == device_id).await {
+        Ok(receivers) => receivers
+            .into_iter()
+            .find(|(receiver, _)| receiver.device_id == device_id)
+            .map(|(_, addresses)| addresses)
+            .unwrap_or_default(),
+        Err(err) => {
+            log::warn!("Failed to look up remote receiver {device_id}: {err}");
+            Vec::new()
+        }
+    }
+}
+
+/// Collects resolved receivers, each with every address it can be dialed
+/// at, until `timeout` passes or `done` accepts one.
+async fn browse<F>(
+    timeout: Duration,
+    mut done: F,
+) -> Result<Vec<(DiscoveredRemoteReceiver, Vec<String>)>, String>
+where
+    F: FnMut(&DiscoveredRemoteReceiver) -> bool,
+{
+    let _guard = BROWSE_LOCK.lock().await;
+    let daemon = daemon()?;
+    let events = daemon
+        .browse(SERVICE_TYPE)
+        .map_err(|err| format!("Failed to browse for remote receivers: {err}"))?;
+
+    let deadline = Instant::now() + timeout;
+    let mut receivers: Vec<(DiscoveredRemoteReceiver, Vec<String>)> = Vec::new();
+    while let Ok(Ok(event)) = tokio::time::timeout_at(deadline, events.recv_async()).await {
+        let ServiceEvent::ServiceResolved(info) = event else {
+            continue;
+        };
+        let ips = info.get_addresses().iter().copied().collect::<Vec<_>>();
+        let addresses = dialable_addresses(&ips, info.get_port());
+        let Some(receiver) = receiver_from_txt(
+            |key| info.get_property_val_str(key).map(str::to_string),
+            &addresses,
+        ) else {
+            continue;
+        };
+
+        let finished = done(&receiver);
+        match receivers
+            .iter_mut()
+            .find(|(known, _)| known.device_id == receiver.device_id)
+        {
+            Some(known) => *known = (receiver, addresses),
+            None => receivers.push((receiver, addresses)),
+        }
+        if finished {
+            break;
+        }
+    }
+
+    if let Err(err) = daemon.stop_browse(SERVICE_TYPE) {
+        log::warn!("Failed to stop browsing for remote receivers: {err}");
+    }
+    receivers.sort_by(|(a, _), (b, _)| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    Ok(receivers)
+}
+
+fn receiver_from_txt(
+    property: impl Fn(&str) -> Option<String>,
+    addresses: &[String],
+) -> Option<DiscoveredRemoteReceiver> {
+    let device_id = property(TXT_DEVICE_ID).filter(|value| !value.trim().is_empty())?;
+    let name = property(TXT_NAME)
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| device_id.clone());
+    let platform = property(TXT_PLATFORM).unwrap_or_default();
+
+    Some(DiscoveredRemoteReceiver {
+        device_id,
+        name,
+        platform,
+        address: addresses.first()?.clone(),
+    })
+}
+
+/// Orders the addresses a sender may dial: IPv4 before IPv6, and never
+/// loopback or link-local IPv6, which would need a scope id. A host can
+/// announce addresses on networks the sender cannot reach, so senders try
+/// them in turn.
+fn dialable_addresses(addresses: &[IpAddr], port: u16) -> Vec<String> {
+    let mut usable = addresses
+        .iter()
+        .filter(|ip| !ip.is_loopback() && !ip.is_unspecified())
+        .filter(|ip| match ip {
+            IpAddr::V4(_) => true,
+            IpAddr::V6(v6) => (v6.segments()[0] & 0xffc0) != 0xfe80,
+        })
+        .collect::<Vec<_>>();
+    usable.sort_by_key(|ip| (ip.is_ipv6(), **ip));
+
+    usable
+        .into_iter()
+        .map(|ip| match ip {
+            IpAddr::V4(v4) => format!("{v4}:{port}"),
+            IpAddr::V6(v6) => format!("[{v6}]:{port}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ipv4_is_preferred_over_ipv6_and_loopback() {
+        let addresses = [
+            "127.0.0.1".parse().unwrap(),
+            "fe80::1".parse().unwrap(),
+            "2001:db8::5".parse().unwrap(),
+            "192.168.1.20".parse().unwrap(),
+            "10.0.0.4".parse().unwrap(),
+        ];
+        assert_eq!(
+            dialable_addresses(&addresses, 4100),
+            vec!["10.0.0.4:4100", "192.168.1.20:4100", "[2001:db8::5]:4100"]
+        );
+        assert_eq!(
+            dialable_addresses(&addresses[..3], 4100),
+            vec!["[2001:db8::5]:4100"]
+        );
+        assert!(dialable_addresses(&addresses[..2], 4100).is_empty());
+    }
+
+    #[test]
+    fn txt_records_need_a_device_id_and_an_address() {
+        let addresses = ["10.0.0.7:5000".to_string()];
+        let txt = |values: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                values
+                    .iter()
+                    .find(|(name, _)| *name == key)
+                    .map(|(_, value)| value.to_string())
+            }
+        };
+
+        assert_eq!(
+            receiver_from_txt(
+                txt(&[("id", "device-1"), ("name", "Desk"), ("platform", "linux")]),
+                &addresses
+            ),
+            Some(DiscoveredRemoteReceiver {
+                device_id: "device-1".to_string(),
+                name: "Desk".to_string(),
+                platform: "linux".to_string(),
+                address: "10.0.0.7:5000".to_string(),
+            })
+        );
+        assert_eq!(
+            receiver_from_txt(txt(&[("id", "device-1")]), &addresses).map(|r| r.name),
+            Some("device-1".to_string())
+        );
+        assert_eq!(
+            receiver_from_txt(txt(&[("name", "Desk")]), &addresses),
+            None
+        );
+        assert_eq!(receiver_from_txt(txt(&[("id", "device-1")]), &[]), None);
+    }
+}
@@ -0,0 +1,383 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::{Mutex, Notify};
+
+use crate::db::remote_outbox_queries;
+use crate::domain::{RemoteDeliveryOutcome, RemoteOutboxItem};
+use crate::state::{OptionKeyDatabase, RemoteReceiverState};
+use crate::system::{remote_sender, remote_stream};
+
+pub const EVT_REMOTE_OUTBOX_CHANGED: &str = "remote_outbox_changed";
+
+const RETRY_BASE: Duration = Duration::from_secs(2);
+const RETRY_MAX: Duration = Duration::from_secs(300);
+/// How long the worker sleeps when nothing is queued.
+const IDLE_INTERVAL: Duration = Duration::from_secs(60);
+const MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+static WAKE: Lazy<Notify> = Lazy::new(Notify::new);
+/// One lock per receiver, held while its queue is sent so every item goes
+/// out once and in order without an unreachable receiver holding up others.
+static FLUSH_LOCKS: Lazy<std::sync::Mutex<HashMap<String, Arc<Mutex<()>>>>> =
+    Lazy::new(Default::default);
+
+fn flush_lock(target_device_id: &str) -> Arc<Mutex<()>> {
+    FLUSH_LOCKS
+        .lock()
+        .unwrap()
+        .entry(target_device_id.to_string())
+        .or_default()
+        .clone()
+}
+
+/// Queues a final text for the target and tries to send the target's
+/// queue right away. Texts that cannot be sent stay queued for the worker.
+pub async fn deliver(
+    app: &AppHandle,
+    target_device_id: &str,
+    text: &str,
+    mode: &str,
+    utterance_id: Option<&str>,
+) -> Result<RemoteDeliveryOutcome, String> {
+    if let Some(utterance_id) = utterance_id {
+        remote_stream::finish(utterance_id).await;
+    }
+
+    let pool = app.state::<OptionKeyDatabase>().pool();
+    let target = crate::db::paired_remote_device_queries::fetch_paired_remote_device_by_id(
+        pool.clone(),
+        target_device_id,
+    )
+    .await
+    .map_err(|err| format!("Failed to load target device: {err}"))?
+    .ok_or_else(|| "Target device is not paired.".to_string())?;
+    if !target.trusted {
+        return Err("Target device is not trusted.".to_string());
+    }
+
+    let item = remote_outbox_queries::enqueue_remote_outbox_item(
+        pool,
+        &RemoteOutboxItem {
+            sequence: 0,
+            event_id: format!("event-{:032x}", rand::random::<u128>()),
+            target_device_id: target.id.clone(),
+            text: text.to_string(),
+            mode: mode.to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            utterance_id: utterance_id.map(str::to_string),
+            attempts: 0,
+            next_attempt_at: now_millis(),
+            last_error: None,
+            stuck: false,
+            needs_confirmation: false,
+        },
+    )
+    .await
+    .map_err(|err| format!("Failed to queue remote delivery: {err}"))?;
+    notify_changed(app);
+
+    // Sending now ignores any backoff, so a new dictation also flushes
+    // older texts that were waiting for the receiver to come back.
+    match flush_target(app, &target.id).await {
+        Ok(()) => Ok(RemoteDeliveryOutcome::Delivered),
+        Err(err) => {
+            log::warn!(
+                "Queued remote delivery {} for {}: {err}",
+                item.event_id,
+                target.id
+            );
+            Ok(RemoteDeliveryOutcome::Queued)
+        }
+    }
+}
+
+pub async fn list(app: &AppHandle) -> Result<Vec<RemoteOutboxItem>, String> {
+    let pool = app.state::<OptionKeyDatabase>().pool();
+    remote_outbox_queries::fetch_remote_outbox_items(pool)
+        .await
+        .map_err(|err| format!("Failed to load the remote outbox: {err}"))
+}
+
+/// Clears the backoff of the item's queue and sends it now, confirming any
+/// items that waited too long. Earlier items for the same receiver go first.
+pub async fn retry(app: &AppHandle, event_id: &str) -> Result<RemoteDeliveryOutcome, String> {
+    let pool = app.state::<OptionKeyDatabase>().pool();
+    let item = remote_outbox_queries::fetch_remote_outbox_item(pool.clone(), event_id)
+        .await
+        .map_err(|err| format!("Failed to load the remote outbox: {err}"))?
+        .ok_or_else(|| "Delivery is no longer queued.".to_string())?;
+
+    remote_outbox_queries::reset_remote_outbox_target(pool, &item.target_device_id, now_millis())
+        .await
+        .map_err(|err| format!("Failed to update the remote outbox: {err}"))?;
+    notify_changed(app);
+
+    match flush_target(app, &item.target_device_id).await {
+        Ok(()) => Ok(RemoteDeliveryOutcome::Delivered),
+        Err(err) => {
+            log::warn!("Retry of remote delivery {event_id} failed: {err}");
+            Ok(RemoteDeliveryOutcome::Queued)
+        }
+    }
+}
+
+pub async fn discard(app: &AppHandle, event_id: &str) -> Result<(), String> {
+    let pool = app.state::<OptionKeyDatabase>().pool();
+    let item = remote_outbox_queries::fetch_remote_outbox_item(pool.clone(), event_id)
+        .await
+        .map_err(|err| format!("Failed to load the remote outbox: {err}"))?
+        .ok_or_else(|| "Delivery is no longer queued.".to_string())?;
+    let lock = flush_lock(&item.target_device_id);
+    let _guard = lock.lock().await;
+    let removed = remote_outbox_queries::delete_remote_outbox_item(pool, event_id)
+        .await
+        .map_err(|err| format!("Failed to update the remote outbox: {err}"))?;
+    if !removed {
+        return Err("Delivery is no longer queued.".to_string());
+    }
+
+    log::info!("Discarded queued remote delivery {event_id}");
+    notify_changed(app);
+    // The next item for the receiver may already be due.
+    WAKE.notify_one();
+    Ok(())
+}
+
+/// Runs the retry worker for the lifetime of the app.
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let wait = run_due(&app).await;
+            tokio::select! {
+                _ = WAKE.notified() => {}
+                _ = tokio::time::sleep(wait) => {}
+            }
+        }
+    });
+}
+
+async fn run_due(app: &AppHandle) -> Duration {
+    let Ok(items) = list(app).await else {
+        return IDLE_INTERVAL;
+    };
+    for target_device_id in due_targets(&items, now_millis()) {
+        if let Err(err) = flush_target(app, &target_device_id).await {
+            log::warn!("Remote delivery to {target_device_id} will be retried: {err}");
+        }
+    }
+
+    match list(app).await {
+        Ok(items) => next_wait(&items, now_millis()),
+        Err(err) => {
+            log::error!("{err}");
+            IDLE_INTERVAL
+        }
+    }
+}
+
+/// Sends everything queued for the target over one session, stopping at
+/// the first failure or unconfirmed stale item so later texts never
+/// overtake it.
+async fn flush_target(app: &AppHandle, target_device_id: &str) -> Result<(), String> {
+    let lock = flush_lock(target_device_id);
+    let _guard = lock.lock().await;
+    let pool = app.state::<OptionKeyDatabase>().pool();
+    let items =
+        remote_outbox_queries::fetch_remote_outbox_items_for_target(pool.clone(), target_device_id)
+            .await
+            .map_err(|err| format!("Failed to load the remote outbox: {err}"))?;
+    let Some(head) = items.first() else {
+        return Ok(());
+    };
+    if head.needs_confirmation {
+        return Err(awaiting_confirmation(head));
+    }
+
+    let sender_state = app.state::<RemoteReceiverState>().inner().clone();
+    let mut failed: Option<(&RemoteOutboxItem, String)> = None;
+    let mut held: Option<&RemoteOutboxItem> = None;
+    match remote_sender::open_session(pool.clone(), sender_state, target_device_id).await {
+        Ok(mut session) => {
+            for item in &items {
+                if item.needs_confirmation {
+                    held = Some(item);
+                    break;
+                }
+                if let Err(err) = session
+                    .send_final_text(
+                        &item.event_id,
+                        item.sequence as u64,
+                        &item.text,
+                        &item.mode,
+                        &item.created_at,
+                        item.utterance_id.as_deref(),
+                    )
+                    .await
+                {
+                    failed = Some((item, err));
+                    break;
+                }
+
+                // The receiver ignores a resent event id, so a failed delete
+                // only costs a duplicate send.
+                if let Err(err) =
+                    remote_outbox_queries::delete_remote_outbox_item(pool.clone(), &item.event_id)
+                        .await
+                {
+                    log::error!("Failed to clear delivered item {}: {err}", item.event_id);
+                }
+            }
+        }
+        Err(err) => failed = Some((head, err)),
+    }
+
+    let result = match failed {
+        Some((item, err)) => {
+            let next_attempt_at = now_millis() + retry_delay(item.attempts + 1).as_millis() as i64;
+            if let Err(db_err) = remote_outbox_queries::record_remote_outbox_failure(
+                pool,
+                &item.event_id,
+                &err,
+                next_attempt_at,
+            )
+            .await
+            {
+                log::error!("Failed to record remote delivery failure: {db_err}");
+            }
+            Err(err)
+        }
+        None => match held {
+            Some(item) => Err(awaiting_confirmation(item)),
+            None => Ok(()),
+        },
+    };
+    notify_changed(app);
+    result
+}
+
+fn awaiting_confirmation(item: &RemoteOutboxItem) -> String {
+    format!(
+        "Text queued at {} waits for confirmation before it is sent.",
+        item.created_at
+    )
+}
+
+fn notify_changed(app: &AppHandle) {
+    if let Err(err) = app.emit(EVT_REMOTE_OUTBOX_CHANGED, ()) {
+        log::warn!("Failed to emit remote outbox change: {err}");
+    }
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+fn retry_delay(attempts: i64) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    RETRY_BASE.saturating_mul(1 << exponent).min(RETRY_MAX)
+}
+
+/// The oldest item of each receiver's queue; only it decides when the
+/// queue is retried, and a head waiting for confirmation holds the queue
+/// until the user acts. `items` must be sorted by sequence.
+fn queue_heads(items: &[RemoteOutboxItem]) -> Vec<&RemoteOutboxItem> {
+    let mut heads: Vec<&RemoteOutboxItem> = Vec::new();
+    for item in items {
+        if !heads
+            .iter()
+            .any(|head| head.target_device_id == item.target_device_id)
+        {
+            heads.push(item);
+        }
+    }
+    heads
+}
+
+fn due_targets(items: &[RemoteOutboxItem], now: i64) -> Vec<String> {
+    queue_heads(items)
+        .into_iter()
+        .filter(|head| !head.needs_confirmation && head.next_attempt_at <= now)
+        .map(|head| head.target_device_id.clone())
+        .collect()
+}
+
+fn next_wait(items: &[RemoteOutboxItem], now: i64) -> Duration {
+    queue_heads(items)
+        .into_iter()
+        .filter(|head| !head.needs_confirmation)
+        .map(|head| Duration::from_millis(head.next_attempt_at.saturating_sub(now).max(0) as u64))
+        .min()
+        .map(|wait| wait.clamp(MIN_INTERVAL, IDLE_INTERVAL))
+        .unwrap_or(IDLE_INTERVAL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(sequence: i64, target: &str, next_attempt_at: i64) -> RemoteOutboxItem {
+        RemoteOutboxItem {
+            sequence,
+            event_id: format!("event-{sequence}"),
+            target_device_id: target.to_string(),
+            text: "hello".to_string(),
+            mode: "dictation".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            utterance_id: None,
+            attempts: 0,
+            next_attempt_at,
+            last_error: None,
+            stuck: false,
+            needs_confirmation: false,
+        }
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap() {
+        assert_eq!(retry_delay(1), Duration::from_secs(2));
+        assert_eq!(retry_delay(2), Duration::from_secs(4));
+        assert_eq!(retry_delay(5), Duration::from_secs(32));
+        assert_eq!(retry_delay(40), RETRY_MAX);
+    }
+
+    #[test]
+    fn only_the_oldest_item_of_a_queue_decides_when_it_is_due() {
+        let items = [
+            item(1, "laptop", 5_000),
+            item(2, "desktop", 1_000),
+            item(3, "laptop", 0),
+        ];
+
+        assert_eq!(due_targets(&items, 2_000), vec!["desktop".to_string()]);
+        assert_eq!(
+            due_targets(&items, 5_000),
+            vec!["laptop".to_string(), "desktop".to_string()]
+        );
+        assert_eq!(next_wait(&items[..1], 2_000), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn queues_waiting_for_confirmation_are_never_due() {
+        let mut held = item(1, "laptop", 0);
+        held.needs_confirmation = true;
+        let items = [held, item(2, "laptop", 0), item(3, "desktop", 4_000)];
+
+        assert_eq!(due_targets(&items, 5_000), vec!["desktop".to_string()]);
+        assert_eq!(next_wait(&items, 2_000), Duration::from_secs(2));
+        assert_eq!(next_wait(&items[..2], 0), IDLE_INTERVAL);
+    }
+
+    #[test]
+    fn next_wait_stays_between_the_bounds() {
+        assert_eq!(next_wait(&[], 0), IDLE_INTERVAL);
+        assert_eq!(next_wait(&[item(1, "laptop", 0)], 10_000), MIN_INTERVAL);
+        assert_eq!(
+            next_wait(&[item(1, "laptop", 10_000_000)], 0),
+            IDLE_INTERVAL
+        );
+    }
+}
@@ -4,11 +4,13 @@ use sqlx::SqlitePool;
 use std::net::UdpSocket;
 use tauri::async_runtime;
 use tauri::{AppHandle, Emitter};
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::watch;
 
 use crate::state::{RemoteReceiverState, RemoteReceiverStatus};
+use crate::system::remote_channel::{self, ChannelPurpose};
+use crate::system::remote_discovery;
+use crate::system::remote_stream::{self, EditOp, CAPABILITY_PARTIAL_TEXT};
 
 pub const EVT_REMOTE_FINAL_TEXT_RECEIVED: &str = "remote_final_text_received";
 
@@ -20,12 +22,10 @@ enum IncomingEnvelope {
         sender_device_id: String,
         sender_device_name: String,
         sender_platform: String,
-        pairing_code: String,
     },
     SessionHello {
         session_id: String,
         sender_device_id: String,
-        auth_token: String,
     },
     FinalText {
         session_id: String,
@@ -34,6 +34,20 @@ enum IncomingEnvelope {
         text: String,
         mode: String,
         created_at: String,
+        #[serde(default)]
+        utterance_id: Option<String>,
+    },
+    PartialText {
+        session_id: String,
+        utterance_id: String,
+        sequence: u64,
+        text: String,
+    },
+    Revise {
+        session_id: String,
+        utterance_id: String,
+        sequence: u64,
+        ops: Vec<EditOp>,
     },
     Heartbeat {
         session_id: String,
@@ -54,6 +68,7 @@ enum OutgoingEnvelope {
     SessionAck {
         session_id: String,
         receiver_device_id: String,
+        capabilities: Vec<String>,
     },
     DeliveryAck {
         session_id: String,
@@ -78,6 +93,8 @@ pub struct RemoteFinalTextReceivedPayload {
     pub text: String,
     pub mode: String,
     pub created_at: String,
+    /// Set when streamed partials already typed the text at the cursor.
+    pub already_inserted: bool,
 }
 
 pub async fn start(
@@ -102,6 +119,17 @@ pub async fn start(
     let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
     state.start(connect_address, local_addr.port(), shutdown_tx);
 
+    let status = state.status();
+    match remote_discovery::advertise(
+        &status.device_id,
+        &status.device_name,
+        &status.device_platform,
+        local_addr.port(),
+    ) {
+        Ok(fullname) => state.set_advertisement(fullname),
+        Err(err) => log::warn!("Remote receiver is not discoverable: {err}"),
+    }
+
     let state_for_task = state.clone();
     async_runtime::spawn(async move {
         loop {
@@ -146,6 +174,9 @@ fn detect_connect_address() -> Option<String> {
 }
 
 pub fn stop(state: RemoteReceiverState) {
+    if let Some(fullname) = state.take_advertisement() {
+        remote_discovery::withdraw(&fullname);
+    }
     state.stop();
 }
 
@@ -161,17 +192,73 @@ async fn handle_connection(
     state: RemoteReceiverState,
     app: AppHandle,
 ) -> Result<(), String> {
-    let (reader, mut writer) = stream.into_split();
-    let mut lines = BufReader::new(reader).lines();
-    let mut authenticated_sender: Option<String> = None;
+    let (reader, writer) = stream.into_split();
+    let pending = remote_channel::read_hello(reader, writer).await?;
+    let purpose = pending.purpose();
+    let hello_sender_id = pending.sender_device_id().to_string();
+
+    let mut channel = match purpose {
+        ChannelPurpose::Pairing => {
+            let pairing_code = state.status().pairing_code;
+            pending.accept(pairing_code.as_bytes()).await?
+        }
+        ChannelPurpose::Session => {
+            let device = crate::db::paired_remote_device_queries::fetch_paired_remote_device_by_id(
+                pool.clone(),
+                &hello_sender_id,
+            )
+            .await
+            .map_err(|err| format!("Failed to validate sender device: {err}"))?;
+
+            match device {
+                Some(device) if device.trusted => {
+                    pending.accept(device.shared_secret.as_bytes()).await?
+                }
+                device => {
+                    let (code, message) = match device {
+                        None => ("unknown_sender", "Sender is not paired."),
+                        Some(_) => ("unauthorized", "Sender authentication failed."),
+                    };
+                    state.record_error(
+                        Some(hello_sender_id.clone()),
+                        None,
+                        message.to_string(),
+                        None,
+                        None,
+                        None,
+                    );
+                    return pending.reject(code, message).await;
+                }
+            }
+        }
+    };
 
-    while let Some(line) = lines
-        .next_line()
-        .await
-        .map_err(|err| format!("Failed to read receiver message: {err}"))?
-    {
-        let envelope: IncomingEnvelope = serde_json::from_str(&line)
-            .map_err(|err| format!("Failed to parse receiver message: {err}"))?;
+    let mut authenticated_sender: Option<String> = None;
+    let mut last_sequence = 0u64;
+
+    loop {
+        let envelope = match channel.recv::<IncomingEnvelope>().await {
+            Ok(Some(envelope)) => envelope,
+            Ok(None) => break,
+            // Nothing has opened yet, so the sender does not hold the key.
+            Err(err) if !channel.is_authenticated() => {
+                let (code, message) = match purpose {
+                    ChannelPurpose::Pairing => ("invalid_pairing_code", "Pairing code is invalid."),
+                    ChannelPurpose::Session => ("unauthorized", "Sender authentication failed."),
+                };
+                state.record_error(
+                    Some(hello_sender_id.clone()),
+                    None,
+                    message.to_string(),
+                    None,
+                    None,
+                    None,
+                );
+                channel.reject(code, message).await?;
+                return Err(err);
+            }
+            Err(err) => return Err(err),
+        };
 
         match envelope {
             IncomingEnvelope::PairingRequest {
@@ -179,28 +266,17 @@ async fn handle_connection(
                 sender_device_id,
                 sender_device_name,
                 sender_platform,
-                pairing_code,
             } => {
-                if pairing_code != state.status().pairing_code {
-                    state.record_error(
-                        Some(sender_device_id.clone()),
-                        None,
-                        "Pairing code is invalid.".to_string(),
-                        None,
-                        None,
-                        None,
-                    );
-                    write_message(
-                        &mut writer,
-                        &OutgoingEnvelope::DeliveryError {
+                if purpose != ChannelPurpose::Pairing || sender_device_id != hello_sender_id {
+                    channel
+                        .send(&OutgoingEnvelope::DeliveryError {
                             session_id: String::new(),
                             event_id: String::new(),
                             sequence: 0,
-                            code: "invalid_pairing_code".to_string(),
-                            message: "Pairing code is invalid.".to_string(),
-                        },
-                    )
-                    .await?;
+                            code: "unexpected_message".to_string(),
+                            message: "Pairing requests need a pairing channel.".to_string(),
+                        })
+                        .await?;
                     continue;
                 }
 
@@ -226,55 +302,21 @@ async fn handle_connection(
 
                 state.rotate_pairing_code();
                 let receiver_status = state.status();
-                write_message(
-                    &mut writer,
-                    &OutgoingEnvelope::PairingAccept {
+                channel
+                    .send(&OutgoingEnvelope::PairingAccept {
                         request_id,
                         receiver_device_id: receiver_status.device_id,
                         receiver_device_name: receiver_status.device_name,
                         receiver_platform: receiver_status.device_platform,
                         shared_secret,
-                    },
-                )
-                .await?;
+                    })
+                    .await?;
             }
             IncomingEnvelope::SessionHello {
                 session_id,
                 sender_device_id,
-                auth_token,
             } => {
-                let device =
-                    crate::db::paired_remote_device_queries::fetch_paired_remote_device_by_id(
-                        pool.clone(),
-                        &sender_device_id,
-                    )
-                    .await
-                    .map_err(|err| format!("Failed to validate sender device: {err}"))?;
-
-                let Some(device) = device else {
-                    state.record_error(
-                        Some(sender_device_id.clone()),
-                        None,
-                        "Sender is not paired.".to_string(),
-                        None,
-                        None,
-                        None,
-                    );
-                    write_message(
-                        &mut writer,
-                        &OutgoingEnvelope::DeliveryError {
-                            session_id,
-                            event_id: String::new(),
-                            sequence: 0,
-                            code: "unknown_sender".to_string(),
-                            message: "Sender is not paired.".to_string(),
-                        },
-                    )
-                    .await?;
-                    continue;
-                };
-
-                if !device.trusted || device.shared_secret != auth_token {
+                if purpose != ChannelPurpose::Session || sender_device_id != hello_sender_id {
                     state.record_error(
                         Some(sender_device_id.clone()),
                         None,
@@ -283,29 +325,26 @@ async fn handle_connection(
                         None,
                         None,
                     );
-                    write_message(
-                        &mut writer,
-                        &OutgoingEnvelope::DeliveryError {
+                    channel
+                        .send(&OutgoingEnvelope::DeliveryError {
                             session_id,
                             event_id: String::new(),
                             sequence: 0,
                             code: "unauthorized".to_string(),
                             message: "Sender authentication failed.".to_string(),
-                        },
-                    )
-                    .await?;
+                        })
+                        .await?;
                     continue;
                 }
 
                 authenticated_sender = Some(sender_device_id);
-                write_message(
-                    &mut writer,
-                    &OutgoingEnvelope::SessionAck {
+                channel
+                    .send(&OutgoingEnvelope::SessionAck {
                         session_id,
                         receiver_device_id: state.status().device_id,
-                    },
-                )
-                .await?;
+                        capabilities: receiver_capabilities(),
+                    })
+                    .await?;
             }
             IncomingEnvelope::FinalText {
                 session_id,
@@ -314,6 +353,7 @@ async fn handle_connection(
                 text,
                 mode,
                 created_at,
+                utterance_id,
             } => {
                 let Some(sender_device_id) = authenticated_sender.clone() else {
                     state.record_error(
@@ -324,22 +364,58 @@ async fn handle_connection(
                         None,
                         None,
                     );
-                    write_message(
-                        &mut writer,
-                        &OutgoingEnvelope::DeliveryError {
+                    channel
+                        .send(&OutgoingEnvelope::DeliveryError {
                             session_id,
                             event_id,
                             sequence,
                             code: "unauthorized".to_string(),
                             message: "No authenticated sender session.".to_string(),
-                        },
-                    )
-                    .await?;
+                        })
+                        .await?;
                     continue;
                 };
 
+                if sequence <= last_sequence {
+                    state.record_error(
+                        Some(sender_device_id),
+                        Some(event_id.clone()),
+                        "Remote delivery was replayed.".to_string(),
+                        None,
+                        None,
+                        None,
+                    );
+                    channel
+                        .send(&OutgoingEnvelope::DeliveryError {
+                            session_id,
+                            event_id,
+                            sequence,
+                            code: "replayed_sequence".to_string(),
+                            message: format!(
+                                "Sequence {sequence} was already delivered in this session."
+                            ),
+                        })
+                        .await?;
+                    continue;
+                }
+                last_sequence = sequence;
+
+                if already_delivered(&pool, &sender_device_id, &event_id).await {
+                    log::info!("Acknowledging duplicate remote delivery {event_id}");
+                    channel
+                        .send(&OutgoingEnvelope::DeliveryAck {
+                            session_id,
+                            event_id,
+                            sequence,
+                            delivered_at: chrono::Utc::now().to_rfc3339(),
+                        })
+                        .await?;
+                    continue;
+                }
+
                 if mode == "test" {
                     let delivered_at = chrono::Utc::now().to_rfc3339();
+                    remember_delivery(&pool, &sender_device_id, &event_id).await;
                     state.record_delivery(
                         Some(sender_device_id),
                         Some(event_id.clone()),
@@ -348,19 +424,52 @@ async fn handle_connection(
                         None,
                         None,
                     );
-                    write_message(
-                        &mut writer,
-                        &OutgoingEnvelope::DeliveryAck {
+                    channel
+                        .send(&OutgoingEnvelope::DeliveryAck {
                             session_id,
                             event_id,
                             sequence,
                             delivered_at,
-                        },
-                    )
-                    .await?;
+                        })
+                        .await?;
                     continue;
                 }
 
+                let streamed = utterance_id.and_then(|utterance_id| {
+                    state
+                        .take_utterance_text(&sender_device_id, &utterance_id)
+                        .map(|typed| (utterance_id, typed))
+                });
+                let already_inserted = streamed.is_some();
+                if let Some((utterance_id, typed)) = streamed {
+                    // Partials typed most of the text already; only the
+                    // difference to the final text is typed now.
+                    let final_text = format!("{} ", text.trim());
+                    if let Err(message) =
+                        type_edits(remote_stream::edit_ops(&typed, &final_text)).await
+                    {
+                        state.set_utterance_text(&sender_device_id, &utterance_id, typed);
+                        state.record_error(
+                            Some(sender_device_id),
+                            Some(event_id.clone()),
+                            message.clone(),
+                            None,
+                            None,
+                            None,
+                        );
+                        channel
+                            .send(&OutgoingEnvelope::DeliveryError {
+                                session_id,
+                                event_id,
+                                sequence,
+                                code: "input_failed".to_string(),
+                                message,
+                            })
+                            .await?;
+                        continue;
+                    }
+                }
+
                 let target_info = current_target_info();
                 let target_editable = current_target_editable_status();
                 let payload = RemoteFinalTextReceivedPayload {
@@ -369,11 +478,13 @@ async fn handle_connection(
                     text: text.clone(),
                     mode,
                     created_at,
+                    already_inserted,
                 };
 
                 match app.emit(EVT_REMOTE_FINAL_TEXT_RECEIVED, payload) {
                     Ok(()) => {
                         let delivered_at = chrono::Utc::now().to_rfc3339();
+                        remember_delivery(&pool, &sender_device_id, &event_id).await;
                         state.record_delivery(
                             Some(sender_device_id),
                             Some(event_id.clone()),
@@ -382,16 +493,14 @@ async fn handle_connection(
                             target_info.title.clone(),
                             target_editable,
                         );
-                        write_message(
-                            &mut writer,
-                            &OutgoingEnvelope::DeliveryAck {
+                        channel
+                            .send(&OutgoingEnvelope::DeliveryAck {
                                 session_id,
                                 event_id,
                                 sequence,
                                 delivered_at,
-                            },
-                        )
-                        .await?;
+                            })
+                            .await?;
                     }
                     Err(err) => {
                         let message = format!("Failed to emit remote transcript event: {err}");
@@ -403,32 +512,65 @@ async fn handle_connection(
                             target_info.title.clone(),
                             target_editable,
                         );
-                        write_message(
-                            &mut writer,
-                            &OutgoingEnvelope::DeliveryError {
+                        channel
+                            .send(&OutgoingEnvelope::DeliveryError {
                                 session_id,
                                 event_id,
                                 sequence,
                                 code: "event_emit_failed".to_string(),
                                 message,
-                            },
-                        )
-                        .await?;
+                            })
+                            .await?;
                     }
                 }
             }
+            IncomingEnvelope::PartialText {
+                session_id,
+                utterance_id,
+                sequence,
+                text,
+            } => {
+                let reply = apply_stream_edit(
+                    &state,
+                    authenticated_sender.as_deref(),
+                    &mut last_sequence,
+                    session_id,
+                    utterance_id,
+                    sequence,
+                    vec![EditOp::Insert { text }],
+                )
+                .await;
+                channel.send(&reply).await?;
+            }
+            IncomingEnvelope::Revise {
+                session_id,
+                utterance_id,
+                sequence,
+                ops,
+            } => {
+                let reply = apply_stream_edit(
+                    &state,
+                    authenticated_sender.as_deref(),
+                    &mut last_sequence,
+                    session_id,
+                    utterance_id,
+                    sequence,
+                    ops,
+                )
+                .await;
+                channel.send(&reply).await?;
+            }
             IncomingEnvelope::Heartbeat {
                 session_id,
                 sent_at: _sent_at,
             } => {
-                write_message(
-                    &mut writer,
-                    &OutgoingEnvelope::SessionAck {
+                channel
+                    .send(&OutgoingEnvelope::SessionAck {
                         session_id,
                         receiver_device_id: state.status().device_id,
-                    },
-                )
-                .await?;
+                        capabilities: receiver_capabilities(),
+                    })
+                    .await?;
             }
         }
     }
@@ -436,6 +578,119 @@ async fn handle_connection(
     Ok(())
 }
 
+/// Whether the event was delivered before, possibly before a restart. A
+/// failed lookup counts as new, so the text is typed rather than lost.
+async fn already_delivered(pool: &SqlitePool, sender_device_id: &str, event_id: &str) -> bool {
+    crate::db::remote_delivered_event_queries::has_delivered_event(
+        pool.clone(),
+        sender_device_id,
+        event_id,
+    )
+    .await
+    .unwrap_or_else(|err| {
+        log::error!("Failed to look up remote delivery {event_id}: {err}");
+        false
+    })
+}
+
+async fn remember_delivery(pool: &SqlitePool, sender_device_id: &str, event_id: &str) {
+    if let Err(err) = crate::db::remote_delivered_event_queries::record_delivered_event(
+        pool.clone(),
+        sender_device_id,
+        event_id,
+        chrono::Utc::now().timestamp_millis(),
+    )
+    .await
+    {
+        log::error!("Failed to record remote delivery {event_id}: {err}");
+    }
+}
+
+fn receiver_capabilities() -> Vec<String> {
+    vec![CAPABILITY_PARTIAL_TEXT.to_string()]
+}
+
+/// Types a streamed update for an utterance at the cursor and returns the
+/// reply. Updates are acknowledged under the utterance id.
+async fn apply_stream_edit(
+    state: &RemoteReceiverState,
+    authenticated_sender: Option<&str>,
+    last_sequence: &mut u64,
+    session_id: String,
+    utterance_id: String,
+    sequence: u64,
+    ops: Vec<EditOp>,
+) -> OutgoingEnvelope {
+    let Some(sender_device_id) = authenticated_sender else {
+        return OutgoingEnvelope::DeliveryError {
+            session_id,
+            event_id: utterance_id,
+            sequence,
+            code: "unauthorized".to_string(),
+            message: "No authenticated sender session.".to_string(),
+        };
+    };
+    if sequence <= *last_sequence {
+        return OutgoingEnvelope::DeliveryError {
+            session_id,
+            event_id: utterance_id,
+            sequence,
+            code: "replayed_sequence".to_string(),
+            message: format!("Sequence {sequence} was already delivered in this session."),
+        };
+    }
+    *last_sequence = sequence;
+
+    // Replaying the difference keeps erases inside the utterance.
+    let typed = state
+        .utterance_text(sender_device_id, &utterance_id)
+        .unwrap_or_default();
+    let updated = remote_stream::apply_ops(&typed, &ops);
+    if let Err(message) = type_edits(remote_stream::edit_ops(&typed, &updated)).await {
+        state.record_error(
+            Some(sender_device_id.to_string()),
+            Some(utterance_id.clone()),
+            message.clone(),
+            None,
+            None,
+            None,
+        );
+        return OutgoingEnvelope::DeliveryError {
+            session_id,
+            event_id: utterance_id,
+            sequence,
+            code: "input_failed".to_string(),
+            message,
+        };
+    }
+
+    state.set_utterance_text(sender_device_id, &utterance_id, updated);
+    OutgoingEnvelope::DeliveryAck {
+        session_id,
+        event_id: utterance_id,
+        sequence,
+        delivered_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+async fn type_edits(ops: Vec<EditOp>) -> Result<(), String> {
+    if ops.is_empty() {
+        return Ok(());
+    }
+
+    async_runtime::spawn_blocking(move || -> Result<(), String> {
+        for op in ops {
+            match op {
+                EditOp::Erase { count } => crate::platform::input::erase_before_cursor(count)?,
+                EditOp::Insert { text } => crate::platform::input::type_text_at_cursor(&text)?,
+            }
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|err| format!("Typing task failed: {err}"))?
+}
+
 #[cfg(target_os = "windows")]
 fn current_target_info() -> crate::platform::windows::input::WindowTargetInfo {
     crate::platform::windows::input::get_foreground_window_target_info()
@@ -464,20 +719,3 @@ struct FallbackWindowTargetInfo {
     class_name: Option<String>,
     title: Option<String>,
 }
-
-async fn write_message(
-    writer: &mut tokio::net::tcp::OwnedWriteHalf,
-    message: &OutgoingEnvelope,
-) -> Result<(), String> {
-    let json = serde_json::to_string(message)
-        .map_err(|err| format!("Failed to serialize receiver message: {err}"))?;
-    writer
-        .write_all(json.as_bytes())
-        .await
-        .map_err(|err| format!("Failed to write receiver message: {err}"))?;
-    writer
-        .write_all(b"\n")
-        .await
-        .map_err(|err| format!("Failed to finalize receiver message: {err}"))?;
-    Ok(())
-}
@@ -2,10 +2,15 @@ use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::net::TcpStream;
 
 use crate::state::RemoteReceiverState;
+use crate::system::remote_channel::{self, ChannelPurpose, SecureChannel};
+use crate::system::remote_discovery;
+use crate::system::remote_stream::EditOp;
+
+const REMOTE_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -15,12 +20,10 @@ enum OutgoingEnvelope {
         sender_device_id: String,
         sender_device_name: String,
         sender_platform: String,
-        pairing_code: String,
     },
     SessionHello {
         session_id: String,
         sender_device_id: String,
-        auth_token: String,
     },
     FinalText {
         session_id: String,
@@ -29,6 +32,20 @@ enum OutgoingEnvelope {
         text: String,
         mode: String,
         created_at: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        utterance_id: Option<String>,
+    },
+    PartialText {
+        session_id: String,
+        utterance_id: String,
+        sequence: u64,
+        text: String,
+    },
+    Revise {
+        session_id: String,
+        utterance_id: String,
+        sequence: u64,
+        ops: Vec<EditOp>,
     },
 }
 
@@ -45,6 +62,8 @@ enum IncomingEnvelope {
     SessionAck {
         session_id: String,
         receiver_device_id: String,
+        #[serde(default)]
+        capabilities: Vec<String>,
     },
     DeliveryAck {
         session_id: String,
@@ -72,28 +91,25 @@ pub async fn pair_with_receiver(
 ) -> Result<crate::domain::PairedRemoteDevice, String> {
     let sender = sender_state.status();
     let request_id = generate_id("pair");
-    let stream = tokio::time::timeout(Duration::from_secs(5), TcpStream::connect(receiver_address))
-        .await
-        .map_err(|_| format!("Timed out connecting to remote receiver at {receiver_address}"))?
-        .map_err(|err| {
-            format!("Failed to connect to remote receiver at {receiver_address}: {err}")
-        })?;
-    let (reader, mut writer) = stream.into_split();
-    let mut lines = BufReader::new(reader).lines();
-
-    write_message(
-        &mut writer,
-        &OutgoingEnvelope::PairingRequest {
+    // The pairing code keys the channel, so it never crosses the network.
+    let mut channel = open_channel(
+        receiver_address,
+        ChannelPurpose::Pairing,
+        &sender.device_id,
+        pairing_code.as_bytes(),
+    )
+    .await?;
+
+    channel
+        .send(&OutgoingEnvelope::PairingRequest {
             request_id: request_id.clone(),
             sender_device_id: sender.device_id.clone(),
             sender_device_name: sender.device_name.clone(),
             sender_platform: sender.device_platform.clone(),
-            pairing_code: pairing_code.to_string(),
-        },
-    )
-    .await?;
+        })
+        .await?;
 
-    match read_message(&mut lines).await? {
+    match read_message(&mut channel).await? {
         IncomingEnvelope::PairingAccept {
             request_id: ack_request_id,
             receiver_device_id: ack_receiver_device_id,
@@ -147,19 +163,21 @@ pub async fn pair_with_receiver(
     }
 }
 
-pub async fn deliver_final_text(
+/// An acknowledged session with a paired receiver. Final texts sent over
+/// it must use increasing sequence numbers.
+pub struct RemoteSession {
+    channel: SecureChannel<OwnedReadHalf, OwnedWriteHalf>,
+    session_id: String,
+    capabilities: Vec<String>,
+}
+
+pub async fn open_session(
     pool: SqlitePool,
     sender_state: RemoteReceiverState,
     target_device_id: &str,
-    text: &str,
-    mode: &str,
-) -> Result<(), String> {
-    if text.trim().is_empty() {
-        return Ok(());
-    }
-
+) -> Result<RemoteSession, String> {
     let target = crate::db::paired_remote_device_queries::fetch_paired_remote_device_by_id(
-        pool,
+        pool.clone(),
         target_device_id,
     )
     .await
@@ -170,36 +188,87 @@ pub async fn deliver_final_text(
         return Err("Target device is not trusted.".to_string());
     }
 
-    let address = target
+    let sender_device_id = sender_state.status().device_id;
+
+    // The last known address usually still answers, so the network is only
+    // browsed when it does not.
+    let cached = target
         .last_known_address
         .clone()
-        .filter(|value| !value.trim().is_empty())
-        .ok_or_else(|| "Target device does not have a known receiver address.".to_string())?;
+        .filter(|value| !value.trim().is_empty());
+    let mut last_error = None;
+    if let Some(address) = cached.as_deref() {
+        match start_session(address, &target, &sender_device_id).await {
+            Ok(session) => return Ok(session),
+            Err(err) => {
+                log::info!(
+                    "Remote receiver {} did not answer at {address}: {err}",
+                    target.id
+                );
+                last_error = Some(err);
+            }
+        }
+    }
 
-    let sender = sender_state.status();
-    let session_id = generate_id("session");
-    let event_id = generate_id("event");
-    let stream = tokio::time::timeout(Duration::from_secs(5), TcpStream::connect(address.as_str()))
-        .await
-        .map_err(|_| format!("Timed out connecting to remote receiver at {address}"))?
-        .map_err(|err| format!("Failed to connect to remote receiver at {address}: {err}"))?;
-    let (reader, mut writer) = stream.into_split();
-    let mut lines = BufReader::new(reader).lines();
+    for address in remote_discovery::resolve(&target.id).await {
+        if cached.as_deref() == Some(address.as_str()) {
+            continue;
+        }
+        match start_session(&address, &target, &sender_device_id).await {
+            Ok(session) => {
+                log::info!("Remote receiver {} moved to {address}", target.id);
+                if let Err(err) =
+                    crate::db::paired_remote_device_queries::update_last_known_address(
+                        pool, &target.id, &address,
+                    )
+                    .await
+                {
+                    log::warn!("Failed to save the receiver address: {err}");
+                }
+                return Ok(session);
+            }
+            Err(err) => {
+                log::info!(
+                    "Remote receiver {} did not answer at {address}: {err}",
+                    target.id
+                );
+                last_error = Some(err);
+            }
+        }
+    }
 
-    write_message(
-        &mut writer,
-        &OutgoingEnvelope::SessionHello {
-            session_id: session_id.clone(),
-            sender_device_id: sender.device_id,
-            auth_token: target.shared_secret.clone(),
-        },
+    Err(last_error
+        .unwrap_or_else(|| "Target device does not have a known receiver address.".to_string()))
+}
+
+/// Opens a session at `address` and checks that the paired receiver is the
+/// one answering.
+async fn start_session(
+    address: &str,
+    target: &crate::domain::PairedRemoteDevice,
+    sender_device_id: &str,
+) -> Result<RemoteSession, String> {
+    let session_id = generate_id("session");
+    let mut channel = open_channel(
+        address,
+        ChannelPurpose::Session,
+        sender_device_id,
+        target.shared_secret.as_bytes(),
     )
     .await?;
 
-    match read_message(&mut lines).await? {
+    channel
+        .send(&OutgoingEnvelope::SessionHello {
+            session_id: session_id.clone(),
+            sender_device_id: sender_device_id.to_string(),
+        })
+        .await?;
+
+    let capabilities = match read_message(&mut channel).await? {
         IncomingEnvelope::SessionAck {
             session_id: ack_session_id,
             receiver_device_id,
+            capabilities,
         } => {
             if ack_session_id != session_id {
                 return Err("Remote receiver acknowledged the wrong session.".to_string());
@@ -207,6 +276,7 @@ pub async fn deliver_final_text(
             if receiver_device_id != target.id {
                 return Err("Connected receiver does not match the selected device.".to_string());
             }
+            capabilities
         }
         IncomingEnvelope::DeliveryError { message, .. } => {
             return Err(message);
@@ -217,80 +287,188 @@ pub async fn deliver_final_text(
         IncomingEnvelope::PairingAccept { .. } => {
             return Err("Remote receiver returned an unexpected pairing accept.".to_string());
         }
+    };
+
+    Ok(RemoteSession {
+        channel,
+        session_id,
+        capabilities,
+    })
+}
+
+impl RemoteSession {
+    pub fn supports(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|known| known == capability)
     }
 
-    write_message(
-        &mut writer,
-        &OutgoingEnvelope::FinalText {
-            session_id: session_id.clone(),
-            event_id: event_id.clone(),
-            sequence: 1,
-            text: text.to_string(),
-            mode: mode.to_string(),
-            created_at: chrono::Utc::now().to_rfc3339(),
-        },
-    )
-    .await?;
+    /// Sends the final text of a delivery. `utterance_id` names the
+    /// partials it completes, if any were streamed.
+    pub async fn send_final_text(
+        &mut self,
+        event_id: &str,
+        sequence: u64,
+        text: &str,
+        mode: &str,
+        created_at: &str,
+        utterance_id: Option<&str>,
+    ) -> Result<(), String> {
+        let delivered_at = self
+            .send_and_confirm(
+                OutgoingEnvelope::FinalText {
+                    session_id: self.session_id.clone(),
+                    event_id: event_id.to_string(),
+                    sequence,
+                    text: text.to_string(),
+                    mode: mode.to_string(),
+                    created_at: created_at.to_string(),
+                    utterance_id: utterance_id.map(str::to_string),
+                },
+                event_id,
+                sequence,
+            )
+            .await?;
+        log::info!("Remote final text {event_id} delivered at {delivered_at}");
+        Ok(())
+    }
 
-    match read_message(&mut lines).await? {
-        IncomingEnvelope::DeliveryAck {
-            session_id: ack_session_id,
-            event_id: ack_event_id,
+    /// Appends text to an utterance the receiver is typing.
+    pub async fn send_partial_text(
+        &mut self,
+        utterance_id: &str,
+        sequence: u64,
+        text: &str,
+    ) -> Result<(), String> {
+        self.send_and_confirm(
+            OutgoingEnvelope::PartialText {
+                session_id: self.session_id.clone(),
+                utterance_id: utterance_id.to_string(),
+                sequence,
+                text: text.to_string(),
+            },
+            utterance_id,
             sequence,
-            delivered_at,
-        } => {
-            if ack_session_id != session_id || ack_event_id != event_id || sequence != 1 {
-                return Err("Remote receiver acknowledged the wrong delivery.".to_string());
-            }
-            log::info!("Remote final text delivered at {delivered_at}");
-            Ok(())
-        }
-        IncomingEnvelope::DeliveryError {
-            session_id,
-            event_id,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    pub async fn send_revise(
+        &mut self,
+        utterance_id: &str,
+        sequence: u64,
+        ops: &[EditOp],
+    ) -> Result<(), String> {
+        self.send_and_confirm(
+            OutgoingEnvelope::Revise {
+                session_id: self.session_id.clone(),
+                utterance_id: utterance_id.to_string(),
+                sequence,
+                ops: ops.to_vec(),
+            },
+            utterance_id,
             sequence,
-            code,
-            message,
-        } => Err(format!(
-            "Remote delivery failed ({code}) for session {session_id}, event {event_id}, sequence {sequence}: {message}"
-        )),
-        IncomingEnvelope::SessionAck { .. } => {
-            Err("Remote receiver returned an unexpected session ack.".to_string())
-        }
-        IncomingEnvelope::PairingAccept { .. } => {
-            Err("Remote receiver returned an unexpected pairing accept.".to_string())
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Sends an envelope and returns when the receiver acknowledged it.
+    /// Partials are acknowledged under their utterance id.
+    async fn send_and_confirm(
+        &mut self,
+        envelope: OutgoingEnvelope,
+        event_id: &str,
+        sequence: u64,
+    ) -> Result<String, String> {
+        self.channel.send(&envelope).await?;
+
+        match read_message(&mut self.channel).await? {
+            IncomingEnvelope::DeliveryAck {
+                session_id: ack_session_id,
+                event_id: ack_event_id,
+                sequence: ack_sequence,
+                delivered_at,
+            } => {
+                if ack_session_id != self.session_id
+                    || ack_event_id != event_id
+                    || ack_sequence != sequence
+                {
+                    return Err("Remote receiver acknowledged the wrong delivery.".to_string());
+                }
+                Ok(delivered_at)
+            }
+            IncomingEnvelope::DeliveryError {
+                session_id,
+                event_id,
+                sequence,
+                code,
+                message,
+            } => Err(format!(
+                "Remote delivery failed ({code}) for session {session_id}, event {event_id}, sequence {sequence}: {message}"
+            )),
+            IncomingEnvelope::SessionAck { .. } => {
+                Err("Remote receiver returned an unexpected session ack.".to_string())
+            }
+            IncomingEnvelope::PairingAccept { .. } => {
+                Err("Remote receiver returned an unexpected pairing accept.".to_string())
+            }
         }
     }
 }
 
-async fn read_message(
-    lines: &mut tokio::io::Lines<BufReader<tokio::net::tcp::OwnedReadHalf>>,
-) -> Result<IncomingEnvelope, String> {
-    let line = tokio::time::timeout(Duration::from_secs(5), lines.next_line())
-        .await
-        .map_err(|_| "Timed out waiting for remote receiver response.".to_string())?
-        .map_err(|err| format!("Failed to read remote receiver response: {err}"))?
-        .ok_or_else(|| "Remote receiver closed the connection.".to_string())?;
+/// Sends one final text right away, without queueing it. Used for
+/// transport tests, where a failure should surface immediately.
+pub async fn deliver_final_text(
+    pool: SqlitePool,
+    sender_state: RemoteReceiverState,
+    target_device_id: &str,
+    text: &str,
+    mode: &str,
+) -> Result<(), String> {
+    if text.trim().is_empty() {
+        return Ok(());
+    }
 
-    serde_json::from_str(&line)
-        .map_err(|err| format!("Failed to parse remote receiver response: {err}"))
+    let mut session = open_session(pool, sender_state, target_device_id).await?;
+    session
+        .send_final_text(
+            &generate_id("event"),
+            1,
+            text,
+            mode,
+            &chrono::Utc::now().to_rfc3339(),
+            None,
+        )
+        .await
 }
 
-async fn write_message(
-    writer: &mut tokio::net::tcp::OwnedWriteHalf,
-    message: &OutgoingEnvelope,
-) -> Result<(), String> {
-    let json = serde_json::to_string(message)
-        .map_err(|err| format!("Failed to serialize remote sender message: {err}"))?;
-    writer
-        .write_all(json.as_bytes())
+async fn open_channel(
+    address: &str,
+    purpose: ChannelPurpose,
+    sender_device_id: &str,
+    psk: &[u8],
+) -> Result<SecureChannel<OwnedReadHalf, OwnedWriteHalf>, String> {
+    let stream = tokio::time::timeout(REMOTE_TIMEOUT, TcpStream::connect(address))
         .await
-        .map_err(|err| format!("Failed to write remote sender message: {err}"))?;
-    writer
-        .write_all(b"\n")
+        .map_err(|_| format!("Timed out connecting to remote receiver at {address}"))?
+        .map_err(|err| format!("Failed to connect to remote receiver at {address}: {err}"))?;
+    let (reader, writer) = stream.into_split();
+
+    tokio::time::timeout(
+        REMOTE_TIMEOUT,
+        remote_channel::connect(reader, writer, purpose, sender_device_id, psk),
+    )
+    .await
+    .map_err(|_| format!("Timed out securing the connection to {address}"))?
+}
+
+async fn read_message(
+    channel: &mut SecureChannel<OwnedReadHalf, OwnedWriteHalf>,
+) -> Result<IncomingEnvelope, String> {
+    tokio::time::timeout(REMOTE_TIMEOUT, channel.recv())
         .await
-        .map_err(|err| format!("Failed to finalize remote sender message: {err}"))?;
-    Ok(())
+        .map_err(|_| "Timed out waiting for remote receiver response.".to_string())??
+        .ok_or_else(|| "Remote receiver closed the connection.".to_string())
 }
 
 fn generate_id(prefix: &str) -> String {
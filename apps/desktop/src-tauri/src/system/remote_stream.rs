@@ -0,0 +1,212 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tokio::sync::Mutex;
+
+use crate::state::RemoteReceiverState;
+use crate::system::remote_sender::{self, RemoteSession};
+
+/// Advertised in the session ack by receivers that apply `PartialText`
+/// and `Revise` envelopes.
+pub const CAPABILITY_PARTIAL_TEXT: &str = "partial_text";
+
+/// One change to the text typed for an utterance, applied at the cursor.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum EditOp {
+    /// Removes `count` characters before the cursor.
+    Erase {
+        count: usize,
+    },
+    Insert {
+        text: String,
+    },
+}
+
+/// The ops that turn `from` into `to`, keeping their common prefix.
+pub fn edit_ops(from: &str, to: &str) -> Vec<EditOp> {
+    let prefix_len = from
+        .chars()
+        .zip(to.chars())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let erase = from.chars().count() - prefix_len;
+    let insert: String = to.chars().skip(prefix_len).collect();
+
+    let mut ops = Vec::new();
+    if erase > 0 {
+        ops.push(EditOp::Erase { count: erase });
+    }
+    if !insert.is_empty() {
+        ops.push(EditOp::Insert { text: insert });
+    }
+    ops
+}
+
+/// Applies ops to `text`, clamping erases to what is there.
+pub fn apply_ops(text: &str, ops: &[EditOp]) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    for op in ops {
+        match op {
+            EditOp::Erase { count } => {
+                let keep = chars.len().saturating_sub(*count);
+                chars.truncate(keep);
+            }
+            EditOp::Insert { text } => chars.extend(text.chars()),
+        }
+    }
+    chars.into_iter().collect()
+}
+
+struct ActiveStream {
+    target_device_id: String,
+    session: RemoteSession,
+    next_sequence: u64,
+    utterance_id: String,
+    sent_text: String,
+}
+
+/// The session partials are streamed over. Held open for the whole
+/// utterance so each update costs one round trip.
+static STREAM: Lazy<Mutex<Option<ActiveStream>>> = Lazy::new(|| Mutex::new(None));
+
+/// Brings the receiver's copy of the utterance up to `text`. Returns
+/// `false` when the receiver only accepts final text.
+pub async fn send_partial(
+    pool: SqlitePool,
+    sender_state: RemoteReceiverState,
+    target_device_id: &str,
+    utterance_id: &str,
+    text: &str,
+) -> Result<bool, String> {
+    let mut stream = STREAM.lock().await;
+    let reusable = stream
+        .take()
+        .filter(|active| active.target_device_id == target_device_id);
+    let mut active = match reusable {
+        Some(active) => active,
+        None => ActiveStream {
+            target_device_id: target_device_id.to_string(),
+            session: remote_sender::open_session(pool, sender_state, target_device_id).await?,
+            next_sequence: 1,
+            utterance_id: utterance_id.to_string(),
+            sent_text: String::new(),
+        },
+    };
+
+    if !active.session.supports(CAPABILITY_PARTIAL_TEXT) {
+        *stream = Some(active);
+        return Ok(false);
+    }
+    if active.utterance_id != utterance_id {
+        active.utterance_id = utterance_id.to_string();
+        active.sent_text = String::new();
+    }
+
+    let ops = edit_ops(&active.sent_text, text);
+    if ops.is_empty() {
+        *stream = Some(active);
+        return Ok(true);
+    }
+
+    let sequence = active.next_sequence;
+    active.next_sequence += 1;
+    match ops.as_slice() {
+        [EditOp::Insert { text: appended }] => {
+            active
+                .session
+                .send_partial_text(utterance_id, sequence, appended)
+                .await?
+        }
+        _ => {
+            active
+                .session
+                .send_revise(utterance_id, sequence, &ops)
+                .await?
+        }
+    }
+
+    // On failure the stream is dropped; the receiver keeps what it applied
+    // and the final text reconciles it.
+    active.sent_text = text.to_string();
+    *stream = Some(active);
+    Ok(true)
+}
+
+/// Closes the stream once the utterance's final text is on its way.
+pub async fn finish(utterance_id: &str) {
+    let mut stream = STREAM.lock().await;
+    if stream
+        .as_ref()
+        .is_some_and(|active| active.utterance_id == utterance_id)
+    {
+        *stream = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appending_only_inserts_the_new_suffix() {
+        assert_eq!(
+            edit_ops("hello ", "hello world "),
+            vec![EditOp::Insert {
+                text: "world ".to_string()
+            }]
+        );
+        assert_eq!(edit_ops("same", "same"), vec![]);
+    }
+
+    #[test]
+    fn revisions_erase_back_to_the_common_prefix() {
+        let ops = edit_ops("I scream ", "ice cream ");
+        assert_eq!(
+            ops,
+            vec![
+                EditOp::Erase { count: 9 },
+                EditOp::Insert {
+                    text: "ice cream ".to_string()
+                },
+            ]
+        );
+        assert_eq!(apply_ops("I scream ", &ops), "ice cream ");
+
+        let ops = edit_ops("naïve cafe", "naïve café");
+        assert_eq!(
+            ops,
+            vec![
+                EditOp::Erase { count: 1 },
+                EditOp::Insert {
+                    text: "é".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn erases_never_reach_past_the_utterance() {
+        assert_eq!(
+            apply_ops(
+                "hi",
+                &[
+                    EditOp::Erase { count: 10 },
+                    EditOp::Insert {
+                        text: "hey".to_string()
+                    }
+                ]
+            ),
+            "hey"
+        );
+    }
+
+    #[test]
+    fn edit_ops_use_an_op_tag_on_the_wire() {
+        let json = serde_json::to_string(&edit_ops("ab", "ac")).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"op":"erase","count":1},{"op":"insert","text":"c"}]"#
+        );
+    }
+}
@@ -0,0 +1,554 @@
+use std::cmp::Reverse;
+use std::ops::Range;
+
+use regex::{Regex, RegexBuilder};
+
+use crate::domain::{Term, TermMatchMode, TermPreview, TermPreviewError, TermPreviewMatch};
+
+/// Keeps user-supplied patterns from compiling into huge automata.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+enum Matcher {
+    Literal {
+        pattern: Regex,
+        source: String,
+        whole_word: bool,
+    },
+    Pattern(Regex),
+}
+
+struct CompiledRule {
+    term_id: String,
+    matcher: Matcher,
+    destination: String,
+    preserve_case: bool,
+    priority: i64,
+}
+
+/// Applies dictionary replacement terms to final text in a single pass. At
+/// each point the leftmost match wins; matches starting together go to the
+/// higher priority and then the longer match, so phrases win over the words
+/// they contain. Replaced text is never matched again, so rules cannot chain.
+pub struct ReplacementEngine {
+    rules: Vec<CompiledRule>,
+}
+
+impl ReplacementEngine {
+    /// Compiles the active replacement terms among `terms`. Terms that cannot
+    /// be compiled are skipped and reported.
+    pub fn new(terms: &[Term]) -> (Self, Vec<TermPreviewError>) {
+        let mut candidates: Vec<&Term> = terms
+            .iter()
+            .filter(|term| term.is_replacement && !term.is_deleted)
+            .filter(|term| !term.source_value.trim().is_empty())
+            .collect();
+        candidates.sort_by_key(|term| {
+            (
+                Reverse(term.priority),
+                Reverse(term.source_value.chars().count()),
+                term.created_at,
+            )
+        });
+
+        let mut rules = Vec::with_capacity(candidates.len());
+        let mut errors = Vec::new();
+        for term in candidates {
+            match compile(term) {
+                Ok(rule) => rules.push(rule),
+                Err(message) => errors.push(TermPreviewError {
+                    term_id: term.id.clone(),
+                    message,
+                }),
+            }
+        }
+
+        (Self { rules }, errors)
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        self.run(text).0
+    }
+
+    /// Applies every rule and reports how often each one matched.
+    pub fn run(&self, text: &str) -> (String, Vec<TermPreviewMatch>) {
+        let mut output = String::with_capacity(text.len());
+        let mut counts = vec![0u32; self.rules.len()];
+        let mut last = 0;
+        // Each rule's next match at or after `last`, refreshed once the
+        // output moves past its start.
+        let mut upcoming: Vec<Option<(Range<usize>, String)>> = self
+            .rules
+            .iter()
+            .map(|rule| rule.find_at(text, 0))
+            .collect();
+
+        loop {
+            let best = upcoming
+                .iter()
+                .enumerate()
+                .filter_map(|(index, found)| found.as_ref().map(|(range, _)| (index, range)))
+                .min_by_key(|(index, range)| {
+                    (
+                        range.start,
+                        Reverse(self.rules[*index].priority),
+                        Reverse(range.len()),
+                        *index,
+                    )
+                })
+                .map(|(index, _)| index);
+            let Some(index) = best else {
+                break;
+            };
+            let Some((range, replacement)) = upcoming[index].take() else {
+                break;
+            };
+
+            output.push_str(&text[last..range.start]);
+            output.push_str(&replacement);
+            last = range.end;
+            counts[index] += 1;
+
+            for (rule, found) in self.rules.iter().zip(upcoming.iter_mut()) {
+                if found.as_ref().is_some_and(|(range, _)| range.start < last) {
+                    *found = rule.find_at(text, last);
+                }
+            }
+            upcoming[index] = self.rules[index].find_at(text, last);
+        }
+        output.push_str(&text[last..]);
+
+        let applied = self
+            .rules
+            .iter()
+            .zip(counts)
+            .filter(|(_, count)| *count > 0)
+            .map(|(rule, count)| TermPreviewMatch {
+                term_id: rule.term_id.clone(),
+                count,
+            })
+            .collect();
+
+        (output, applied)
+    }
+}
+
+/// Runs `terms` over `text` and reports the result, matches and any terms
+/// that failed to compile.
+pub fn preview(text: &str, terms: &[Term]) -> TermPreview {
+    let (engine, errors) = ReplacementEngine::new(terms);
+    let (text, applied) = engine.run(text);
+    TermPreview {
+        text,
+        applied,
+        errors,
+    }
+}
+
+fn compile(term: &Term) -> Result<CompiledRule, String> {
+    let (matcher, destination) = match term.match_mode {
+        TermMatchMode::Regex => {
+            let pattern = build_regex(&term.source_value, term.case_sensitive)?;
+            if pattern.is_match("") {
+                return Err("pattern matches empty text".to_string());
+            }
+            (Matcher::Pattern(pattern), term.destination_value.clone())
+        }
+        TermMatchMode::WholeWord => {
+            // Punctuation around a whole-word source is not part of the
+            // word; the text's own punctuation is kept instead.
+            let source = term.source_value.trim_matches(|ch: char| !is_word_char(ch));
+            if source.is_empty() {
+                return Err("source has no letters or digits".to_string());
+            }
+            let destination = if source.len() == term.source_value.len() {
+                term.destination_value.clone()
+            } else {
+                term.destination_value
+                    .trim_matches(|ch: char| !is_word_char(ch))
+                    .to_string()
+            };
+            let matcher = Matcher::Literal {
+                pattern: build_regex(&regex::escape(source), term.case_sensitive)?,
+                source: source.to_string(),
+                whole_word: true,
+            };
+            (matcher, destination)
+        }
+        TermMatchMode::Substring => {
+            let matcher = Matcher::Literal {
+                pattern: build_regex(&regex::escape(&term.source_value), term.case_sensitive)?,
+                source: term.source_value.clone(),
+                whole_word: false,
+            };
+            (matcher, term.destination_value.clone())
+        }
+    };
+
+    Ok(CompiledRule {
+        term_id: term.id.clone(),
+        matcher,
+        destination,
+        preserve_case: term.preserve_case && !term.case_sensitive,
+        priority: term.priority,
+    })
+}
+
+fn build_regex(pattern: &str, case_sensitive: bool) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(!case_sensitive)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|err| err.to_string())
+}
+
+impl CompiledRule {
+    /// The first match starting at or after `from`, with the text that
+    /// replaces it.
+    fn find_at(&self, text: &str, from: usize) -> Option<(Range<usize>, String)> {
+        match &self.matcher {
+            Matcher::Pattern(pattern) => {
+                let captures = pattern.captures_at(text, from)?;
+                let mut replacement = String::new();
+                captures.expand(&self.destination, &mut replacement);
+                Some((captures.get(0)?.range(), replacement))
+            }
+            Matcher::Literal {
+                pattern,
+                source,
+                whole_word,
+            } => {
+                let needs_start = *whole_word && source.chars().next().is_some_and(is_word_char);
+                let needs_end = *whole_word && source.chars().last().is_some_and(is_word_char);
+                let mut at = from;
+
+                while let Some(found) = pattern.find_at(text, at) {
+                    let starts_word = !needs_start
+                        || !text[..found.start()]
+                            .chars()
+                            .next_back()
+                            .is_some_and(is_word_char);
+                    let ends_word =
+                        !needs_end || !text[found.end()..].chars().next().is_some_and(is_word_char);
+                    if starts_word && ends_word {
+                        let replacement = if self.preserve_case {
+                            match_case(found.as_str(), source, &self.destination)
+                        } else {
+                            self.destination.clone()
+                        };
+                        return Some((found.range(), replacement));
+                    }
+                    // A later match may still overlap this one, so resume
+                    // one character in rather than at its end.
+                    at = found.start() + text[found.start()..].chars().next()?.len_utf8();
+                }
+
+                None
+            }
+        }
+    }
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric()
+}
+
+/// Carries the capitalization of `matched` over to `destination` when it
+/// differs from how the source was written: all caps stays all caps and a
+/// capitalized match capitalizes the replacement. Otherwise the destination
+/// is used exactly as written.
+fn match_case(matched: &str, source: &str, destination: &str) -> String {
+    if matched == source {
+        return destination.to_string();
+    }
+
+    let mut letters = matched.chars().filter(|ch| ch.is_alphabetic()).peekable();
+    let is_all_caps =
+        letters.peek().is_some() && matched.chars().count() > 1 && letters.all(char::is_uppercase);
+    if is_all_caps {
+        return destination.to_uppercase();
+    }
+
+    let starts_upper = |value: &str| value.chars().next().is_some_and(char::is_uppercase);
+    if starts_upper(matched) && !starts_upper(source) {
+        let mut chars = destination.chars();
+        return match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        };
+    }
+
+    destination.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(source: &str, destination: &str) -> Term {
+        Term {
+            id: source.to_string(),
+            created_at: 0,
+            created_by_user_id: String::new(),
+            source_value: source.to_string(),
+            destination_value: destination.to_string(),
+            is_replacement: true,
+            is_deleted: false,
+            match_mode: TermMatchMode::WholeWord,
+            case_sensitive: false,
+            preserve_case: false,
+            priority: 0,
+            pack_id: None,
+        }
+    }
+
+    fn apply(text: &str, terms: &[Term]) -> String {
+        ReplacementEngine::new(terms).0.apply(text)
+    }
+
+    #[test]
+    fn whole_word_keeps_surrounding_punctuation_and_whitespace() {
+        let rules = [term("hello", "hi")];
+        assert_eq!(apply("  hello   world  ", &rules), "  hi   world  ");
+        assert_eq!(apply("(hello)", &rules), "(hi)");
+        assert_eq!(apply("hello,\n\nworld!", &rules), "hi,\n\nworld!");
+        assert_eq!(apply("hello?!", &rules), "hi?!");
+        assert_eq!(apply("hallo world", &rules), "hallo world");
+        assert_eq!(apply("helloworld", &rules), "helloworld");
+        assert_eq!(
+            apply("Rafa's is awesome.", &[term("Rafa", "Rapha")]),
+            "Rapha's is awesome."
+        );
+        assert_eq!(
+            apply("hello, world!", &[term("hello,", "hi,")]),
+            "hi, world!"
+        );
+    }
+
+    #[test]
+    fn whole_word_matches_phrases_and_unicode() {
+        assert_eq!(
+            apply("¡Hola, señor!", &[term("señor", "caballero")]),
+            "¡Hola, caballero!"
+        );
+        assert_eq!(
+            apply("ship it with vs code today", &[term("vs code", "VS Code")]),
+            "ship it with VS Code today"
+        );
+    }
+
+    #[test]
+    fn destinations_are_inserted_as_written_by_default() {
+        let rules = [term("LLM", "Claude")];
+        assert_eq!(apply("I use LLM daily", &rules), "I use Claude daily");
+        assert_eq!(apply("I use llm daily", &rules), "I use Claude daily");
+
+        let rules = [term("iphone", "iPhone"), term("gpt-4o", "GPT-4o")];
+        assert_eq!(
+            apply("Iphone and IPHONE run Gpt-4o", &rules),
+            "iPhone and iPhone run GPT-4o"
+        );
+    }
+
+    #[test]
+    fn preserve_case_follows_the_matched_capitalization() {
+        let mut rule = term("voquill", "voquill app");
+        rule.preserve_case = true;
+        let rules = [rule];
+        assert_eq!(apply("voquill rocks", &rules), "voquill app rocks");
+        assert_eq!(apply("Voquill rocks", &rules), "Voquill app rocks");
+        assert_eq!(apply("VOQUILL rocks", &rules), "VOQUILL APP rocks");
+    }
+
+    #[test]
+    fn case_sensitive_terms_only_match_exact_case() {
+        let mut rule = term("Go", "Golang");
+        rule.case_sensitive = true;
+        assert_eq!(apply("Go and go", &[rule]), "Golang and go");
+    }
+
+    #[test]
+    fn substring_terms_match_inside_words() {
+        let mut rule = term("colour", "color");
+        rule.match_mode = TermMatchMode::Substring;
+        assert_eq!(
+            apply("Colourful colours", &[rule.clone()]),
+            "colorful colors"
+        );
+        rule.preserve_case = true;
+        assert_eq!(apply("Colourful colours", &[rule]), "Colorful colors");
+    }
+
+    #[test]
+    fn regex_terms_expand_capture_groups() {
+        let mut rule = term(r"(\d+) percent", "$1%");
+        rule.match_mode = TermMatchMode::Regex;
+        assert_eq!(
+            apply("up 20 percent, down 5 Percent", &[rule]),
+            "up 20%, down 5%"
+        );
+
+        let mut named = term(r"ticket (?P<id>\d+)", "#${id}");
+        named.match_mode = TermMatchMode::Regex;
+        assert_eq!(apply("see ticket 42", &[named]), "see #42");
+    }
+
+    #[test]
+    fn invalid_and_empty_matching_patterns_are_reported() {
+        let mut broken = term("(unclosed", "x");
+        broken.match_mode = TermMatchMode::Regex;
+        let mut empty = term("a*", "x");
+        empty.match_mode = TermMatchMode::Regex;
+        empty.id = "empty".to_string();
+
+        let preview = preview("aaa", &[broken, empty, term("", "ignored")]);
+        assert_eq!(preview.text, "aaa");
+        let failed: Vec<&str> = preview
+            .errors
+            .iter()
+            .map(|err| err.term_id.as_str())
+            .collect();
+        assert_eq!(failed, vec!["(unclosed", "empty"]);
+    }
+
+    #[test]
+    fn priority_and_length_decide_the_order() {
+        let mut low = term("new york", "NY");
+        low.priority = -1;
+        let city = term("new york city", "NYC");
+        assert_eq!(
+            apply("new york city and new york", &[low, city]),
+            "NYC and NY"
+        );
+
+        let mut short = term("new", "old");
+        short.priority = 1;
+        let long = term("new york", "NY");
+        assert_eq!(apply("new york", &[long, short]), "old york");
+
+        assert_eq!(
+            apply(
+                "testing in progress",
+                &[term("test", "exam"), term("testing", "examining")]
+            ),
+            "examining in progress"
+        );
+    }
+
+    #[test]
+    fn replaced_text_is_never_matched_again() {
+        let mut first = term("cat", "dog");
+        first.priority = 2;
+        let second = term("dog", "wolf");
+        let chained = preview("cat", &[second, first]);
+        assert_eq!(chained.text, "dog");
+        assert_eq!(chained.applied.len(), 1);
+        assert_eq!(chained.applied[0].term_id, "cat");
+
+        let swap = [term("cat", "dog"), term("dog", "cat")];
+        assert_eq!(apply("cat chases dog", &swap), "dog chases cat");
+
+        let repeated = preview("foo and foo and foo bar", &[term("foo", "foo bar")]);
+        assert_eq!(repeated.text, "foo bar and foo bar and foo bar bar");
+        assert_eq!(repeated.applied[0].count, 3);
+    }
+
+    #[test]
+    fn leaves_text_alone_without_matching_rules() {
+        assert_eq!(apply("hello world", &[]), "hello world");
+        assert_eq!(apply("", &[term("hello", "hi")]), "");
+        assert_eq!(
+            apply("completely different text", &[term("hello", "hi")]),
+            "completely different text"
+        );
+        assert_eq!(apply("help world", &[term("hello", "hi")]), "help world");
+    }
+
+    #[test]
+    fn applies_multiple_rules_and_occurrences() {
+        let rules = [term("LLM", "Claude"), term("JS", "JavaScript")];
+        assert_eq!(
+            apply("I use LLM and JS", &rules),
+            "I use Claude and JavaScript"
+        );
+        assert_eq!(apply("LLM", &rules), "Claude");
+        assert_eq!(
+            apply("foo\nfoo\tfoo", &[term("foo", "bar")]),
+            "bar\nbar\tbar"
+        );
+        assert_eq!(
+            apply("AI is amazing", &[term("AI", "Artificial Intelligence")]),
+            "Artificial Intelligence is amazing"
+        );
+        assert_eq!(
+            apply("I use v2", &[term("v2", "version 2")]),
+            "I use version 2"
+        );
+    }
+
+    #[test]
+    fn keeps_punctuation_around_replaced_words() {
+        let rules = [term("hello", "hi")];
+        for (input, expected) in [
+            ("hello.", "hi."),
+            ("hello;", "hi;"),
+            ("hello...", "hi..."),
+            ("hello!!!", "hi!!!"),
+            ("\"hello world", "\"hi world"),
+            ("'hello'", "'hi'"),
+            ("[hello]", "[hi]"),
+            ("\thello;", "\thi;"),
+        ] {
+            assert_eq!(apply(input, &rules), expected);
+        }
+
+        let rules = [term("LLM", "Claude")];
+        assert_eq!(apply("Is LLM good?", &rules), "Is Claude good?");
+        assert_eq!(apply("LLM, the best AI.", &rules), "Claude, the best AI.");
+    }
+
+    #[test]
+    fn matches_words_in_other_scripts() {
+        assert_eq!(
+            apply("meet at café", &[term("café", "coffee shop")]),
+            "meet at coffee shop"
+        );
+        let rules = [term("français", "French")];
+        assert_eq!(apply("Je parle français.", &rules), "Je parle French.");
+        assert_eq!(apply("«français»", &rules), "«French»");
+        assert_eq!(
+            apply("Die größe ist gut.", &[term("größe", "size")]),
+            "Die size ist gut."
+        );
+
+        // CJK punctuation separates words just like spaces do.
+        let rules = [term("你好", "hello")];
+        assert_eq!(apply("你好 世界", &rules), "hello 世界");
+        assert_eq!(apply("「你好」", &rules), "「hello」");
+        assert_eq!(apply("你好，世界", &rules), "hello，世界");
+        assert_eq!(apply("你好世界", &rules), "你好世界");
+        assert_eq!(
+            apply("こんにちは、世界", &[term("こんにちは", "hello")]),
+            "hello、世界"
+        );
+
+        assert_eq!(apply("안녕, 세상", &[term("안녕", "hello")]), "hello, 세상");
+        assert_eq!(apply("«привет»", &[term("привет", "hello")]), "«hello»");
+        assert_eq!(
+            apply("مرحبا، عالم", &[term("مرحبا", "hello")]),
+            "hello، عالم"
+        );
+        assert_eq!(
+            apply("γεια, κόσμε!", &[term("γεια", "hello")]),
+            "hello, κόσμε!"
+        );
+    }
+
+    #[test]
+    fn non_replacement_and_deleted_terms_are_ignored() {
+        let mut glossary = term("foo", "bar");
+        glossary.is_replacement = false;
+        let mut deleted = term("baz", "qux");
+        deleted.is_deleted = true;
+        assert_eq!(apply("foo baz", &[glossary, deleted]), "foo baz");
+    }
+}
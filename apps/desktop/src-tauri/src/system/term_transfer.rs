@@ -0,0 +1,569 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::domain::{Term, TermMatchMode, TermTransferFormat};
+use crate::system::history_transfer::escape_csv;
+
+const CSV_HEADER: &[&str] = &[
+    "source_value",
+    "destination_value",
+    "is_replacement",
+    "match_mode",
+    "case_sensitive",
+    "preserve_case",
+    "priority",
+];
+
+/// The portable part of a term, as written to export files and packs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TermRecord {
+    pub source_value: String,
+    pub destination_value: String,
+    pub is_replacement: bool,
+    pub match_mode: TermMatchMode,
+    pub case_sensitive: bool,
+    pub preserve_case: bool,
+    pub priority: i64,
+}
+
+/// Entries may leave out everything but the source. A term with a
+/// destination is a replacement unless it says otherwise.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTermRecord {
+    source_value: String,
+    #[serde(default)]
+    destination_value: String,
+    #[serde(default)]
+    is_replacement: Option<bool>,
+    #[serde(default)]
+    match_mode: TermMatchMode,
+    #[serde(default)]
+    case_sensitive: bool,
+    #[serde(default)]
+    preserve_case: bool,
+    #[serde(default)]
+    priority: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonDocument {
+    Terms(Vec<serde_json::Value>),
+    Pack {
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        version: Option<String>,
+        terms: Vec<serde_json::Value>,
+    },
+}
+
+#[derive(Debug, Default)]
+pub struct ParsedTerms {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub records: Vec<TermRecord>,
+    pub errors: Vec<String>,
+}
+
+impl TermRecord {
+    pub fn from_term(term: &Term) -> Self {
+        Self {
+            source_value: term.source_value.clone(),
+            destination_value: term.destination_value.clone(),
+            is_replacement: term.is_replacement,
+            match_mode: term.match_mode,
+            case_sensitive: term.case_sensitive,
+            preserve_case: term.preserve_case,
+            priority: term.priority,
+        }
+    }
+
+    /// Whether `term` already carries exactly this record's values.
+    pub fn matches(&self, term: &Term) -> bool {
+        *self == Self::from_term(term)
+    }
+
+    /// Copies this record's values onto `term`, keeping its identity.
+    pub fn apply_to(&self, term: &Term) -> Term {
+        Term {
+            source_value: self.source_value.clone(),
+            destination_value: self.destination_value.clone(),
+            is_replacement: self.is_replacement,
+            match_mode: self.match_mode,
+            case_sensitive: self.case_sensitive,
+            preserve_case: self.preserve_case,
+            priority: self.priority,
+            ..term.clone()
+        }
+    }
+
+    pub fn into_term(self, id: String, created_at: i64, pack_id: Option<String>) -> Term {
+        Term {
+            id,
+            created_at,
+            created_by_user_id: String::new(),
+            source_value: self.source_value,
+            destination_value: self.destination_value,
+            is_replacement: self.is_replacement,
+            is_deleted: false,
+            match_mode: self.match_mode,
+            case_sensitive: self.case_sensitive,
+            preserve_case: self.preserve_case,
+            priority: self.priority,
+            pack_id,
+        }
+    }
+}
+
+impl RawTermRecord {
+    fn into_record(self) -> Result<TermRecord, String> {
+        let source_value = self.source_value.trim().to_string();
+        if source_value.is_empty() {
+            return Err("missing source value".to_string());
+        }
+        let destination_value = self.destination_value.trim().to_string();
+        let is_replacement = self.is_replacement.unwrap_or(!destination_value.is_empty());
+
+        Ok(TermRecord {
+            source_value,
+            destination_value,
+            is_replacement,
+            match_mode: self.match_mode,
+            case_sensitive: self.case_sensitive,
+            preserve_case: self.preserve_case,
+            priority: self.priority,
+        })
+    }
+}
+
+/// Terms are deduplicated by source value, ignoring case and surrounding
+/// whitespace.
+pub fn dedupe_key(source_value: &str) -> String {
+    source_value.trim().to_lowercase()
+}
+
+/// Keeps one record per source value. A later entry replaces an earlier one
+/// but keeps its position.
+pub fn dedupe(records: Vec<TermRecord>) -> Vec<TermRecord> {
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut unique: Vec<TermRecord> = Vec::with_capacity(records.len());
+    for record in records {
+        let key = dedupe_key(&record.source_value);
+        match positions.get(&key) {
+            Some(&index) => unique[index] = record,
+            None => {
+                positions.insert(key, unique.len());
+                unique.push(record);
+            }
+        }
+    }
+    unique
+}
+
+pub fn file_extension(format: TermTransferFormat) -> &'static str {
+    match format {
+        TermTransferFormat::Csv => "csv",
+        TermTransferFormat::Json => "json",
+    }
+}
+
+pub fn render(format: TermTransferFormat, records: &[TermRecord]) -> Result<String, String> {
+    match format {
+        TermTransferFormat::Csv => Ok(render_csv(records)),
+        TermTransferFormat::Json => {
+            serde_json::to_string_pretty(records).map_err(|err| err.to_string())
+        }
+    }
+}
+
+fn render_csv(records: &[TermRecord]) -> String {
+    let mut output = CSV_HEADER.join(",");
+    output.push('\n');
+    for record in records {
+        let fields = [
+            escape_csv(&record.source_value),
+            escape_csv(&record.destination_value),
+            record.is_replacement.to_string(),
+            record.match_mode.as_str().to_string(),
+            record.case_sensitive.to_string(),
+            record.preserve_case.to_string(),
+            record.priority.to_string(),
+        ];
+        output.push_str(&fields.join(","));
+        output.push('\n');
+    }
+    output
+}
+
+/// Reads a CSV or JSON term file. The format is taken from the extension,
+/// falling back to sniffing the first character.
+pub fn read_terms(path: &Path) -> Result<ParsedTerms, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+    let contents = contents.trim_start_matches('\u{feff}');
+
+    let is_json = match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("json") => true,
+        Some(ext) if ext.eq_ignore_ascii_case("csv") => false,
+        _ => contents.trim_start().starts_with(['[', '{']),
+    };
+
+    if is_json {
+        parse_json(contents)
+    } else {
+        Ok(parse_csv(contents))
+    }
+}
+
+/// Reads a pack from a single term file, or from every CSV and JSON file
+/// directly inside a directory. The pack is named by the first file that
+/// declares a name, otherwise by the file or directory itself.
+pub fn read_pack(path: &Path) -> Result<ParsedTerms, String> {
+    let files = if path.is_dir() {
+        let mut files = std::fs::read_dir(path)
+            .map_err(|err| format!("Failed to read {}: {err}", path.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|file| file.is_file() && is_term_file(file))
+            .collect::<Vec<_>>();
+        files.sort();
+        if files.is_empty() {
+            return Err(format!("{} contains no term files", path.display()));
+        }
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut pack = ParsedTerms::default();
+    for file in &files {
+        let parsed = read_terms(file)?;
+        let label = file
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        pack.name = pack.name.or(parsed.name);
+        pack.version = pack.version.or(parsed.version);
+        pack.records.extend(parsed.records);
+        pack.errors.extend(
+            parsed
+                .errors
+                .into_iter()
+                .map(|error| format!("{label}: {error}")),
+        );
+    }
+
+    pack.name = pack
+        .name
+        .filter(|name| !name.trim().is_empty())
+        .or_else(|| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+        });
+    pack.records = dedupe(pack.records);
+    Ok(pack)
+}
+
+fn is_term_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv") || ext.eq_ignore_ascii_case("json"))
+}
+
+/// Packs are identified by their source path, so subscribing to the same
+/// file twice refreshes the existing pack.
+pub fn pack_id_for(path: &Path) -> String {
+    let digest = Sha256::digest(path.to_string_lossy().as_bytes());
+    format!("pack-{digest:x}")[..21].to_string()
+}
+
+/// Pack terms get ids derived from their source value, so re-reading an
+/// updated pack file touches the same rows.
+pub fn pack_term_id(pack_id: &str, source_value: &str) -> String {
+    let digest = Sha256::digest(dedupe_key(source_value).as_bytes());
+    format!("{pack_id}-{}", &format!("{digest:x}")[..16])
+}
+
+fn parse_json(contents: &str) -> Result<ParsedTerms, String> {
+    let document = serde_json::from_str::<JsonDocument>(contents)
+        .map_err(|err| format!("Invalid term file: {err}"))?;
+    let (name, version, entries) = match document {
+        JsonDocument::Terms(entries) => (None, None, entries),
+        JsonDocument::Pack {
+            name,
+            version,
+            terms,
+        } => (name, version, terms),
+    };
+
+    let mut parsed = ParsedTerms {
+        name,
+        version,
+        ..Default::default()
+    };
+    for (index, entry) in entries.into_iter().enumerate() {
+        let record = serde_json::from_value::<RawTermRecord>(entry)
+            .map_err(|err| err.to_string())
+            .and_then(RawTermRecord::into_record);
+        match record {
+            Ok(record) => parsed.records.push(record),
+            Err(err) => parsed.errors.push(format!("entry {}: {err}", index + 1)),
+        }
+    }
+    Ok(parsed)
+}
+
+struct CsvColumns {
+    source: usize,
+    destination: Option<usize>,
+    is_replacement: Option<usize>,
+    match_mode: Option<usize>,
+    case_sensitive: Option<usize>,
+    preserve_case: Option<usize>,
+    priority: Option<usize>,
+}
+
+fn parse_csv(contents: &str) -> ParsedTerms {
+    let mut parsed = ParsedTerms::default();
+    let mut rows = split_csv(contents).into_iter();
+    let Some((_, header)) = rows.next() else {
+        return parsed;
+    };
+
+    let header = header
+        .iter()
+        .map(|name| name.trim().to_lowercase())
+        .collect::<Vec<_>>();
+    let column = |name: &str| header.iter().position(|field| field == name);
+    let Some(source) = column("source_value") else {
+        parsed
+            .errors
+            .push("missing source_value column".to_string());
+        return parsed;
+    };
+    let columns = CsvColumns {
+        source,
+        destination: column("destination_value"),
+        is_replacement: column("is_replacement"),
+        match_mode: column("match_mode"),
+        case_sensitive: column("case_sensitive"),
+        preserve_case: column("preserve_case"),
+        priority: column("priority"),
+    };
+
+    for (line, row) in rows {
+        if row.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+        match parse_csv_row(&row, &columns) {
+            Ok(record) => parsed.records.push(record),
+            Err(err) => parsed.errors.push(format!("line {line}: {err}")),
+        }
+    }
+
+    parsed
+}
+
+fn parse_csv_row(row: &[String], columns: &CsvColumns) -> Result<TermRecord, String> {
+    let field = |index: Option<usize>| {
+        index
+            .and_then(|index| row.get(index))
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+    let flag = |index: Option<usize>, name: &str| {
+        field(index)
+            .map(|value| parse_bool(value).ok_or(format!("invalid {name} {value:?}")))
+            .transpose()
+    };
+
+    let match_mode = match field(columns.match_mode) {
+        None | Some("whole_word") => TermMatchMode::WholeWord,
+        Some("substring") => TermMatchMode::Substring,
+        Some("regex") => TermMatchMode::Regex,
+        Some(value) => return Err(format!("invalid match_mode {value:?}")),
+    };
+    let priority = field(columns.priority)
+        .map(|value| {
+            value
+                .parse::<i64>()
+                .map_err(|_| format!("invalid priority {value:?}"))
+        })
+        .transpose()?
+        .unwrap_or(0);
+
+    RawTermRecord {
+        source_value: field(Some(columns.source)).unwrap_or_default().to_string(),
+        destination_value: field(columns.destination).unwrap_or_default().to_string(),
+        is_replacement: flag(columns.is_replacement, "is_replacement")?,
+        match_mode,
+        case_sensitive: flag(columns.case_sensitive, "case_sensitive")?.unwrap_or(false),
+        preserve_case: flag(columns.preserve_case, "preserve_case")?.unwrap_or(false),
+        priority,
+    }
+    .into_record()
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "true" | "1" | "yes" => Some(true),
+        "false" | "0" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Splits CSV text into rows tagged with the line they start on. Quoted
+/// fields may contain separators, doubled quotes and line breaks.
+fn split_csv(contents: &str) -> Vec<(usize, Vec<String>)> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut chars = contents.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch == '\n' {
+            line += 1;
+        }
+        match ch {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if field.is_empty() => in_quotes = true,
+            ',' if !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push((row_line, std::mem::take(&mut row)));
+                row_line = line;
+            }
+            _ => field.push(ch),
+        }
+    }
+
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push((row_line, row));
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(source: &str, destination: &str) -> TermRecord {
+        TermRecord {
+            source_value: source.to_string(),
+            destination_value: destination.to_string(),
+            is_replacement: !destination.is_empty(),
+            match_mode: TermMatchMode::WholeWord,
+            case_sensitive: false,
+            preserve_case: false,
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn csv_round_trips_quoted_fields() {
+        let mut quoted = record("vs code", "VS Code, \"the editor\"");
+        quoted.match_mode = TermMatchMode::Substring;
+        quoted.priority = 3;
+        let records = vec![quoted, record("Kubernetes", "")];
+
+        let parsed = parse_csv(&render_csv(&records));
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        assert_eq!(parsed.records, records);
+    }
+
+    #[test]
+    fn csv_reports_bad_rows_by_line() {
+        let parsed = parse_csv(
+            "source_value,destination_value,match_mode\n\
+             gpt,GPT,\n\
+             ,missing,\n\
+             foo,bar,fuzzy\n",
+        );
+        assert_eq!(parsed.records, vec![record("gpt", "GPT")]);
+        assert_eq!(
+            parsed.errors,
+            vec![
+                "line 3: missing source value".to_string(),
+                "line 4: invalid match_mode \"fuzzy\"".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn json_accepts_bare_lists_and_pack_documents() {
+        let parsed = parse_json(r#"[{"sourceValue": "voquill"}, {"destinationValue": "x"}]"#)
+            .expect("list parses");
+        assert_eq!(parsed.records, vec![record("voquill", "")]);
+        assert_eq!(parsed.errors.len(), 1);
+
+        let parsed = parse_json(
+            r#"{"name": "Acme", "version": "2", "terms": [
+                {"sourceValue": "acme corp", "destinationValue": "ACME Corp.", "priority": 5}
+            ]}"#,
+        )
+        .expect("pack parses");
+        assert_eq!(parsed.name.as_deref(), Some("Acme"));
+        assert_eq!(parsed.version.as_deref(), Some("2"));
+        assert_eq!(parsed.records[0].priority, 5);
+        assert!(parsed.records[0].is_replacement);
+    }
+
+    #[test]
+    fn dedupe_keeps_the_last_entry_in_the_first_position() {
+        let records = dedupe(vec![
+            record("GPT", "gpt"),
+            record("claude", ""),
+            record(" gpt ", "GPT"),
+        ]);
+        assert_eq!(records, vec![record(" gpt ", "GPT"), record("claude", "")]);
+    }
+
+    #[test]
+    fn pack_ids_are_stable() {
+        let pack_id = pack_id_for(Path::new("/shared/acme.json"));
+        assert_eq!(pack_id, pack_id_for(Path::new("/shared/acme.json")));
+        assert_ne!(pack_id, pack_id_for(Path::new("/shared/other.json")));
+        assert_eq!(
+            pack_term_id(&pack_id, "Acme Corp"),
+            pack_term_id(&pack_id, " acme corp")
+        );
+    }
+
+    #[test]
+    fn directory_packs_merge_files_in_order() {
+        let dir = std::env::temp_dir().join(format!("voquill-pack-{}", rand::random::<u32>()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("a.json"),
+            r#"{"name": "Team", "terms": [{"sourceValue": "k8s", "destinationValue": "Kubernetes"}]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("b.csv"),
+            "source_value,destination_value\nk8s,K8s\nSRE,\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let pack = read_pack(&dir).expect("pack reads");
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(pack.name.as_deref(), Some("Team"));
+        assert_eq!(pack.records, vec![record("k8s", "K8s"), record("SRE", "")]);
+    }
+}
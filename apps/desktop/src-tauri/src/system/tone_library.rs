@@ -0,0 +1,261 @@
+use std::collections::BTreeSet;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{Tone, ToneDiffLine, ToneDiffOp, ToneTemplateValidation};
+
+const TONE_FILE_FORMAT: &str = "voquill-tones";
+const TONE_FILE_VERSION: u32 = 1;
+
+/// Placeholders the post-processing prompt builder fills in. Keep in sync
+/// with `buildPostProcessingTemplateVars` in the desktop app.
+pub const TEMPLATE_VARIABLES: &[&str] = &["username", "transcript", "language"];
+
+static PLACEHOLDER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<([A-Za-z_][A-Za-z0-9_-]*)/>").expect("valid placeholder regex"));
+
+/// Lists the `<name/>` placeholders in `template` and flags the ones the
+/// runtime does not supply.
+pub fn validate_template(template: &str) -> ToneTemplateValidation {
+    let variables = PLACEHOLDER
+        .captures_iter(template)
+        .map(|captures| captures[1].to_string())
+        .collect::<BTreeSet<_>>();
+    let unknown = variables
+        .iter()
+        .filter(|name| !TEMPLATE_VARIABLES.contains(&name.as_str()))
+        .cloned()
+        .collect();
+
+    ToneTemplateValidation {
+        variables: variables.into_iter().collect(),
+        unknown,
+    }
+}
+
+/// Rejects templates that use placeholders the runtime would leave as-is.
+pub fn ensure_valid_template(template: &str) -> Result<(), String> {
+    let validation = validate_template(template);
+    if validation.unknown.is_empty() {
+        return Ok(());
+    }
+
+    let unknown = validation
+        .unknown
+        .iter()
+        .map(|name| format!("<{name}/>"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let supported = TEMPLATE_VARIABLES
+        .iter()
+        .map(|name| format!("<{name}/>"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(format!(
+        "Unknown template variables {unknown}; supported variables are {supported}"
+    ))
+}
+
+/// Line diff between two templates, from the longest common subsequence of
+/// their lines.
+pub fn diff_templates(before: &str, after: &str) -> Vec<ToneDiffLine> {
+    let before = before.lines().collect::<Vec<_>>();
+    let after = after.lines().collect::<Vec<_>>();
+
+    // lengths[i][j] is the LCS length of before[i..] and after[j..].
+    let mut lengths = vec![vec![0usize; after.len() + 1]; before.len() + 1];
+    for i in (0..before.len()).rev() {
+        for j in (0..after.len()).rev() {
+            lengths[i][j] = if before[i] == after[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let line = |op: ToneDiffOp, text: &str| ToneDiffLine {
+        op,
+        text: text.to_string(),
+    };
+    let mut diff = Vec::with_capacity(before.len().max(after.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < before.len() && j < after.len() {
+        if before[i] == after[j] {
+            diff.push(line(ToneDiffOp::Unchanged, before[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            diff.push(line(ToneDiffOp::Removed, before[i]));
+            i += 1;
+        } else {
+            diff.push(line(ToneDiffOp::Added, after[j]));
+            j += 1;
+        }
+    }
+    diff.extend(
+        before[i..]
+            .iter()
+            .map(|text| line(ToneDiffOp::Removed, text)),
+    );
+    diff.extend(after[j..].iter().map(|text| line(ToneDiffOp::Added, text)));
+    diff
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToneFile<'a> {
+    format: &'a str,
+    version: u32,
+    tones: Vec<ToneFileEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawToneFile {
+    format: String,
+    version: u32,
+    tones: Vec<serde_json::Value>,
+}
+
+/// A tone as stored in a portable tone file.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToneFileEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    pub prompt_template: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i32>,
+}
+
+#[derive(Debug, Default)]
+pub struct ParsedToneFile {
+    pub tones: Vec<ToneFileEntry>,
+    pub errors: Vec<String>,
+}
+
+pub fn render_tone_file(tones: &[Tone]) -> Result<String, String> {
+    let file = ToneFile {
+        format: TONE_FILE_FORMAT,
+        version: TONE_FILE_VERSION,
+        tones: tones
+            .iter()
+            .map(|tone| ToneFileEntry {
+                id: Some(tone.id.clone()),
+                name: tone.name.clone(),
+                prompt_template: tone.prompt_template.clone(),
+                sort_order: Some(tone.sort_order),
+            })
+            .collect(),
+    };
+    serde_json::to_string_pretty(&file).map_err(|err| err.to_string())
+}
+
+/// Parses a tone file. Entries with a missing name or an invalid template
+/// are reported and left out.
+pub fn parse_tone_file(contents: &str) -> Result<ParsedToneFile, String> {
+    let file = serde_json::from_str::<RawToneFile>(contents.trim_start_matches('\u{feff}'))
+        .map_err(|err| format!("Invalid tone file: {err}"))?;
+    if file.format != TONE_FILE_FORMAT {
+        return Err(format!("Not a tone file (format {:?})", file.format));
+    }
+    if file.version > TONE_FILE_VERSION {
+        return Err(format!(
+            "Tone file version {} is newer than this app supports",
+            file.version
+        ));
+    }
+
+    let mut parsed = ParsedToneFile::default();
+    for (index, value) in file.tones.into_iter().enumerate() {
+        let entry = serde_json::from_value::<ToneFileEntry>(value)
+            .map_err(|err| err.to_string())
+            .and_then(|entry| {
+                if entry.name.trim().is_empty() {
+                    return Err("missing name".to_string());
+                }
+                ensure_valid_template(&entry.prompt_template)?;
+                Ok(entry)
+            });
+        match entry {
+            Ok(entry) => parsed.tones.push(entry),
+            Err(err) => parsed.errors.push(format!("tone {}: {err}", index + 1)),
+        }
+    }
+
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation_lists_known_and_unknown_placeholders() {
+        let validation = validate_template(
+            "Hi <username/>, rewrite <transcript/> in <language/>. App: <app_name/>, <app_name/>. Keep <b>bold</b>.",
+        );
+        assert_eq!(
+            validation.variables,
+            vec!["app_name", "language", "transcript", "username"]
+        );
+        assert_eq!(validation.unknown, vec!["app_name"]);
+
+        assert!(ensure_valid_template("Fix <transcript/>").is_ok());
+        let err = ensure_valid_template("Use <selected_text/>").unwrap_err();
+        assert!(err.contains("<selected_text/>"), "{err}");
+    }
+
+    #[test]
+    fn diff_marks_added_and_removed_lines() {
+        let diff = diff_templates("a\nb\nc", "a\nc\nd");
+        let ops = diff
+            .iter()
+            .map(|line| (line.op, line.text.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ops,
+            vec![
+                (ToneDiffOp::Unchanged, "a"),
+                (ToneDiffOp::Removed, "b"),
+                (ToneDiffOp::Unchanged, "c"),
+                (ToneDiffOp::Added, "d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tone_files_round_trip_and_reject_bad_entries() {
+        let tone = Tone {
+            id: "formal".to_string(),
+            name: "Formal".to_string(),
+            prompt_template: "Make <transcript/> formal".to_string(),
+            created_at: 1,
+            sort_order: 3,
+        };
+        let parsed = parse_tone_file(&render_tone_file(&[tone]).unwrap()).unwrap();
+        assert_eq!(parsed.tones.len(), 1);
+        assert_eq!(parsed.tones[0].id.as_deref(), Some("formal"));
+        assert_eq!(parsed.tones[0].sort_order, Some(3));
+
+        let parsed = parse_tone_file(
+            r#"{"format": "voquill-tones", "version": 1, "tones": [
+                {"name": "", "promptTemplate": "x"},
+                {"name": "Bad", "promptTemplate": "<clipboard/>"},
+                {"name": "Ok", "promptTemplate": "ok"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.tones.len(), 1);
+        assert_eq!(parsed.errors.len(), 2);
+
+        assert!(parse_tone_file(r#"{"format": "other", "version": 1, "tones": []}"#).is_err());
+        assert!(
+            parse_tone_file(r#"{"format": "voquill-tones", "version": 9, "tones": []}"#).is_err()
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::system::crypto::{self, CryptoError};
+
+/// Opt-in encryption at rest for dictation audio and transcript text. Values
+/// are sealed with a per-value data key wrapped by a vault key held in the
+/// OS keyring. Reads always accept both sealed and plain values, so data
+/// stays readable while it is being migrated in either direction.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static VAULT_KEY: OnceLock<[u8; 32]> = OnceLock::new();
+
+const KEYRING_USER: &str = "vault-master-key";
+const KEY_FILE_NAME: &str = "vault-master.key";
+const SETTINGS_FILE_NAME: &str = "vault.json";
+
+/// Prefix of sealed text columns, followed by the base64 envelope. The FTS
+/// triggers skip values with this prefix.
+pub const TEXT_PREFIX: &str = "vault:v1:";
+const FILE_MAGIC: &[u8; 8] = b"VQVAULT\x01";
+const AUDIO_AAD: &[u8] = b"transcription-audio";
+
+#[derive(Debug, thiserror::Error)]
+pub enum VaultError {
+    #[error(transparent)]
+    Crypto(#[from] CryptoError),
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("vault key is unavailable; sealed data cannot be read")]
+    Locked,
+    #[error("vault key could not be stored where it would be found again")]
+    KeyNotPersisted,
+    #[error("sealed value is malformed: {0}")]
+    Malformed(String),
+}
+
+impl From<VaultError> for io::Error {
+    fn from(err: VaultError) -> Self {
+        match err {
+            VaultError::Io(err) => err,
+            other => io::Error::new(io::ErrorKind::InvalidData, other.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct VaultSettings {
+    enabled: bool,
+}
+
+/// Loads the vault settings at startup. The key is loaded whenever the vault
+/// has ever been enabled, since data may still be sealed after disabling.
+pub fn init(app: &tauri::AppHandle) -> Result<(), VaultError> {
+    let path = settings_path(app)?;
+    let settings = match fs::read(&path) {
+        Ok(bytes) => serde_json::from_slice::<VaultSettings>(&bytes)
+            .map_err(|err| VaultError::Malformed(err.to_string()))?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+
+    load_key(app, true)?;
+    ENABLED.store(settings.enabled, Ordering::SeqCst);
+    Ok(())
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// True once the vault key is loaded, i.e. the vault has been enabled on
+/// this install. Sealed values may exist even while it is disabled.
+pub fn has_key() -> bool {
+    VAULT_KEY.get().is_some()
+}
+
+/// Switches the mode for new writes and persists it. Existing data is
+/// migrated separately by the caller.
+pub fn set_enabled(app: &tauri::AppHandle, enabled: bool) -> Result<(), VaultError> {
+    // Nothing can be sealed before the settings are first written.
+    let sealed_data_exists = settings_path(app)?.exists();
+    load_key(app, sealed_data_exists)?;
+    write_settings(app, enabled)?;
+
+    ENABLED.store(enabled, Ordering::SeqCst);
+    Ok(())
+}
+
+/// The loaded vault key, if any. Passphrase-encrypted backups carry it so
+/// sealed data can be restored on another install.
+pub fn export_key() -> Option<[u8; 32]> {
+    VAULT_KEY.get().copied()
+}
+
+/// Installs a vault key restored from a backup, replacing the current one.
+/// Runs before [`init`], which then loads the restored key.
+pub fn import_key(app: &tauri::AppHandle, key: &[u8; 32], enabled: bool) -> Result<(), VaultError> {
+    crypto::store_install_key(app, KEYRING_USER, KEY_FILE_NAME, key)?;
+    write_settings(app, enabled)
+}
+
+pub fn is_sealed_text(value: &str) -> bool {
+    value.starts_with(TEXT_PREFIX)
+}
+
+/// Seals `value` when the vault is enabled; returns it unchanged otherwise.
+/// `column` is bound to the ciphertext so values cannot be swapped between
+/// columns.
+pub fn protect_text(column: &str, value: &str) -> Result<String, VaultError> {
+    if !is_enabled() || is_sealed_text(value) {
+        return Ok(value.to_string());
+    }
+
+    seal_text(key()?, column, value)
+}
+
+fn seal_text(key: &[u8; 32], column: &str, value: &str) -> Result<String, VaultError> {
+    let envelope = crypto::seal_envelope(key, column.as_bytes(), value.as_bytes())?;
+    Ok(format!(
+        "{TEXT_PREFIX}{}",
+        general_purpose::STANDARD.encode(envelope)
+    ))
+}
+
+/// Seals `value` as if the vault were enabled, loading a fixed key, so
+/// tests can store sealed rows without switching the mode for other tests.
+#[cfg(test)]
+pub(crate) fn seal_text_for_test(column: &str, value: &str) -> String {
+    let key = VAULT_KEY.get_or_init(|| [7u8; 32]);
+    seal_text(key, column, value).unwrap()
+}
+
+pub fn reveal_text(column: &str, value: String) -> Result<String, VaultError> {
+    let Some(encoded) = value.strip_prefix(TEXT_PREFIX) else {
+        return Ok(value);
+    };
+
+    let envelope = general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|err| VaultError::Malformed(err.to_string()))?;
+    let plaintext = crypto::open_envelope(key()?, column.as_bytes(), &envelope)?;
+    String::from_utf8(plaintext).map_err(|err| VaultError::Malformed(err.to_string()))
+}
+
+/// Writes `bytes` to `path`, sealed when the vault is enabled.
+pub fn write_file(path: &Path, bytes: &[u8]) -> Result<(), VaultError> {
+    let contents = if is_enabled() {
+        let mut sealed = FILE_MAGIC.to_vec();
+        sealed.extend(crypto::seal_envelope(key()?, AUDIO_AAD, bytes)?);
+        sealed
+    } else {
+        bytes.to_vec()
+    };
+
+    write_atomically(path, &contents)
+}
+
+/// Reads `path`, opening it if it was sealed.
+pub fn read_file(path: &Path) -> Result<Vec<u8>, VaultError> {
+    let mut bytes = Vec::new();
+    fs::File::open(path)?.read_to_end(&mut bytes)?;
+
+    match bytes.strip_prefix(FILE_MAGIC.as_slice()) {
+        Some(envelope) => Ok(crypto::open_envelope(key()?, AUDIO_AAD, envelope)?),
+        None => Ok(bytes),
+    }
+}
+
+/// Rewrites a file so that it matches the current mode. Returns whether the
+/// file changed.
+pub fn migrate_file(path: &Path) -> Result<bool, VaultError> {
+    let mut magic = [0u8; 8];
+    let read = fs::File::open(path)?.read(&mut magic)?;
+    let is_sealed = magic[..read] == FILE_MAGIC[..];
+    if is_sealed == is_enabled() {
+        return Ok(false);
+    }
+
+    let plaintext = read_file(path)?;
+    write_file(path, &plaintext)?;
+    Ok(true)
+}
+
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), VaultError> {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+
+    fs::write(&partial, contents)?;
+    if let Err(err) = fs::rename(&partial, path) {
+        let _ = fs::remove_file(&partial);
+        return Err(err.into());
+    }
+    Ok(())
+}
+
+fn load_key(
+    app: &tauri::AppHandle,
+    sealed_data_exists: bool,
+) -> Result<&'static [u8; 32], VaultError> {
+    if let Some(key) = VAULT_KEY.get() {
+        return Ok(key);
+    }
+
+    let key =
+        crypto::load_or_create_install_key(app, KEYRING_USER, KEY_FILE_NAME, sealed_data_exists)?;
+    // Data sealed with a key that is not found again on the next launch is
+    // lost, so the key must read back from its store before it is used.
+    if crypto::read_install_key(app, KEYRING_USER, KEY_FILE_NAME)? != Some(key) {
+        return Err(VaultError::KeyNotPersisted);
+    }
+    Ok(VAULT_KEY.get_or_init(|| key))
+}
+
+fn key() -> Result<&'static [u8; 32], VaultError> {
+    VAULT_KEY.get().ok_or(VaultError::Locked)
+}
+
+fn write_settings(app: &tauri::AppHandle, enabled: bool) -> Result<(), VaultError> {
+    let path = settings_path(app)?;
+    let bytes = serde_json::to_vec_pretty(&VaultSettings { enabled })
+        .map_err(|err| VaultError::Malformed(err.to_string()))?;
+    write_atomically(&path, &bytes)
+}
+
+fn settings_path(app: &tauri::AppHandle) -> io::Result<PathBuf> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|err| io::Error::other(err.to_string()))?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join(SETTINGS_FILE_NAME))
+}
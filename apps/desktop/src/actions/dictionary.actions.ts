@@ -1,10 +1,63 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { Term } from "@voquill/types";
 import dayjs from "dayjs";
 import { getTermRepo } from "../repos";
-import { produceAppState } from "../store";
+import { LocalTermRepo, toLocalTerm } from "../repos/term.repo";
+import { getAppState, produceAppState } from "../store";
 import { registerTerms } from "../utils/app.utils";
+import { getLogger } from "../utils/log.utils";
+
+type TermPreview = {
+  text: string;
+  applied: { termId: string; count: number }[];
+  errors: { termId: string; message: string }[];
+};
+
+type TermTransferFormat = "csv" | "json";
+
+type TermImportSummary = {
+  created: number;
+  updated: number;
+  unchanged: number;
+  errors: string[];
+};
+
+type TermPackSyncSummary = {
+  packId: string;
+  added: number;
+  updated: number;
+  removed: number;
+  errors: string[];
+};
+
+/**
+ * Term packs always live in the local database, so outside local mode they
+ * are merged in next to the account's personal terms.
+ */
+const listTermsWithPacks = async (): Promise<Term[]> => {
+  try {
+    await invoke<TermPackSyncSummary[]>("term_pack_sync", { id: null });
+  } catch (error) {
+    getLogger().error(`Failed to sync term packs: ${error}`);
+  }
+
+  const repo = getTermRepo();
+  const terms = await repo.listTerms();
+  if (repo instanceof LocalTermRepo) {
+    return terms;
+  }
+
+  try {
+    const localTerms = await new LocalTermRepo().listTerms();
+    return [...terms, ...localTerms.filter((term) => term.packId)];
+  } catch (error) {
+    getLogger().error(`Failed to load term packs: ${error}`);
+    return terms;
+  }
+};
 
 export const loadDictionary = async (): Promise<void> => {
-  const terms = await getTermRepo().listTerms();
+  const terms = await listTermsWithPacks();
   const activeTerms = terms.sort(
     (a, b) => dayjs(b.createdAt).valueOf() - dayjs(a.createdAt).valueOf(),
   );
@@ -14,3 +67,59 @@ export const loadDictionary = async (): Promise<void> => {
     draft.dictionary.termIds = activeTerms.map((term) => term.id);
   });
 };
+
+/**
+ * Runs the loaded replacement terms over `text` with the native engine, so
+ * every transcription provider gets identical replacements.
+ */
+export const applyTermReplacements = async (text: string): Promise<string> => {
+  const terms = Object.values(getAppState().termById).filter(
+    (term) => term.isReplacement,
+  );
+  if (terms.length === 0 || !text) {
+    return text;
+  }
+
+  try {
+    const preview = await invoke<TermPreview>("term_preview", {
+      text,
+      terms: terms.map(toLocalTerm),
+    });
+    return preview.text;
+  } catch (error) {
+    getLogger().error(`Failed to apply term replacements: ${error}`);
+    return text;
+  }
+};
+
+export const exportTerms = async (
+  format: TermTransferFormat,
+): Promise<boolean> => {
+  return invoke<boolean>("term_export", { format });
+};
+
+export const importTerms = async (): Promise<TermImportSummary | null> => {
+  const summary = await invoke<TermImportSummary | null>("term_import");
+  if (summary) {
+    await loadDictionary();
+  }
+  return summary;
+};
+
+export const subscribeTermPack = async (
+  directory: boolean,
+): Promise<TermPackSyncSummary | null> => {
+  const summary = await invoke<TermPackSyncSummary | null>(
+    "term_pack_subscribe",
+    { directory },
+  );
+  if (summary) {
+    await loadDictionary();
+  }
+  return summary;
+};
+
+export const unsubscribeTermPack = async (packId: string): Promise<void> => {
+  await invoke<void>("term_pack_unsubscribe", { id: packId });
+  await loadDictionary();
+};
@@ -198,6 +198,10 @@ export const submitOnboarding = async () => {
       dictationAudioDim: 1.0,
       pasteKeybind: null,
       menuBarIconHidden: false,
+      audioStorageFormat: "flac",
+      audioRetentionMaxCount: 20,
+      audioRetentionMaxAgeDays: null,
+      audioRetentionMaxTotalMb: null,
     };
 
     const [savedUser, savedPreferences] = await Promise.all([
@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import type { RemoteDeliveryOutcome, RemoteOutboxItem } from "@voquill/types";
 import { getActiveRemoteTarget } from "../utils/device.utils";
 import { getAppState } from "../store";
 import { showErrorSnackbar, showSnackbar } from "./app.actions";
@@ -33,13 +34,45 @@ export const sendTextToActiveRemoteTarget = async (
     return;
   }
 
-  await invoke<void>("remote_sender_deliver_final_text", {
-    args: {
-      targetDeviceId: target.id,
-      text: trimmed,
-      mode,
+  const outcome = await invoke<RemoteDeliveryOutcome>(
+    "remote_sender_deliver_final_text",
+    {
+      args: {
+        targetDeviceId: target.id,
+        text: trimmed,
+        mode,
+      },
     },
-  });
+  );
 
+  if (outcome === "queued") {
+    showSnackbar(
+      `${target.name} is unreachable. The text will be sent when it reconnects.`,
+    );
+    return;
+  }
   showSnackbar(`Sent to ${target.name}.`, { mode: "success" });
 };
+
+export const listRemoteOutbox = async (): Promise<RemoteOutboxItem[]> => {
+  return invoke<RemoteOutboxItem[]>("remote_outbox_list");
+};
+
+export const retryRemoteOutboxItem = async (
+  eventId: string,
+): Promise<void> => {
+  const outcome = await invoke<RemoteDeliveryOutcome>("remote_outbox_retry", {
+    args: { eventId },
+  });
+  if (outcome === "queued") {
+    showErrorSnackbar("The receiver is still unreachable.");
+    return;
+  }
+  showSnackbar("Queued text delivered.", { mode: "success" });
+};
+
+export const discardRemoteOutboxItem = async (
+  eventId: string,
+): Promise<void> => {
+  await invoke<void>("remote_outbox_discard", { args: { eventId } });
+};
@@ -1,4 +1,5 @@
 import type {
+  DiscoveredRemoteReceiver,
   PairedRemoteDevice,
   RemoteDevicePlatform,
   RemoteReceiverStatus,
@@ -91,10 +92,17 @@ export const parseRemotePairingInvite = (
   return invite;
 };
 
-export const pairWithRemoteInvite = async (
-  inviteCode: string,
+export const discoverRemoteReceivers = async (): Promise<
+  DiscoveredRemoteReceiver[]
+> => {
+  return invoke<DiscoveredRemoteReceiver[]>(
+    "remote_sender_discover_receivers",
+  );
+};
+
+const pairWithReceiver = async (
+  invite: Omit<RemotePairingInvite, "version">,
 ): Promise<PairedRemoteDevice> => {
-  const invite = parseRemotePairingInvite(inviteCode);
   const previousTargetId =
     getAppState().userPrefs?.remoteTargetDeviceId ?? null;
   const device = await invoke<PairedRemoteDevice>(
@@ -119,3 +127,27 @@ export const pairWithRemoteInvite = async (
   }
   return device;
 };
+
+export const pairWithRemoteInvite = async (
+  inviteCode: string,
+): Promise<PairedRemoteDevice> => {
+  return pairWithReceiver(parseRemotePairingInvite(inviteCode));
+};
+
+export const pairWithDiscoveredReceiver = async (
+  receiver: DiscoveredRemoteReceiver,
+  pairingCode: string,
+): Promise<PairedRemoteDevice> => {
+  const trimmedCode = pairingCode.trim();
+  if (!trimmedCode) {
+    throw new Error("Enter the pairing code shown on the receiver.");
+  }
+
+  return pairWithReceiver({
+    receiverDeviceId: receiver.deviceId,
+    receiverDeviceName: receiver.name,
+    receiverPlatform: receiver.platform,
+    receiverAddress: receiver.address,
+    pairingCode: trimmedCode,
+  });
+};
@@ -17,6 +17,7 @@ export type RemoteFinalTextReceivedPayload = {
   text: string;
   mode: string;
   createdAt: string;
+  alreadyInserted: boolean;
 };
 
 const storeRemoteTranscription = async ({
@@ -75,8 +76,11 @@ export const handleRemoteFinalTextReceived = async (
     return;
   }
 
-  await new Promise<void>((resolve) => setTimeout(resolve, 20));
-  await insertLocalTranscriptOutput(`${finalText} `, null);
+  // Streamed partials were typed at the cursor as they arrived.
+  if (!payload.alreadyInserted) {
+    await new Promise<void>((resolve) => setTimeout(resolve, 20));
+    await insertLocalTranscriptOutput(`${finalText} `, null);
+  }
   await storeRemoteTranscription({
     senderDeviceId: payload.senderDeviceId,
     rawTranscript: finalText,
@@ -1,3 +1,4 @@
+import { invoke } from "@tauri-apps/api/core";
 import { Tone } from "@voquill/types";
 import { getIntl } from "../i18n/intl";
 import { getToneRepo, getUserPreferencesRepo } from "../repos";
@@ -37,7 +38,11 @@ export const upsertTone = async (tone: Tone): Promise<Tone> => {
   } catch (error) {
     console.error("Failed to save tone", error);
     showErrorSnackbar(
-      error instanceof Error ? error.message : "Failed to save tone.",
+      error instanceof Error
+        ? error.message
+        : typeof error === "string"
+          ? error
+          : "Failed to save tone.",
     );
     throw error;
   }
@@ -167,3 +172,45 @@ export const closeToneEditorDialog = (): void => {
     draft.toneEditor.open = false;
   });
 };
+
+export type ToneVersion = {
+  id: string;
+  toneId: string;
+  name: string;
+  promptTemplate: string;
+  createdAt: number;
+  isCurrent: boolean;
+};
+
+type ToneImportSummary = {
+  created: number;
+  updated: number;
+  unchanged: number;
+  errors: string[];
+};
+
+export const listToneVersions = async (
+  toneId: string,
+): Promise<ToneVersion[]> => {
+  return invoke<ToneVersion[]>("tone_version_list", { toneId });
+};
+
+export const restoreToneVersion = async (
+  toneId: string,
+  versionId: string,
+): Promise<void> => {
+  await invoke("tone_version_restore", { toneId, versionId });
+  await loadTones();
+};
+
+export const exportTones = async (): Promise<boolean> => {
+  return invoke<boolean>("tone_export");
+};
+
+export const importTones = async (): Promise<ToneImportSummary | null> => {
+  const summary = await invoke<ToneImportSummary | null>("tone_import");
+  if (summary) {
+    await loadTones();
+  }
+  return summary;
+};
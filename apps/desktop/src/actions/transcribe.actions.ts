@@ -3,6 +3,7 @@ import {
   Nullable,
   Transcription,
   TranscriptionAudioSnapshot,
+  TranscriptionSegmentConfidence,
 } from "@voquill/types";
 import { countWords, dedup } from "@voquill/utilities";
 import dayjs from "dayjs";
@@ -56,6 +57,7 @@ export type TranscribeAudioMetadata = {
   transcriptionApiKeyId?: string | null;
   transcriptionMode?: TranscriptionMode | null;
   transcriptionDurationMs?: number | null;
+  confidenceSegments?: TranscriptionSegmentConfidence[] | null;
 };
 
 export type TranscribeAudioResult = {
@@ -156,6 +158,8 @@ export const transcribeAudio = async ({
   metadata.transcriptionApiKeyId = transcriptionApiKeyId;
   metadata.transcriptionMode =
     transcribeOutput.metadata?.transcriptionMode || null;
+  metadata.confidenceSegments =
+    transcribeOutput.metadata?.confidenceSegments ?? null;
 
   if (warnings.length > 0) {
     getLogger().warning(`Transcription warnings: ${warnings.join("; ")}`);
@@ -429,6 +433,7 @@ export const storeTranscription = async (
     warnings: input.warnings.length > 0 ? input.warnings : null,
     remoteStatus: input.remoteStatus ?? null,
     remoteDeviceId: input.remoteDeviceId ?? null,
+    confidenceSegments: input.transcriptionMetadata.confidenceSegments ?? null,
   };
 
   let storedTranscription: Transcription;
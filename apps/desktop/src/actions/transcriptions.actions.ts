@@ -1,11 +1,10 @@
-import { Transcription } from "@voquill/types";
+import { TranscriptionVersion } from "@voquill/types";
 import { getRec } from "@voquill/utilities";
 import { getTranscriptionRepo } from "../repos";
 import { getAppState, produceAppState } from "../store";
-import {
-  applyReplacements,
-  applySymbolConversions,
-} from "../utils/string.utils";
+import { createId } from "../utils/id.utils";
+import { applySymbolConversions } from "../utils/string.utils";
+import { applyTermReplacements } from "./dictionary.actions";
 import { postProcessTranscript, transcribeAudio } from "./transcribe.actions";
 
 export const openTranscriptionDetailsDialog = (transcriptionId: string) => {
@@ -76,14 +75,7 @@ export const retranscribeTranscription = async ({
 
   const rawTranscript = transcribeResult.rawTranscript;
 
-  const replacementRules = Object.values(state.termById)
-    .filter((term) => term.isReplacement)
-    .map((term) => ({
-      sourceValue: term.sourceValue,
-      destinationValue: term.destinationValue,
-    }));
-
-  const afterReplacements = applyReplacements(rawTranscript, replacementRules);
+  const afterReplacements = await applyTermReplacements(rawTranscript);
   const sanitizedTranscript = applySymbolConversions(afterReplacements);
 
   const postProcessResult = await postProcessTranscript({
@@ -107,24 +99,39 @@ export const retranscribeTranscription = async ({
     throw new Error("Retranscription produced no text.");
   }
 
-  const updatedPayload: Transcription = {
-    ...transcription,
+  // The new result is kept as a version next to the previous ones, so the
+  // user can switch back to any earlier result.
+  const version: TranscriptionVersion = {
+    id: `${transcriptionId}-${createId()}`,
+    transcriptionId,
+    createdAt: Date.now(),
+    isActive: false,
     transcript: finalTranscript,
+    rawTranscript: rawTranscript ?? finalTranscript,
     sanitizedTranscript,
+    confidenceSegments: metadata?.confidenceSegments ?? null,
+    transcriptionMode: metadata?.transcriptionMode ?? null,
     modelSize: metadata?.modelSize ?? null,
     inferenceDevice: metadata?.inferenceDevice ?? null,
-    rawTranscript: rawTranscript ?? finalTranscript,
     transcriptionPrompt: metadata?.transcriptionPrompt ?? null,
-    postProcessPrompt: metadata?.postProcessPrompt ?? null,
     transcriptionApiKeyId: metadata?.transcriptionApiKeyId ?? null,
-    postProcessApiKeyId: metadata?.postProcessApiKeyId ?? null,
-    transcriptionMode: metadata?.transcriptionMode ?? null,
+    transcriptionDurationMs: metadata?.transcriptionDurationMs ?? null,
     postProcessMode: metadata?.postProcessMode ?? null,
     postProcessDevice: metadata?.postProcessDevice ?? null,
-    warnings: warnings.length > 0 ? warnings : null,
+    postProcessPrompt: metadata?.postProcessPrompt ?? null,
+    postProcessApiKeyId: metadata?.postProcessApiKeyId ?? null,
+    postprocessDurationMs: metadata?.postprocessDurationMs ?? null,
   };
 
-  const updated = await repo.updateTranscription(updatedPayload);
+  await repo.createTranscriptionVersion(version);
+  const activated = await repo.activateTranscriptionVersion(
+    transcriptionId,
+    version.id,
+  );
+  const updated = await repo.updateTranscription({
+    ...activated,
+    warnings: warnings.length > 0 ? warnings : null,
+  });
 
   produceAppState((draft) => {
     draft.transcriptionById[transcriptionId] = updated;
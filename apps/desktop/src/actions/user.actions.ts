@@ -115,6 +115,10 @@ export const createDefaultPreferences = (): UserPreferences => ({
   dictationAudioDim: 1.0,
   pasteKeybind: null,
   menuBarIconHidden: false,
+  audioStorageFormat: "flac",
+  audioRetentionMaxCount: 20,
+  audioRetentionMaxAgeDays: null,
+  audioRetentionMaxTotalMb: null,
 });
 
 export const updateUserPreferences = async (
@@ -1,4 +1,8 @@
 import { invoke } from "@tauri-apps/api/core";
+import {
+  BRIDGE_CONTROL_EVENT,
+  type BridgeControlPayload,
+} from "@voquill/desktop-utils";
 import { AppTarget } from "@voquill/types";
 import { delayed } from "@voquill/utilities";
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
@@ -24,7 +28,11 @@ import {
   setToolAlwaysAllow,
 } from "../../actions/tool.actions";
 import { storeTranscription } from "../../actions/transcribe.actions";
-import { recordStreak } from "../../actions/user.actions";
+import {
+  activateAndSelectTone,
+  recordStreak,
+  setPreferredLanguage,
+} from "../../actions/user.actions";
 import {
   useHotkeyFire,
   useHotkeyHold,
@@ -837,6 +845,46 @@ export const DictationSideEffects = () => {
     }
   });
 
+  useTauriListen<BridgeControlPayload>(
+    BRIDGE_CONTROL_EVENT,
+    async (payload) => {
+      const isRecording = getAppState().activeRecordingMode !== null;
+      switch (payload.action) {
+        case "start_dictation":
+          if (!isDictationInteractable || isRecording) {
+            return;
+          }
+          if (payload.language) {
+            await startRecording({
+              mode: "dictate",
+              language: payload.language,
+            });
+          } else {
+            await startDictationRecording();
+          }
+          return;
+        case "stop_dictation":
+          if (isRecording) {
+            await stopDictationRecording();
+          }
+          return;
+        case "cancel_dictation":
+          await abortRecording();
+          return;
+        case "set_tone":
+          if (payload.toneId) {
+            await activateAndSelectTone(payload.toneId);
+          }
+          return;
+        case "set_language":
+          if (payload.language) {
+            await setPreferredLanguage(payload.language);
+          }
+          return;
+      }
+    },
+  );
+
   useTauriListen<void>("tone-switch-forward", () => {
     switchWritingStyleForward();
   });
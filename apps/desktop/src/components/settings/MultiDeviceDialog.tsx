@@ -13,16 +13,25 @@ import {
   Typography,
 } from "@mui/material";
 import type {
+  DiscoveredRemoteReceiver,
   PairedRemoteDevice,
   RemoteDevicePlatform,
+  RemoteOutboxItem,
   RemoteDeviceRole,
 } from "@voquill/types";
 import { ChangeEvent, useEffect, useState } from "react";
 import { FormattedMessage, useIntl } from "react-intl";
 import { showErrorSnackbar, showSnackbar } from "../../actions/app.actions";
-import { sendRemoteTestOutput } from "../../actions/remote-output.actions";
+import {
+  discardRemoteOutboxItem,
+  listRemoteOutbox,
+  retryRemoteOutboxItem,
+  sendRemoteTestOutput,
+} from "../../actions/remote-output.actions";
 import {
   buildRemotePairingInvite,
+  discoverRemoteReceivers,
+  pairWithDiscoveredReceiver,
   pairWithRemoteInvite,
 } from "../../actions/remote-pairing.actions";
 import {
@@ -44,6 +53,7 @@ import {
   getRemoteReceiverStatus,
   listPairedRemoteDevices,
 } from "../../utils/device.utils";
+import { useTauriListen } from "../../hooks/tauri.hooks";
 import { produceAppState, useAppStore } from "../../store";
 import { getMyUserPreferences } from "../../utils/user.utils";
 import { SettingSection } from "../common/SettingSection";
@@ -63,8 +73,16 @@ export const MultiDeviceDialog = () => {
   const [pairRole, setPairRole] = useState<RemoteDeviceRole>("receiver");
   const [importDialogOpen, setImportDialogOpen] = useState(false);
   const [inviteCodeDraft, setInviteCodeDraft] = useState("");
+  const [discoveredReceivers, setDiscoveredReceivers] = useState<
+    DiscoveredRemoteReceiver[]
+  >([]);
+  const [discoveryBusy, setDiscoveryBusy] = useState(false);
+  const [selectedReceiver, setSelectedReceiver] =
+    useState<DiscoveredRemoteReceiver | null>(null);
+  const [pairingCodeDraft, setPairingCodeDraft] = useState("");
   const [pairingBusy, setPairingBusy] = useState(false);
   const [testBusy, setTestBusy] = useState(false);
+  const [outboxItems, setOutboxItems] = useState<RemoteOutboxItem[]>([]);
   const [
     open,
     remoteOutputEnabled,
@@ -287,6 +305,36 @@ export const MultiDeviceDialog = () => {
     }
   };
 
+  const handleDiscoverReceivers = async () => {
+    if (discoveryBusy) {
+      return;
+    }
+
+    setDiscoveryBusy(true);
+    try {
+      setDiscoveredReceivers(await discoverRemoteReceivers());
+    } catch (error) {
+      showErrorSnackbar(error);
+    } finally {
+      setDiscoveryBusy(false);
+    }
+  };
+
+  const openImportDialog = () => {
+    setImportDialogOpen(true);
+    setDiscoveredReceivers([]);
+    setSelectedReceiver(null);
+    setPairingCodeDraft("");
+    void handleDiscoverReceivers();
+  };
+
+  const closeImportDialog = () => {
+    setImportDialogOpen(false);
+    setInviteCodeDraft("");
+    setSelectedReceiver(null);
+    setPairingCodeDraft("");
+  };
+
   const handleImportInvite = async () => {
     if (pairingBusy) {
       return;
@@ -294,10 +342,11 @@ export const MultiDeviceDialog = () => {
 
     setPairingBusy(true);
     try {
-      const device = await pairWithRemoteInvite(inviteCodeDraft);
+      const device = selectedReceiver
+        ? await pairWithDiscoveredReceiver(selectedReceiver, pairingCodeDraft)
+        : await pairWithRemoteInvite(inviteCodeDraft);
       showSnackbar(`Paired with ${device.name}.`, { mode: "success" });
-      setImportDialogOpen(false);
-      setInviteCodeDraft("");
+      closeImportDialog();
     } catch (error) {
       showErrorSnackbar(error);
     } finally {
@@ -380,6 +429,26 @@ export const MultiDeviceDialog = () => {
     };
   }, [open]);
 
+  const refreshOutbox = async () => {
+    try {
+      setOutboxItems(await listRemoteOutbox());
+    } catch (error) {
+      showErrorSnackbar(error);
+    }
+  };
+
+  useEffect(() => {
+    if (open) {
+      void refreshOutbox();
+    }
+  }, [open]);
+
+  useTauriListen<void>("remote_outbox_changed", async () => {
+    if (open) {
+      await refreshOutbox();
+    }
+  });
+
   useEffect(() => {
     setReceiverPortDraft(
       remoteReceiverPort == null ? "" : String(remoteReceiverPort),
@@ -555,8 +624,16 @@ export const MultiDeviceDialog = () => {
                         <FormattedMessage defaultMessage="The last target window was active, but no editable text field was focused. Click back into the destination text field on the receiver machine before sending text." />
                       </Typography>
                     )}
+                    {receiverStatus.enabled && (
+                      <Typography variant="caption" color="text.secondary">
+                        <FormattedMessage
+                          defaultMessage="Pairing code: {code}"
+                          values={{ code: receiverStatus.pairingCode }}
+                        />
+                      </Typography>
+                    )}
                     <Typography variant="caption" color="text.secondary">
-                      <FormattedMessage defaultMessage="Use Copy invite on the receiver machine, then Import invite on the sender machine. Manual trusted-device entry still works as a fallback." />
+                      <FormattedMessage defaultMessage="Senders on the same network find this receiver automatically and ask for the pairing code. You can also use Copy invite here, then Import invite on the sender machine. Manual trusted-device entry still works as a fallback." />
                     </Typography>
                     <Stack direction="row" spacing={1} sx={{ pt: 0.5 }}>
                       <Button
@@ -570,7 +647,7 @@ export const MultiDeviceDialog = () => {
                       <Button
                         size="small"
                         variant="outlined"
-                        onClick={() => setImportDialogOpen(true)}
+                        onClick={openImportDialog}
                       >
                         <FormattedMessage defaultMessage="Import invite" />
                       </Button>
@@ -672,6 +749,32 @@ export const MultiDeviceDialog = () => {
               </>
             )}
 
+            {outboxItems.length > 0 && (
+              <>
+                <SettingSection
+                  title={
+                    <FormattedMessage defaultMessage="Pending deliveries" />
+                  }
+                  description={
+                    <FormattedMessage defaultMessage="Texts waiting for their receiver. They are retried automatically and delivered in order." />
+                  }
+                />
+                <Stack spacing={1} sx={{ mt: -1 }}>
+                  {outboxItems.map((item) => (
+                    <OutboxItemRow
+                      key={item.eventId}
+                      item={item}
+                      targetName={
+                        pairedDevices.find(
+                          (device) => device.id === item.targetDeviceId,
+                        )?.name ?? item.targetDeviceId
+                      }
+                    />
+                  ))}
+                </Stack>
+              </>
+            )}
+
             <SettingSection
               title={<FormattedMessage defaultMessage="Trusted devices" />}
               description={
@@ -801,44 +904,110 @@ export const MultiDeviceDialog = () => {
         open={importDialogOpen}
         onClose={() => {
           if (!pairingBusy) {
-            setImportDialogOpen(false);
+            closeImportDialog();
           }
         }}
         maxWidth="sm"
         fullWidth
       >
         <DialogTitle>
-          <FormattedMessage defaultMessage="Import pairing invite" />
+          <FormattedMessage defaultMessage="Pair with a receiver" />
         </DialogTitle>
         <DialogContent dividers>
           <Stack spacing={2} sx={{ pt: 0.5 }}>
-            <Typography variant="body2" color="text.secondary">
-              <FormattedMessage defaultMessage="Paste the invite copied from the receiver machine. Voquill will trust both devices automatically." />
-            </Typography>
-            <TextField
-              label={intl.formatMessage({ defaultMessage: "Pairing invite" })}
-              value={inviteCodeDraft}
-              onChange={(event) => setInviteCodeDraft(event.target.value)}
-              multiline
-              minRows={3}
-              fullWidth
-            />
+            <Stack
+              direction="row"
+              alignItems="center"
+              justifyContent="space-between"
+            >
+              <Typography variant="subtitle2">
+                <FormattedMessage defaultMessage="Receivers on this network" />
+              </Typography>
+              <Button
+                size="small"
+                onClick={handleDiscoverReceivers}
+                disabled={discoveryBusy || pairingBusy}
+              >
+                {discoveryBusy ? (
+                  <FormattedMessage defaultMessage="Searching..." />
+                ) : (
+                  <FormattedMessage defaultMessage="Search again" />
+                )}
+              </Button>
+            </Stack>
+            {discoveredReceivers.length === 0 && !discoveryBusy && (
+              <Typography variant="body2" color="text.secondary">
+                <FormattedMessage defaultMessage="No receivers found. Turn on the receiver on the other machine, or paste its invite below." />
+              </Typography>
+            )}
+            {discoveredReceivers.map((receiver) => (
+              <Button
+                key={receiver.deviceId}
+                variant={
+                  selectedReceiver?.deviceId === receiver.deviceId
+                    ? "contained"
+                    : "outlined"
+                }
+                onClick={() =>
+                  setSelectedReceiver(
+                    selectedReceiver?.deviceId === receiver.deviceId
+                      ? null
+                      : receiver,
+                  )
+                }
+                disabled={pairingBusy}
+                sx={{ justifyContent: "space-between", textTransform: "none" }}
+              >
+                <span>{receiver.name}</span>
+                <Typography variant="caption" component="span">
+                  {receiver.address}
+                </Typography>
+              </Button>
+            ))}
+            {selectedReceiver ? (
+              <TextField
+                label={intl.formatMessage({ defaultMessage: "Pairing code" })}
+                helperText={intl.formatMessage({
+                  defaultMessage:
+                    "Shown under the receiver settings on the other machine.",
+                })}
+                value={pairingCodeDraft}
+                onChange={(event) => setPairingCodeDraft(event.target.value)}
+                autoFocus
+                fullWidth
+              />
+            ) : (
+              <>
+                <Typography variant="body2" color="text.secondary">
+                  <FormattedMessage defaultMessage="Or paste the invite copied from the receiver machine. Voquill will trust both devices automatically." />
+                </Typography>
+                <TextField
+                  label={intl.formatMessage({
+                    defaultMessage: "Pairing invite",
+                  })}
+                  value={inviteCodeDraft}
+                  onChange={(event) => setInviteCodeDraft(event.target.value)}
+                  multiline
+                  minRows={3}
+                  fullWidth
+                />
+              </>
+            )}
           </Stack>
         </DialogContent>
         <DialogActions>
-          <Button
-            onClick={() => {
-              setImportDialogOpen(false);
-              setInviteCodeDraft("");
-            }}
-            disabled={pairingBusy}
-          >
+          <Button onClick={closeImportDialog} disabled={pairingBusy}>
             <FormattedMessage defaultMessage="Cancel" />
           </Button>
           <Button
             variant="contained"
             onClick={handleImportInvite}
-            disabled={!inviteCodeDraft.trim() || pairingBusy}
+            disabled={
+              pairingBusy ||
+              (selectedReceiver
+                ? !pairingCodeDraft.trim()
+                : !inviteCodeDraft.trim())
+            }
           >
             <FormattedMessage defaultMessage="Pair" />
           </Button>
@@ -848,6 +1017,94 @@ export const MultiDeviceDialog = () => {
   );
 };
 
+type OutboxItemRowProps = {
+  item: RemoteOutboxItem;
+  targetName: string;
+};
+
+const OutboxItemRow = ({ item, targetName }: OutboxItemRowProps) => {
+  const [busy, setBusy] = useState(false);
+
+  const run = async (action: () => Promise<void>) => {
+    if (busy) {
+      return;
+    }
+
+    setBusy(true);
+    try {
+      await action();
+    } catch (error) {
+      showErrorSnackbar(error);
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <Stack
+      spacing={0.25}
+      sx={{
+        px: 1.5,
+        py: 1,
+        borderRadius: 1,
+        backgroundColor: "level1",
+        minWidth: 0,
+      }}
+    >
+      <Typography variant="body2" noWrap>
+        {item.text}
+      </Typography>
+      <Typography variant="caption" color="text.secondary">
+        <FormattedMessage
+          defaultMessage="To {device} • {attempts, plural, =0 {not tried yet} one {# failed attempt} other {# failed attempts}}"
+          values={{ device: targetName, attempts: item.attempts }}
+        />
+      </Typography>
+      {item.needsConfirmation && (
+        <Typography variant="caption" color="warning.main">
+          <FormattedMessage
+            defaultMessage="Queued a while ago. Send it now or discard it."
+          />
+        </Typography>
+      )}
+      {item.lastError && (
+        <Typography
+          variant="caption"
+          color={item.stuck ? "error.main" : "text.secondary"}
+          sx={{ wordBreak: "break-word" }}
+        >
+          {item.lastError}
+        </Typography>
+      )}
+      <Stack
+        direction="row"
+        justifyContent="flex-end"
+        sx={{ pt: 0.5, flexWrap: "wrap", gap: 0.5 }}
+      >
+        <Button
+          size="small"
+          disabled={busy}
+          onClick={() => void run(() => retryRemoteOutboxItem(item.eventId))}
+        >
+          {item.needsConfirmation ? (
+            <FormattedMessage defaultMessage="Send now" />
+          ) : (
+            <FormattedMessage defaultMessage="Retry now" />
+          )}
+        </Button>
+        <Button
+          size="small"
+          color="error"
+          disabled={busy}
+          onClick={() => void run(() => discardRemoteOutboxItem(item.eventId))}
+        >
+          <FormattedMessage defaultMessage="Discard" />
+        </Button>
+      </Stack>
+    </Stack>
+  );
+};
+
 type PairedDeviceRowProps = {
   device: PairedRemoteDevice;
   onEdit: () => void;
@@ -1,5 +1,6 @@
 import {
   AgentMode,
+  AudioStorageFormat,
   DictationPillVisibility,
   Nullable,
   PostProcessingMode,
@@ -51,6 +52,10 @@ type LocalUserPreferences = {
   pasteKeybind: Nullable<string>;
   useNewBackend: boolean;
   menuBarIconHidden: boolean;
+  audioStorageFormat?: Nullable<AudioStorageFormat>;
+  audioRetentionMaxCount?: Nullable<number>;
+  audioRetentionMaxAgeDays?: Nullable<number>;
+  audioRetentionMaxTotalMb?: Nullable<number>;
 };
 
 // Normalize post-processing mode for backwards compatibility
@@ -107,6 +112,10 @@ const fromLocalPreferences = (
   dictationAudioDim: preferences.dictationAudioDim ?? 1.0,
   pasteKeybind: preferences.pasteKeybind ?? null,
   menuBarIconHidden: preferences.menuBarIconHidden ?? false,
+  audioStorageFormat: preferences.audioStorageFormat ?? "flac",
+  audioRetentionMaxCount: preferences.audioRetentionMaxCount ?? null,
+  audioRetentionMaxAgeDays: preferences.audioRetentionMaxAgeDays ?? null,
+  audioRetentionMaxTotalMb: preferences.audioRetentionMaxTotalMb ?? null,
 });
 
 const toLocalPreferences = (
@@ -152,6 +161,10 @@ const toLocalPreferences = (
   pasteKeybind: preferences.pasteKeybind ?? null,
   useNewBackend: true,
   menuBarIconHidden: preferences.menuBarIconHidden ?? false,
+  audioStorageFormat: preferences.audioStorageFormat ?? "flac",
+  audioRetentionMaxCount: preferences.audioRetentionMaxCount ?? null,
+  audioRetentionMaxAgeDays: preferences.audioRetentionMaxAgeDays ?? null,
+  audioRetentionMaxTotalMb: preferences.audioRetentionMaxTotalMb ?? null,
 });
 
 export abstract class BaseUserPreferencesRepo extends BaseRepo {
@@ -1,11 +1,11 @@
 import { invokeHandler } from "@voquill/functions";
-import { Term } from "@voquill/types";
+import { Term, TermMatchMode } from "@voquill/types";
 import { invoke } from "@tauri-apps/api/core";
 import dayjs from "dayjs";
 import { invokeEnterprise } from "../utils/enterprise.utils";
 import { BaseRepo } from "./base.repo";
 
-type LocalTerm = {
+export type LocalTerm = {
   id: string;
   createdAt: number;
   createdByUserId: string;
@@ -13,9 +13,14 @@ type LocalTerm = {
   destinationValue: string;
   isReplacement: boolean;
   isDeleted: boolean;
+  matchMode: TermMatchMode;
+  caseSensitive: boolean;
+  preserveCase: boolean;
+  priority: number;
+  packId: string | null;
 };
 
-const toLocalTerm = (term: Term): LocalTerm => ({
+export const toLocalTerm = (term: Term): LocalTerm => ({
   id: term.id,
   createdAt: dayjs(term.createdAt).valueOf(),
   createdByUserId: "",
@@ -23,6 +28,11 @@ const toLocalTerm = (term: Term): LocalTerm => ({
   destinationValue: term.destinationValue,
   isReplacement: term.isReplacement,
   isDeleted: false,
+  matchMode: term.matchMode ?? "whole_word",
+  caseSensitive: term.caseSensitive ?? false,
+  preserveCase: term.preserveCase ?? false,
+  priority: term.priority ?? 0,
+  packId: term.packId ?? null,
 });
 
 const fromLocalTerm = (term: LocalTerm): Term => ({
@@ -31,6 +41,11 @@ const fromLocalTerm = (term: LocalTerm): Term => ({
   sourceValue: term.sourceValue,
   destinationValue: term.destinationValue,
   isReplacement: term.isReplacement,
+  matchMode: term.matchMode,
+  caseSensitive: term.caseSensitive,
+  preserveCase: term.preserveCase,
+  priority: term.priority,
+  packId: term.packId,
 });
 
 export abstract class BaseTermRepo extends BaseRepo {
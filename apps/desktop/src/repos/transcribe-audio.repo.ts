@@ -1,5 +1,5 @@
 import { invokeHandler } from "@voquill/functions";
-import { Nullable } from "@voquill/types";
+import { Nullable, TranscriptionSegmentConfidence } from "@voquill/types";
 import { batchAsync } from "@voquill/utilities";
 import {
   aldeaTranscribeAudio,
@@ -52,6 +52,7 @@ export type TranscribeAudioMetadata = {
   inferenceDevice?: Nullable<string>;
   modelSize?: Nullable<string>;
   transcriptionMode?: Nullable<TranscriptionMode>;
+  confidenceSegments?: Nullable<TranscriptionSegmentConfidence[]>;
 };
 
 export type TranscribeAudioInput = {
@@ -158,8 +159,12 @@ export abstract class BaseTranscribeAudioRepo extends BaseRepo {
     const transcriptionTexts = results.map((r) => r.text);
     const mergedText = mergeTranscriptions(transcriptionTexts);
 
-    // Use metadata from first result (all segments use same provider/device)
-    const metadata = results[0]?.metadata ?? null;
+    // Use metadata from first result (all segments use same provider/device).
+    // Confidence timings are per segment and do not survive the merge.
+    const firstMetadata = results[0]?.metadata;
+    const metadata = firstMetadata
+      ? { ...firstMetadata, confidenceSegments: null }
+      : null;
 
     return {
       text: mergedText,
@@ -208,6 +213,7 @@ export class LocalTranscribeAudioRepo extends BaseTranscribeAudioRepo {
       initialPrompt: input.prompt ?? undefined,
       language: input.language,
       deviceId: options.deviceId,
+      includeConfidence: true,
     });
 
     return {
@@ -216,6 +222,7 @@ export class LocalTranscribeAudioRepo extends BaseTranscribeAudioRepo {
         inferenceDevice: output.inferenceDevice,
         modelSize: output.model,
         transcriptionMode: "local",
+        confidenceSegments: output.segments,
       },
     };
   }
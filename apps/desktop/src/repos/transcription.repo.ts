@@ -3,6 +3,8 @@ import {
   Transcription,
   TranscriptionAudioSnapshot,
   TranscriptionMode,
+  TranscriptionSegmentConfidence,
+  TranscriptionVersion,
 } from "@voquill/types";
 import { invoke } from "@tauri-apps/api/core";
 import dayjs from "dayjs";
@@ -33,6 +35,12 @@ type LocalTranscription = {
   warnings?: string[] | null;
   remoteStatus?: string | null;
   remoteDeviceId?: string | null;
+  confidenceSegments?: TranscriptionSegmentConfidence[] | null;
+};
+
+type AudioPurgeSummary = {
+  purgedIds: string[];
+  reclaimedBytes: number;
 };
 
 export type TranscriptionAudioData = {
@@ -73,6 +81,7 @@ const toLocalTranscription = (
   warnings: transcription.warnings ?? null,
   remoteStatus: transcription.remoteStatus ?? null,
   remoteDeviceId: transcription.remoteDeviceId ?? null,
+  confidenceSegments: transcription.confidenceSegments ?? null,
 });
 
 const fromLocalTranscription = (
@@ -106,6 +115,7 @@ const fromLocalTranscription = (
   remoteStatus:
     (transcription.remoteStatus as "sent" | "received") ?? undefined,
   remoteDeviceId: transcription.remoteDeviceId ?? undefined,
+  confidenceSegments: transcription.confidenceSegments ?? undefined,
 });
 
 export abstract class BaseTranscriptionRepo extends BaseRepo {
@@ -119,6 +129,16 @@ export abstract class BaseTranscriptionRepo extends BaseRepo {
   abstract updateTranscription(
     transcription: Transcription,
   ): Promise<Transcription>;
+  abstract createTranscriptionVersion(
+    version: TranscriptionVersion,
+  ): Promise<TranscriptionVersion>;
+  abstract listTranscriptionVersions(
+    transcriptionId: string,
+  ): Promise<TranscriptionVersion[]>;
+  abstract activateTranscriptionVersion(
+    transcriptionId: string,
+    versionId: string,
+  ): Promise<Transcription>;
   abstract loadTranscriptionAudio(id: string): Promise<TranscriptionAudioData>;
   abstract purgeStaleAudio(): Promise<string[]>;
 }
@@ -161,14 +181,41 @@ export class LocalTranscriptionRepo extends BaseTranscriptionRepo {
     return fromLocalTranscription(stored);
   }
 
+  async createTranscriptionVersion(
+    version: TranscriptionVersion,
+  ): Promise<TranscriptionVersion> {
+    return invoke<TranscriptionVersion>("transcription_version_create", {
+      version,
+    });
+  }
+
+  async listTranscriptionVersions(
+    transcriptionId: string,
+  ): Promise<TranscriptionVersion[]> {
+    return invoke<TranscriptionVersion[]>("transcription_version_list", {
+      transcriptionId,
+    });
+  }
+
+  async activateTranscriptionVersion(
+    transcriptionId: string,
+    versionId: string,
+  ): Promise<Transcription> {
+    const stored = await invoke<LocalTranscription>(
+      "transcription_version_activate",
+      { transcriptionId, versionId },
+    );
+    return fromLocalTranscription(stored);
+  }
+
   async loadTranscriptionAudio(id: string): Promise<TranscriptionAudioData> {
     return invoke<TranscriptionAudioData>("transcription_audio_load", { id });
   }
 
   async purgeStaleAudio(): Promise<string[]> {
-    const purged = await invoke<string[] | undefined>(
+    const summary = await invoke<AudioPurgeSummary | undefined>(
       "purge_stale_transcription_audio",
     );
-    return Array.isArray(purged) ? purged : [];
+    return Array.isArray(summary?.purgedIds) ? summary.purgedIds : [];
   }
 }
@@ -61,6 +61,7 @@ export class LocalTranscriptionSession implements TranscriptionSession {
           language: whisperLanguage,
           initialPrompt: prompt || undefined,
           deviceId: getTranscriptionSidecarDeviceId(settings.device),
+          includeConfidence: true,
         });
 
       this.session = sidecarSession;
@@ -112,6 +113,7 @@ export class LocalTranscriptionSession implements TranscriptionSession {
           transcriptionMode: "local",
           transcriptionPrompt: this.context?.prompt ?? null,
           transcriptionDurationMs: Math.round(output.durationMs),
+          confidenceSegments: output.segments,
         },
         warnings,
       };
@@ -1,4 +1,5 @@
 import { appDataDir, join } from "@tauri-apps/api/path";
+import type { TranscriptionSegmentConfidence } from "@voquill/types";
 import {
   LOCAL_WHISPER_MODELS,
   type LocalWhisperModel,
@@ -38,6 +39,7 @@ type SidecarTranscriptionResponse = {
   model: LocalWhisperModel;
   inferenceDevice: string;
   durationMs: number;
+  segments?: TranscriptionSegmentConfidence[] | null;
 };
 
 type SidecarCreateTranscriptionSessionResponse = {
@@ -76,6 +78,7 @@ export type LocalSidecarTranscribeInput = {
   initialPrompt?: string;
   preferGpu: boolean;
   deviceId?: string;
+  includeConfidence?: boolean;
 };
 
 export type LocalSidecarStreamingSessionInput = Omit<
@@ -89,6 +92,7 @@ export type LocalSidecarTranscribeOutput = {
   inferenceDevice: string;
   durationMs: number;
   mode: SidecarMode;
+  segments: TranscriptionSegmentConfidence[] | null;
 };
 
 export type LocalSidecarStreamingSession = {
@@ -383,6 +387,7 @@ export class LocalTranscriptionSidecar extends BaseSidecar {
             inferenceDevice: result.inferenceDevice,
             durationMs: result.durationMs,
             mode: this.mode,
+            segments: result.segments ?? null,
           };
         })();
 
@@ -444,6 +449,7 @@ export class LocalTranscriptionSidecar extends BaseSidecar {
     language?: string;
     initialPrompt?: string;
     deviceId?: string;
+    includeConfidence?: boolean;
   } {
     const normalizedDeviceId = input.deviceId?.trim().toLowerCase();
     const deviceId =
@@ -461,6 +467,7 @@ export class LocalTranscriptionSidecar extends BaseSidecar {
       language: input.language === "auto" ? undefined : input.language,
       initialPrompt: input.initialPrompt,
       deviceId,
+      includeConfidence: input.includeConfidence,
     };
   }
 
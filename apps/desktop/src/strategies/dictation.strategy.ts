@@ -2,6 +2,7 @@ import { invoke } from "@tauri-apps/api/core";
 import type { Nullable } from "@voquill/types";
 import { showErrorSnackbar, showSnackbar } from "../actions/app.actions";
 import { tryRegisterCurrentAppTarget } from "../actions/app-target.actions";
+import { applyTermReplacements } from "../actions/dictionary.actions";
 import { showToast } from "../actions/toast.actions";
 import {
   postProcessTranscript,
@@ -15,13 +16,14 @@ import type {
   HandleTranscriptResult,
   StrategyValidationError,
 } from "../types/strategy.types";
+import { createId } from "../utils/id.utils";
 import { getLogger } from "../utils/log.utils";
 import { getMemberExceedsLimitByState } from "../utils/member.utils";
-import { routeTranscriptOutput } from "../utils/output-routing.utils";
 import {
-  applyReplacements,
-  applySymbolConversions,
-} from "../utils/string.utils";
+  routeTranscriptOutput,
+  streamRemotePartialText,
+} from "../utils/output-routing.utils";
+import { applySymbolConversions } from "../utils/string.utils";
 import { getToneIdToUse, VERBATIM_TONE_ID } from "../utils/tone.utils";
 import {
   getEffectivePostProcessingMode,
@@ -35,6 +37,10 @@ export class DictationStrategy extends BaseStrategy {
   private streamedProcessedText = "";
   private pasteQueue: Promise<void> = Promise.resolve();
   private currentAppId: string | null = null;
+  private utteranceId = createId();
+  // null until the receiver says whether it applies partial text.
+  private remotePartialTextSupported: boolean | null = null;
+  private remotePartialTextFailed = false;
 
   shouldStoreTranscript(): boolean {
     return true;
@@ -62,8 +68,7 @@ export class DictationStrategy extends BaseStrategy {
       return;
     }
 
-    const sanitized = this.sanitizeTranscript(segment);
-    if (!sanitized) {
+    if (!segment) {
       return;
     }
 
@@ -71,10 +76,38 @@ export class DictationStrategy extends BaseStrategy {
     this.streamedSegmentCount++;
 
     this.pasteQueue = this.pasteQueue.then(async () => {
-      const text = sanitized;
+      const text = await this.sanitizeTranscript(segment);
+      if (!text) {
+        return;
+      }
+
       const textToPaste = text + " ";
       this.streamedProcessedText += (isFirst ? "" : " ") + text;
 
+      if (
+        this.getActiveRemoteTargetDeviceId() &&
+        this.remotePartialTextSupported !== false
+      ) {
+        // After a failure the final text reconciles whatever arrived.
+        if (this.remotePartialTextFailed) {
+          return;
+        }
+
+        try {
+          this.remotePartialTextSupported = await streamRemotePartialText({
+            utteranceId: this.utteranceId,
+            text: this.streamedProcessedText + " ",
+          });
+          if (this.remotePartialTextSupported) {
+            return;
+          }
+        } catch (error) {
+          getLogger().warning(`Failed to stream partial text: ${error}`);
+          this.remotePartialTextFailed = true;
+          return;
+        }
+      }
+
       try {
         await routeTranscriptOutput({
           text: textToPaste,
@@ -87,16 +120,8 @@ export class DictationStrategy extends BaseStrategy {
     });
   }
 
-  private sanitizeTranscript(text: string): string | null {
-    const state = getAppState();
-    const replacementRules = Object.values(state.termById)
-      .filter((term) => term.isReplacement)
-      .map((term) => ({
-        sourceValue: term.sourceValue,
-        destinationValue: term.destinationValue,
-      }));
-
-    const afterReplacements = applyReplacements(text, replacementRules);
+  private async sanitizeTranscript(text: string): Promise<string | null> {
+    const afterReplacements = await applyTermReplacements(text);
     return applySymbolConversions(afterReplacements);
   }
 
@@ -142,7 +167,9 @@ export class DictationStrategy extends BaseStrategy {
   private async handleFinalStreamedTranscript(
     args: HandleTranscriptParams,
   ): Promise<HandleTranscriptResult> {
-    const sanitizedTranscript = this.sanitizeTranscript(args.rawTranscript);
+    const sanitizedTranscript = await this.sanitizeTranscript(
+      args.rawTranscript,
+    );
 
     await this.pasteQueue;
 
@@ -151,14 +178,42 @@ export class DictationStrategy extends BaseStrategy {
       `Streaming dictation complete (${this.streamedSegmentCount} segments)`,
     );
 
+    // Receivers that only take final text already got every segment.
+    let remoteStatus: "sent" | null = null;
+    const remoteDeviceId = this.getActiveRemoteTargetDeviceId();
+    if (
+      remoteDeviceId &&
+      transcript &&
+      this.remotePartialTextSupported !== false
+    ) {
+      try {
+        const result = await routeTranscriptOutput({
+          text: transcript.trim() + " ",
+          mode: "dictation",
+          currentAppId: this.currentAppId,
+          utteranceId: this.utteranceId,
+        });
+        if (result.delivered) {
+          remoteStatus = "sent";
+        } else if (result.queued) {
+          showSnackbar(
+            "Receiver is unreachable. The transcript will be sent when it reconnects.",
+          );
+        }
+      } catch (error) {
+        getLogger().error(`Failed to send final streamed transcript: ${error}`);
+        showErrorSnackbar("Unable to send transcription to receiver.");
+      }
+    }
+
     return {
       shouldContinue: false,
       transcript: transcript,
       sanitizedTranscript,
       postProcessMetadata: {},
       postProcessWarnings: [],
-      remoteStatus: null,
-      remoteDeviceId: null,
+      remoteStatus,
+      remoteDeviceId: remoteStatus ? remoteDeviceId : null,
     };
   }
 
@@ -173,7 +228,7 @@ export class DictationStrategy extends BaseStrategy {
     const remoteDeviceId = this.getActiveRemoteTargetDeviceId();
 
     try {
-      sanitizedTranscript = this.sanitizeTranscript(args.rawTranscript);
+      sanitizedTranscript = await this.sanitizeTranscript(args.rawTranscript);
       if (sanitizedTranscript) {
         if (args.processedTranscript) {
           transcript = args.processedTranscript;
@@ -208,6 +263,10 @@ export class DictationStrategy extends BaseStrategy {
             showSnackbar("Transcript sent to paired receiver.", {
               mode: "success",
             });
+          } else if (result.remote && result.queued) {
+            showSnackbar(
+              "Receiver is unreachable. The transcript will be sent when it reconnects.",
+            );
           }
 
           getLogger().info("Transcript output routed successfully");
@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
+  RemoteDeliveryOutcome,
   RouteTranscriptOutputArgs,
   RouteTranscriptOutputResult,
 } from "@voquill/types";
@@ -29,17 +30,22 @@ export const routeTranscriptOutput = async (
       };
     }
 
-    await invoke<void>("remote_sender_deliver_final_text", {
-      args: {
-        targetDeviceId: prefs.remoteTargetDeviceId,
-        text: args.text,
-        mode: args.mode,
+    const outcome = await invoke<RemoteDeliveryOutcome>(
+      "remote_sender_deliver_final_text",
+      {
+        args: {
+          targetDeviceId: prefs.remoteTargetDeviceId,
+          text: args.text,
+          mode: args.mode,
+          utteranceId: args.utteranceId,
+        },
       },
-    });
+    );
 
     return {
-      delivered: true,
+      delivered: outcome === "delivered",
       remote: true,
+      queued: outcome === "queued",
     };
   }
 
@@ -56,6 +62,31 @@ export const routeTranscriptOutput = async (
   };
 };
 
+/**
+ * Types the utterance's text so far on the remote target. Resolves to
+ * `false` when remote output is off or the receiver only accepts final text.
+ */
+export const streamRemotePartialText = async ({
+  utteranceId,
+  text,
+}: {
+  utteranceId: string;
+  text: string;
+}): Promise<boolean> => {
+  const prefs = getMyUserPreferences(getAppState());
+  if (!prefs?.remoteOutputEnabled || !prefs.remoteTargetDeviceId) {
+    return false;
+  }
+
+  return invoke<boolean>("remote_sender_stream_partial_text", {
+    args: {
+      targetDeviceId: prefs.remoteTargetDeviceId,
+      utteranceId,
+      text,
+    },
+  });
+};
+
 export const insertLocalTranscriptOutput = async (
   text: string,
   keybind: string | null,
@@ -94,6 +94,8 @@ export type PostProcessingPromptInput = {
   tone: ToneConfig;
 };
 
+// Tone templates are validated against these names natively; keep
+// TEMPLATE_VARIABLES in system/tone_library.rs in sync.
 const buildPostProcessingTemplateVars = (
   input: PostProcessingPromptInput,
 ): [name: string, value: string][] => {
@@ -13,6 +13,13 @@ export const KEYS_HELD_EVENT = "keys_held";
  */
 export const BRIDGE_HOTKEY_TRIGGER_EVENT = "bridge_hotkey_trigger";
 
+/**
+ * Emitted when a local client asks the authenticated bridge API to control
+ * dictation. The session itself lives in the webview, so it carries out the
+ * request.
+ */
+export const BRIDGE_CONTROL_EVENT = "bridge_control";
+
 export type KeysHeldPayload = {
   keys: string[];
 };
@@ -20,3 +27,16 @@ export type KeysHeldPayload = {
 export type BridgeHotkeyTriggerPayload = {
   hotkey: string;
 };
+
+export type BridgeControlAction =
+  | "start_dictation"
+  | "stop_dictation"
+  | "cancel_dictation"
+  | "set_tone"
+  | "set_language";
+
+export type BridgeControlPayload = {
+  action: BridgeControlAction;
+  toneId: string | null;
+  language: string | null;
+};
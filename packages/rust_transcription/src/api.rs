@@ -3,29 +3,47 @@ use std::path::Path as FsPath;
 use std::path::PathBuf;
 use std::time::Instant;
 
-use axum::body::Bytes;
+use axum::body::{Body, Bytes};
 use axum::extract::{DefaultBodyLimit, Path, Query, State};
+use axum::http::header::CONTENT_TYPE;
+use axum::http::HeaderMap;
 use axum::routing::{delete, get, post};
 use axum::{Json, Router};
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
-use crate::errors::ApiError;
-use crate::models::WhisperModel;
+use crate::audio_decoding::ChunkEncoding;
+use crate::benchmark::{BenchmarkOptions, BenchmarkReport};
+use crate::confidence::SegmentConfidence;
+use crate::errors::SidecarError;
+use crate::ggml::GgmlHeader;
+use crate::models::{ModelQuantization, WhisperModel};
+use crate::preprocessing::{AudioLevel, PreprocessingOptions};
 use crate::state::AppState;
 use crate::transcription::{ComputeDevice, TranscriptionInput};
 
 pub fn create_router(state: AppState) -> Router {
     Router::new()
         .route("/health", get(get_health))
+        .route("/v1/models/import", post(import_model))
         .route("/v1/models/:model/download", post(download_model))
         .route(
             "/v1/models/:model/download/:job_id",
             get(get_download_progress),
         )
+        .route("/v1/models/:model/quantize", post(quantize_model))
+        .route(
+            "/v1/models/:model/quantize/:job_id",
+            get(get_quantization_progress),
+        )
         .route("/v1/models/:model", delete(delete_model))
         .route("/v1/models/:model/status", get(get_model_status))
         .route("/v1/devices", get(list_devices))
+        .route("/v1/benchmark", post(run_benchmark))
         .route("/v1/transcriptions", post(transcribe))
         .route(
             "/v1/transcriptions/sessions",
@@ -44,6 +62,9 @@ pub fn create_router(state: AppState) -> Router {
             delete(delete_transcription_session),
         )
         .layer(DefaultBodyLimit::max(250 * 1024 * 1024))
+        .layer(axum::middleware::from_fn(
+            crate::request_id::request_context,
+        ))
         .with_state(state)
 }
 
@@ -67,16 +88,24 @@ struct DevicesResponse {
     devices: Vec<ComputeDevice>,
 }
 
-async fn list_devices(State(state): State<AppState>) -> Result<Json<DevicesResponse>, ApiError> {
-    let devices = state
-        .transcriber
-        .list_devices()
-        .await
-        .map_err(|err| ApiError::internal("device_list_failed", err))?;
+async fn list_devices(
+    State(state): State<AppState>,
+) -> Result<Json<DevicesResponse>, SidecarError> {
+    let devices = state.transcriber.list_devices().await?;
 
     Ok(Json(DevicesResponse { devices }))
 }
 
+async fn run_benchmark(
+    State(state): State<AppState>,
+    body: Option<Json<BenchmarkOptions>>,
+) -> Result<Json<BenchmarkReport>, SidecarError> {
+    let options = body.map(|Json(options)| options).unwrap_or_default();
+    let report = crate::benchmark::run_benchmark(&state, options).await?;
+
+    Ok(Json(report))
+}
+
 #[derive(Debug, Deserialize)]
 struct ModelPath {
     model: String,
@@ -102,12 +131,40 @@ struct ModelStatusResponse {
     valid: bool,
     file_bytes: Option<u64>,
     validation_error: Option<String>,
+    quantizations: Vec<ModelQuantization>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImportModelQuery {
+    model: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportModelFromPathRequest {
+    model: String,
+    path: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportModelResponse {
+    model: WhisperModel,
+    file_bytes: u64,
+    sha256: String,
+    file_type: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QuantizeModelRequest {
+    quantization: ModelQuantization,
 }
 
 async fn download_model(
     State(state): State<AppState>,
     Path(path): Path<ModelPath>,
-) -> Result<Json<crate::downloads::DownloadJobSnapshot>, ApiError> {
+) -> Result<Json<crate::downloads::DownloadJobSnapshot>, SidecarError> {
     let model = parse_model(&path.model)?;
     let destination = state.model_path(model);
     let url = model.download_url();
@@ -115,8 +172,7 @@ async fn download_model(
     let snapshot = state
         .downloads
         .start_or_get_active(model, url, destination, state.http_client.clone())
-        .await
-        .map_err(|err| ApiError::internal("download_start_failed", err))?;
+        .await?;
 
     Ok(Json(snapshot))
 }
@@ -124,16 +180,15 @@ async fn download_model(
 async fn get_download_progress(
     State(state): State<AppState>,
     Path(path): Path<DownloadProgressPath>,
-) -> Result<Json<crate::downloads::DownloadJobSnapshot>, ApiError> {
+) -> Result<Json<crate::downloads::DownloadJobSnapshot>, SidecarError> {
     let model = parse_model(&path.model)?;
-    let job_id = Uuid::parse_str(path.job_id.trim())
-        .map_err(|_| ApiError::bad_request("invalid_job_id", "jobId must be a valid UUID"))?;
+    let job_id = Uuid::parse_str(path.job_id.trim()).map_err(|_| SidecarError::InvalidJobId)?;
 
     let snapshot = state
         .downloads
         .get_job(model, job_id)
         .await
-        .ok_or_else(|| ApiError::not_found("download_not_found", "download job was not found"))?;
+        .ok_or(SidecarError::DownloadNotFound)?;
 
     Ok(Json(snapshot))
 }
@@ -142,7 +197,7 @@ async fn get_model_status(
     State(state): State<AppState>,
     Path(path): Path<ModelPath>,
     Query(query): Query<ModelStatusQuery>,
-) -> Result<Json<ModelStatusResponse>, ApiError> {
+) -> Result<Json<ModelStatusResponse>, SidecarError> {
     let model = parse_model(&path.model)?;
     let status = read_model_status(&state, model, query.validate.unwrap_or(true)).await?;
     Ok(Json(status))
@@ -151,61 +206,204 @@ async fn get_model_status(
 async fn delete_model(
     State(state): State<AppState>,
     Path(path): Path<ModelPath>,
-) -> Result<Json<ModelStatusResponse>, ApiError> {
+) -> Result<Json<ModelStatusResponse>, SidecarError> {
     let model = parse_model(&path.model)?;
+    ensure_model_idle(&state, model).await?;
 
-    if let Some(active_job) = state.downloads.get_active_job(model).await {
-        if matches!(
-            active_job.status,
-            crate::downloads::DownloadJobStatus::Pending
-                | crate::downloads::DownloadJobStatus::Running
-        ) {
-            return Err(ApiError::bad_request(
-                "download_in_progress",
-                format!(
-                    "model '{}' is currently downloading; wait for it to finish before deleting",
+    let model_path = state.model_path(model);
+    let variant_paths = ModelQuantization::all()
+        .iter()
+        .map(|quantization| state.quantized_model_path(model, *quantization));
+
+    for path in std::iter::once(model_path).chain(variant_paths) {
+        match tokio::fs::remove_file(&path).await {
+            Ok(_) => {}
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => {
+                return Err(SidecarError::ModelDeleteFailed(format!(
+                    "failed to delete model '{}': {err}",
                     model.as_slug()
-                ),
-            ));
+                )));
+            }
         }
-    }
 
-    let model_path = state.model_path(model);
-    match tokio::fs::remove_file(&model_path).await {
-        Ok(_) => {}
-        Err(err) if err.kind() == ErrorKind::NotFound => {}
-        Err(err) => {
-            return Err(ApiError::internal(
-                "model_delete_failed",
-                format!("failed to delete model '{}': {err}", model.as_slug()),
-            ));
-        }
+        remove_partial_model_downloads(&path, model).await?;
+        state.transcriber.evict_model(&path);
     }
 
-    remove_partial_model_downloads(&model_path, model).await?;
     let status = read_model_status(&state, model, false).await?;
     Ok(Json(status))
 }
 
+/// Registers a model without network access, either from a raw upload
+/// (`?model=` plus the file as the body) or from a JSON `{model, path}` body
+/// pointing inside one of `RUST_TRANSCRIPTION_IMPORT_DIRS`.
+async fn import_model(
+    State(state): State<AppState>,
+    Query(query): Query<ImportModelQuery>,
+    headers: HeaderMap,
+    body: Body,
+) -> Result<Json<ImportModelResponse>, SidecarError> {
+    let is_json = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| {
+            value
+                .trim()
+                .to_ascii_lowercase()
+                .starts_with("application/json")
+        })
+        .unwrap_or(false);
+
+    let (model, source_path) = if is_json {
+        let bytes = axum::body::to_bytes(body, 64 * 1024)
+            .await
+            .map_err(|err| SidecarError::InvalidRequest(format!("invalid import body: {err}")))?;
+        let request: ImportModelFromPathRequest = serde_json::from_slice(&bytes)
+            .map_err(|err| SidecarError::InvalidRequest(format!("invalid import body: {err}")))?;
+        let model = parse_model(&request.model)?;
+        (
+            model,
+            Some(resolve_import_path(&state, &request.path).await?),
+        )
+    } else {
+        let model = query.model.as_deref().ok_or_else(|| {
+            SidecarError::InvalidRequest(
+                "model query parameter is required for uploads".to_string(),
+            )
+        })?;
+        (parse_model(model)?, None)
+    };
+
+    ensure_model_idle(&state, model).await?;
+
+    let destination = state.model_path(model);
+    let temp_path =
+        destination.with_file_name(format!("{}.{}.download", model.filename(), Uuid::new_v4()));
+
+    let result = async {
+        let (file_bytes, sha256) = match source_path {
+            Some(source_path) => copy_import_file(source_path, temp_path.clone()).await?,
+            None => write_import_upload(body, &temp_path).await?,
+        };
+
+        let header_path = temp_path.clone();
+        let header = tokio::task::spawn_blocking(move || GgmlHeader::read_from_path(&header_path))
+            .await
+            .map_err(|err| SidecarError::Internal(format!("model import task failed: {err}")))?
+            .map_err(SidecarError::InvalidModelFile)?;
+        header
+            .check_matches(model)
+            .map_err(SidecarError::InvalidModelFile)?;
+
+        if destination.exists() {
+            tokio::fs::remove_file(&destination).await.map_err(|err| {
+                SidecarError::ModelImportFailed(format!(
+                    "failed to replace existing model file: {err}"
+                ))
+            })?;
+        }
+
+        tokio::fs::rename(&temp_path, &destination)
+            .await
+            .map_err(|err| {
+                SidecarError::ModelImportFailed(format!("failed to finalize model file: {err}"))
+            })?;
+
+        Ok(ImportModelResponse {
+            model,
+            file_bytes,
+            sha256,
+            file_type: header.file_type().unwrap_or("unknown"),
+        })
+    }
+    .await;
+
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(&temp_path).await;
+    }
+
+    let response = result?;
+    state.transcriber.evict_model(&destination);
+    Ok(Json(response))
+}
+
+async fn quantize_model(
+    State(state): State<AppState>,
+    Path(path): Path<ModelPath>,
+    Json(request): Json<QuantizeModelRequest>,
+) -> Result<Json<crate::quantization::QuantizationJobSnapshot>, SidecarError> {
+    let model = parse_model(&path.model)?;
+    if state.downloads.get_active_job(model).await.is_some() {
+        return Err(SidecarError::DownloadInProgress(model));
+    }
+
+    let source = ensure_model_downloaded(&state, model, None).await?;
+    let header_path = source.clone();
+    let header = tokio::task::spawn_blocking(move || GgmlHeader::read_from_path(&header_path))
+        .await
+        .map_err(|err| SidecarError::Internal(format!("model inspection task failed: {err}")))?
+        .map_err(SidecarError::InvalidModelFile)?;
+
+    if header.is_quantized() {
+        return Err(SidecarError::InvalidModelFile(format!(
+            "model '{}' is already quantized ({}); import an f16 or f32 file to quantize it",
+            model.as_slug(),
+            header.file_type().unwrap_or("unknown")
+        )));
+    }
+
+    let destination = state.quantized_model_path(model, request.quantization);
+    let snapshot = state
+        .quantizations
+        .start_or_get_active(model, request.quantization, source, destination)
+        .await?;
+
+    Ok(Json(snapshot))
+}
+
+async fn get_quantization_progress(
+    State(state): State<AppState>,
+    Path(path): Path<DownloadProgressPath>,
+) -> Result<Json<crate::quantization::QuantizationJobSnapshot>, SidecarError> {
+    let model = parse_model(&path.model)?;
+    let job_id = Uuid::parse_str(path.job_id.trim()).map_err(|_| SidecarError::InvalidJobId)?;
+
+    let snapshot = state
+        .quantizations
+        .get_job(model, job_id)
+        .await
+        .ok_or(SidecarError::QuantizationNotFound)?;
+
+    Ok(Json(snapshot))
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct TranscribeRequest {
     model: WhisperModel,
+    quantization: Option<ModelQuantization>,
     samples: Vec<f32>,
     sample_rate: u32,
     language: Option<String>,
     initial_prompt: Option<String>,
     device_id: Option<String>,
+    preprocessing: Option<PreprocessingOptions>,
+    include_confidence: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct CreateTranscriptionSessionRequest {
     model: WhisperModel,
+    quantization: Option<ModelQuantization>,
     sample_rate: u32,
+    channels: Option<u16>,
     language: Option<String>,
     initial_prompt: Option<String>,
     device_id: Option<String>,
+    preprocessing: Option<PreprocessingOptions>,
+    include_confidence: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -237,52 +435,78 @@ struct DeleteTranscriptionSessionResponse {
 struct TranscribeResponse {
     text: String,
     model: WhisperModel,
+    quantization: Option<ModelQuantization>,
     inference_device: String,
     duration_ms: u128,
+    input_level: AudioLevel,
+    segments: Option<Vec<SegmentConfidence>>,
 }
 
 async fn transcribe(
     State(state): State<AppState>,
     Json(request): Json<TranscribeRequest>,
-) -> Result<Json<TranscribeResponse>, ApiError> {
-    let model_path = ensure_model_downloaded(&state, request.model).await?;
+) -> Result<Json<TranscribeResponse>, SidecarError> {
+    let model_path = ensure_model_downloaded(&state, request.model, request.quantization).await?;
 
     let started = Instant::now();
     let output = run_transcription_request(
         &state,
-        request.model,
-        model_path,
-        request.samples,
-        request.sample_rate,
-        request.language,
-        request.initial_prompt,
-        request.device_id,
+        TranscriptionInput {
+            model_path,
+            samples: request.samples,
+            sample_rate: request.sample_rate,
+            language: request.language,
+            initial_prompt: request.initial_prompt,
+            device_id: request.device_id,
+            preprocessing: request.preprocessing.or(state.config.preprocessing),
+            include_confidence: request.include_confidence.unwrap_or(false),
+        },
     )
     .await?;
 
     Ok(Json(TranscribeResponse {
         text: output.text,
         model: request.model,
+        quantization: request.quantization,
         inference_device: output.inference_device,
         duration_ms: started.elapsed().as_millis(),
+        input_level: output.input_level,
+        segments: output.segments,
     }))
 }
 
 async fn create_transcription_session(
     State(state): State<AppState>,
     Json(request): Json<CreateTranscriptionSessionRequest>,
-) -> Result<Json<CreateTranscriptionSessionResponse>, ApiError> {
-    let _ = ensure_model_downloaded(&state, request.model).await?;
+) -> Result<Json<CreateTranscriptionSessionResponse>, SidecarError> {
+    let _ = ensure_model_downloaded(&state, request.model, request.quantization).await?;
+
+    let channels = request.channels.unwrap_or(1);
+    if channels == 0 {
+        return Err(SidecarError::InvalidRequest(
+            "channels must be greater than 0".to_string(),
+        ));
+    }
+
+    if request.sample_rate == 0 {
+        return Err(SidecarError::InvalidRequest(
+            "sampleRate must be greater than 0".to_string(),
+        ));
+    }
 
     let session_id = state
         .transcription_sessions
         .create(
             crate::streaming_sessions::BufferedTranscriptionSessionInput {
                 model: request.model,
+                quantization: request.quantization,
                 sample_rate: request.sample_rate,
+                channels,
                 language: request.language,
                 initial_prompt: request.initial_prompt,
                 device_id: request.device_id,
+                preprocessing: request.preprocessing.or(state.config.preprocessing),
+                include_confidence: request.include_confidence.unwrap_or(false),
             },
         )
         .await;
@@ -293,176 +517,280 @@ async fn create_transcription_session(
 async fn append_transcription_session_chunk(
     State(state): State<AppState>,
     Path(path): Path<TranscriptionSessionPath>,
+    headers: HeaderMap,
     bytes: Bytes,
-) -> Result<Json<AppendTranscriptionChunkResponse>, ApiError> {
+) -> Result<Json<AppendTranscriptionChunkResponse>, SidecarError> {
     let session_id = parse_session_id(&path.session_id)?;
-    let samples = decode_f32le_samples(bytes.as_ref())?;
-    let received_samples = samples.len();
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .map(|value| {
+            value.to_str().map_err(|_| {
+                SidecarError::UnsupportedAudioFormat(
+                    "Content-Type header is not valid ASCII".to_string(),
+                )
+            })
+        })
+        .transpose()?;
+    let encoding = ChunkEncoding::from_content_type(content_type)
+        .map_err(SidecarError::UnsupportedAudioFormat)?;
 
-    let buffered_samples = state
+    let appended = state
         .transcription_sessions
-        .append_samples(session_id, samples)
-        .await
-        .ok_or_else(|| {
-            ApiError::not_found(
-                "session_not_found",
-                "transcription session does not exist or has already completed",
-            )
-        })?;
+        .append_chunk(session_id, encoding, bytes.as_ref())
+        .await?;
 
     Ok(Json(AppendTranscriptionChunkResponse {
-        received_samples,
-        buffered_samples,
+        received_samples: appended.received_samples,
+        buffered_samples: appended.buffered_samples,
     }))
 }
 
 async fn finalize_transcription_session(
     State(state): State<AppState>,
     Path(path): Path<TranscriptionSessionPath>,
-) -> Result<Json<TranscribeResponse>, ApiError> {
+) -> Result<Json<TranscribeResponse>, SidecarError> {
     let session_id = parse_session_id(&path.session_id)?;
     let session = state
         .transcription_sessions
         .take(session_id)
         .await
-        .ok_or_else(|| {
-            ApiError::not_found(
-                "session_not_found",
-                "transcription session does not exist or has already completed",
-            )
-        })?;
+        .ok_or(SidecarError::SessionNotFound)?;
 
-    let model_path = ensure_model_downloaded(&state, session.model).await?;
+    let model_path = ensure_model_downloaded(&state, session.model, session.quantization).await?;
     let started = Instant::now();
     let output = run_transcription_request(
         &state,
-        session.model,
-        model_path,
-        session.samples,
-        session.sample_rate,
-        session.language,
-        session.initial_prompt,
-        session.device_id,
+        TranscriptionInput {
+            model_path,
+            samples: session.samples,
+            sample_rate: session.sample_rate,
+            language: session.language,
+            initial_prompt: session.initial_prompt,
+            device_id: session.device_id,
+            preprocessing: session.preprocessing,
+            include_confidence: session.include_confidence,
+        },
     )
     .await?;
 
     Ok(Json(TranscribeResponse {
         text: output.text,
         model: session.model,
+        quantization: session.quantization,
         inference_device: output.inference_device,
         duration_ms: started.elapsed().as_millis(),
+        input_level: output.input_level,
+        segments: output.segments,
     }))
 }
 
 async fn delete_transcription_session(
     State(state): State<AppState>,
     Path(path): Path<TranscriptionSessionPath>,
-) -> Result<Json<DeleteTranscriptionSessionResponse>, ApiError> {
+) -> Result<Json<DeleteTranscriptionSessionResponse>, SidecarError> {
     let session_id = parse_session_id(&path.session_id)?;
     let deleted = state.transcription_sessions.remove(session_id).await;
     Ok(Json(DeleteTranscriptionSessionResponse { deleted }))
 }
 
-fn parse_model(value: &str) -> Result<WhisperModel, ApiError> {
+fn parse_model(value: &str) -> Result<WhisperModel, SidecarError> {
     WhisperModel::from_slug(value).ok_or_else(|| {
-        ApiError::bad_request(
-            "invalid_model",
-            format!(
-                "unsupported model '{}'; supported values: {}",
-                value,
-                WhisperModel::supported().join(", ")
-            ),
-        )
+        SidecarError::InvalidModel(format!(
+            "unsupported model '{}'; supported values: {}",
+            value,
+            WhisperModel::supported().join(", ")
+        ))
     })
 }
 
-fn parse_session_id(value: &str) -> Result<Uuid, ApiError> {
-    Uuid::parse_str(value.trim())
-        .map_err(|_| ApiError::bad_request("invalid_session_id", "sessionId must be a valid UUID"))
+fn parse_session_id(value: &str) -> Result<Uuid, SidecarError> {
+    Uuid::parse_str(value.trim()).map_err(|_| SidecarError::InvalidSessionId)
+}
+
+async fn ensure_model_downloaded(
+    state: &AppState,
+    model: WhisperModel,
+    quantization: Option<ModelQuantization>,
+) -> Result<PathBuf, SidecarError> {
+    let (model_path, missing) = match quantization {
+        Some(quantization) => (
+            state.quantized_model_path(model, quantization),
+            SidecarError::ModelNotQuantized(model, quantization),
+        ),
+        None => (
+            state.model_path(model),
+            SidecarError::ModelNotDownloaded(model),
+        ),
+    };
+
+    match tokio::fs::metadata(&model_path).await {
+        Ok(metadata) if metadata.is_file() && metadata.len() > 0 => Ok(model_path),
+        _ => Err(missing),
+    }
 }
 
-fn decode_f32le_samples(bytes: &[u8]) -> Result<Vec<f32>, ApiError> {
-    if bytes.is_empty() {
-        return Ok(Vec::new());
+/// Refuses to touch a model's files while a download or quantization is
+/// still writing them.
+async fn ensure_model_idle(state: &AppState, model: WhisperModel) -> Result<(), SidecarError> {
+    if let Some(active_job) = state.downloads.get_active_job(model).await {
+        if matches!(
+            active_job.status,
+            crate::downloads::DownloadJobStatus::Pending
+                | crate::downloads::DownloadJobStatus::Running
+        ) {
+            return Err(SidecarError::DownloadInProgress(model));
+        }
+    }
+
+    if state.quantizations.has_active_job(model).await {
+        return Err(SidecarError::QuantizationInProgress(model));
     }
 
-    if bytes.len() % std::mem::size_of::<f32>() != 0 {
-        return Err(ApiError::bad_request(
-            "invalid_audio_chunk",
-            "audio chunk byte length must be a multiple of 4",
+    Ok(())
+}
+
+async fn resolve_import_path(
+    state: &AppState,
+    requested: &FsPath,
+) -> Result<PathBuf, SidecarError> {
+    if state.config.import_dirs.is_empty() {
+        return Err(SidecarError::ImportPathNotAllowed(
+            "path imports are disabled; set RUST_TRANSCRIPTION_IMPORT_DIRS or upload the file"
+                .to_string(),
         ));
     }
 
-    let mut samples = Vec::with_capacity(bytes.len() / std::mem::size_of::<f32>());
-    for chunk in bytes.chunks_exact(std::mem::size_of::<f32>()) {
-        let value = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
-        if value.is_finite() {
-            samples.push(value);
+    let not_allowed = || {
+        SidecarError::ImportPathNotAllowed(format!(
+            "'{}' is not inside an allowed import directory",
+            requested.display()
+        ))
+    };
+
+    // Canonicalize both sides so `..` and symlinks can't escape the allowlist.
+    let canonical = tokio::fs::canonicalize(requested)
+        .await
+        .map_err(|_| not_allowed())?;
+
+    for dir in &state.config.import_dirs {
+        if let Ok(dir) = tokio::fs::canonicalize(dir).await {
+            if canonical.starts_with(&dir) {
+                return Ok(canonical);
+            }
         }
     }
 
-    Ok(samples)
+    Err(not_allowed())
 }
 
-async fn ensure_model_downloaded(
-    state: &AppState,
-    model: WhisperModel,
-) -> Result<PathBuf, ApiError> {
-    let model_path = state.model_path(model);
-    let metadata = tokio::fs::metadata(&model_path).await.map_err(|_| {
-        ApiError::not_found(
-            "model_not_downloaded",
-            format!(
-                "model '{}' is not downloaded; call /v1/models/{}/download first",
-                model.as_slug(),
-                model.as_slug()
-            ),
-        )
+async fn copy_import_file(
+    source: PathBuf,
+    destination: PathBuf,
+) -> Result<(u64, String), SidecarError> {
+    tokio::task::spawn_blocking(move || {
+        use std::io::{Read, Write};
+
+        let mut input = std::fs::File::open(&source).map_err(|err| {
+            SidecarError::InvalidRequest(format!("failed to open import file: {err}"))
+        })?;
+        let mut output = std::fs::File::create(&destination).map_err(|err| {
+            SidecarError::ModelImportFailed(format!("failed to create model file: {err}"))
+        })?;
+
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0_u8; 1024 * 1024];
+        let mut copied: u64 = 0;
+        loop {
+            let read = input.read(&mut buffer).map_err(|err| {
+                SidecarError::ModelImportFailed(format!("failed to read import file: {err}"))
+            })?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+            output.write_all(&buffer[..read]).map_err(|err| {
+                SidecarError::ModelImportFailed(format!("failed to write model file: {err}"))
+            })?;
+            copied += read as u64;
+        }
+
+        output.sync_all().map_err(|err| {
+            SidecarError::ModelImportFailed(format!("failed to sync model file: {err}"))
+        })?;
+
+        Ok((copied, format!("{:x}", hasher.finalize())))
+    })
+    .await
+    .map_err(|err| SidecarError::Internal(format!("model import task failed: {err}")))?
+}
+
+async fn write_import_upload(
+    body: Body,
+    destination: &FsPath,
+) -> Result<(u64, String), SidecarError> {
+    let mut file = tokio::fs::File::create(destination).await.map_err(|err| {
+        SidecarError::ModelImportFailed(format!("failed to create model file: {err}"))
     })?;
 
-    if !metadata.is_file() || metadata.len() == 0 {
-        return Err(ApiError::not_found(
-            "model_not_downloaded",
-            format!(
-                "model '{}' is not downloaded; call /v1/models/{}/download first",
-                model.as_slug(),
-                model.as_slug()
-            ),
-        ));
+    let mut hasher = Sha256::new();
+    let mut written: u64 = 0;
+    let mut stream = body.into_data_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk
+            .map_err(|err| SidecarError::InvalidRequest(format!("model upload failed: {err}")))?;
+        hasher.update(&chunk);
+        file.write_all(&chunk).await.map_err(|err| {
+            SidecarError::ModelImportFailed(format!("failed to write model file: {err}"))
+        })?;
+        written += chunk.len() as u64;
     }
 
-    Ok(model_path)
+    file.flush().await.map_err(|err| {
+        SidecarError::ModelImportFailed(format!("failed to flush model file: {err}"))
+    })?;
+    file.sync_all().await.map_err(|err| {
+        SidecarError::ModelImportFailed(format!("failed to sync model file: {err}"))
+    })?;
+
+    Ok((written, format!("{:x}", hasher.finalize())))
 }
 
+#[tracing::instrument(
+    name = "transcription",
+    skip_all,
+    fields(
+        model = %input.model_path.file_name().unwrap_or_default().to_string_lossy(),
+        samples = input.samples.len(),
+        sample_rate = input.sample_rate,
+    )
+)]
 async fn run_transcription_request(
     state: &AppState,
-    model: WhisperModel,
-    model_path: PathBuf,
-    samples: Vec<f32>,
-    sample_rate: u32,
-    language: Option<String>,
-    initial_prompt: Option<String>,
-    device_id: Option<String>,
-) -> Result<crate::transcription::TranscriptionOutput, ApiError> {
-    state
-        .transcriber
-        .transcribe(TranscriptionInput {
-            model_path,
-            samples,
-            sample_rate,
-            language,
-            initial_prompt,
-            device_id,
-        })
-        .await
-        .map_err(|error| map_transcription_error(model, error))
+    input: TranscriptionInput,
+) -> Result<crate::transcription::TranscriptionOutput, SidecarError> {
+    match state.transcriber.transcribe(input).await {
+        Ok(output) => {
+            info!(
+                chars = output.text.chars().count(),
+                "transcription finished"
+            );
+            if state.config.log_transcripts {
+                debug!(text = %output.text, "transcript");
+            }
+            Ok(output)
+        }
+        Err(err) => {
+            warn!(code = err.code(), error = %err, "transcription failed");
+            Err(err)
+        }
+    }
 }
 
 async fn read_model_status(
     state: &AppState,
     model: WhisperModel,
     validate: bool,
-) -> Result<ModelStatusResponse, ApiError> {
+) -> Result<ModelStatusResponse, SidecarError> {
     let model_path = state.model_path(model);
     let metadata = tokio::fs::metadata(&model_path).await.ok();
 
@@ -472,6 +800,15 @@ async fn read_model_status(
         .unwrap_or(false);
 
     let file_bytes = metadata.map(|meta| meta.len());
+    let mut quantizations = Vec::new();
+    for quantization in ModelQuantization::all() {
+        if ensure_model_downloaded(state, model, Some(*quantization))
+            .await
+            .is_ok()
+        {
+            quantizations.push(*quantization);
+        }
+    }
 
     if !downloaded {
         return Ok(ModelStatusResponse {
@@ -480,6 +817,7 @@ async fn read_model_status(
             valid: false,
             file_bytes,
             validation_error: None,
+            quantizations,
         });
     }
 
@@ -490,6 +828,7 @@ async fn read_model_status(
             valid: true,
             file_bytes,
             validation_error: None,
+            quantizations,
         });
     }
 
@@ -500,13 +839,15 @@ async fn read_model_status(
             valid,
             file_bytes,
             validation_error: None,
+            quantizations,
         }),
         Err(err) => Ok(ModelStatusResponse {
             model,
             downloaded: true,
             valid: false,
             file_bytes,
-            validation_error: Some(err),
+            validation_error: Some(err.to_string()),
+            quantizations,
         }),
     }
 }
@@ -514,7 +855,7 @@ async fn read_model_status(
 async fn remove_partial_model_downloads(
     model_path: &FsPath,
     model: WhisperModel,
-) -> Result<(), ApiError> {
+) -> Result<(), SidecarError> {
     let parent = match model_path.parent() {
         Some(parent) => parent,
         None => return Ok(()),
@@ -529,24 +870,18 @@ async fn remove_partial_model_downloads(
         Ok(entries) => entries,
         Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
         Err(err) => {
-            return Err(ApiError::internal(
-                "model_delete_failed",
-                format!(
-                    "failed to inspect model directory for '{}': {err}",
-                    model.as_slug()
-                ),
-            ));
+            return Err(SidecarError::ModelDeleteFailed(format!(
+                "failed to inspect model directory for '{}': {err}",
+                model.as_slug()
+            )));
         }
     };
 
     while let Some(entry) = entries.next_entry().await.map_err(|err| {
-        ApiError::internal(
-            "model_delete_failed",
-            format!(
-                "failed to enumerate partial model downloads for '{}': {err}",
-                model.as_slug()
-            ),
-        )
+        SidecarError::ModelDeleteFailed(format!(
+            "failed to enumerate partial model downloads for '{}': {err}",
+            model.as_slug()
+        ))
     })? {
         let file_name = match entry.file_name().to_str() {
             Some(value) => value.to_string(),
@@ -562,14 +897,11 @@ async fn remove_partial_model_downloads(
             Ok(_) => {}
             Err(err) if err.kind() == ErrorKind::NotFound => {}
             Err(err) => {
-                return Err(ApiError::internal(
-                    "model_delete_failed",
-                    format!(
-                        "failed to delete partial model file '{}' for '{}': {err}",
-                        partial_path.display(),
-                        model.as_slug()
-                    ),
-                ));
+                return Err(SidecarError::ModelDeleteFailed(format!(
+                    "failed to delete partial model file '{}' for '{}': {err}",
+                    partial_path.display(),
+                    model.as_slug()
+                )));
             }
         }
     }
@@ -577,31 +909,6 @@ async fn remove_partial_model_downloads(
     Ok(())
 }
 
-fn map_transcription_error(model: WhisperModel, error: String) -> ApiError {
-    let lower = error.to_ascii_lowercase();
-
-    if lower.contains("sample")
-        || lower.contains("language")
-        || lower.contains("prompt")
-        || lower.contains("session")
-    {
-        ApiError::bad_request("invalid_transcription_request", error)
-    } else if lower.contains("device") {
-        ApiError::bad_request("invalid_device", error)
-    } else if lower.contains("model") && lower.contains("failed") {
-        ApiError::not_found(
-            "model_not_downloaded",
-            format!(
-                "model '{}' is not downloaded; call /v1/models/{}/download first",
-                model.as_slug(),
-                model.as_slug()
-            ),
-        )
-    } else {
-        ApiError::internal("transcription_failed", error)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use axum::body::Body;
@@ -623,6 +930,10 @@ mod tests {
             host: "127.0.0.1".parse().expect("valid ip"),
             port: 0,
             models_dir: temp_dir,
+            socket_path: None,
+            import_dirs: Vec::new(),
+            preprocessing: None,
+            log_transcripts: false,
         })
         .expect("failed to build app state")
     }
@@ -643,6 +954,37 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn responses_echo_or_generate_request_id() {
+        let app = create_router(test_state());
+
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/health")
+                    .header("x-request-id", "desktop-42")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["x-request-id"], "desktop-42");
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/health")
+                    .header("x-request-id", "not valid")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let generated = response.headers()["x-request-id"].to_str().unwrap();
+        assert!(Uuid::parse_str(generated).is_ok());
+    }
+
     #[tokio::test]
     async fn status_endpoint_rejects_unknown_model() {
         let app = create_router(test_state());
@@ -691,6 +1033,158 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn benchmark_endpoint_reports_no_results_without_models() {
+        let app = create_router(test_state());
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/benchmark")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"clipSeconds":600}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = http_body_util::BodyExt::collect(response.into_body())
+            .await
+            .unwrap()
+            .to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["mode"], "cpu");
+        assert_eq!(body["clipSeconds"], 120);
+        assert_eq!(body["results"], serde_json::json!([]));
+        assert!(body["recommendation"].is_null());
+    }
+
+    #[tokio::test]
+    async fn transcribe_reports_corrupt_model_as_load_failure() {
+        let state = test_state();
+        std::fs::write(state.model_path(WhisperModel::Tiny), b"not a ggml model")
+            .expect("failed to write fake model");
+
+        let app = create_router(state);
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/transcriptions")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        r#"{"model":"tiny","samples":[0.1,-0.1,0.0],"sampleRate":16000}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body = http_body_util::BodyExt::collect(response.into_body())
+            .await
+            .unwrap()
+            .to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "model_load_failed");
+        assert_eq!(body["error"]["retryable"], false);
+    }
+
+    fn tiny_ggml_header() -> Vec<u8> {
+        let mut bytes = 0x6767_6d6c_u32.to_le_bytes().to_vec();
+        for value in [51_865_i32, 1500, 384, 6, 4, 448, 384, 6, 4, 80, 1] {
+            bytes.extend(value.to_le_bytes());
+        }
+        bytes
+    }
+
+    async fn import_upload(app: Router, model: &str, body: Vec<u8>) -> axum::response::Response {
+        app.oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/models/import?model={model}"))
+                .header("content-type", "application/octet-stream")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn import_endpoint_registers_uploaded_model() {
+        let state = test_state();
+        let model_path = state.model_path(WhisperModel::Tiny);
+        let header = tiny_ggml_header();
+        let app = create_router(state);
+
+        let response = import_upload(app, "tiny", header.clone()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = http_body_util::BodyExt::collect(response.into_body())
+            .await
+            .unwrap()
+            .to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["fileBytes"], header.len());
+        assert_eq!(body["fileType"], "f16");
+        assert_eq!(body["sha256"].as_str().unwrap().len(), 64);
+        assert_eq!(std::fs::read(model_path).unwrap(), header);
+    }
+
+    #[tokio::test]
+    async fn import_endpoint_rejects_invalid_or_mismatched_files() {
+        let state = test_state();
+        let model_path = state.model_path(WhisperModel::Medium);
+        let app = create_router(state);
+
+        let response = import_upload(app.clone(), "medium", b"not a model".to_vec()).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let response = import_upload(app, "medium", tiny_ggml_header()).await;
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(!model_path.exists());
+    }
+
+    #[tokio::test]
+    async fn import_endpoint_rejects_paths_without_allowed_dirs() {
+        let app = create_router(test_state());
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/models/import")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"model":"tiny","path":"/etc/passwd"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn quantize_endpoint_requires_downloaded_model() {
+        let app = create_router(test_state());
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/v1/models/small/quantize")
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"quantization":"q5_0"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn delete_endpoint_handles_missing_model() {
         let app = create_router(test_state());
@@ -0,0 +1,674 @@
+use std::fmt;
+
+const OPUS_SAMPLE_RATES: [u32; 5] = [8_000, 12_000, 16_000, 24_000, 48_000];
+const OPUS_MAX_FRAME_SAMPLES_PER_CHANNEL: usize = 5_760;
+const OPUS_HEAD_MAGIC: &[u8] = b"OpusHead";
+const OPUS_TAGS_MAGIC: &[u8] = b"OpusTags";
+
+/// Audio formats accepted for session chunk uploads, negotiated per request
+/// through `Content-Type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkEncoding {
+    F32Le,
+    L16,
+    OggOpus,
+    WebmOpus,
+}
+
+impl ChunkEncoding {
+    /// Parses a `Content-Type` header value. A missing header or
+    /// `application/octet-stream` keeps the original raw f32le behavior.
+    pub fn from_content_type(value: Option<&str>) -> Result<Self, String> {
+        let Some(value) = value else {
+            return Ok(Self::F32Le);
+        };
+
+        let mut parts = value.split(';').map(str::trim);
+        let essence = parts.next().unwrap_or_default().to_ascii_lowercase();
+        let params: Vec<(String, String)> = parts
+            .filter_map(|param| param.split_once('='))
+            .map(|(key, value)| {
+                (
+                    key.trim().to_ascii_lowercase(),
+                    value.trim().trim_matches('"').to_ascii_lowercase(),
+                )
+            })
+            .collect();
+        let param = |name: &str| {
+            params
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+
+        match essence.as_str() {
+            "" | "application/octet-stream" => Ok(Self::F32Le),
+            "audio/pcm" => match param("format") {
+                None | Some("f32le") => Ok(Self::F32Le),
+                Some("s16le") => Ok(Self::L16),
+                Some(other) => Err(format!("unsupported audio/pcm format '{other}'")),
+            },
+            "audio/l16" => Ok(Self::L16),
+            "audio/ogg" | "audio/opus" => match param("codecs") {
+                None | Some("opus") => Ok(Self::OggOpus),
+                Some(other) => Err(format!("unsupported audio/ogg codec '{other}'")),
+            },
+            "audio/webm" => match param("codecs") {
+                None | Some("opus") => Ok(Self::WebmOpus),
+                Some(other) => Err(format!("unsupported audio/webm codec '{other}'")),
+            },
+            other => Err(format!(
+                "unsupported Content-Type '{other}'; expected application/octet-stream, audio/pcm;format=f32le, audio/L16, audio/ogg;codecs=opus or audio/webm;codecs=opus"
+            )),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::F32Le => "f32le",
+            Self::L16 => "l16",
+            Self::OggOpus => "ogg-opus",
+            Self::WebmOpus => "webm-opus",
+        }
+    }
+}
+
+/// Per-session decoder that turns uploaded chunks into mono samples at the
+/// session sample rate. PCM chunks carry an incomplete trailing frame into
+/// the next chunk; compressed streams keep their container and Opus state
+/// across chunks. Either way frames may be split arbitrarily between uploads.
+pub struct ChunkDecoder {
+    sample_rate: u32,
+    channels: usize,
+    pcm_remainder: Vec<f32>,
+    stream: Option<CompressedStream>,
+}
+
+struct CompressedStream {
+    encoding: ChunkEncoding,
+    demuxer: Demuxer,
+    opus: OpusStream,
+}
+
+enum Demuxer {
+    Ogg(OggDemuxer),
+    Webm(WebmDemuxer),
+}
+
+impl fmt::Debug for ChunkDecoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkDecoder")
+            .field("sample_rate", &self.sample_rate)
+            .field("channels", &self.channels)
+            .field("pcm_remainder", &self.pcm_remainder.len())
+            .field(
+                "stream",
+                &self.stream.as_ref().map(|stream| stream.encoding.as_str()),
+            )
+            .finish()
+    }
+}
+
+impl ChunkDecoder {
+    pub fn new(sample_rate: u32, channels: u16) -> Self {
+        Self {
+            sample_rate,
+            channels: usize::from(channels.max(1)),
+            pcm_remainder: Vec::new(),
+            stream: None,
+        }
+    }
+
+    pub fn decode(&mut self, encoding: ChunkEncoding, bytes: &[u8]) -> Result<Vec<f32>, String> {
+        if let Some(stream) = self.stream.as_ref() {
+            if stream.encoding != encoding {
+                return Err(format!(
+                    "session is streaming {} audio; cannot switch to {} mid-session",
+                    stream.encoding.as_str(),
+                    encoding.as_str()
+                ));
+            }
+        }
+
+        match encoding {
+            ChunkEncoding::F32Le => {
+                let mut samples = self.downmix_pcm(decode_f32le_samples(bytes)?);
+                samples.retain(|value| value.is_finite());
+                Ok(samples)
+            }
+            ChunkEncoding::L16 => Ok(self.downmix_pcm(decode_l16_samples(bytes)?)),
+            ChunkEncoding::OggOpus | ChunkEncoding::WebmOpus => {
+                if !OPUS_SAMPLE_RATES.contains(&self.sample_rate) {
+                    return Err(format!(
+                        "Opus streams require a session sampleRate of 8000, 12000, 16000, 24000 or 48000; got {}",
+                        self.sample_rate
+                    ));
+                }
+
+                let sample_rate = self.sample_rate;
+                let channels = self.channels;
+                let stream = self.stream.get_or_insert_with(|| CompressedStream {
+                    encoding,
+                    demuxer: match encoding {
+                        ChunkEncoding::WebmOpus => Demuxer::Webm(WebmDemuxer::default()),
+                        _ => Demuxer::Ogg(OggDemuxer::default()),
+                    },
+                    opus: OpusStream::new(sample_rate, channels),
+                });
+                stream.decode(bytes)
+            }
+        }
+    }
+
+    /// Downmixes the complete frames of the chunk, keeping a trailing partial
+    /// frame so the next chunk starts on the right channel.
+    fn downmix_pcm(&mut self, samples: Vec<f32>) -> Vec<f32> {
+        let mut interleaved = std::mem::take(&mut self.pcm_remainder);
+        interleaved.extend(samples);
+        let complete = interleaved.len() - interleaved.len() % self.channels;
+        self.pcm_remainder = interleaved.split_off(complete);
+        downmix(&interleaved, self.channels)
+    }
+}
+
+impl CompressedStream {
+    fn decode(&mut self, bytes: &[u8]) -> Result<Vec<f32>, String> {
+        let mut samples = Vec::new();
+        match &mut self.demuxer {
+            Demuxer::Ogg(demuxer) => {
+                for packet in demuxer.push(bytes)? {
+                    if packet.starts_with(OPUS_HEAD_MAGIC) {
+                        self.opus.apply_head(&packet)?;
+                    } else if packet.starts_with(OPUS_TAGS_MAGIC) {
+                        continue;
+                    } else {
+                        samples.extend(self.opus.decode_packet(&packet)?);
+                    }
+                }
+            }
+            Demuxer::Webm(demuxer) => {
+                let frames = demuxer.push(bytes)?;
+                if let Some(codec_private) = demuxer.codec_private.take() {
+                    if codec_private.starts_with(OPUS_HEAD_MAGIC) {
+                        self.opus.apply_head(&codec_private)?;
+                    }
+                }
+                for frame in frames {
+                    samples.extend(self.opus.decode_packet(&frame)?);
+                }
+            }
+        }
+        Ok(samples)
+    }
+}
+
+pub fn decode_f32le_samples(bytes: &[u8]) -> Result<Vec<f32>, String> {
+    if bytes.len() % std::mem::size_of::<f32>() != 0 {
+        return Err("audio chunk byte length must be a multiple of 4".to_string());
+    }
+
+    Ok(bytes
+        .chunks_exact(std::mem::size_of::<f32>())
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect())
+}
+
+pub fn decode_l16_samples(bytes: &[u8]) -> Result<Vec<f32>, String> {
+    if bytes.len() % std::mem::size_of::<i16>() != 0 {
+        return Err("audio/L16 chunk byte length must be a multiple of 2".to_string());
+    }
+
+    Ok(bytes
+        .chunks_exact(std::mem::size_of::<i16>())
+        .map(|chunk| f32::from(i16::from_le_bytes([chunk[0], chunk[1]])) / 32_768.0)
+        .collect())
+}
+
+fn downmix(interleaved: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved.to_vec();
+    }
+
+    interleaved
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+struct OpusStream {
+    sample_rate: u32,
+    channels: usize,
+    pre_skip_remaining: usize,
+    decoder: Option<opus::Decoder>,
+}
+
+impl OpusStream {
+    fn new(sample_rate: u32, channels: usize) -> Self {
+        Self {
+            sample_rate,
+            channels: channels.clamp(1, 2),
+            pre_skip_remaining: 0,
+            decoder: None,
+        }
+    }
+
+    /// Applies the `OpusHead` identification header (RFC 7845 §5.1).
+    fn apply_head(&mut self, head: &[u8]) -> Result<(), String> {
+        if head.len() < 19 {
+            return Err("OpusHead header is truncated".to_string());
+        }
+        if self.decoder.is_some() {
+            return Err("unexpected OpusHead after audio frames".to_string());
+        }
+
+        let channels = usize::from(head[9]);
+        if !(1..=2).contains(&channels) {
+            return Err(format!(
+                "unsupported Opus channel count {channels}; only mono and stereo are supported"
+            ));
+        }
+
+        let pre_skip_48k = usize::from(u16::from_le_bytes([head[10], head[11]]));
+        self.channels = channels;
+        self.pre_skip_remaining = pre_skip_48k * self.sample_rate as usize / 48_000;
+        Ok(())
+    }
+
+    fn decode_packet(&mut self, packet: &[u8]) -> Result<Vec<f32>, String> {
+        if packet.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let channels = self.channels;
+        if self.decoder.is_none() {
+            let layout = if channels == 2 {
+                opus::Channels::Stereo
+            } else {
+                opus::Channels::Mono
+            };
+            let decoder = opus::Decoder::new(self.sample_rate, layout)
+                .map_err(|err| format!("failed to initialize opus decoder: {err}"))?;
+            self.decoder = Some(decoder);
+        }
+        let decoder = self
+            .decoder
+            .as_mut()
+            .expect("opus decoder initialized above");
+
+        let mut output = vec![0.0_f32; OPUS_MAX_FRAME_SAMPLES_PER_CHANNEL * channels];
+        let frames = decoder
+            .decode_float(packet, &mut output, false)
+            .map_err(|err| format!("failed to decode opus frame: {err}"))?;
+        output.truncate(frames * channels);
+
+        let mut mono = downmix(&output, channels);
+        let skip = self.pre_skip_remaining.min(mono.len());
+        self.pre_skip_remaining -= skip;
+        mono.drain(..skip);
+        Ok(mono)
+    }
+}
+
+/// Incremental Ogg page parser (RFC 3533) that reassembles packets across
+/// page and chunk boundaries.
+#[derive(Default)]
+struct OggDemuxer {
+    buffer: Vec<u8>,
+    packet: Vec<u8>,
+}
+
+impl OggDemuxer {
+    fn push(&mut self, bytes: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+        self.buffer.extend_from_slice(bytes);
+        let mut packets = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let page = &self.buffer[offset..];
+            if page.len() < 27 {
+                break;
+            }
+            if &page[..4] != b"OggS" {
+                return Err("invalid Ogg page: missing capture pattern".to_string());
+            }
+
+            let segment_count = usize::from(page[26]);
+            if page.len() < 27 + segment_count {
+                break;
+            }
+
+            let lacing = &page[27..27 + segment_count];
+            let body_len: usize = lacing.iter().map(|value| usize::from(*value)).sum();
+            let page_len = 27 + segment_count + body_len;
+            if page.len() < page_len {
+                break;
+            }
+
+            let mut body_offset = 27 + segment_count;
+            for value in lacing {
+                let len = usize::from(*value);
+                self.packet
+                    .extend_from_slice(&page[body_offset..body_offset + len]);
+                body_offset += len;
+                if len < 255 {
+                    packets.push(std::mem::take(&mut self.packet));
+                }
+            }
+
+            offset += page_len;
+        }
+
+        self.buffer.drain(..offset);
+        Ok(packets)
+    }
+}
+
+const EBML_ID_SEGMENT: u32 = 0x1853_8067;
+const EBML_ID_CLUSTER: u32 = 0x1F43_B675;
+const EBML_ID_TRACKS: u32 = 0x1654_AE6B;
+const EBML_ID_TRACK_ENTRY: u32 = 0xAE;
+const EBML_ID_BLOCK_GROUP: u32 = 0xA0;
+const EBML_ID_BLOCK: u32 = 0xA1;
+const EBML_ID_SIMPLE_BLOCK: u32 = 0xA3;
+const EBML_ID_CODEC_PRIVATE: u32 = 0x63A2;
+
+/// Incremental WebM (Matroska) demuxer. It descends into the master elements
+/// that lead to audio blocks and skips everything else, so live streams with
+/// unknown-size segments and clusters (as produced by `MediaRecorder`) work.
+#[derive(Default)]
+struct WebmDemuxer {
+    buffer: Vec<u8>,
+    skip_remaining: usize,
+    codec_private: Option<Vec<u8>>,
+}
+
+impl WebmDemuxer {
+    fn push(&mut self, bytes: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+        let mut input = bytes;
+        if self.skip_remaining > 0 {
+            let skipped = self.skip_remaining.min(input.len());
+            self.skip_remaining -= skipped;
+            input = &input[skipped..];
+        }
+        self.buffer.extend_from_slice(input);
+
+        let mut frames = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let Some((id, id_len)) = read_element_id(&self.buffer[offset..])? else {
+                break;
+            };
+            let Some((size, size_len)) = read_element_size(&self.buffer[offset + id_len..])? else {
+                break;
+            };
+            let header_len = id_len + size_len;
+
+            match id {
+                EBML_ID_SEGMENT | EBML_ID_CLUSTER | EBML_ID_TRACKS | EBML_ID_TRACK_ENTRY
+                | EBML_ID_BLOCK_GROUP => {
+                    offset += header_len;
+                }
+                EBML_ID_SIMPLE_BLOCK | EBML_ID_BLOCK | EBML_ID_CODEC_PRIVATE => {
+                    let size = size.ok_or("WebM block has unknown size")?;
+                    let end = offset + header_len + size;
+                    if self.buffer.len() < end {
+                        break;
+                    }
+
+                    let payload = &self.buffer[offset + header_len..end];
+                    if id == EBML_ID_CODEC_PRIVATE {
+                        self.codec_private = Some(payload.to_vec());
+                    } else {
+                        frames.push(block_frame(payload)?.to_vec());
+                    }
+                    offset = end;
+                }
+                _ => {
+                    let size = size.ok_or("WebM element has unknown size")?;
+                    let available = self.buffer.len() - offset - header_len;
+                    if available < size {
+                        self.skip_remaining = size - available;
+                        offset = self.buffer.len();
+                        break;
+                    }
+                    offset += header_len + size;
+                }
+            }
+        }
+
+        self.buffer.drain(..offset);
+        Ok(frames)
+    }
+}
+
+fn block_frame(payload: &[u8]) -> Result<&[u8], String> {
+    let (_, track_len) =
+        read_vint(payload, false)?.ok_or("WebM block is missing a track number")?;
+    let header_len = track_len + 3;
+    if payload.len() < header_len {
+        return Err("WebM block header is truncated".to_string());
+    }
+
+    let flags = payload[track_len + 2];
+    if flags & 0x06 != 0 {
+        return Err("laced WebM blocks are not supported".to_string());
+    }
+
+    Ok(&payload[header_len..])
+}
+
+fn read_element_id(bytes: &[u8]) -> Result<Option<(u32, usize)>, String> {
+    match read_vint(bytes, true)? {
+        Some((value, len)) if len <= 4 => Ok(Some((value as u32, len))),
+        Some(_) => Err("invalid WebM element id".to_string()),
+        None => Ok(None),
+    }
+}
+
+/// Returns `Some(None)` for the reserved "unknown size" value.
+fn read_element_size(bytes: &[u8]) -> Result<Option<(Option<usize>, usize)>, String> {
+    let Some((value, len)) = read_vint(bytes, false)? else {
+        return Ok(None);
+    };
+
+    let unknown = (1_u64 << (7 * len)) - 1;
+    if value == unknown {
+        Ok(Some((None, len)))
+    } else {
+        let size = usize::try_from(value).map_err(|_| "WebM element is too large".to_string())?;
+        Ok(Some((Some(size), len)))
+    }
+}
+
+fn read_vint(bytes: &[u8], keep_marker: bool) -> Result<Option<(u64, usize)>, String> {
+    let Some(first) = bytes.first().copied() else {
+        return Ok(None);
+    };
+    if first == 0 {
+        return Err("invalid EBML variable-length integer".to_string());
+    }
+
+    let len = first.leading_zeros() as usize + 1;
+    if bytes.len() < len {
+        return Ok(None);
+    }
+
+    let mut value = if keep_marker {
+        u64::from(first)
+    } else {
+        u64::from(first) & (0xFF_u64 >> len)
+    };
+    for byte in &bytes[1..len] {
+        value = (value << 8) | u64::from(*byte);
+    }
+
+    Ok(Some((value, len)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ogg_page(packets: &[&[u8]]) -> Vec<u8> {
+        let mut lacing = Vec::new();
+        let mut body = Vec::new();
+        for packet in packets {
+            let mut remaining = packet.len();
+            loop {
+                let value = remaining.min(255);
+                lacing.push(value as u8);
+                remaining -= value;
+                if value < 255 {
+                    break;
+                }
+            }
+            body.extend_from_slice(packet);
+        }
+
+        let mut page = b"OggS".to_vec();
+        page.extend_from_slice(&[0; 22]);
+        page.push(lacing.len() as u8);
+        page.extend_from_slice(&lacing);
+        page.extend_from_slice(&body);
+        page
+    }
+
+    #[test]
+    fn parses_content_types() {
+        assert_eq!(
+            ChunkEncoding::from_content_type(None).unwrap(),
+            ChunkEncoding::F32Le
+        );
+        assert_eq!(
+            ChunkEncoding::from_content_type(Some("application/octet-stream")).unwrap(),
+            ChunkEncoding::F32Le
+        );
+        assert_eq!(
+            ChunkEncoding::from_content_type(Some("audio/pcm; format=f32le")).unwrap(),
+            ChunkEncoding::F32Le
+        );
+        assert_eq!(
+            ChunkEncoding::from_content_type(Some("audio/L16;rate=16000")).unwrap(),
+            ChunkEncoding::L16
+        );
+        assert_eq!(
+            ChunkEncoding::from_content_type(Some("audio/ogg; codecs=\"opus\"")).unwrap(),
+            ChunkEncoding::OggOpus
+        );
+        assert_eq!(
+            ChunkEncoding::from_content_type(Some("audio/webm;codecs=opus")).unwrap(),
+            ChunkEncoding::WebmOpus
+        );
+        assert!(ChunkEncoding::from_content_type(Some("audio/mpeg")).is_err());
+        assert!(ChunkEncoding::from_content_type(Some("audio/webm;codecs=vorbis")).is_err());
+    }
+
+    #[test]
+    fn decodes_l16_and_downmixes_stereo() {
+        let mut decoder = ChunkDecoder::new(16_000, 2);
+        let bytes: Vec<u8> = [16_384_i16, 0, -32_768, -32_768]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+
+        let samples = decoder.decode(ChunkEncoding::L16, &bytes).unwrap();
+        assert_eq!(samples, vec![0.25, -1.0]);
+    }
+
+    #[test]
+    fn carries_partial_frames_between_pcm_chunks() {
+        let mut decoder = ChunkDecoder::new(16_000, 2);
+        let bytes = |values: &[f32]| -> Vec<u8> {
+            values
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect()
+        };
+
+        let first = decoder
+            .decode(ChunkEncoding::F32Le, &bytes(&[0.5, 0.5, 0.25]))
+            .unwrap();
+        assert_eq!(first, vec![0.5]);
+
+        let second = decoder
+            .decode(ChunkEncoding::F32Le, &bytes(&[0.75]))
+            .unwrap();
+        assert_eq!(second, vec![0.5]);
+
+        let third = decoder
+            .decode(ChunkEncoding::F32Le, &bytes(&[-1.0, -1.0]))
+            .unwrap();
+        assert_eq!(third, vec![-1.0]);
+    }
+
+    #[test]
+    fn rejects_misaligned_pcm_chunks() {
+        assert!(decode_f32le_samples(&[0, 0, 0]).is_err());
+        assert!(decode_l16_samples(&[0]).is_err());
+    }
+
+    #[test]
+    fn drops_non_finite_f32_frames() {
+        let bytes: Vec<u8> = [0.5_f32, 0.5, f32::NAN, 0.0, -0.5, -0.5]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+
+        let mut decoder = ChunkDecoder::new(16_000, 2);
+        let samples = decoder.decode(ChunkEncoding::F32Le, &bytes).unwrap();
+        assert_eq!(samples, vec![0.5, -0.5]);
+    }
+
+    #[test]
+    fn ogg_demuxer_reassembles_packets_across_chunks() {
+        let long_packet = vec![7_u8; 300];
+        let mut stream = ogg_page(&[b"OpusHead-like", &long_packet]);
+        stream.extend(ogg_page(&[b"second"]));
+
+        let mut demuxer = OggDemuxer::default();
+        let mut packets = Vec::new();
+        for chunk in stream.chunks(11) {
+            packets.extend(demuxer.push(chunk).unwrap());
+        }
+
+        assert_eq!(packets.len(), 3);
+        assert_eq!(packets[0], b"OpusHead-like");
+        assert_eq!(packets[1], long_packet);
+        assert_eq!(packets[2], b"second");
+    }
+
+    #[test]
+    fn webm_demuxer_extracts_simple_blocks_from_unknown_size_cluster() {
+        let mut stream = vec![0x1A, 0x45, 0xDF, 0xA3, 0x82, 0x42, 0x86];
+        stream.extend([
+            0x18, 0x53, 0x80, 0x67, 0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+        ]);
+        stream.extend([0x16, 0x54, 0xAE, 0x6B, 0x88, 0xAE, 0x86, 0x63, 0xA2, 0x83]);
+        stream.extend(b"abc");
+        stream.extend([
+            0x1F, 0x43, 0xB6, 0x75, 0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+        ]);
+        stream.extend([0xE7, 0x81, 0x00]);
+        stream.extend([0xA3, 0x87, 0x81, 0x00, 0x00, 0x80, 0x01, 0x02, 0x03]);
+        stream.extend([0xA3, 0x86, 0x81, 0x00, 0x14, 0x80, 0x04, 0x05]);
+
+        let mut demuxer = WebmDemuxer::default();
+        let mut frames = Vec::new();
+        for chunk in stream.chunks(5) {
+            frames.extend(demuxer.push(chunk).unwrap());
+        }
+
+        assert_eq!(frames, vec![vec![1, 2, 3], vec![4, 5]]);
+        assert_eq!(demuxer.codec_private.as_deref(), Some(&b"abc"[..]));
+    }
+
+    #[test]
+    fn rejects_switching_encodings_mid_session() {
+        let mut decoder = ChunkDecoder::new(16_000, 1);
+        decoder
+            .decode(ChunkEncoding::OggOpus, &ogg_page(&[]))
+            .unwrap();
+        assert!(decoder.decode(ChunkEncoding::L16, &[0, 0]).is_err());
+    }
+}
@@ -0,0 +1,392 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::SidecarError;
+use crate::models::WhisperModel;
+use crate::state::AppState;
+
+const BENCHMARK_SAMPLE_RATE: u32 = 16_000;
+const DEFAULT_CLIP_SECONDS: u32 = 10;
+const MAX_CLIP_SECONDS: u32 = 120;
+
+/// A model is only recommended when it transcribes at least twice as fast as
+/// real time, which leaves headroom for longer dictations and background load.
+const RECOMMENDED_MAX_REAL_TIME_FACTOR: f64 = 0.5;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkOptions {
+    pub models: Option<Vec<WhisperModel>>,
+    pub device_ids: Option<Vec<String>>,
+    pub clip_seconds: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkResult {
+    pub model: WhisperModel,
+    pub device_id: String,
+    pub device_name: String,
+    pub load_ms: Option<u128>,
+    pub transcribe_ms: Option<u128>,
+    pub real_time_factor: Option<f64>,
+    /// Peak resident memory of the whole sidecar process during the run, so
+    /// requests served alongside it are included. `None` when the peak
+    /// could not be reset before the run.
+    pub peak_rss_bytes: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkRecommendation {
+    pub model: WhisperModel,
+    pub device_id: String,
+    pub device_name: String,
+    pub real_time_factor: f64,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkReport {
+    pub mode: &'static str,
+    pub clip_seconds: u32,
+    pub results: Vec<BenchmarkResult>,
+    pub recommendation: Option<BenchmarkRecommendation>,
+}
+
+pub async fn run_benchmark(
+    state: &AppState,
+    options: BenchmarkOptions,
+) -> Result<BenchmarkReport, SidecarError> {
+    let clip_seconds = options
+        .clip_seconds
+        .unwrap_or(DEFAULT_CLIP_SECONDS)
+        .clamp(1, MAX_CLIP_SECONDS);
+    let clip = Arc::new(synthetic_clip(clip_seconds));
+    let audio_seconds = clip.len() as f64 / f64::from(BENCHMARK_SAMPLE_RATE);
+
+    let devices = state.transcriber.list_devices().await?;
+    let devices: Vec<_> = match options.device_ids.as_ref() {
+        Some(requested) => {
+            if let Some(unknown) = requested
+                .iter()
+                .find(|id| !devices.iter().any(|device| &device.id == *id))
+            {
+                return Err(SidecarError::InvalidDevice(format!(
+                    "unsupported deviceId '{unknown}'"
+                )));
+            }
+            devices
+                .into_iter()
+                .filter(|device| requested.contains(&device.id))
+                .collect()
+        }
+        None => devices,
+    };
+
+    let candidates = options
+        .models
+        .clone()
+        .unwrap_or_else(|| WhisperModel::all().to_vec());
+
+    let mut results = Vec::new();
+    for model in candidates {
+        let model_path = state.model_path(model);
+        if !is_downloaded(&model_path).await {
+            continue;
+        }
+
+        for device in &devices {
+            tracing::debug!(
+                model = model.as_slug(),
+                device = %device.id,
+                "running transcription benchmark"
+            );
+
+            let peak_was_reset = reset_peak_rss();
+            let run = state
+                .transcriber
+                .benchmark(model_path.clone(), device.id.clone(), clip.clone())
+                .await;
+            // A peak that was not reset is the process lifetime peak, which
+            // says nothing about this run.
+            let peak_rss_bytes = peak_was_reset.then(read_peak_rss_bytes).flatten();
+
+            results.push(match run {
+                Ok(run) => BenchmarkResult {
+                    model,
+                    device_id: run.device_id,
+                    device_name: run.device_name,
+                    load_ms: Some(run.load_duration.as_millis()),
+                    transcribe_ms: Some(run.inference_duration.as_millis()),
+                    real_time_factor: Some(run.inference_duration.as_secs_f64() / audio_seconds),
+                    peak_rss_bytes,
+                    error: None,
+                },
+                Err(err) => BenchmarkResult {
+                    model,
+                    device_id: device.id.clone(),
+                    device_name: device.name.clone(),
+                    load_ms: None,
+                    transcribe_ms: None,
+                    real_time_factor: None,
+                    peak_rss_bytes,
+                    error: Some(err.to_string()),
+                },
+            });
+        }
+    }
+
+    let recommendation = recommend(&results);
+
+    Ok(BenchmarkReport {
+        mode: state.config.mode.as_str(),
+        clip_seconds,
+        results,
+        recommendation,
+    })
+}
+
+pub fn format_table(report: &BenchmarkReport) -> String {
+    let mut output = format!(
+        "{:<16} {:<28} {:>10} {:>12} {:>8} {:>12}\n",
+        "MODEL", "DEVICE", "LOAD", "TRANSCRIBE", "RTF", "PEAK RSS"
+    );
+
+    for result in &report.results {
+        let device = format!("{} ({})", result.device_name, result.device_id);
+        match &result.error {
+            Some(err) => output.push_str(&format!(
+                "{:<16} {:<28} failed: {err}\n",
+                result.model.as_slug(),
+                device
+            )),
+            None => output.push_str(&format!(
+                "{:<16} {:<28} {:>10} {:>12} {:>8} {:>12}\n",
+                result.model.as_slug(),
+                device,
+                format_ms(result.load_ms),
+                format_ms(result.transcribe_ms),
+                result
+                    .real_time_factor
+                    .map(|rtf| format!("{rtf:.2}"))
+                    .unwrap_or_else(|| "-".to_string()),
+                result
+                    .peak_rss_bytes
+                    .map(|bytes| format!("{} MB", bytes / (1024 * 1024)))
+                    .unwrap_or_else(|| "-".to_string()),
+            )),
+        }
+    }
+
+    if report.results.is_empty() {
+        output.push_str("no downloaded models to benchmark\n");
+    }
+
+    match &report.recommendation {
+        Some(recommendation) => output.push_str(&format!(
+            "\nrecommended: {} on {} ({})\n",
+            recommendation.model.as_slug(),
+            recommendation.device_name,
+            recommendation.reason
+        )),
+        None => output.push_str("\nno recommendation available\n"),
+    }
+
+    output
+}
+
+fn format_ms(value: Option<u128>) -> String {
+    value
+        .map(|ms| format!("{ms} ms"))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn recommend(results: &[BenchmarkResult]) -> Option<BenchmarkRecommendation> {
+    let measured: Vec<(&BenchmarkResult, u8, f64)> = results
+        .iter()
+        .filter_map(|result| {
+            let rank = quality_rank(result.model)?;
+            let rtf = result.real_time_factor?;
+            Some((result, rank, rtf))
+        })
+        .collect();
+
+    let best_within_budget = measured
+        .iter()
+        .filter(|(_, _, rtf)| *rtf <= RECOMMENDED_MAX_REAL_TIME_FACTOR)
+        .max_by(|(_, rank_a, rtf_a), (_, rank_b, rtf_b)| {
+            rank_a.cmp(rank_b).then_with(|| rtf_b.total_cmp(rtf_a))
+        });
+
+    if let Some((result, _, rtf)) = best_within_budget {
+        return Some(BenchmarkRecommendation {
+            model: result.model,
+            device_id: result.device_id.clone(),
+            device_name: result.device_name.clone(),
+            real_time_factor: *rtf,
+            reason: format!(
+                "most accurate model that runs at least {:.0}x faster than real time",
+                1.0 / RECOMMENDED_MAX_REAL_TIME_FACTOR
+            ),
+        });
+    }
+
+    measured
+        .iter()
+        .min_by(|(_, _, rtf_a), (_, _, rtf_b)| rtf_a.total_cmp(rtf_b))
+        .map(|(result, _, rtf)| BenchmarkRecommendation {
+            model: result.model,
+            device_id: result.device_id.clone(),
+            device_name: result.device_name.clone(),
+            real_time_factor: *rtf,
+            reason: "fastest available model; none ran comfortably faster than real time"
+                .to_string(),
+        })
+}
+
+/// Relative accuracy of the general-purpose models. Specialized models are
+/// never recommended automatically.
+fn quality_rank(model: WhisperModel) -> Option<u8> {
+    match model {
+        WhisperModel::Tiny => Some(0),
+        WhisperModel::Base => Some(1),
+        WhisperModel::Small => Some(2),
+        WhisperModel::Medium => Some(3),
+        WhisperModel::Turbo => Some(4),
+        WhisperModel::Large => Some(5),
+        WhisperModel::Hindi2Hinglish => None,
+    }
+}
+
+/// Builds a deterministic speech-like clip: voiced syllables with a drifting
+/// pitch and a few harmonics, separated by short pauses and light noise.
+fn synthetic_clip(seconds: u32) -> Vec<f32> {
+    let sample_rate = BENCHMARK_SAMPLE_RATE as f32;
+    let total = (BENCHMARK_SAMPLE_RATE * seconds) as usize;
+    let mut noise_state: u32 = 0x1234_5678;
+    let mut phase = 0.0_f32;
+    let mut samples = Vec::with_capacity(total);
+
+    for index in 0..total {
+        let t = index as f32 / sample_rate;
+        let syllable_pos = (t * 4.0).fract();
+        let envelope = if syllable_pos < 0.7 {
+            (std::f32::consts::PI * syllable_pos / 0.7).sin()
+        } else {
+            0.0
+        };
+
+        let pitch = 140.0 + 30.0 * (t * 0.7).sin();
+        phase += 2.0 * std::f32::consts::PI * pitch / sample_rate;
+        if phase > 2.0 * std::f32::consts::PI {
+            phase -= 2.0 * std::f32::consts::PI;
+        }
+
+        let voiced = phase.sin() + 0.5 * (phase * 2.0).sin() + 0.25 * (phase * 3.0).sin();
+
+        noise_state = noise_state
+            .wrapping_mul(1_664_525)
+            .wrapping_add(1_013_904_223);
+        let noise = (noise_state >> 8) as f32 / (1u32 << 24) as f32 - 0.5;
+
+        samples.push(0.2 * envelope * voiced + 0.01 * noise);
+    }
+
+    samples
+}
+
+async fn is_downloaded(path: &std::path::Path) -> bool {
+    tokio::fs::metadata(path)
+        .await
+        .map(|meta| meta.is_file() && meta.len() > 0)
+        .unwrap_or(false)
+}
+
+/// Resets the kernel's high-water mark so the next reading reflects only the
+/// following run. Returns `false` when the kernel refused.
+#[cfg(target_os = "linux")]
+fn reset_peak_rss() -> bool {
+    std::fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn reset_peak_rss() -> bool {
+    false
+}
+
+#[cfg(target_os = "linux")]
+fn read_peak_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kib = line
+        .trim_start_matches("VmHWM:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_peak_rss_bytes() -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measured(model: WhisperModel, rtf: f64) -> BenchmarkResult {
+        BenchmarkResult {
+            model,
+            device_id: "cpu:0".to_string(),
+            device_name: "CPU".to_string(),
+            load_ms: Some(100),
+            transcribe_ms: Some(1_000),
+            real_time_factor: Some(rtf),
+            peak_rss_bytes: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn recommends_most_accurate_model_within_budget() {
+        let results = vec![
+            measured(WhisperModel::Tiny, 0.05),
+            measured(WhisperModel::Small, 0.3),
+            measured(WhisperModel::Large, 1.4),
+        ];
+
+        let recommendation = recommend(&results).expect("expected a recommendation");
+        assert_eq!(recommendation.model, WhisperModel::Small);
+    }
+
+    #[test]
+    fn falls_back_to_fastest_model_when_none_fit_budget() {
+        let results = vec![
+            measured(WhisperModel::Medium, 0.9),
+            measured(WhisperModel::Large, 1.8),
+        ];
+
+        let recommendation = recommend(&results).expect("expected a recommendation");
+        assert_eq!(recommendation.model, WhisperModel::Medium);
+    }
+
+    #[test]
+    fn never_recommends_specialized_models() {
+        let results = vec![measured(WhisperModel::Hindi2Hinglish, 0.1)];
+        assert!(recommend(&results).is_none());
+    }
+
+    #[test]
+    fn synthetic_clip_has_requested_length_and_range() {
+        let clip = synthetic_clip(2);
+        assert_eq!(clip.len(), 32_000);
+        assert!(clip.iter().all(|sample| sample.abs() <= 1.0));
+    }
+}
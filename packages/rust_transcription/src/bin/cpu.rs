@@ -1,21 +1,20 @@
-use rust_transcription::{run_server, ComputeMode};
+use rust_transcription::{init_tracing, run_benchmark_cli, run_server, ComputeMode};
 
 #[tokio::main]
 async fn main() {
     init_tracing();
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("bench") {
+        if let Err(err) = run_benchmark_cli(ComputeMode::Cpu, &args[1..]).await {
+            eprintln!("[rust-transcription-cpu] {err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     if let Err(err) = run_server(ComputeMode::Cpu).await {
         eprintln!("[rust-transcription-cpu] {err}");
         std::process::exit(1);
     }
 }
-
-fn init_tracing() {
-    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
-
-    let _ = tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .with_target(false)
-        .try_init();
-}
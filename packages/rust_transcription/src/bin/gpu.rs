@@ -1,4 +1,6 @@
-use rust_transcription::{ensure_gpu_runtime_available, run_server, ComputeMode};
+use rust_transcription::{
+    ensure_gpu_runtime_available, init_tracing, run_benchmark_cli, run_server, ComputeMode,
+};
 
 #[tokio::main]
 async fn main() {
@@ -9,18 +11,17 @@ async fn main() {
         std::process::exit(1);
     }
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("bench") {
+        if let Err(err) = run_benchmark_cli(ComputeMode::Gpu, &args[1..]).await {
+            eprintln!("[rust-transcription-gpu] {err}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     if let Err(err) = run_server(ComputeMode::Gpu).await {
         eprintln!("[rust-transcription-gpu] {err}");
         std::process::exit(1);
     }
 }
-
-fn init_tracing() {
-    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
-
-    let _ = tracing_subscriber::fmt()
-        .with_env_filter(filter)
-        .with_target(false)
-        .try_init();
-}
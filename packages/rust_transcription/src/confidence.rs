@@ -0,0 +1,139 @@
+use serde::Serialize;
+
+/// A decoded segment with whisper's per-token probabilities rolled up into
+/// words. `confidence` is the mean token probability across the segment.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SegmentConfidence {
+    pub text: String,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub confidence: f32,
+    pub words: Vec<WordConfidence>,
+}
+
+/// `confidence` is the lowest probability of the tokens making up the word,
+/// so a single doubtful sub-word is enough to flag it.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WordConfidence {
+    pub text: String,
+    pub confidence: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenProbability {
+    pub text: String,
+    pub probability: f32,
+}
+
+pub fn build_segment(
+    text: String,
+    start_ms: i64,
+    end_ms: i64,
+    tokens: &[TokenProbability],
+) -> SegmentConfidence {
+    let confidence = if tokens.is_empty() {
+        0.0
+    } else {
+        tokens.iter().map(|token| token.probability).sum::<f32>() / tokens.len() as f32
+    };
+
+    SegmentConfidence {
+        text,
+        start_ms,
+        end_ms,
+        confidence,
+        words: group_words(tokens),
+    }
+}
+
+/// Whisper's BPE tokens carry the leading space of the word they start, so a
+/// new word begins at every token with leading whitespace. Tokens without
+/// letters or digits (punctuation) stay attached to the preceding word.
+fn group_words(tokens: &[TokenProbability]) -> Vec<WordConfidence> {
+    let mut words: Vec<WordConfidence> = Vec::new();
+
+    for token in tokens {
+        let starts_word = token.text.starts_with(char::is_whitespace);
+        let is_punctuation = !token.text.chars().any(char::is_alphanumeric);
+        let trimmed = token.text.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        match words.last_mut() {
+            Some(word) if !starts_word || is_punctuation => {
+                word.text.push_str(trimmed);
+                if !is_punctuation {
+                    word.confidence = word.confidence.min(token.probability);
+                }
+            }
+            _ => words.push(WordConfidence {
+                text: trimmed.to_string(),
+                confidence: token.probability,
+            }),
+        }
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(text: &str, probability: f32) -> TokenProbability {
+        TokenProbability {
+            text: text.to_string(),
+            probability,
+        }
+    }
+
+    #[test]
+    fn groups_sub_word_tokens_and_keeps_lowest_probability() {
+        let tokens = [
+            token(" Hello", 0.98),
+            token(" Vo", 0.91),
+            token("qu", 0.42),
+            token("ill", 0.77),
+            token(".", 0.2),
+        ];
+
+        let words = group_words(&tokens);
+        assert_eq!(
+            words,
+            vec![
+                WordConfidence {
+                    text: "Hello".to_string(),
+                    confidence: 0.98,
+                },
+                WordConfidence {
+                    text: "Voquill.".to_string(),
+                    confidence: 0.42,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn segment_confidence_is_mean_token_probability() {
+        let segment = build_segment(
+            "Hi there".to_string(),
+            0,
+            1_200,
+            &[token(" Hi", 0.9), token(" there", 0.5)],
+        );
+
+        assert!((segment.confidence - 0.7).abs() < 1e-6);
+        assert_eq!(segment.words.len(), 2);
+        assert_eq!(segment.end_ms, 1_200);
+    }
+
+    #[test]
+    fn empty_segment_has_zero_confidence() {
+        let segment = build_segment(String::new(), 0, 0, &[]);
+        assert_eq!(segment.confidence, 0.0);
+        assert!(segment.words.is_empty());
+    }
+}
@@ -2,6 +2,7 @@ use std::net::IpAddr;
 use std::path::PathBuf;
 
 use crate::compute::ComputeMode;
+use crate::preprocessing::PreprocessingOptions;
 
 #[derive(Debug, Clone)]
 pub struct SidecarConfig {
@@ -9,6 +10,10 @@ pub struct SidecarConfig {
     pub host: IpAddr,
     pub port: u16,
     pub models_dir: PathBuf,
+    pub socket_path: Option<PathBuf>,
+    pub import_dirs: Vec<PathBuf>,
+    pub preprocessing: Option<PreprocessingOptions>,
+    pub log_transcripts: bool,
 }
 
 impl SidecarConfig {
@@ -32,6 +37,48 @@ impl SidecarConfig {
                     .join("models")
             });
 
+        let socket_path = std::env::var("RUST_TRANSCRIPTION_SOCKET")
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from);
+
+        if let Some(path) = socket_path.as_ref() {
+            if !path.is_absolute() {
+                return Err("RUST_TRANSCRIPTION_SOCKET must be an absolute path".to_string());
+            }
+
+            if !cfg!(unix) {
+                return Err(
+                    "RUST_TRANSCRIPTION_SOCKET is only supported on unix platforms".to_string(),
+                );
+            }
+        }
+
+        let import_dirs: Vec<PathBuf> = std::env::var_os("RUST_TRANSCRIPTION_IMPORT_DIRS")
+            .map(|value| {
+                std::env::split_paths(&value)
+                    .filter(|path| !path.as_os_str().is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if import_dirs.iter().any(|dir| !dir.is_absolute()) {
+            return Err(
+                "RUST_TRANSCRIPTION_IMPORT_DIRS must only contain absolute paths".to_string(),
+            );
+        }
+
+        let preprocessing = match std::env::var("RUST_TRANSCRIPTION_PREPROCESSING") {
+            Ok(value) => parse_preprocessing(&value)?,
+            Err(_) => None,
+        };
+
+        // Transcript text is user content; it only reaches the logs on request.
+        let log_transcripts = std::env::var("RUST_TRANSCRIPTION_LOG_TRANSCRIPTS")
+            .map(|value| matches!(value.trim().to_ascii_lowercase().as_str(), "1" | "true"))
+            .unwrap_or(false);
+
         if let Some(parent) = models_dir.parent() {
             if parent.as_os_str().is_empty() {
                 return Err("RUST_TRANSCRIPTION_MODELS_DIR is not a valid path".to_string());
@@ -43,6 +90,10 @@ impl SidecarConfig {
             host,
             port,
             models_dir,
+            socket_path,
+            import_dirs,
+            preprocessing,
+            log_transcripts,
         })
     }
 
@@ -50,3 +101,15 @@ impl SidecarConfig {
         format!("{}:{}", self.host, self.port)
     }
 }
+
+/// Accepts `off`, `on`/`default` for the default chain, or a JSON object with
+/// the same fields as the request-level `preprocessing` option.
+fn parse_preprocessing(value: &str) -> Result<Option<PreprocessingOptions>, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "" | "off" | "false" | "0" => Ok(None),
+        "on" | "default" | "true" | "1" => Ok(Some(PreprocessingOptions::default())),
+        _ => serde_json::from_str(value.trim())
+            .map(Some)
+            .map_err(|err| format!("RUST_TRANSCRIPTION_PREPROCESSING is not valid: {err}")),
+    }
+}
@@ -6,8 +6,10 @@ use futures_util::StreamExt;
 use serde::Serialize;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
+use tracing::{info, warn, Instrument};
 use uuid::Uuid;
 
+use crate::errors::SidecarError;
 use crate::models::WhisperModel;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -29,6 +31,7 @@ pub struct DownloadJobSnapshot {
     pub total_bytes: Option<u64>,
     pub progress: Option<f64>,
     pub error: Option<String>,
+    pub error_code: Option<&'static str>,
 }
 
 #[derive(Debug, Clone)]
@@ -37,7 +40,7 @@ struct DownloadJobRecord {
     status: DownloadJobStatus,
     bytes_downloaded: u64,
     total_bytes: Option<u64>,
-    error: Option<String>,
+    error: Option<SidecarError>,
 }
 
 #[derive(Default)]
@@ -58,7 +61,7 @@ impl DownloadRegistry {
         download_url: String,
         destination: PathBuf,
         client: reqwest::Client,
-    ) -> Result<DownloadJobSnapshot, String> {
+    ) -> Result<DownloadJobSnapshot, SidecarError> {
         if let Some(existing_size) = existing_model_file_size(&destination).await {
             let job_id = Uuid::new_v4();
             let mut store = self.inner.lock().await;
@@ -73,18 +76,18 @@ impl DownloadRegistry {
                 },
             );
 
-            return store
-                .snapshot(job_id)
-                .ok_or_else(|| "failed to create completed job snapshot".to_string());
+            return store.snapshot(job_id).ok_or_else(|| {
+                SidecarError::Internal("failed to create completed job snapshot".to_string())
+            });
         }
 
         let (job_id, snapshot) = {
             let mut store = self.inner.lock().await;
 
             if let Some(existing_id) = store.active_by_model.get(&model).copied() {
-                let existing = store
-                    .snapshot(existing_id)
-                    .ok_or_else(|| "active download job is missing".to_string())?;
+                let existing = store.snapshot(existing_id).ok_or_else(|| {
+                    SidecarError::Internal("active download job is missing".to_string())
+                })?;
                 return Ok(existing);
             }
 
@@ -101,22 +104,38 @@ impl DownloadRegistry {
             );
             store.active_by_model.insert(model, job_id);
 
-            let snapshot = store
-                .snapshot(job_id)
-                .ok_or_else(|| "failed to create job snapshot".to_string())?;
+            let snapshot = store.snapshot(job_id).ok_or_else(|| {
+                SidecarError::Internal("failed to create job snapshot".to_string())
+            })?;
 
             (job_id, snapshot)
         };
 
+        // The job outlives the request, but keeps its span so its logs stay
+        // correlated with the request that started it.
         let registry = self.clone();
-        tokio::spawn(async move {
-            if let Err(err) = registry
-                .run_download_job(job_id, model, download_url, destination, client)
-                .await
-            {
-                let _ = registry.mark_failed(job_id, model, err).await;
+        tokio::spawn(
+            async move {
+                info!(model = model.as_slug(), %job_id, "model download started");
+                match registry
+                    .run_download_job(job_id, model, download_url, destination, client)
+                    .await
+                {
+                    Ok(()) => info!(model = model.as_slug(), %job_id, "model download completed"),
+                    Err(err) => {
+                        warn!(
+                            model = model.as_slug(),
+                            %job_id,
+                            code = err.code(),
+                            error = %err,
+                            "model download failed"
+                        );
+                        let _ = registry.mark_failed(job_id, model, err).await;
+                    }
+                }
             }
-        });
+            .instrument(tracing::Span::current()),
+        );
 
         Ok(snapshot)
     }
@@ -144,73 +163,79 @@ impl DownloadRegistry {
         download_url: String,
         destination: PathBuf,
         client: reqwest::Client,
-    ) -> Result<(), String> {
+    ) -> Result<(), SidecarError> {
         self.mark_running(job_id).await?;
 
         if let Some(parent) = destination.parent() {
-            tokio::fs::create_dir_all(parent)
-                .await
-                .map_err(|err| format!("failed to create model directory: {err}"))?;
+            tokio::fs::create_dir_all(parent).await.map_err(|err| {
+                SidecarError::DownloadFailed(format!("failed to create model directory: {err}"))
+            })?;
         }
 
         let filename = destination
             .file_name()
             .and_then(|name| name.to_str())
-            .ok_or_else(|| "invalid destination filename".to_string())?;
+            .ok_or_else(|| SidecarError::Internal("invalid destination filename".to_string()))?;
 
         let temp_path = destination.with_file_name(format!("{filename}.{job_id}.download"));
 
         let _ = tokio::fs::remove_file(&temp_path).await;
 
-        let result: Result<(u64, Option<u64>), String> = async {
-            let response = client
-                .get(download_url)
-                .send()
-                .await
-                .map_err(|err| format!("failed to request model download: {err}"))?;
+        let result: Result<(u64, Option<u64>), SidecarError> = async {
+            let response = client.get(download_url).send().await.map_err(|err| {
+                SidecarError::DownloadFailed(format!("failed to request model download: {err}"))
+            })?;
 
             if !response.status().is_success() {
-                return Err(format!(
+                return Err(SidecarError::DownloadFailed(format!(
                     "model download request failed with status {}",
                     response.status()
-                ));
+                )));
             }
 
             let total_bytes = response.content_length();
             self.set_progress(job_id, 0, total_bytes).await?;
 
             let mut stream = response.bytes_stream();
-            let mut file = tokio::fs::File::create(&temp_path)
-                .await
-                .map_err(|err| format!("failed to create temporary model file: {err}"))?;
+            let mut file = tokio::fs::File::create(&temp_path).await.map_err(|err| {
+                SidecarError::DownloadFailed(format!(
+                    "failed to create temporary model file: {err}"
+                ))
+            })?;
 
             let mut downloaded: u64 = 0;
 
             while let Some(item) = stream.next().await {
-                let chunk = item.map_err(|err| format!("download stream failed: {err}"))?;
-                file.write_all(&chunk)
-                    .await
-                    .map_err(|err| format!("failed to write model file: {err}"))?;
+                let chunk = item.map_err(|err| {
+                    SidecarError::DownloadFailed(format!("download stream failed: {err}"))
+                })?;
+                file.write_all(&chunk).await.map_err(|err| {
+                    SidecarError::DownloadFailed(format!("failed to write model file: {err}"))
+                })?;
                 downloaded += chunk.len() as u64;
                 self.set_progress(job_id, downloaded, total_bytes).await?;
             }
 
-            file.flush()
-                .await
-                .map_err(|err| format!("failed to flush model file: {err}"))?;
-            file.sync_all()
-                .await
-                .map_err(|err| format!("failed to sync model file: {err}"))?;
+            file.flush().await.map_err(|err| {
+                SidecarError::DownloadFailed(format!("failed to flush model file: {err}"))
+            })?;
+            file.sync_all().await.map_err(|err| {
+                SidecarError::DownloadFailed(format!("failed to sync model file: {err}"))
+            })?;
 
             if destination.exists() {
-                tokio::fs::remove_file(&destination)
-                    .await
-                    .map_err(|err| format!("failed to replace existing model file: {err}"))?;
+                tokio::fs::remove_file(&destination).await.map_err(|err| {
+                    SidecarError::DownloadFailed(format!(
+                        "failed to replace existing model file: {err}"
+                    ))
+                })?;
             }
 
             tokio::fs::rename(&temp_path, &destination)
                 .await
-                .map_err(|err| format!("failed to finalize model file: {err}"))?;
+                .map_err(|err| {
+                    SidecarError::DownloadFailed(format!("failed to finalize model file: {err}"))
+                })?;
 
             Ok((downloaded, total_bytes))
         }
@@ -226,12 +251,12 @@ impl DownloadRegistry {
             .await
     }
 
-    async fn mark_running(&self, job_id: Uuid) -> Result<(), String> {
+    async fn mark_running(&self, job_id: Uuid) -> Result<(), SidecarError> {
         let mut store = self.inner.lock().await;
         let job = store
             .jobs
             .get_mut(&job_id)
-            .ok_or_else(|| "download job not found".to_string())?;
+            .ok_or_else(|| SidecarError::Internal("download job not found".to_string()))?;
 
         job.status = DownloadJobStatus::Running;
         job.error = None;
@@ -243,12 +268,12 @@ impl DownloadRegistry {
         job_id: Uuid,
         downloaded: u64,
         total_bytes: Option<u64>,
-    ) -> Result<(), String> {
+    ) -> Result<(), SidecarError> {
         let mut store = self.inner.lock().await;
         let job = store
             .jobs
             .get_mut(&job_id)
-            .ok_or_else(|| "download job not found".to_string())?;
+            .ok_or_else(|| SidecarError::Internal("download job not found".to_string()))?;
 
         job.bytes_downloaded = downloaded;
         job.total_bytes = total_bytes;
@@ -261,12 +286,12 @@ impl DownloadRegistry {
         model: WhisperModel,
         downloaded: u64,
         total_bytes: Option<u64>,
-    ) -> Result<(), String> {
+    ) -> Result<(), SidecarError> {
         let mut store = self.inner.lock().await;
         let job = store
             .jobs
             .get_mut(&job_id)
-            .ok_or_else(|| "download job not found".to_string())?;
+            .ok_or_else(|| SidecarError::Internal("download job not found".to_string()))?;
 
         job.status = DownloadJobStatus::Completed;
         job.bytes_downloaded = downloaded;
@@ -281,12 +306,12 @@ impl DownloadRegistry {
         &self,
         job_id: Uuid,
         model: WhisperModel,
-        error_message: String,
-    ) -> Result<(), String> {
+        error: SidecarError,
+    ) -> Result<(), SidecarError> {
         let mut store = self.inner.lock().await;
         if let Some(job) = store.jobs.get_mut(&job_id) {
             job.status = DownloadJobStatus::Failed;
-            job.error = Some(error_message);
+            job.error = Some(error);
         }
         store.active_by_model.remove(&model);
         Ok(())
@@ -309,7 +334,8 @@ impl DownloadStore {
             bytes_downloaded: job.bytes_downloaded,
             total_bytes: job.total_bytes,
             progress,
-            error: job.error.clone(),
+            error: job.error.as_ref().map(ToString::to_string),
+            error_code: job.error.as_ref().map(SidecarError::code),
         })
     }
 }
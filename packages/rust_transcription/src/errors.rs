@@ -1,41 +1,173 @@
+use std::fmt;
+
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 use serde::Serialize;
 
-#[derive(Debug)]
-pub struct ApiError {
-    status: StatusCode,
-    code: &'static str,
-    message: String,
+use crate::models::{ModelQuantization, WhisperModel};
+
+/// Every failure the sidecar can report. Each variant maps to a stable,
+/// machine-readable `code`, an HTTP status and a retryability hint, so clients
+/// never have to inspect error messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SidecarError {
+    InvalidModel(String),
+    InvalidJobId,
+    InvalidSessionId,
+    InvalidRequest(String),
+    InvalidDevice(String),
+    InvalidAudioChunk(String),
+    UnsupportedAudioFormat(String),
+    DownloadInProgress(WhisperModel),
+    QuantizationInProgress(WhisperModel),
+    ImportPathNotAllowed(String),
+    ModelNotDownloaded(WhisperModel),
+    ModelNotQuantized(WhisperModel, ModelQuantization),
+    DownloadNotFound,
+    QuantizationNotFound,
+    SessionNotFound,
+    InvalidModelFile(String),
+    ModelLoadFailed(String),
+    DeviceUnavailable(String),
+    DownloadFailed(String),
+    ModelDeleteFailed(String),
+    ModelImportFailed(String),
+    QuantizationFailed(String),
+    TranscriptionFailed(String),
+    Internal(String),
 }
 
-impl ApiError {
-    pub fn bad_request(code: &'static str, message: impl Into<String>) -> Self {
-        Self {
-            status: StatusCode::BAD_REQUEST,
-            code,
-            message: message.into(),
+impl SidecarError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidModel(_) => "invalid_model",
+            Self::InvalidJobId => "invalid_job_id",
+            Self::InvalidSessionId => "invalid_session_id",
+            Self::InvalidRequest(_) => "invalid_transcription_request",
+            Self::InvalidDevice(_) => "invalid_device",
+            Self::InvalidAudioChunk(_) => "invalid_audio_chunk",
+            Self::UnsupportedAudioFormat(_) => "unsupported_audio_format",
+            Self::DownloadInProgress(_) => "download_in_progress",
+            Self::QuantizationInProgress(_) => "quantization_in_progress",
+            Self::ImportPathNotAllowed(_) => "import_path_not_allowed",
+            Self::ModelNotDownloaded(_) => "model_not_downloaded",
+            Self::ModelNotQuantized(_, _) => "model_not_quantized",
+            Self::DownloadNotFound => "download_not_found",
+            Self::QuantizationNotFound => "quantization_not_found",
+            Self::SessionNotFound => "session_not_found",
+            Self::InvalidModelFile(_) => "invalid_model_file",
+            Self::ModelLoadFailed(_) => "model_load_failed",
+            Self::DeviceUnavailable(_) => "device_unavailable",
+            Self::DownloadFailed(_) => "download_failed",
+            Self::ModelDeleteFailed(_) => "model_delete_failed",
+            Self::ModelImportFailed(_) => "model_import_failed",
+            Self::QuantizationFailed(_) => "quantization_failed",
+            Self::TranscriptionFailed(_) => "transcription_failed",
+            Self::Internal(_) => "internal_error",
         }
     }
 
-    pub fn not_found(code: &'static str, message: impl Into<String>) -> Self {
-        Self {
-            status: StatusCode::NOT_FOUND,
-            code,
-            message: message.into(),
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::InvalidModel(_)
+            | Self::InvalidJobId
+            | Self::InvalidSessionId
+            | Self::InvalidRequest(_)
+            | Self::InvalidDevice(_)
+            | Self::InvalidAudioChunk(_) => StatusCode::BAD_REQUEST,
+            Self::ImportPathNotAllowed(_) => StatusCode::FORBIDDEN,
+            Self::DownloadInProgress(_) | Self::QuantizationInProgress(_) => StatusCode::CONFLICT,
+            Self::UnsupportedAudioFormat(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::ModelNotDownloaded(_)
+            | Self::ModelNotQuantized(_, _)
+            | Self::DownloadNotFound
+            | Self::QuantizationNotFound
+            | Self::SessionNotFound => StatusCode::NOT_FOUND,
+            Self::InvalidModelFile(_) | Self::ModelLoadFailed(_) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            Self::DeviceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            Self::DownloadFailed(_) => StatusCode::BAD_GATEWAY,
+            Self::ModelDeleteFailed(_)
+            | Self::ModelImportFailed(_)
+            | Self::QuantizationFailed(_)
+            | Self::TranscriptionFailed(_)
+            | Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
-    pub fn internal(code: &'static str, message: impl Into<String>) -> Self {
-        Self {
-            status: StatusCode::INTERNAL_SERVER_ERROR,
-            code,
-            message: message.into(),
+    /// Whether repeating the same request later may succeed without the
+    /// client changing anything.
+    pub fn retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::DownloadInProgress(_)
+                | Self::QuantizationInProgress(_)
+                | Self::DeviceUnavailable(_)
+                | Self::DownloadFailed(_)
+                | Self::ModelDeleteFailed(_)
+                | Self::ModelImportFailed(_)
+                | Self::TranscriptionFailed(_)
+                | Self::Internal(_)
+        )
+    }
+}
+
+impl fmt::Display for SidecarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidJobId => f.write_str("jobId must be a valid UUID"),
+            Self::InvalidSessionId => f.write_str("sessionId must be a valid UUID"),
+            Self::DownloadInProgress(model) => write!(
+                f,
+                "model '{}' is currently downloading; wait for it to finish first",
+                model.as_slug()
+            ),
+            Self::QuantizationInProgress(model) => write!(
+                f,
+                "model '{}' is being quantized; wait for it to finish first",
+                model.as_slug()
+            ),
+            Self::ModelNotDownloaded(model) => write!(
+                f,
+                "model '{}' is not downloaded; call /v1/models/{}/download first",
+                model.as_slug(),
+                model.as_slug()
+            ),
+            Self::ModelNotQuantized(model, quantization) => write!(
+                f,
+                "model '{}' has no {} variant; call /v1/models/{}/quantize first",
+                model.as_slug(),
+                quantization.as_str(),
+                model.as_slug()
+            ),
+            Self::DownloadNotFound => f.write_str("download job was not found"),
+            Self::QuantizationNotFound => f.write_str("quantization job was not found"),
+            Self::SessionNotFound => {
+                f.write_str("transcription session does not exist or has already completed")
+            }
+            Self::InvalidModel(message)
+            | Self::InvalidRequest(message)
+            | Self::InvalidDevice(message)
+            | Self::InvalidAudioChunk(message)
+            | Self::UnsupportedAudioFormat(message)
+            | Self::ImportPathNotAllowed(message)
+            | Self::InvalidModelFile(message)
+            | Self::ModelLoadFailed(message)
+            | Self::DeviceUnavailable(message)
+            | Self::DownloadFailed(message)
+            | Self::ModelDeleteFailed(message)
+            | Self::ModelImportFailed(message)
+            | Self::QuantizationFailed(message)
+            | Self::TranscriptionFailed(message)
+            | Self::Internal(message) => f.write_str(message),
         }
     }
 }
 
+impl std::error::Error for SidecarError {}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct ErrorBody {
@@ -47,17 +179,195 @@ struct ErrorBody {
 struct ErrorDetail {
     code: &'static str,
     message: String,
+    retryable: bool,
 }
 
-impl IntoResponse for ApiError {
+impl IntoResponse for SidecarError {
     fn into_response(self) -> Response {
         let body = ErrorBody {
             error: ErrorDetail {
-                code: self.code,
-                message: self.message,
+                code: self.code(),
+                message: self.to_string(),
+                retryable: self.retryable(),
             },
         };
 
-        (self.status, Json(body)).into_response()
+        (self.status(), Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_statuses_and_retryability_are_stable() {
+        let message = || "details".to_string();
+        let cases = [
+            (
+                SidecarError::InvalidModel(message()),
+                "invalid_model",
+                400,
+                false,
+            ),
+            (SidecarError::InvalidJobId, "invalid_job_id", 400, false),
+            (
+                SidecarError::InvalidSessionId,
+                "invalid_session_id",
+                400,
+                false,
+            ),
+            (
+                SidecarError::InvalidRequest(message()),
+                "invalid_transcription_request",
+                400,
+                false,
+            ),
+            (
+                SidecarError::InvalidDevice(message()),
+                "invalid_device",
+                400,
+                false,
+            ),
+            (
+                SidecarError::InvalidAudioChunk(message()),
+                "invalid_audio_chunk",
+                400,
+                false,
+            ),
+            (
+                SidecarError::UnsupportedAudioFormat(message()),
+                "unsupported_audio_format",
+                415,
+                false,
+            ),
+            (
+                SidecarError::DownloadInProgress(WhisperModel::Tiny),
+                "download_in_progress",
+                409,
+                true,
+            ),
+            (
+                SidecarError::QuantizationInProgress(WhisperModel::Tiny),
+                "quantization_in_progress",
+                409,
+                true,
+            ),
+            (
+                SidecarError::ImportPathNotAllowed(message()),
+                "import_path_not_allowed",
+                403,
+                false,
+            ),
+            (
+                SidecarError::ModelNotQuantized(WhisperModel::Tiny, ModelQuantization::Q5_0),
+                "model_not_quantized",
+                404,
+                false,
+            ),
+            (
+                SidecarError::QuantizationNotFound,
+                "quantization_not_found",
+                404,
+                false,
+            ),
+            (
+                SidecarError::InvalidModelFile(message()),
+                "invalid_model_file",
+                422,
+                false,
+            ),
+            (
+                SidecarError::ModelImportFailed(message()),
+                "model_import_failed",
+                500,
+                true,
+            ),
+            (
+                SidecarError::QuantizationFailed(message()),
+                "quantization_failed",
+                500,
+                false,
+            ),
+            (
+                SidecarError::ModelNotDownloaded(WhisperModel::Tiny),
+                "model_not_downloaded",
+                404,
+                false,
+            ),
+            (
+                SidecarError::DownloadNotFound,
+                "download_not_found",
+                404,
+                false,
+            ),
+            (
+                SidecarError::SessionNotFound,
+                "session_not_found",
+                404,
+                false,
+            ),
+            (
+                SidecarError::ModelLoadFailed(message()),
+                "model_load_failed",
+                422,
+                false,
+            ),
+            (
+                SidecarError::DeviceUnavailable(message()),
+                "device_unavailable",
+                503,
+                true,
+            ),
+            (
+                SidecarError::DownloadFailed(message()),
+                "download_failed",
+                502,
+                true,
+            ),
+            (
+                SidecarError::ModelDeleteFailed(message()),
+                "model_delete_failed",
+                500,
+                true,
+            ),
+            (
+                SidecarError::TranscriptionFailed(message()),
+                "transcription_failed",
+                500,
+                true,
+            ),
+            (
+                SidecarError::Internal(message()),
+                "internal_error",
+                500,
+                true,
+            ),
+        ];
+
+        for (error, code, status, retryable) in cases {
+            assert_eq!(error.code(), code);
+            assert_eq!(error.status().as_u16(), status, "status for {code}");
+            assert_eq!(error.retryable(), retryable, "retryable for {code}");
+        }
+    }
+
+    #[test]
+    fn model_load_failure_is_not_reported_as_missing_model() {
+        let error = SidecarError::ModelLoadFailed(
+            "failed to initialize whisper context: model file is corrupt".to_string(),
+        );
+
+        assert_ne!(error.code(), "model_not_downloaded");
+        assert_ne!(error.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn missing_model_message_points_at_download_endpoint() {
+        let error = SidecarError::ModelNotDownloaded(WhisperModel::Base);
+        assert_eq!(
+            error.to_string(),
+            "model 'base' is not downloaded; call /v1/models/base/download first"
+        );
     }
 }
@@ -0,0 +1,547 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use whisper_rs::whisper_rs_sys;
+
+use crate::models::{ModelQuantization, WhisperModel};
+
+/// `ggml` in little-endian, as written at the start of every whisper.cpp model.
+const GGML_MAGIC: u32 = 0x6767_6d6c;
+const GGML_QNT_VERSION: i32 = 2;
+const GGML_QNT_VERSION_FACTOR: i32 = 1000;
+const QUANT_BLOCK_SIZE: i64 = 32;
+const MAX_TENSOR_DIMS: i32 = 4;
+
+/// Tensors whisper.cpp keeps at full precision when quantizing.
+const SKIP_QUANTIZATION: &[&str] = &[
+    "encoder.conv1.bias",
+    "encoder.conv2.bias",
+    "encoder.positional_embedding",
+    "decoder.positional_embedding",
+];
+
+const TENSOR_TYPE_F32: i32 = 0;
+const TENSOR_TYPE_F16: i32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GgmlHeader {
+    pub n_vocab: i32,
+    pub n_audio_ctx: i32,
+    pub n_audio_state: i32,
+    pub n_audio_head: i32,
+    pub n_audio_layer: i32,
+    pub n_text_ctx: i32,
+    pub n_text_state: i32,
+    pub n_text_head: i32,
+    pub n_text_layer: i32,
+    pub n_mels: i32,
+    pub ftype: i32,
+}
+
+impl GgmlHeader {
+    pub fn read_from_path(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|err| format!("failed to open model file: {err}"))?;
+        Self::read(&mut BufReader::new(file))
+    }
+
+    pub fn read(reader: &mut impl Read) -> Result<Self, String> {
+        let magic = read_u32(reader).map_err(|_| "file is too short to be a ggml model")?;
+        if magic != GGML_MAGIC {
+            return Err("file is not a ggml whisper model (bad magic)".to_string());
+        }
+
+        let mut values = [0_i32; 11];
+        for value in values.iter_mut() {
+            *value = read_i32(reader).map_err(|_| "ggml header is truncated")?;
+        }
+
+        let header = Self {
+            n_vocab: values[0],
+            n_audio_ctx: values[1],
+            n_audio_state: values[2],
+            n_audio_head: values[3],
+            n_audio_layer: values[4],
+            n_text_ctx: values[5],
+            n_text_state: values[6],
+            n_text_head: values[7],
+            n_text_layer: values[8],
+            n_mels: values[9],
+            ftype: values[10],
+        };
+
+        if values[..10].iter().any(|value| *value <= 0) {
+            return Err("ggml header has non-positive hyperparameters".to_string());
+        }
+
+        if header.file_type().is_none() {
+            return Err(format!("ggml header has unknown ftype {}", header.ftype));
+        }
+
+        Ok(header)
+    }
+
+    fn write(&self, writer: &mut impl Write) -> io::Result<()> {
+        writer.write_all(&GGML_MAGIC.to_le_bytes())?;
+        for value in [
+            self.n_vocab,
+            self.n_audio_ctx,
+            self.n_audio_state,
+            self.n_audio_head,
+            self.n_audio_layer,
+            self.n_text_ctx,
+            self.n_text_state,
+            self.n_text_head,
+            self.n_text_layer,
+            self.n_mels,
+            self.ftype,
+        ] {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Weight format of the file, e.g. `f16` or `q5_1`.
+    pub fn file_type(&self) -> Option<&'static str> {
+        match self.ftype % GGML_QNT_VERSION_FACTOR {
+            0 => Some("f32"),
+            1 => Some("f16"),
+            2 => Some("q4_0"),
+            3 => Some("q4_1"),
+            7 => Some("q8_0"),
+            8 => Some("q5_0"),
+            9 => Some("q5_1"),
+            _ => None,
+        }
+    }
+
+    pub fn is_quantized(&self) -> bool {
+        !matches!(self.ftype % GGML_QNT_VERSION_FACTOR, 0 | 1)
+    }
+
+    /// Rejects files whose shape doesn't match `model`, so a `small` upload
+    /// can't be registered as `medium`.
+    pub fn check_matches(&self, model: WhisperModel) -> Result<(), String> {
+        let Some((audio_state, text_layers)) = model.expected_dimensions() else {
+            return Ok(());
+        };
+
+        if self.n_audio_state != audio_state || self.n_text_layer != text_layers {
+            return Err(format!(
+                "file does not look like the '{}' model (audio state {}, {} text layers)",
+                model.as_slug(),
+                self.n_audio_state,
+                self.n_text_layer
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Rewrites an f16/f32 whisper model as `quantization`, mirroring
+/// whisper.cpp's `quantize` tool: 2-D weight matrices are quantized, biases,
+/// convolutions and positional embeddings are copied unchanged.
+/// `on_progress` receives `(bytes_read, total_bytes)` after every tensor.
+pub fn quantize_model(
+    source: &Path,
+    destination: &Path,
+    quantization: ModelQuantization,
+    mut on_progress: impl FnMut(u64, u64),
+) -> Result<(), String> {
+    let total_bytes = std::fs::metadata(source)
+        .map_err(|err| format!("failed to read source model: {err}"))?
+        .len();
+    let file = File::open(source).map_err(|err| format!("failed to open source model: {err}"))?;
+    let mut reader = CountingReader::new(BufReader::new(file));
+    let output = File::create(destination)
+        .map_err(|err| format!("failed to create quantized model file: {err}"))?;
+    let mut writer = BufWriter::new(output);
+
+    let mut header = GgmlHeader::read(&mut reader)?;
+    if header.is_quantized() {
+        return Err(format!(
+            "source model is already quantized ({}); quantize from an f16 or f32 model",
+            header.file_type().unwrap_or("unknown")
+        ));
+    }
+
+    let (tensor_type, ftype) = ggml_types(quantization);
+    header.ftype = GGML_QNT_VERSION * GGML_QNT_VERSION_FACTOR + ftype;
+    header.write(&mut writer).map_err(write_error)?;
+
+    copy_mel_filters(&mut reader, &mut writer)?;
+    copy_vocabulary(&mut reader, &mut writer)?;
+    on_progress(reader.count, total_bytes);
+
+    unsafe { whisper_rs_sys::ggml_quantize_init(tensor_type) };
+
+    while reader.count < total_bytes {
+        quantize_tensor(&mut reader, &mut writer, tensor_type)?;
+        on_progress(reader.count, total_bytes);
+    }
+
+    writer.flush().map_err(write_error)?;
+    writer
+        .get_ref()
+        .sync_all()
+        .map_err(|err| format!("failed to sync quantized model file: {err}"))
+}
+
+fn ggml_types(quantization: ModelQuantization) -> (whisper_rs_sys::ggml_type, i32) {
+    match quantization {
+        ModelQuantization::Q5_0 => (whisper_rs_sys::ggml_type_GGML_TYPE_Q5_0, 8),
+        ModelQuantization::Q5_1 => (whisper_rs_sys::ggml_type_GGML_TYPE_Q5_1, 9),
+        ModelQuantization::Q8_0 => (whisper_rs_sys::ggml_type_GGML_TYPE_Q8_0, 7),
+    }
+}
+
+fn copy_mel_filters(reader: &mut impl Read, writer: &mut impl Write) -> Result<(), String> {
+    let n_mel = read_i32(reader).map_err(truncated)?;
+    let n_fft = read_i32(reader).map_err(truncated)?;
+    if n_mel < 0 || n_fft < 0 {
+        return Err("mel filter bank has negative dimensions".to_string());
+    }
+
+    writer
+        .write_all(&n_mel.to_le_bytes())
+        .map_err(write_error)?;
+    writer
+        .write_all(&n_fft.to_le_bytes())
+        .map_err(write_error)?;
+    copy_exact(reader, writer, n_mel as u64 * n_fft as u64 * 4)
+}
+
+fn copy_vocabulary(reader: &mut impl Read, writer: &mut impl Write) -> Result<(), String> {
+    let n_vocab = read_i32(reader).map_err(truncated)?;
+    if n_vocab < 0 {
+        return Err("vocabulary size is negative".to_string());
+    }
+    writer
+        .write_all(&n_vocab.to_le_bytes())
+        .map_err(write_error)?;
+
+    for _ in 0..n_vocab {
+        let len = read_u32(reader).map_err(truncated)?;
+        writer.write_all(&len.to_le_bytes()).map_err(write_error)?;
+        copy_exact(reader, writer, u64::from(len))?;
+    }
+
+    Ok(())
+}
+
+fn quantize_tensor(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+    tensor_type: whisper_rs_sys::ggml_type,
+) -> Result<(), String> {
+    let n_dims = read_i32(reader).map_err(truncated)?;
+    let name_len = read_i32(reader).map_err(truncated)?;
+    let source_type = read_i32(reader).map_err(truncated)?;
+    if !(1..=MAX_TENSOR_DIMS).contains(&n_dims) || name_len <= 0 {
+        return Err("tensor header is malformed".to_string());
+    }
+
+    let mut ne = [1_i64; MAX_TENSOR_DIMS as usize];
+    for dim in ne.iter_mut().take(n_dims as usize) {
+        *dim = i64::from(read_i32(reader).map_err(truncated)?);
+    }
+    let mut name_bytes = vec![0_u8; name_len as usize];
+    reader.read_exact(&mut name_bytes).map_err(truncated)?;
+    let name = String::from_utf8_lossy(&name_bytes).into_owned();
+
+    let element_size = match source_type {
+        TENSOR_TYPE_F32 => 4,
+        TENSOR_TYPE_F16 => 2,
+        other => {
+            return Err(format!(
+                "tensor '{name}' has unsupported type {other}; quantize from an f16 or f32 model"
+            ))
+        }
+    };
+    let elements: i64 = ne.iter().product();
+    if elements <= 0 {
+        return Err(format!("tensor '{name}' has invalid dimensions"));
+    }
+    let mut data = vec![0_u8; elements as usize * element_size];
+    reader.read_exact(&mut data).map_err(truncated)?;
+
+    let quantize =
+        n_dims == 2 && !SKIP_QUANTIZATION.contains(&name.as_str()) && ne[0] % QUANT_BLOCK_SIZE == 0;
+
+    let (output_type, output) = if quantize {
+        let values = tensor_to_f32(&data, source_type);
+        let rows = elements / ne[0];
+        let row_size = unsafe { whisper_rs_sys::ggml_row_size(tensor_type, ne[0]) };
+        let mut output = vec![0_u8; row_size * rows as usize];
+        let written = unsafe {
+            whisper_rs_sys::ggml_quantize_chunk(
+                tensor_type,
+                values.as_ptr(),
+                output.as_mut_ptr().cast(),
+                0,
+                rows,
+                ne[0],
+                std::ptr::null(),
+            )
+        };
+        output.truncate(written);
+        (tensor_type as i32, output)
+    } else {
+        (source_type, data)
+    };
+
+    writer
+        .write_all(&n_dims.to_le_bytes())
+        .map_err(write_error)?;
+    writer
+        .write_all(&name_len.to_le_bytes())
+        .map_err(write_error)?;
+    writer
+        .write_all(&output_type.to_le_bytes())
+        .map_err(write_error)?;
+    for dim in ne.iter().take(n_dims as usize) {
+        writer
+            .write_all(&(*dim as i32).to_le_bytes())
+            .map_err(write_error)?;
+    }
+    writer.write_all(&name_bytes).map_err(write_error)?;
+    writer.write_all(&output).map_err(write_error)
+}
+
+fn tensor_to_f32(data: &[u8], source_type: i32) -> Vec<f32> {
+    if source_type == TENSOR_TYPE_F32 {
+        return data
+            .chunks_exact(4)
+            .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect();
+    }
+
+    data.chunks_exact(2)
+        .map(|bytes| f16_to_f32(u16::from_le_bytes([bytes[0], bytes[1]])))
+        .collect()
+}
+
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = u32::from(bits >> 15) << 31;
+    let exponent = u32::from((bits >> 10) & 0x1f);
+    let mantissa = u32::from(bits & 0x3ff);
+
+    let value = match (exponent, mantissa) {
+        (0, 0) => sign,
+        (0, _) => {
+            // Subnormal: renormalize into an f32 exponent.
+            let shift = mantissa.leading_zeros() - 21;
+            let mantissa = (mantissa << shift) & 0x3ff;
+            sign | ((113 - shift) << 23) | (mantissa << 13)
+        }
+        (0x1f, _) => sign | 0x7f80_0000 | (mantissa << 13),
+        _ => sign | ((exponent + 112) << 23) | (mantissa << 13),
+    };
+
+    f32::from_bits(value)
+}
+
+fn copy_exact(reader: &mut impl Read, writer: &mut impl Write, len: u64) -> Result<(), String> {
+    let copied = io::copy(&mut reader.take(len), writer).map_err(write_error)?;
+    if copied != len {
+        return Err("model file is truncated".to_string());
+    }
+    Ok(())
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0_u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_i32(reader: &mut impl Read) -> io::Result<i32> {
+    let mut bytes = [0_u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(i32::from_le_bytes(bytes))
+}
+
+fn truncated(_: io::Error) -> String {
+    "model file is truncated".to_string()
+}
+
+fn write_error(err: io::Error) -> String {
+    format!("failed to write quantized model file: {err}")
+}
+
+struct CountingReader<R> {
+    inner: R,
+    count: u64,
+}
+
+impl<R> CountingReader<R> {
+    fn new(inner: R) -> Self {
+        Self { inner, count: 0 }
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count += read as u64;
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(ftype: i32) -> GgmlHeader {
+        GgmlHeader {
+            n_vocab: 2,
+            n_audio_ctx: 1500,
+            n_audio_state: 384,
+            n_audio_head: 6,
+            n_audio_layer: 4,
+            n_text_ctx: 448,
+            n_text_state: 384,
+            n_text_head: 6,
+            n_text_layer: 4,
+            n_mels: 80,
+            ftype,
+        }
+    }
+
+    fn write_tensor(out: &mut Vec<u8>, name: &str, ne: &[i32], values: &[f32]) {
+        out.extend((ne.len() as i32).to_le_bytes());
+        out.extend((name.len() as i32).to_le_bytes());
+        out.extend(TENSOR_TYPE_F32.to_le_bytes());
+        for dim in ne {
+            out.extend(dim.to_le_bytes());
+        }
+        out.extend(name.as_bytes());
+        for value in values {
+            out.extend(value.to_le_bytes());
+        }
+    }
+
+    fn synthetic_model() -> Vec<u8> {
+        let mut out = Vec::new();
+        header(0).write(&mut out).unwrap();
+        // 2x2 mel filter bank.
+        out.extend(2_i32.to_le_bytes());
+        out.extend(2_i32.to_le_bytes());
+        for value in [0.1_f32, 0.2, 0.3, 0.4] {
+            out.extend(value.to_le_bytes());
+        }
+        // Two-token vocabulary.
+        out.extend(2_i32.to_le_bytes());
+        for token in ["a", "bc"] {
+            out.extend((token.len() as u32).to_le_bytes());
+            out.extend(token.as_bytes());
+        }
+        let weights: Vec<f32> = (0..64).map(|index| index as f32 / 64.0).collect();
+        write_tensor(
+            &mut out,
+            "decoder.blocks.0.mlp.0.weight",
+            &[32, 2],
+            &weights,
+        );
+        write_tensor(&mut out, "decoder.blocks.0.mlp.0.bias", &[2], &[0.5, -0.5]);
+        out
+    }
+
+    #[test]
+    fn reads_header_and_reports_file_type() {
+        let mut bytes = Vec::new();
+        header(1).write(&mut bytes).unwrap();
+        let parsed = GgmlHeader::read(&mut bytes.as_slice()).unwrap();
+        assert_eq!(parsed, header(1));
+        assert_eq!(parsed.file_type(), Some("f16"));
+        assert!(!parsed.is_quantized());
+
+        let quantized = header(GGML_QNT_VERSION * GGML_QNT_VERSION_FACTOR + 9);
+        assert_eq!(quantized.file_type(), Some("q5_1"));
+        assert!(quantized.is_quantized());
+    }
+
+    #[test]
+    fn rejects_files_without_ggml_magic() {
+        let err = GgmlHeader::read(&mut b"GGUF\x03\x00\x00\x00".as_slice()).unwrap_err();
+        assert!(err.contains("bad magic"));
+    }
+
+    #[test]
+    fn rejects_header_for_a_different_model() {
+        assert!(header(1).check_matches(WhisperModel::Tiny).is_ok());
+        assert!(header(1).check_matches(WhisperModel::Medium).is_err());
+        assert!(header(1)
+            .check_matches(WhisperModel::Hindi2Hinglish)
+            .is_ok());
+    }
+
+    #[test]
+    fn converts_half_precision_values() {
+        assert_eq!(f16_to_f32(0x3c00), 1.0);
+        assert_eq!(f16_to_f32(0xc000), -2.0);
+        assert_eq!(f16_to_f32(0x0000), 0.0);
+        assert_eq!(f16_to_f32(0x0001), 2.0_f32.powi(-24));
+        assert!(f16_to_f32(0x7c00).is_infinite());
+    }
+
+    #[test]
+    fn quantizes_weight_matrices_and_keeps_biases() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("ggml-tiny.bin");
+        let destination = dir.path().join("ggml-tiny-q8_0.bin");
+        std::fs::write(&source, synthetic_model()).unwrap();
+
+        let mut last_progress = (0, 0);
+        quantize_model(
+            &source,
+            &destination,
+            ModelQuantization::Q8_0,
+            |done, total| {
+                last_progress = (done, total);
+            },
+        )
+        .unwrap();
+
+        let total = std::fs::metadata(&source).unwrap().len();
+        assert_eq!(last_progress, (total, total));
+
+        let output = std::fs::read(&destination).unwrap();
+        let mut reader = output.as_slice();
+        let parsed = GgmlHeader::read(&mut reader).unwrap();
+        assert_eq!(parsed.file_type(), Some("q8_0"));
+
+        let mut sink = Vec::new();
+        copy_mel_filters(&mut reader, &mut sink).unwrap();
+        copy_vocabulary(&mut reader, &mut sink).unwrap();
+
+        // Header of the first tensor: dims, name length, then type.
+        let _ = read_i32(&mut reader).unwrap();
+        let _ = read_i32(&mut reader).unwrap();
+        assert_eq!(
+            read_i32(&mut reader).unwrap(),
+            whisper_rs_sys::ggml_type_GGML_TYPE_Q8_0 as i32
+        );
+    }
+
+    #[test]
+    fn refuses_to_quantize_an_already_quantized_model() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("ggml-tiny.bin");
+        let mut bytes = Vec::new();
+        header(GGML_QNT_VERSION * GGML_QNT_VERSION_FACTOR + 8)
+            .write(&mut bytes)
+            .unwrap();
+        std::fs::write(&source, bytes).unwrap();
+
+        let err = quantize_model(
+            &source,
+            &dir.path().join("out.bin"),
+            ModelQuantization::Q5_1,
+            |_, _| {},
+        )
+        .unwrap_err();
+        assert!(err.contains("already quantized"));
+    }
+}
@@ -1,18 +1,30 @@
 mod api;
+mod audio_decoding;
+mod benchmark;
 mod compute;
+mod confidence;
 mod config;
 mod downloads;
 mod errors;
+mod ggml;
+mod logging;
 mod models;
+mod preprocessing;
+mod quantization;
+mod request_id;
 mod state;
 mod streaming_sessions;
 mod transcription;
+#[cfg(unix)]
+mod unix_socket;
 
 pub use compute::ComputeMode;
+pub use logging::init_tracing;
 pub use models::WhisperModel;
 pub use transcription::ensure_gpu_runtime_available;
 
 use std::io::{self, Write};
+use std::path::Path;
 
 use tokio::net::TcpListener;
 use tracing::info;
@@ -27,10 +39,14 @@ pub async fn run_server(mode: ComputeMode) -> Result<(), String> {
         .await
         .map_err(|err| format!("failed to create models directory: {err}"))?;
 
-    let address = config.bind_address();
     let state = AppState::new(config.clone())?;
     let router = api::create_router(state);
 
+    if let Some(socket_path) = config.socket_path.clone() {
+        return serve_unix_socket(&config, &socket_path, router).await;
+    }
+
+    let address = config.bind_address();
     let listener = TcpListener::bind(&address)
         .await
         .map_err(|err| format!("failed to bind to {address}: {err}"))?;
@@ -54,11 +70,106 @@ pub async fn run_server(mode: ComputeMode) -> Result<(), String> {
         .map_err(|err| format!("sidecar server failed: {err}"))
 }
 
+/// Entry point for the `bench` subcommand shared by the CPU and GPU binaries.
+pub async fn run_benchmark_cli(mode: ComputeMode, args: &[String]) -> Result<(), String> {
+    let mut options = benchmark::BenchmarkOptions::default();
+    let mut json = false;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--model" => {
+                let value = args.next().ok_or("--model requires a value")?;
+                let model = WhisperModel::from_slug(value).ok_or_else(|| {
+                    format!(
+                        "unsupported model '{value}'; supported values: {}",
+                        WhisperModel::supported().join(", ")
+                    )
+                })?;
+                options.models.get_or_insert_with(Vec::new).push(model);
+            }
+            "--device" => {
+                let value = args.next().ok_or("--device requires a value")?;
+                options
+                    .device_ids
+                    .get_or_insert_with(Vec::new)
+                    .push(value.clone());
+            }
+            "--seconds" => {
+                let value = args.next().ok_or("--seconds requires a value")?;
+                let seconds = value
+                    .parse::<u32>()
+                    .map_err(|_| format!("invalid --seconds value '{value}'"))?;
+                options.clip_seconds = Some(seconds);
+            }
+            other => {
+                return Err(format!(
+                    "unknown bench argument '{other}'; usage: bench [--json] [--model <model>]... [--device <id>]... [--seconds <n>]"
+                ))
+            }
+        }
+    }
+
+    let config = SidecarConfig::from_env(mode)?;
+    let state = AppState::new(config)?;
+    let report = benchmark::run_benchmark(&state, options)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let output = if json {
+        serde_json::to_string_pretty(&report)
+            .map_err(|err| format!("failed to serialize benchmark report: {err}"))?
+    } else {
+        benchmark::format_table(&report)
+    };
+
+    announce(output.trim_end())
+}
+
+#[cfg(unix)]
+async fn serve_unix_socket(
+    config: &SidecarConfig,
+    socket_path: &Path,
+    router: axum::Router,
+) -> Result<(), String> {
+    let listener = unix_socket::bind(socket_path)?;
+
+    // Same line as the TCP announcement; launchers that only expect a
+    // port ignore the `unix:` value.
+    announce(&format!(
+        "RUST_TRANSCRIPTION_BOUND_PORT=unix:{}",
+        socket_path.display()
+    ))?;
+
+    info!(
+        mode = config.mode.as_str(),
+        socket = %socket_path.display(),
+        models_dir = %config.models_dir.display(),
+        "rust_transcription sidecar started"
+    );
+
+    unix_socket::serve(listener, router).await
+}
+
+#[cfg(not(unix))]
+async fn serve_unix_socket(
+    _config: &SidecarConfig,
+    _socket_path: &Path,
+    _router: axum::Router,
+) -> Result<(), String> {
+    Err("unix socket listener is not supported on this platform".to_string())
+}
+
 fn announce_bound_port(port: u16) -> Result<(), String> {
+    announce(&format!("RUST_TRANSCRIPTION_BOUND_PORT={port}"))
+}
+
+fn announce(line: &str) -> Result<(), String> {
     let mut stdout = io::stdout();
-    writeln!(stdout, "RUST_TRANSCRIPTION_BOUND_PORT={port}")
-        .map_err(|err| format!("failed to write bound port announcement: {err}"))?;
+    writeln!(stdout, "{line}")
+        .map_err(|err| format!("failed to write bound address announcement: {err}"))?;
     stdout
         .flush()
-        .map_err(|err| format!("failed to flush bound port announcement: {err}"))
+        .map_err(|err| format!("failed to flush bound address announcement: {err}"))
 }
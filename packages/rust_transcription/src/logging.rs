@@ -0,0 +1,34 @@
+use tracing_subscriber::EnvFilter;
+
+/// Installs the global subscriber. `RUST_TRANSCRIPTION_LOG_FORMAT=json` emits
+/// one JSON object per line (with the active request span's fields);
+/// anything else keeps the human-readable format. `RUST_LOG` still controls
+/// the level.
+pub fn init_tracing() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let format = std::env::var("RUST_TRANSCRIPTION_LOG_FORMAT").unwrap_or_default();
+
+    match format.trim().to_ascii_lowercase().as_str() {
+        "json" => {
+            let _ = tracing_subscriber::fmt()
+                .json()
+                .with_env_filter(filter)
+                .with_current_span(true)
+                .with_span_list(false)
+                .with_target(false)
+                .try_init();
+        }
+        other => {
+            if !matches!(other, "" | "text" | "pretty") {
+                eprintln!(
+                    "[rust-transcription] unknown RUST_TRANSCRIPTION_LOG_FORMAT '{other}', using text"
+                );
+            }
+
+            let _ = tracing_subscriber::fmt()
+                .with_env_filter(filter)
+                .with_target(false)
+                .try_init();
+        }
+    }
+}
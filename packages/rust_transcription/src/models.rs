@@ -64,6 +64,27 @@ impl WhisperModel {
         }
     }
 
+    /// File name of a locally quantized variant, e.g. `ggml-medium-q5_0.bin`.
+    pub fn quantized_filename(self, quantization: ModelQuantization) -> String {
+        let stem = self.filename().trim_end_matches(".bin");
+        format!("{stem}-{}.bin", quantization.as_str())
+    }
+
+    /// Width of the audio encoder and depth of the text decoder, used to check
+    /// that an imported file really is this model. `None` when the model is a
+    /// fine-tune whose shape isn't pinned.
+    pub fn expected_dimensions(self) -> Option<(i32, i32)> {
+        match self {
+            Self::Tiny => Some((384, 4)),
+            Self::Base => Some((512, 6)),
+            Self::Small => Some((768, 12)),
+            Self::Medium => Some((1024, 24)),
+            Self::Large => Some((1280, 32)),
+            Self::Turbo => Some((1280, 4)),
+            Self::Hindi2Hinglish => None,
+        }
+    }
+
     pub fn download_url(self) -> String {
         let env_var = format!(
             "RUST_TRANSCRIPTION_MODEL_URL_{}",
@@ -99,6 +120,18 @@ impl WhisperModel {
         .to_string()
     }
 
+    pub fn all() -> &'static [WhisperModel] {
+        &[
+            Self::Tiny,
+            Self::Base,
+            Self::Small,
+            Self::Medium,
+            Self::Large,
+            Self::Turbo,
+            Self::Hindi2Hinglish,
+        ]
+    }
+
     pub fn supported() -> &'static [&'static str] {
         &[
             "tiny",
@@ -111,3 +144,44 @@ impl WhisperModel {
         ]
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ModelQuantization {
+    #[serde(rename = "q5_0")]
+    Q5_0,
+    #[serde(rename = "q5_1")]
+    Q5_1,
+    #[serde(rename = "q8_0")]
+    Q8_0,
+}
+
+impl ModelQuantization {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Q5_0 => "q5_0",
+            Self::Q5_1 => "q5_1",
+            Self::Q8_0 => "q8_0",
+        }
+    }
+
+    pub fn all() -> &'static [ModelQuantization] {
+        &[Self::Q5_0, Self::Q5_1, Self::Q8_0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantized_filename_keeps_model_stem() {
+        assert_eq!(
+            WhisperModel::Medium.quantized_filename(ModelQuantization::Q5_0),
+            "ggml-medium-q5_0.bin"
+        );
+        assert_eq!(
+            WhisperModel::Turbo.quantized_filename(ModelQuantization::Q8_0),
+            "ggml-large-v3-turbo-q8_0.bin"
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use serde::{Deserialize, Serialize};
+
+const LEVEL_FLOOR_DBFS: f32 = -120.0;
+const DEFAULT_HIGH_PASS_HZ: f32 = 80.0;
+const DEFAULT_PEAK_TARGET_DBFS: f32 = -1.0;
+const DEFAULT_RMS_TARGET_DBFS: f32 = -20.0;
+const LIMITER_CEILING_DBFS: f32 = -1.0;
+const LIMITER_RELEASE_MS: f32 = 50.0;
+const MAX_NORMALIZATION_GAIN_DB: f32 = 30.0;
+const GATE_WINDOW_MS: f32 = 20.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NormalizeMode {
+    None,
+    Peak,
+    #[default]
+    Rms,
+}
+
+/// Optional cleanup applied to raw samples before resampling. Sending an
+/// empty object enables the default chain: DC removal, an 80 Hz high-pass and
+/// RMS normalization with a peak limiter. The noise gate is off unless a
+/// threshold is given.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PreprocessingOptions {
+    pub remove_dc: bool,
+    pub high_pass_hz: Option<f32>,
+    pub normalize: NormalizeMode,
+    pub target_dbfs: Option<f32>,
+    pub noise_gate_dbfs: Option<f32>,
+}
+
+impl Default for PreprocessingOptions {
+    fn default() -> Self {
+        Self {
+            remove_dc: true,
+            high_pass_hz: Some(DEFAULT_HIGH_PASS_HZ),
+            normalize: NormalizeMode::Rms,
+            target_dbfs: None,
+            noise_gate_dbfs: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioLevel {
+    pub peak_dbfs: f32,
+    pub rms_dbfs: f32,
+}
+
+pub fn measure_level(samples: &[f32]) -> AudioLevel {
+    AudioLevel {
+        peak_dbfs: amplitude_to_dbfs(peak(samples)),
+        rms_dbfs: amplitude_to_dbfs(rms(samples)),
+    }
+}
+
+pub fn preprocess(samples: &mut [f32], sample_rate: u32, options: &PreprocessingOptions) {
+    if samples.is_empty() || sample_rate == 0 {
+        return;
+    }
+
+    if options.remove_dc {
+        remove_dc(samples);
+    }
+
+    if let Some(cutoff_hz) = options.high_pass_hz.filter(|hz| *hz > 0.0) {
+        high_pass(samples, sample_rate, cutoff_hz);
+    }
+
+    if let Some(threshold_dbfs) = options.noise_gate_dbfs {
+        noise_gate(samples, sample_rate, threshold_dbfs);
+    }
+
+    match options.normalize {
+        NormalizeMode::None => return,
+        NormalizeMode::Peak => {
+            let target = options.target_dbfs.unwrap_or(DEFAULT_PEAK_TARGET_DBFS);
+            apply_gain_to_reach(samples, peak(samples), target);
+        }
+        NormalizeMode::Rms => {
+            let target = options.target_dbfs.unwrap_or(DEFAULT_RMS_TARGET_DBFS);
+            apply_gain_to_reach(samples, rms(samples), target);
+        }
+    }
+
+    limit(
+        samples,
+        sample_rate,
+        dbfs_to_amplitude(LIMITER_CEILING_DBFS),
+    );
+}
+
+fn remove_dc(samples: &mut [f32]) {
+    let mean = samples.iter().map(|value| f64::from(*value)).sum::<f64>() / samples.len() as f64;
+    let mean = mean as f32;
+    for sample in samples.iter_mut() {
+        *sample -= mean;
+    }
+}
+
+/// Second-order Butterworth high-pass (RBJ biquad).
+fn high_pass(samples: &mut [f32], sample_rate: u32, cutoff_hz: f32) {
+    let nyquist = sample_rate as f32 / 2.0;
+    if cutoff_hz >= nyquist {
+        return;
+    }
+
+    let omega = 2.0 * std::f32::consts::PI * cutoff_hz / sample_rate as f32;
+    let alpha = omega.sin() / (2.0 * std::f32::consts::FRAC_1_SQRT_2);
+    let cos_omega = omega.cos();
+
+    let a0 = 1.0 + alpha;
+    let b0 = (1.0 + cos_omega) / 2.0 / a0;
+    let b1 = -(1.0 + cos_omega) / a0;
+    let b2 = b0;
+    let a1 = -2.0 * cos_omega / a0;
+    let a2 = (1.0 - alpha) / a0;
+
+    let (mut x1, mut x2, mut y1, mut y2) = (0.0_f32, 0.0_f32, 0.0_f32, 0.0_f32);
+    for sample in samples.iter_mut() {
+        let x0 = *sample;
+        let y0 = b0 * x0 + b1 * x1 + b2 * x2 - a1 * y1 - a2 * y2;
+        x2 = x1;
+        x1 = x0;
+        y2 = y1;
+        y1 = y0;
+        *sample = y0;
+    }
+}
+
+/// Silences windows whose RMS falls below the threshold. Gain changes are
+/// ramped across each window so gating doesn't introduce clicks.
+fn noise_gate(samples: &mut [f32], sample_rate: u32, threshold_dbfs: f32) {
+    let threshold = dbfs_to_amplitude(threshold_dbfs);
+    let window = ((sample_rate as f32 * GATE_WINDOW_MS / 1000.0) as usize).max(1);
+    let mut previous_gain = 1.0_f32;
+
+    for chunk in samples.chunks_mut(window) {
+        let target_gain = if rms(chunk) < threshold { 0.0 } else { 1.0 };
+        let len = chunk.len() as f32;
+        for (index, sample) in chunk.iter_mut().enumerate() {
+            let t = (index + 1) as f32 / len;
+            *sample *= previous_gain + (target_gain - previous_gain) * t;
+        }
+        previous_gain = target_gain;
+    }
+}
+
+fn apply_gain_to_reach(samples: &mut [f32], current: f32, target_dbfs: f32) {
+    if current <= 0.0 {
+        return;
+    }
+
+    let gain_db = (target_dbfs - amplitude_to_dbfs(current)).min(MAX_NORMALIZATION_GAIN_DB);
+    let gain = dbfs_to_amplitude(gain_db);
+    for sample in samples.iter_mut() {
+        *sample *= gain;
+    }
+}
+
+/// Peak limiter with instant attack and exponential release, so the output
+/// never exceeds `ceiling` while quiet passages recover smoothly.
+fn limit(samples: &mut [f32], sample_rate: u32, ceiling: f32) {
+    let release = (-1.0 / (sample_rate as f32 * LIMITER_RELEASE_MS / 1000.0)).exp();
+    let mut envelope = 0.0_f32;
+
+    for sample in samples.iter_mut() {
+        let magnitude = sample.abs();
+        envelope = if magnitude > envelope {
+            magnitude
+        } else {
+            envelope * release + magnitude * (1.0 - release)
+        };
+
+        if envelope > ceiling {
+            *sample *= ceiling / envelope;
+        }
+    }
+}
+
+fn peak(samples: &[f32]) -> f32 {
+    samples
+        .iter()
+        .fold(0.0_f32, |max, value| max.max(value.abs()))
+}
+
+fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+
+    let sum: f64 = samples
+        .iter()
+        .map(|value| f64::from(*value) * f64::from(*value))
+        .sum();
+    (sum / samples.len() as f64).sqrt() as f32
+}
+
+fn amplitude_to_dbfs(amplitude: f32) -> f32 {
+    if amplitude <= 0.0 {
+        return LEVEL_FLOOR_DBFS;
+    }
+
+    (20.0 * amplitude.log10()).max(LEVEL_FLOOR_DBFS)
+}
+
+fn dbfs_to_amplitude(dbfs: f32) -> f32 {
+    10.0_f32.powf(dbfs / 20.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 16_000;
+
+    fn sine(frequency: f32, amplitude: f32, seconds: f32) -> Vec<f32> {
+        let total = (RATE as f32 * seconds) as usize;
+        (0..total)
+            .map(|index| {
+                let t = index as f32 / RATE as f32;
+                amplitude * (2.0 * std::f32::consts::PI * frequency * t).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn measures_level_of_full_scale_sine() {
+        let level = measure_level(&sine(440.0, 1.0, 1.0));
+        assert!(level.peak_dbfs > -0.1);
+        assert!((level.rms_dbfs + 3.01).abs() < 0.1);
+    }
+
+    #[test]
+    fn measures_silence_at_floor() {
+        let level = measure_level(&[0.0; 160]);
+        assert_eq!(level.peak_dbfs, LEVEL_FLOOR_DBFS);
+        assert_eq!(level.rms_dbfs, LEVEL_FLOOR_DBFS);
+    }
+
+    #[test]
+    fn removes_dc_offset() {
+        let mut samples: Vec<f32> = sine(440.0, 0.1, 0.5)
+            .into_iter()
+            .map(|value| value + 0.3)
+            .collect();
+        remove_dc(&mut samples);
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        assert!(mean.abs() < 1e-3);
+    }
+
+    #[test]
+    fn high_pass_attenuates_hum_and_keeps_speech_band() {
+        let mut hum = sine(30.0, 0.5, 1.0);
+        high_pass(&mut hum, RATE, DEFAULT_HIGH_PASS_HZ);
+        assert!(rms(&hum[RATE as usize / 2..]) < 0.5 * std::f32::consts::FRAC_1_SQRT_2 * 0.2);
+
+        let mut voice = sine(1_000.0, 0.5, 1.0);
+        high_pass(&mut voice, RATE, DEFAULT_HIGH_PASS_HZ);
+        assert!(rms(&voice[RATE as usize / 2..]) > 0.5 * std::f32::consts::FRAC_1_SQRT_2 * 0.95);
+    }
+
+    #[test]
+    fn rms_normalization_boosts_quiet_audio_without_clipping() {
+        let mut samples = sine(440.0, 0.01, 1.0);
+        preprocess(&mut samples, RATE, &PreprocessingOptions::default());
+
+        let level = measure_level(&samples);
+        assert!((level.rms_dbfs - DEFAULT_RMS_TARGET_DBFS).abs() < 1.0);
+        assert!(level.peak_dbfs <= LIMITER_CEILING_DBFS + 0.01);
+    }
+
+    #[test]
+    fn limiter_caps_peaks_at_ceiling() {
+        let mut samples = sine(440.0, 0.9, 1.0);
+        let options = PreprocessingOptions {
+            normalize: NormalizeMode::Rms,
+            target_dbfs: Some(-3.0),
+            ..PreprocessingOptions::default()
+        };
+        preprocess(&mut samples, RATE, &options);
+        assert!(peak(&samples) <= dbfs_to_amplitude(LIMITER_CEILING_DBFS) + 1e-4);
+    }
+
+    #[test]
+    fn noise_gate_silences_quiet_windows_only() {
+        let mut samples = sine(440.0, 0.001, 0.5);
+        samples.extend(sine(440.0, 0.5, 0.5));
+        noise_gate(&mut samples, RATE, -40.0);
+
+        let half = samples.len() / 2;
+        let window = (RATE as f32 * GATE_WINDOW_MS / 1000.0) as usize;
+        assert!(peak(&samples[window..half]) < 1e-6);
+        assert!(rms(&samples[half + 400..]) > 0.3);
+    }
+}
@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::errors::SidecarError;
+use crate::ggml::quantize_model;
+use crate::models::{ModelQuantization, WhisperModel};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuantizationJobStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuantizationJobSnapshot {
+    pub job_id: Uuid,
+    pub model: WhisperModel,
+    pub quantization: ModelQuantization,
+    pub status: QuantizationJobStatus,
+    pub bytes_processed: u64,
+    pub total_bytes: Option<u64>,
+    pub progress: Option<f64>,
+    pub error: Option<String>,
+    pub error_code: Option<&'static str>,
+}
+
+#[derive(Debug, Clone)]
+struct QuantizationJobRecord {
+    model: WhisperModel,
+    quantization: ModelQuantization,
+    status: QuantizationJobStatus,
+    bytes_processed: u64,
+    total_bytes: Option<u64>,
+    error: Option<SidecarError>,
+}
+
+#[derive(Default)]
+struct QuantizationStore {
+    jobs: HashMap<Uuid, QuantizationJobRecord>,
+    active_by_variant: HashMap<(WhisperModel, ModelQuantization), Uuid>,
+}
+
+#[derive(Clone, Default)]
+pub struct QuantizationRegistry {
+    inner: Arc<Mutex<QuantizationStore>>,
+}
+
+impl QuantizationRegistry {
+    pub async fn start_or_get_active(
+        &self,
+        model: WhisperModel,
+        quantization: ModelQuantization,
+        source: PathBuf,
+        destination: PathBuf,
+    ) -> Result<QuantizationJobSnapshot, SidecarError> {
+        let existing_size = match tokio::fs::metadata(&destination).await {
+            Ok(metadata) if metadata.is_file() && metadata.len() > 0 => Some(metadata.len()),
+            _ => None,
+        };
+
+        let mut store = self.inner.lock().await;
+
+        if let Some(existing_size) = existing_size {
+            let job_id = Uuid::new_v4();
+            store.jobs.insert(
+                job_id,
+                QuantizationJobRecord {
+                    model,
+                    quantization,
+                    status: QuantizationJobStatus::Completed,
+                    bytes_processed: existing_size,
+                    total_bytes: Some(existing_size),
+                    error: None,
+                },
+            );
+
+            return store.snapshot(job_id).ok_or_else(|| {
+                SidecarError::Internal("failed to create completed job snapshot".to_string())
+            });
+        }
+
+        if let Some(existing_id) = store.active_by_variant.get(&(model, quantization)).copied() {
+            return store.snapshot(existing_id).ok_or_else(|| {
+                SidecarError::Internal("active quantization job is missing".to_string())
+            });
+        }
+
+        let job_id = Uuid::new_v4();
+        store.jobs.insert(
+            job_id,
+            QuantizationJobRecord {
+                model,
+                quantization,
+                status: QuantizationJobStatus::Pending,
+                bytes_processed: 0,
+                total_bytes: None,
+                error: None,
+            },
+        );
+        store
+            .active_by_variant
+            .insert((model, quantization), job_id);
+
+        let snapshot = store.snapshot(job_id).ok_or_else(|| {
+            SidecarError::Internal("failed to create quantization job snapshot".to_string())
+        })?;
+        drop(store);
+
+        let registry = self.clone();
+        let span = tracing::Span::current();
+        tokio::task::spawn_blocking(move || {
+            span.in_scope(|| {
+                info!(
+                    model = model.as_slug(),
+                    quantization = quantization.as_str(),
+                    %job_id,
+                    "model quantization started"
+                );
+                let guard = FinishGuard {
+                    registry,
+                    job_id,
+                    model,
+                    quantization,
+                    finished: false,
+                };
+                let result = guard
+                    .registry
+                    .run_job_blocking(job_id, &source, &destination);
+                match &result {
+                    Ok(()) => info!(%job_id, "model quantization completed"),
+                    Err(err) => warn!(%job_id, error = %err, "model quantization failed"),
+                }
+                guard.finish(result);
+            })
+        });
+
+        Ok(snapshot)
+    }
+
+    pub async fn get_job(
+        &self,
+        model: WhisperModel,
+        job_id: Uuid,
+    ) -> Option<QuantizationJobSnapshot> {
+        let store = self.inner.lock().await;
+        let snapshot = store.snapshot(job_id)?;
+        if snapshot.model == model {
+            Some(snapshot)
+        } else {
+            None
+        }
+    }
+
+    pub async fn has_active_job(&self, model: WhisperModel) -> bool {
+        let store = self.inner.lock().await;
+        store
+            .active_by_variant
+            .keys()
+            .any(|(active_model, _)| *active_model == model)
+    }
+
+    fn run_job_blocking(
+        &self,
+        job_id: Uuid,
+        source: &std::path::Path,
+        destination: &std::path::Path,
+    ) -> Result<(), SidecarError> {
+        let filename = destination
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| SidecarError::Internal("invalid destination filename".to_string()))?;
+        let temp_path = destination.with_file_name(format!("{filename}.{job_id}.download"));
+
+        let quantization = {
+            let mut store = self.inner.blocking_lock();
+            let job = store
+                .jobs
+                .get_mut(&job_id)
+                .ok_or_else(|| SidecarError::Internal("quantization job not found".to_string()))?;
+            job.status = QuantizationJobStatus::Running;
+            job.quantization
+        };
+
+        let result = quantize_model(source, &temp_path, quantization, |processed, total| {
+            let mut store = self.inner.blocking_lock();
+            if let Some(job) = store.jobs.get_mut(&job_id) {
+                job.bytes_processed = processed;
+                job.total_bytes = Some(total);
+            }
+        })
+        .map_err(SidecarError::QuantizationFailed)
+        .and_then(|_| {
+            std::fs::rename(&temp_path, destination).map_err(|err| {
+                SidecarError::QuantizationFailed(format!(
+                    "failed to finalize quantized model file: {err}"
+                ))
+            })
+        });
+
+        if result.is_err() {
+            let _ = std::fs::remove_file(&temp_path);
+        }
+
+        result
+    }
+
+    fn finish_blocking(
+        &self,
+        job_id: Uuid,
+        model: WhisperModel,
+        quantization: ModelQuantization,
+        result: Result<(), SidecarError>,
+    ) {
+        let mut store = self.inner.blocking_lock();
+        if let Some(job) = store.jobs.get_mut(&job_id) {
+            match result {
+                Ok(()) => {
+                    job.status = QuantizationJobStatus::Completed;
+                    job.bytes_processed = job.total_bytes.unwrap_or(job.bytes_processed);
+                    job.error = None;
+                }
+                Err(err) => {
+                    job.status = QuantizationJobStatus::Failed;
+                    job.error = Some(err);
+                }
+            }
+        }
+        store.active_by_variant.remove(&(model, quantization));
+    }
+}
+
+/// Finishes the job when dropped, so a panic inside the quantizer still
+/// marks it failed and frees the variant for another attempt.
+struct FinishGuard {
+    registry: QuantizationRegistry,
+    job_id: Uuid,
+    model: WhisperModel,
+    quantization: ModelQuantization,
+    finished: bool,
+}
+
+impl FinishGuard {
+    fn finish(mut self, result: Result<(), SidecarError>) {
+        self.finished = true;
+        self.registry
+            .finish_blocking(self.job_id, self.model, self.quantization, result);
+    }
+}
+
+impl Drop for FinishGuard {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+
+        warn!(job_id = %self.job_id, "model quantization panicked");
+        self.registry.finish_blocking(
+            self.job_id,
+            self.model,
+            self.quantization,
+            Err(SidecarError::QuantizationFailed(
+                "quantization stopped unexpectedly".to_string(),
+            )),
+        );
+    }
+}
+
+impl QuantizationStore {
+    fn snapshot(&self, job_id: Uuid) -> Option<QuantizationJobSnapshot> {
+        let job = self.jobs.get(&job_id)?;
+        let progress = match job.total_bytes {
+            Some(total) if total > 0 => Some((job.bytes_processed as f64 / total as f64).min(1.0)),
+            Some(_) => Some(1.0),
+            None => None,
+        };
+
+        Some(QuantizationJobSnapshot {
+            job_id,
+            model: job.model,
+            quantization: job.quantization,
+            status: job.status,
+            bytes_processed: job.bytes_processed,
+            total_bytes: job.total_bytes,
+            progress,
+            error: job.error.as_ref().map(ToString::to_string),
+            error_code: job.error.as_ref().map(SidecarError::code),
+        })
+    }
+}
@@ -2,7 +2,8 @@ use std::path::PathBuf;
 
 use crate::config::SidecarConfig;
 use crate::downloads::DownloadRegistry;
-use crate::models::WhisperModel;
+use crate::models::{ModelQuantization, WhisperModel};
+use crate::quantization::QuantizationRegistry;
 use crate::streaming_sessions::TranscriptionSessionRegistry;
 use crate::transcription::TranscriptionEngine;
 
@@ -10,6 +11,7 @@ use crate::transcription::TranscriptionEngine;
 pub struct AppState {
     pub config: SidecarConfig,
     pub downloads: DownloadRegistry,
+    pub quantizations: QuantizationRegistry,
     pub transcription_sessions: TranscriptionSessionRegistry,
     pub http_client: reqwest::Client,
     pub transcriber: TranscriptionEngine,
@@ -26,6 +28,7 @@ impl AppState {
             transcriber: TranscriptionEngine::new(config.mode),
             config,
             downloads: DownloadRegistry::default(),
+            quantizations: QuantizationRegistry::default(),
             transcription_sessions: TranscriptionSessionRegistry::default(),
             http_client,
         })
@@ -34,4 +37,14 @@ impl AppState {
     pub fn model_path(&self, model: WhisperModel) -> PathBuf {
         self.config.models_dir.join(model.filename())
     }
+
+    pub fn quantized_model_path(
+        &self,
+        model: WhisperModel,
+        quantization: ModelQuantization,
+    ) -> PathBuf {
+        self.config
+            .models_dir
+            .join(model.quantized_filename(quantization))
+    }
 }
@@ -4,25 +4,43 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
-use crate::models::WhisperModel;
+use crate::audio_decoding::{ChunkDecoder, ChunkEncoding};
+use crate::errors::SidecarError;
+use crate::models::{ModelQuantization, WhisperModel};
+use crate::preprocessing::PreprocessingOptions;
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct BufferedTranscriptionSession {
     pub model: WhisperModel,
+    pub quantization: Option<ModelQuantization>,
     pub sample_rate: u32,
+    pub channels: u16,
     pub language: Option<String>,
     pub initial_prompt: Option<String>,
     pub device_id: Option<String>,
+    pub preprocessing: Option<PreprocessingOptions>,
+    pub include_confidence: bool,
     pub samples: Vec<f32>,
+    decoder: ChunkDecoder,
 }
 
 #[derive(Debug, Clone)]
 pub struct BufferedTranscriptionSessionInput {
     pub model: WhisperModel,
+    pub quantization: Option<ModelQuantization>,
     pub sample_rate: u32,
+    pub channels: u16,
     pub language: Option<String>,
     pub initial_prompt: Option<String>,
     pub device_id: Option<String>,
+    pub preprocessing: Option<PreprocessingOptions>,
+    pub include_confidence: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AppendedChunk {
+    pub received_samples: usize,
+    pub buffered_samples: usize,
 }
 
 #[derive(Default)]
@@ -40,11 +58,16 @@ impl TranscriptionSessionRegistry {
         let session_id = Uuid::new_v4();
         let session = BufferedTranscriptionSession {
             model: input.model,
+            quantization: input.quantization,
             sample_rate: input.sample_rate,
+            channels: input.channels,
             language: input.language,
             initial_prompt: input.initial_prompt,
             device_id: input.device_id,
+            preprocessing: input.preprocessing,
+            include_confidence: input.include_confidence,
             samples: Vec::new(),
+            decoder: ChunkDecoder::new(input.sample_rate, input.channels),
         };
 
         let mut store = self.inner.lock().await;
@@ -52,11 +75,30 @@ impl TranscriptionSessionRegistry {
         session_id
     }
 
-    pub async fn append_samples(&self, session_id: Uuid, samples: Vec<f32>) -> Option<usize> {
+    /// Decodes one uploaded chunk into the session buffer.
+    pub async fn append_chunk(
+        &self,
+        session_id: Uuid,
+        encoding: ChunkEncoding,
+        bytes: &[u8],
+    ) -> Result<AppendedChunk, SidecarError> {
         let mut store = self.inner.lock().await;
-        let session = store.sessions.get_mut(&session_id)?;
+        let session = store
+            .sessions
+            .get_mut(&session_id)
+            .ok_or(SidecarError::SessionNotFound)?;
+
+        let samples = session
+            .decoder
+            .decode(encoding, bytes)
+            .map_err(SidecarError::InvalidAudioChunk)?;
+        let received_samples = samples.len();
         session.samples.extend(samples);
-        Some(session.samples.len())
+
+        Ok(AppendedChunk {
+            received_samples,
+            buffered_samples: session.samples.len(),
+        })
     }
 
     pub async fn take(&self, session_id: Uuid) -> Option<BufferedTranscriptionSession> {
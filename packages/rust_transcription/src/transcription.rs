@@ -3,9 +3,14 @@ use std::collections::HashMap;
 use std::ffi::CStr;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::compute::ComputeMode;
+use crate::confidence::{build_segment, SegmentConfidence, TokenProbability};
+use crate::errors::SidecarError;
+use crate::preprocessing::{measure_level, preprocess, AudioLevel, PreprocessingOptions};
 use serde::Serialize;
+use tracing::{debug, info};
 use whisper_rs::{
     FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters, WhisperError,
 };
@@ -18,12 +23,24 @@ pub struct TranscriptionInput {
     pub language: Option<String>,
     pub initial_prompt: Option<String>,
     pub device_id: Option<String>,
+    pub preprocessing: Option<PreprocessingOptions>,
+    pub include_confidence: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct TranscriptionOutput {
     pub text: String,
     pub inference_device: String,
+    pub input_level: AudioLevel,
+    pub segments: Option<Vec<SegmentConfidence>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchmarkRun {
+    pub device_id: String,
+    pub device_name: String,
+    pub load_duration: Duration,
+    pub inference_duration: Duration,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -58,148 +75,215 @@ impl TranscriptionEngine {
     pub async fn transcribe(
         &self,
         input: TranscriptionInput,
-    ) -> Result<TranscriptionOutput, String> {
+    ) -> Result<TranscriptionOutput, SidecarError> {
         let engine = self.clone();
-        tokio::task::spawn_blocking(move || engine.transcribe_blocking(input))
+        let span = tracing::Span::current();
+        tokio::task::spawn_blocking(move || span.in_scope(|| engine.transcribe_blocking(input)))
             .await
-            .map_err(|err| format!("transcription task failed: {err}"))?
+            .map_err(|err| SidecarError::Internal(format!("transcription task failed: {err}")))?
     }
 
-    pub async fn list_devices(&self) -> Result<Vec<ComputeDevice>, String> {
+    pub async fn list_devices(&self) -> Result<Vec<ComputeDevice>, SidecarError> {
         let engine = self.clone();
-        tokio::task::spawn_blocking(move || engine.list_devices_blocking())
+        let span = tracing::Span::current();
+        tokio::task::spawn_blocking(move || span.in_scope(|| engine.list_devices_blocking()))
             .await
-            .map_err(|err| format!("device listing task failed: {err}"))?
+            .map_err(|err| SidecarError::Internal(format!("device listing task failed: {err}")))?
     }
 
-    pub async fn validate_model(&self, model_path: PathBuf) -> Result<bool, String> {
+    /// Loads the model from scratch (bypassing the context cache) and runs one
+    /// inference pass over 16 kHz `samples`, timing both phases.
+    pub async fn benchmark(
+        &self,
+        model_path: PathBuf,
+        device_id: String,
+        samples: Arc<Vec<f32>>,
+    ) -> Result<BenchmarkRun, SidecarError> {
         let engine = self.clone();
-        tokio::task::spawn_blocking(move || engine.validate_model_blocking(&model_path))
-            .await
-            .map_err(|err| format!("model validation task failed: {err}"))?
+        let span = tracing::Span::current();
+        tokio::task::spawn_blocking(move || {
+            span.in_scope(|| engine.benchmark_blocking(&model_path, &device_id, &samples))
+        })
+        .await
+        .map_err(|err| SidecarError::Internal(format!("benchmark task failed: {err}")))?
+    }
+
+    pub async fn validate_model(&self, model_path: PathBuf) -> Result<bool, SidecarError> {
+        let engine = self.clone();
+        let span = tracing::Span::current();
+        tokio::task::spawn_blocking(move || {
+            span.in_scope(|| engine.validate_model_blocking(&model_path))
+        })
+        .await
+        .map_err(|err| SidecarError::Internal(format!("model validation task failed: {err}")))?
+    }
+
+    /// Drops cached contexts for `model_path` so a replaced file is reloaded
+    /// on the next request.
+    pub fn evict_model(&self, model_path: &Path) {
+        let Some(model_key) = model_path.to_str() else {
+            return;
+        };
+        let prefix = format!("{model_key}#");
+        if let Ok(mut cache) = self.context_cache.lock() {
+            cache.retain(|key, _| !key.starts_with(&prefix));
+        }
     }
 
     fn transcribe_blocking(
         &self,
         input: TranscriptionInput,
-    ) -> Result<TranscriptionOutput, String> {
+    ) -> Result<TranscriptionOutput, SidecarError> {
         if input.sample_rate == 0 {
-            return Err("sampleRate must be greater than 0".to_string());
+            return Err(SidecarError::InvalidRequest(
+                "sampleRate must be greater than 0".to_string(),
+            ));
         }
 
         if input.samples.is_empty() {
-            return Err("samples must not be empty".to_string());
+            return Err(SidecarError::InvalidRequest(
+                "samples must not be empty".to_string(),
+            ));
         }
 
-        let filtered_samples: Vec<f32> = input
+        let mut filtered_samples: Vec<f32> = input
             .samples
             .into_iter()
             .filter(|sample| sample.is_finite())
             .collect();
 
         if filtered_samples.is_empty() {
-            return Err("no finite samples provided".to_string());
+            return Err(SidecarError::InvalidRequest(
+                "no finite samples provided".to_string(),
+            ));
+        }
+
+        let input_level = measure_level(&filtered_samples);
+        if let Some(options) = input.preprocessing.as_ref() {
+            preprocess(&mut filtered_samples, input.sample_rate, options);
         }
 
         let processed = resample_to_16khz(&filtered_samples, input.sample_rate);
         if processed.is_empty() {
-            return Err("unable to resample audio".to_string());
+            return Err(SidecarError::InvalidRequest(
+                "unable to resample audio".to_string(),
+            ));
         }
 
         let device = self.resolve_device_blocking(input.device_id.as_deref())?;
+        debug!(device = %device.id, "resolved inference device");
         let context = self.context_for_model(&input.model_path, &device)?;
-        let mut state = context
-            .create_state()
-            .map_err(|err| format!("failed to create whisper state: {err}"))?;
-
-        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
-        params.set_translate(false);
-        params.set_print_special(false);
-        params.set_print_progress(false);
-        params.set_print_realtime(false);
-        params.set_print_timestamps(false);
-        params.set_no_context(true);
-
-        if let Some(language) = input
-            .language
-            .as_deref()
-            .map(str::trim)
-            .filter(|v| !v.is_empty())
-        {
-            params.set_language(Some(language));
-        }
 
-        if let Some(prompt) = input
-            .initial_prompt
-            .as_deref()
-            .map(str::trim)
-            .filter(|v| !v.is_empty())
-        {
-            let sanitized: String = prompt.chars().filter(|ch| *ch != '\0').collect();
-            if !sanitized.is_empty() {
-                params.set_initial_prompt(&sanitized);
-            }
-        }
-
-        state
-            .full(params, &processed)
-            .map_err(|err| format!("failed to run whisper inference: {err}"))?;
-
-        let text = collect_transcription(&state)?;
+        let inference_started = Instant::now();
+        let (text, segments) = run_inference(
+            &context,
+            &processed,
+            input.language.as_deref(),
+            input.initial_prompt.as_deref(),
+            input.include_confidence,
+        )?;
+        info!(
+            device = %device.id,
+            audio_ms = processed.len() as u64 * 1000 / 16_000,
+            inference_ms = inference_started.elapsed().as_millis() as u64,
+            "inference finished"
+        );
         let inference_device = device.name.clone();
 
         Ok(TranscriptionOutput {
             text,
             inference_device,
+            input_level,
+            segments,
         })
     }
 
-    fn validate_model_blocking(&self, model_path: &Path) -> Result<bool, String> {
+    fn benchmark_blocking(
+        &self,
+        model_path: &Path,
+        device_id: &str,
+        samples: &[f32],
+    ) -> Result<BenchmarkRun, SidecarError> {
+        let model_path_str = model_path
+            .to_str()
+            .ok_or_else(|| SidecarError::Internal("model path is not valid UTF-8".to_string()))?;
+
+        let device = self.resolve_device_blocking(Some(device_id))?;
+        let params = self.context_params(&device)?;
+
+        let load_started = Instant::now();
+        let context = WhisperContext::new_with_params(model_path_str, params).map_err(|err| {
+            SidecarError::ModelLoadFailed(format!("failed to initialize whisper context: {err}"))
+        })?;
+        let load_duration = load_started.elapsed();
+
+        let inference_started = Instant::now();
+        run_inference(&context, samples, None, None, false)?;
+        let inference_duration = inference_started.elapsed();
+
+        Ok(BenchmarkRun {
+            device_id: device.id,
+            device_name: device.name,
+            load_duration,
+            inference_duration,
+        })
+    }
+
+    fn validate_model_blocking(&self, model_path: &Path) -> Result<bool, SidecarError> {
         if !model_path.exists() {
             return Ok(false);
         }
 
         let model_path_str = model_path
             .to_str()
-            .ok_or_else(|| "model path is not valid UTF-8".to_string())?;
+            .ok_or_else(|| SidecarError::Internal("model path is not valid UTF-8".to_string()))?;
 
         let device = self.resolve_device_blocking(None)?;
         let params = self.context_params(&device)?;
         WhisperContext::new_with_params(model_path_str, params)
             .map(|_| true)
-            .map_err(|err| format!("failed to load model: {err}"))
+            .map_err(|err| SidecarError::ModelLoadFailed(format!("failed to load model: {err}")))
     }
 
     fn context_for_model(
         &self,
         model_path: &Path,
         device: &ResolvedDevice,
-    ) -> Result<Arc<WhisperContext>, String> {
+    ) -> Result<Arc<WhisperContext>, SidecarError> {
         let model_key = model_path
             .to_str()
-            .ok_or_else(|| "model path is not valid UTF-8".to_string())?
+            .ok_or_else(|| SidecarError::Internal("model path is not valid UTF-8".to_string()))?
             .to_string();
         let key = format!("{model_key}#{}", device.id);
 
         if let Some(existing) = self
             .context_cache
             .lock()
-            .map_err(|_| "context cache lock poisoned".to_string())?
+            .map_err(|_| SidecarError::Internal("context cache lock poisoned".to_string()))?
             .get(&key)
             .cloned()
         {
+            debug!(model = %model_key, device = %device.id, "using cached whisper context");
             return Ok(existing);
         }
 
         let params = self.context_params(device)?;
-        let context = WhisperContext::new_with_params(&model_key, params)
-            .map_err(|err| format!("failed to initialize whisper context: {err}"))?;
+        let load_started = Instant::now();
+        let context = WhisperContext::new_with_params(&model_key, params).map_err(|err| {
+            SidecarError::ModelLoadFailed(format!("failed to initialize whisper context: {err}"))
+        })?;
+        info!(
+            model = %model_key,
+            device = %device.id,
+            load_ms = load_started.elapsed().as_millis() as u64,
+            "loaded whisper context"
+        );
 
         let context = Arc::new(context);
         let mut cache = self
             .context_cache
             .lock()
-            .map_err(|_| "context cache lock poisoned".to_string())?;
+            .map_err(|_| SidecarError::Internal("context cache lock poisoned".to_string()))?;
 
         Ok(cache.entry(key).or_insert_with(|| context.clone()).clone())
     }
@@ -207,7 +291,7 @@ impl TranscriptionEngine {
     fn context_params(
         &self,
         device: &ResolvedDevice,
-    ) -> Result<WhisperContextParameters<'_>, String> {
+    ) -> Result<WhisperContextParameters<'_>, SidecarError> {
         let mut params = WhisperContextParameters::default();
         match self.mode {
             ComputeMode::Cpu => {
@@ -225,13 +309,15 @@ impl TranscriptionEngine {
 
                 #[cfg(not(feature = "gpu"))]
                 {
-                    Err("gpu mode requested but binary was built without gpu feature".to_string())
+                    Err(SidecarError::DeviceUnavailable(
+                        "gpu mode requested but binary was built without gpu feature".to_string(),
+                    ))
                 }
             }
         }
     }
 
-    fn list_devices_blocking(&self) -> Result<Vec<ComputeDevice>, String> {
+    fn list_devices_blocking(&self) -> Result<Vec<ComputeDevice>, SidecarError> {
         match self.mode {
             ComputeMode::Cpu => Ok(vec![ComputeDevice {
                 id: "cpu:0".to_string(),
@@ -245,7 +331,9 @@ impl TranscriptionEngine {
 
                 #[cfg(not(feature = "gpu"))]
                 {
-                    Err("gpu mode requested but binary was built without gpu feature".to_string())
+                    Err(SidecarError::DeviceUnavailable(
+                        "gpu mode requested but binary was built without gpu feature".to_string(),
+                    ))
                 }
             }
         }
@@ -254,10 +342,13 @@ impl TranscriptionEngine {
     fn resolve_device_blocking(
         &self,
         requested_device_id: Option<&str>,
-    ) -> Result<ResolvedDevice, String> {
+    ) -> Result<ResolvedDevice, SidecarError> {
         let devices = self.list_devices_blocking()?;
         if devices.is_empty() {
-            return Err(format!("no {} devices available", self.mode.as_str()));
+            return Err(SidecarError::DeviceUnavailable(format!(
+                "no {} devices available",
+                self.mode.as_str()
+            )));
         }
 
         let selected = if let Some(device_id) = requested_device_id
@@ -267,7 +358,9 @@ impl TranscriptionEngine {
             devices
                 .into_iter()
                 .find(|device| device.id == device_id)
-                .ok_or_else(|| format!("unsupported deviceId '{device_id}'"))?
+                .ok_or_else(|| {
+                    SidecarError::InvalidDevice(format!("unsupported deviceId '{device_id}'"))
+                })?
         } else {
             devices
                 .into_iter()
@@ -297,13 +390,12 @@ impl TranscriptionEngine {
             ComputeMode::Gpu => {
                 #[cfg(feature = "gpu")]
                 {
-                    let (_, index) = selected
-                        .id
-                        .split_once(':')
-                        .ok_or_else(|| format!("invalid gpu device id '{}'", selected.id))?;
-                    let gpu_device = index
-                        .parse::<i32>()
-                        .map_err(|_| format!("invalid gpu device id '{}'", selected.id))?;
+                    let (_, index) = selected.id.split_once(':').ok_or_else(|| {
+                        SidecarError::Internal(format!("invalid gpu device id '{}'", selected.id))
+                    })?;
+                    let gpu_device = index.parse::<i32>().map_err(|_| {
+                        SidecarError::Internal(format!("invalid gpu device id '{}'", selected.id))
+                    })?;
 
                     Ok(ResolvedDevice {
                         id: selected.id,
@@ -314,14 +406,60 @@ impl TranscriptionEngine {
 
                 #[cfg(not(feature = "gpu"))]
                 {
-                    Err("gpu mode requested but binary was built without gpu feature".to_string())
+                    Err(SidecarError::DeviceUnavailable(
+                        "gpu mode requested but binary was built without gpu feature".to_string(),
+                    ))
                 }
             }
         }
     }
 }
 
-fn collect_transcription(state: &whisper_rs::WhisperState) -> Result<String, String> {
+fn run_inference(
+    context: &WhisperContext,
+    samples: &[f32],
+    language: Option<&str>,
+    initial_prompt: Option<&str>,
+    include_confidence: bool,
+) -> Result<(String, Option<Vec<SegmentConfidence>>), SidecarError> {
+    let mut state = context.create_state().map_err(|err| {
+        SidecarError::TranscriptionFailed(format!("failed to create whisper state: {err}"))
+    })?;
+
+    let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+    params.set_translate(false);
+    params.set_print_special(false);
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_print_timestamps(false);
+    params.set_no_context(true);
+
+    if let Some(language) = language.map(str::trim).filter(|v| !v.is_empty()) {
+        params.set_language(Some(language));
+    }
+
+    if let Some(prompt) = initial_prompt.map(str::trim).filter(|v| !v.is_empty()) {
+        let sanitized: String = prompt.chars().filter(|ch| *ch != '\0').collect();
+        if !sanitized.is_empty() {
+            params.set_initial_prompt(&sanitized);
+        }
+    }
+
+    state.full(params, samples).map_err(|err| {
+        SidecarError::TranscriptionFailed(format!("failed to run whisper inference: {err}"))
+    })?;
+
+    let text = collect_transcription(&state)?;
+    let segments = if include_confidence {
+        Some(collect_confidence(context, &state))
+    } else {
+        None
+    };
+
+    Ok((text, segments))
+}
+
+fn collect_transcription(state: &whisper_rs::WhisperState) -> Result<String, SidecarError> {
     let mut transcript = String::new();
 
     for segment in state.as_iter() {
@@ -331,7 +469,11 @@ fn collect_transcription(state: &whisper_rs::WhisperState) -> Result<String, Str
                 .to_str_lossy()
                 .map(|value| value.trim().to_string())
                 .unwrap_or_default(),
-            Err(err) => return Err(format!("failed to read whisper segment: {err}")),
+            Err(err) => {
+                return Err(SidecarError::TranscriptionFailed(format!(
+                    "failed to read whisper segment: {err}"
+                )))
+            }
         };
 
         if piece.is_empty() {
@@ -348,6 +490,48 @@ fn collect_transcription(state: &whisper_rs::WhisperState) -> Result<String, Str
     Ok(transcript.trim().to_string())
 }
 
+/// Walks the same segments as `collect_transcription`, keeping per-token
+/// probabilities. Special tokens (timestamps, end-of-text) are skipped.
+fn collect_confidence(
+    context: &WhisperContext,
+    state: &whisper_rs::WhisperState,
+) -> Vec<SegmentConfidence> {
+    let eot = context.token_eot();
+    let mut segments = Vec::new();
+
+    for segment in state.as_iter() {
+        let text = segment
+            .to_str_lossy()
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default();
+        if text.is_empty() {
+            continue;
+        }
+
+        let tokens: Vec<TokenProbability> = (0..segment.n_tokens())
+            .filter_map(|index| segment.get_token(index))
+            .filter(|token| token.token_id() < eot)
+            .map(|token| TokenProbability {
+                text: token
+                    .to_str_lossy()
+                    .map(|value| value.into_owned())
+                    .unwrap_or_default(),
+                probability: token.token_probability(),
+            })
+            .collect();
+
+        // Whisper timestamps are in centiseconds.
+        segments.push(build_segment(
+            text,
+            segment.start_timestamp() * 10,
+            segment.end_timestamp() * 10,
+            &tokens,
+        ));
+    }
+
+    segments
+}
+
 fn resample_to_16khz(samples: &[f32], sample_rate: u32) -> Vec<f32> {
     const TARGET_RATE: u32 = 16_000;
 
@@ -385,7 +569,10 @@ fn resample_to_16khz(samples: &[f32], sample_rate: u32) -> Vec<f32> {
 pub fn ensure_gpu_runtime_available() -> Result<(), String> {
     #[cfg(feature = "gpu")]
     {
-        if list_gpu_devices()?.is_empty() {
+        if list_gpu_devices()
+            .map_err(|err| err.to_string())?
+            .is_empty()
+        {
             return Err("no GPU-capable backend detected".to_string());
         }
 
@@ -399,7 +586,7 @@ pub fn ensure_gpu_runtime_available() -> Result<(), String> {
 }
 
 #[cfg(feature = "gpu")]
-fn list_gpu_devices() -> Result<Vec<ComputeDevice>, String> {
+fn list_gpu_devices() -> Result<Vec<ComputeDevice>, SidecarError> {
     let mut devices = Vec::new();
     let mut gpu_index = 0usize;
 
@@ -429,7 +616,9 @@ fn list_gpu_devices() -> Result<Vec<ComputeDevice>, String> {
 #[cfg(feature = "gpu")]
 fn describe_gpu_device(device: whisper_rs::whisper_rs_sys::ggml_backend_dev_t) -> String {
     let description = unsafe {
-        c_string(whisper_rs::whisper_rs_sys::ggml_backend_dev_description(device))
+        c_string(whisper_rs::whisper_rs_sys::ggml_backend_dev_description(
+            device,
+        ))
     };
     let name = unsafe { c_string(whisper_rs::whisper_rs_sys::ggml_backend_dev_name(device)) };
     let backend = unsafe {
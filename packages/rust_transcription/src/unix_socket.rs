@@ -0,0 +1,88 @@
+use std::io::ErrorKind;
+use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+use std::path::Path;
+
+use axum::Router;
+use hyper_util::rt::TokioIo;
+use hyper_util::service::TowerToHyperService;
+use tokio::net::UnixListener;
+use tracing::warn;
+
+const SOCKET_PERMISSIONS: u32 = 0o600;
+const STAGING_DIR_PERMISSIONS: u32 = 0o700;
+
+/// Binds the socket inside a private staging directory and moves it into
+/// place once restricted, so it is never reachable with umask permissions.
+pub fn bind(path: &Path) -> Result<UnixListener, String> {
+    remove_stale_socket(path)?;
+
+    let parent = path
+        .parent()
+        .ok_or_else(|| format!("unix socket {} has no parent directory", path.display()))?;
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("unix socket {} has no file name", path.display()))?;
+    let staging = parent.join(format!(
+        ".{}.{}",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::DirBuilder::new()
+        .mode(STAGING_DIR_PERMISSIONS)
+        .create(&staging)
+        .map_err(|err| format!("failed to create {}: {err}", staging.display()))?;
+
+    let result = bind_staged(&staging.join("socket"), path);
+    let _ = std::fs::remove_dir_all(&staging);
+    result
+}
+
+fn bind_staged(staged: &Path, path: &Path) -> Result<UnixListener, String> {
+    let listener = UnixListener::bind(staged)
+        .map_err(|err| format!("failed to bind unix socket {}: {err}", path.display()))?;
+
+    let permissions = std::fs::Permissions::from_mode(SOCKET_PERMISSIONS);
+    std::fs::set_permissions(staged, permissions).map_err(|err| {
+        format!(
+            "failed to restrict permissions on unix socket {}: {err}",
+            path.display()
+        )
+    })?;
+    std::fs::rename(staged, path)
+        .map_err(|err| format!("failed to move unix socket to {}: {err}", path.display()))?;
+
+    Ok(listener)
+}
+
+pub async fn serve(listener: UnixListener, router: Router) -> Result<(), String> {
+    loop {
+        let (stream, _) = listener
+            .accept()
+            .await
+            .map_err(|err| format!("failed to accept unix socket connection: {err}"))?;
+
+        let service = TowerToHyperService::new(router.clone());
+        tokio::spawn(async move {
+            if let Err(err) = hyper::server::conn::http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                warn!(error = %err, "unix socket connection failed");
+            }
+        });
+    }
+}
+
+fn remove_stale_socket(path: &Path) -> Result<(), String> {
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path)
+            .map_err(|err| format!("failed to remove stale socket {}: {err}", path.display())),
+        Ok(_) => Err(format!(
+            "refusing to replace non-socket file at {}",
+            path.display()
+        )),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(format!("failed to inspect {}: {err}", path.display())),
+    }
+}
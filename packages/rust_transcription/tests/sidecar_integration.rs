@@ -67,6 +67,7 @@ struct TranscribeRequest {
     language: Option<String>,
     initial_prompt: Option<String>,
     device_id: Option<String>,
+    include_confidence: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -74,6 +75,7 @@ struct TranscribeRequest {
 struct CreateTranscriptionSessionRequest {
     model: String,
     sample_rate: u32,
+    channels: Option<u16>,
     language: Option<String>,
     initial_prompt: Option<String>,
     device_id: Option<String>,
@@ -103,6 +105,21 @@ struct DeleteTranscriptionSessionResponse {
 struct TranscribeResponse {
     text: String,
     inference_device: String,
+    segments: Option<Vec<SegmentConfidence>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SegmentConfidence {
+    text: String,
+    confidence: f32,
+    words: Vec<WordConfidence>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WordConfidence {
+    confidence: f32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -271,6 +288,7 @@ async fn cpu_sidecar_health_and_missing_model_error_flow(
             language: Some("en".to_string()),
             initial_prompt: None,
             device_id: None,
+            include_confidence: None,
         })
         .send()
         .await?;
@@ -312,6 +330,7 @@ async fn cpu_sidecar_lists_cpu_device_and_accepts_device_id(
             language: Some("en".to_string()),
             initial_prompt: None,
             device_id: Some(devices.devices[0].id.clone()),
+            include_confidence: None,
         })
         .send()
         .await?;
@@ -339,6 +358,7 @@ async fn cpu_sidecar_rejects_invalid_device_id(
             language: Some("en".to_string()),
             initial_prompt: None,
             device_id: Some("cpu:999".to_string()),
+            include_confidence: None,
         })
         .send()
         .await?;
@@ -363,6 +383,7 @@ async fn cpu_sidecar_transcription_session_lifecycle(
         .json(&CreateTranscriptionSessionRequest {
             model: "tiny".to_string(),
             sample_rate: 16_000,
+            channels: None,
             language: Some("en".to_string()),
             initial_prompt: Some("Please transcribe.".to_string()),
             device_id: Some("cpu:0".to_string()),
@@ -420,6 +441,65 @@ async fn cpu_sidecar_transcription_session_lifecycle(
     Ok(())
 }
 
+#[tokio::test]
+async fn cpu_sidecar_transcription_session_accepts_l16_and_rejects_unknown_formats(
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let sidecar = RunningSidecar::start_cpu().await?;
+    tokio::fs::write(sidecar.model_path(TINY_MODEL_FILENAME), b"fake model bytes").await?;
+
+    let session = sidecar
+        .client
+        .post(sidecar.url("/v1/transcriptions/sessions"))
+        .json(&CreateTranscriptionSessionRequest {
+            model: "tiny".to_string(),
+            sample_rate: 16_000,
+            channels: Some(2),
+            language: None,
+            initial_prompt: None,
+            device_id: None,
+        })
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<CreateTranscriptionSessionResponse>()
+        .await?;
+    let chunks_url = sidecar.url(&format!(
+        "/v1/transcriptions/sessions/{}/chunks",
+        session.session_id
+    ));
+
+    let stereo_frames: Vec<u8> = [1_000_i16, -1_000, 2_000, -2_000, 0, 0]
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect();
+    let append = sidecar
+        .client
+        .post(&chunks_url)
+        .header("Content-Type", "audio/L16;rate=16000;channels=2")
+        .body(stereo_frames)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<AppendTranscriptionChunkResponse>()
+        .await?;
+
+    assert_eq!(append.received_samples, 3);
+    assert_eq!(append.buffered_samples, 3);
+
+    let unsupported = sidecar
+        .client
+        .post(&chunks_url)
+        .header("Content-Type", "audio/mpeg")
+        .body(vec![0_u8; 16])
+        .send()
+        .await?;
+    assert_eq!(unsupported.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    let body = unsupported.json::<ApiErrorEnvelope>().await?;
+    assert_eq!(body.error.code, "unsupported_audio_format");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn cpu_sidecar_delete_model_removes_model_and_partial_fragments(
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -482,7 +562,7 @@ async fn cpu_sidecar_delete_model_rejects_while_download_is_active(
         .delete(sidecar.url("/v1/models/tiny"))
         .send()
         .await?;
-    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.status(), StatusCode::CONFLICT);
 
     let body = response.json::<ApiErrorEnvelope>().await?;
     assert_eq!(body.error.code, "download_in_progress");
@@ -492,6 +572,65 @@ async fn cpu_sidecar_delete_model_rejects_while_download_is_active(
     Ok(())
 }
 
+#[cfg(unix)]
+#[tokio::test]
+async fn cpu_sidecar_serves_over_unix_socket(
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    use std::io::{BufRead, BufReader};
+    use std::os::unix::fs::PermissionsExt;
+    use tokio::net::UnixStream;
+
+    let models_dir = tempfile::tempdir()?;
+    let socket_dir = tempfile::tempdir()?;
+    let socket_path = socket_dir.path().join("voquill-transcription.sock");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rust-transcription-cpu"))
+        .env("RUST_TRANSCRIPTION_SOCKET", &socket_path)
+        .env("RUST_TRANSCRIPTION_MODELS_DIR", models_dir.path())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()?;
+
+    let result = async {
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or("sidecar stdout was not captured")?;
+        let announcement = BufReader::new(stdout)
+            .lines()
+            .map_while(Result::ok)
+            .find(|line| line.starts_with("RUST_TRANSCRIPTION_BOUND_PORT="))
+            .ok_or("sidecar did not announce its socket path")?;
+        assert_eq!(
+            announcement,
+            format!(
+                "RUST_TRANSCRIPTION_BOUND_PORT=unix:{}",
+                socket_path.display()
+            )
+        );
+
+        let mode = std::fs::metadata(&socket_path)?.permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let mut stream = UnixStream::connect(&socket_path).await?;
+        stream
+            .write_all(b"GET /health HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("\"mode\":\"cpu\""));
+
+        Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+    }
+    .await;
+
+    let _ = child.kill();
+    let _ = child.wait();
+    result
+}
+
 #[tokio::test]
 #[ignore = "downloads tiny model and runs real transcription against sidecar"]
 async fn cpu_sidecar_end_to_end_download_and_transcribe(
@@ -582,6 +721,7 @@ async fn cpu_sidecar_end_to_end_download_and_transcribe(
                     .id
                     .clone(),
             ),
+            include_confidence: Some(true),
         })
         .send()
         .await?
@@ -592,6 +732,19 @@ async fn cpu_sidecar_end_to_end_download_and_transcribe(
     assert!(!transcription.text.trim().is_empty());
     assert_eq!(transcription.inference_device, "CPU");
 
+    let segments = transcription
+        .segments
+        .ok_or("missing confidence segments")?;
+    assert!(!segments.is_empty());
+    for segment in &segments {
+        assert!(!segment.text.is_empty());
+        assert!((0.0..=1.0).contains(&segment.confidence));
+        assert!(segment
+            .words
+            .iter()
+            .all(|word| (0.0..=1.0).contains(&word.confidence)));
+    }
+
     Ok(())
 }
 
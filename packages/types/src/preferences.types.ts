@@ -1,5 +1,6 @@
 import type {
   AgentMode,
+  AudioStorageFormat,
   DictationPillVisibility,
   Nullable,
   PostProcessingMode,
@@ -38,6 +39,10 @@ export type UserPreferences = {
   dictationAudioDim: number;
   pasteKeybind: Nullable<string>;
   menuBarIconHidden: boolean;
+  audioStorageFormat: AudioStorageFormat;
+  audioRetentionMaxCount: Nullable<number>;
+  audioRetentionMaxAgeDays: Nullable<number>;
+  audioRetentionMaxTotalMb: Nullable<number>;
 
   // deprecated
   isEnterprise: boolean;
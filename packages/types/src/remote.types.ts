@@ -16,6 +16,13 @@ export type PairedRemoteDevice = {
   trusted: boolean;
 };
 
+export type DiscoveredRemoteReceiver = {
+  deviceId: string;
+  name: string;
+  platform: RemoteDevicePlatform;
+  address: string;
+};
+
 export type RemoteOutputMode = "local" | "remote";
 
 export type RemoteOutputPreferences = {
@@ -54,11 +61,30 @@ export type RouteTranscriptOutputArgs = {
   text: string;
   mode: "dictation";
   currentAppId: Nullable<string>;
+  utteranceId?: string;
+};
+
+export type RemoteDeliveryOutcome = "delivered" | "queued";
+
+export type RemoteOutboxItem = {
+  sequence: number;
+  eventId: string;
+  targetDeviceId: string;
+  text: string;
+  mode: string;
+  createdAt: string;
+  utteranceId: Nullable<string>;
+  attempts: number;
+  nextAttemptAt: number;
+  lastError: Nullable<string>;
+  stuck: boolean;
+  needsConfirmation: boolean;
 };
 
 export type RouteTranscriptOutputResult = {
   delivered: boolean;
   remote: boolean;
+  queued?: boolean;
 };
 
 export type PairingRequest = {
@@ -67,7 +93,6 @@ export type PairingRequest = {
   senderDeviceId: string;
   senderDeviceName: string;
   senderPlatform: RemoteDevicePlatform;
-  pairingCode: string;
 };
 
 export type PairingAccept = {
@@ -83,13 +108,13 @@ export type SessionHello = {
   type: "session_hello";
   sessionId: string;
   senderDeviceId: string;
-  authToken: string;
 };
 
 export type SessionAck = {
   type: "session_ack";
   sessionId: string;
   receiverDeviceId: string;
+  capabilities: string[];
 };
 
 export type Heartbeat = {
@@ -106,6 +131,27 @@ export type FinalTextEvent = {
   text: string;
   mode: "dictation" | "test";
   createdAt: string;
+  utteranceId?: string;
+};
+
+export type RemoteEditOp =
+  | { op: "erase"; count: number }
+  | { op: "insert"; text: string };
+
+export type PartialTextEvent = {
+  type: "partial_text";
+  sessionId: string;
+  utteranceId: string;
+  sequence: number;
+  text: string;
+};
+
+export type ReviseEvent = {
+  type: "revise";
+  sessionId: string;
+  utteranceId: string;
+  sequence: number;
+  ops: RemoteEditOp[];
 };
 
 export type DeliveryAck = {
@@ -132,5 +178,7 @@ export type RemoteEnvelope =
   | SessionAck
   | Heartbeat
   | FinalTextEvent
+  | PartialTextEvent
+  | ReviseEvent
   | DeliveryAck
   | DeliveryError;
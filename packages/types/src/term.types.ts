@@ -2,6 +2,8 @@ import { FiremixTimestamp } from "@firemix/core";
 import { Replace } from "./common.types";
 import z from "zod";
 
+export type TermMatchMode = "whole_word" | "substring" | "regex";
+
 export type DatabaseTerm = {
   id: string;
   createdAt: FiremixTimestamp;
@@ -9,6 +11,11 @@ export type DatabaseTerm = {
   destinationValue: string;
   isReplacement: boolean;
   isGlobal?: boolean;
+  matchMode?: TermMatchMode;
+  caseSensitive?: boolean;
+  preserveCase?: boolean;
+  priority?: number;
+  packId?: string | null;
 };
 
 export type Term = Replace<DatabaseTerm, FiremixTimestamp, string>;
@@ -27,5 +34,10 @@ export const TermZod = z
     destinationValue: z.string(),
     isReplacement: z.boolean(),
     isGlobal: z.boolean().optional(),
+    matchMode: z.enum(["whole_word", "substring", "regex"]).optional(),
+    caseSensitive: z.boolean().optional(),
+    preserveCase: z.boolean().optional(),
+    priority: z.number().int().optional(),
+    packId: z.string().nullable().optional(),
   })
   .strict() satisfies z.ZodType<Term>;
@@ -23,6 +23,46 @@ export type Transcription = {
   warnings?: string[] | null;
   remoteStatus?: "sent" | "received" | null;
   remoteDeviceId?: string | null;
+  confidenceSegments?: TranscriptionSegmentConfidence[] | null;
+};
+
+export type TranscriptionWordConfidence = {
+  text: string;
+  confidence: number;
+};
+
+export type TranscriptionSegmentConfidence = {
+  text: string;
+  startMs: number;
+  endMs: number;
+  confidence: number;
+  words: TranscriptionWordConfidence[];
+};
+
+/**
+ * One transcription and post-processing result for a recording. The active
+ * version's text is mirrored on the transcription.
+ */
+export type TranscriptionVersion = {
+  id: string;
+  transcriptionId: string;
+  createdAt: number;
+  isActive: boolean;
+  transcript: string;
+  rawTranscript?: string | null;
+  sanitizedTranscript?: string | null;
+  confidenceSegments?: TranscriptionSegmentConfidence[] | null;
+  transcriptionMode?: TranscriptionMode | null;
+  modelSize?: string | null;
+  inferenceDevice?: string | null;
+  transcriptionPrompt?: string | null;
+  transcriptionApiKeyId?: string | null;
+  transcriptionDurationMs?: number | null;
+  postProcessMode?: PostProcessingMode | null;
+  postProcessDevice?: string | null;
+  postProcessPrompt?: string | null;
+  postProcessApiKeyId?: string | null;
+  postprocessDurationMs?: number | null;
 };
 
 export type TranscriptionAudioSnapshot = {